@@ -66,8 +66,6 @@ impl InputHandler {
     fn handle_input(&mut self) {
         let mut err_ctx = OPENCALLS.with(|ctx| *ctx.borrow());
         err_ctx.add_call(ContextType::StdinHandler);
-        let bracketed_paste_start = vec![27, 91, 50, 48, 48, 126]; // \u{1b}[200~
-        let bracketed_paste_end = vec![27, 91, 50, 48, 49, 126]; // \u{1b}[201~
         if self.options.mouse_mode.unwrap_or(true) {
             self.os_input.enable_mouse();
         }
@@ -104,11 +102,7 @@ impl InputHandler {
                         }
                         InputEvent::Paste(pasted_text) => {
                             if self.mode == InputMode::Normal || self.mode == InputMode::Locked {
-                                self.dispatch_action(Action::Write(bracketed_paste_start.clone()));
-                                self.dispatch_action(Action::Write(
-                                    pasted_text.as_bytes().to_vec(),
-                                ));
-                                self.dispatch_action(Action::Write(bracketed_paste_end.clone()));
+                                self.dispatch_action(Action::Paste(pasted_text));
                             }
                         }
                         _ => {}
@@ -172,6 +166,22 @@ impl InputHandler {
                     }
                     self.holding_mouse = true;
                 }
+                MouseButton::CtrlLeft => {
+                    if self.holding_mouse {
+                        self.dispatch_action(Action::MouseHold(point));
+                    } else {
+                        self.dispatch_action(Action::CtrlLeftClick(point));
+                    }
+                    self.holding_mouse = true;
+                }
+                MouseButton::AltLeft => {
+                    if self.holding_mouse {
+                        self.dispatch_action(Action::MouseHold(point));
+                    } else {
+                        self.dispatch_action(Action::AltLeftClick(point));
+                    }
+                    self.holding_mouse = true;
+                }
                 MouseButton::Right => {
                     if self.holding_mouse {
                         self.dispatch_action(Action::MouseHold(point));
@@ -50,7 +50,10 @@ fn should_hold_mouse(input_event: InputEvent) -> bool {
         let mouse_event = zellij_utils::input::mouse::MouseEvent::from(mouse_event);
         if let MouseEvent::Press(button, _point) = mouse_event {
             match button {
-                MouseButton::Left | MouseButton::Right => {
+                MouseButton::Left
+                | MouseButton::CtrlLeft
+                | MouseButton::AltLeft
+                | MouseButton::Right => {
                     return true;
                 }
                 _ => {}
@@ -73,6 +73,7 @@ pub enum ServerInstruction {
     DetachSession(ClientId),
     AttachClient(ClientAttributes, Options, ClientId),
     ConnStatus(ClientId),
+    OpenUrl(String),
 }
 
 impl From<&ServerInstruction> for ServerContext {
@@ -88,6 +89,7 @@ impl From<&ServerInstruction> for ServerContext {
             ServerInstruction::DetachSession(..) => ServerContext::DetachSession,
             ServerInstruction::AttachClient(..) => ServerContext::AttachClient,
             ServerInstruction::ConnStatus(..) => ServerContext::ConnStatus,
+            ServerInstruction::OpenUrl(..) => ServerContext::OpenUrl,
         }
     }
 }
@@ -529,6 +531,26 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                 os_input.send_to_client(client_id, ServerToClientMsg::Connected);
                 remove_client!(client_id, os_input, session_state);
             }
+            ServerInstruction::OpenUrl(url) => {
+                let opener = if cfg!(target_os = "macos") {
+                    "open"
+                } else {
+                    "xdg-open"
+                };
+                match std::process::Command::new(opener).arg(&url).spawn() {
+                    Ok(mut child) => {
+                        // reap the opener once it exits so it does not linger as a zombie
+                        let _ = thread::Builder::new().name("url_opener".to_string()).spawn(
+                            move || {
+                                let _ = child.wait();
+                            },
+                        );
+                    }
+                    Err(e) => {
+                        log::error!("Failed to open {}: {}", url, e);
+                    }
+                }
+            }
         }
     }
 
@@ -621,7 +643,7 @@ fn init_session(
         .spawn({
             let screen_bus = Bus::new(
                 vec![screen_receiver, bounded_screen_receiver],
-                None,
+                Some(&to_screen), // for the instructions the screen sends itself, eg. timers
                 Some(&to_pty),
                 Some(&to_plugin),
                 Some(&to_server),
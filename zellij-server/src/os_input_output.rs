@@ -4,7 +4,7 @@ use crate::panes::PaneId;
 
 use std::env;
 use std::os::unix::io::RawFd;
-use std::os::unix::process::CommandExt;
+use std::os::unix::process::{CommandExt, ExitStatusExt};
 use std::path::PathBuf;
 use std::process::{Child, Command};
 use std::sync::{Arc, Mutex};
@@ -18,7 +18,7 @@ use interprocess::local_socket::LocalSocketStream;
 use sysinfo::{ProcessExt, ProcessRefreshKind, System, SystemExt};
 
 use nix::pty::{openpty, OpenptyResult, Winsize};
-use nix::sys::signal::{kill, Signal};
+use nix::sys::signal::{kill, killpg, Signal};
 use nix::sys::termios;
 
 use nix::unistd;
@@ -100,7 +100,7 @@ fn handle_command_exit(mut child: Child) {
 fn handle_openpty(
     open_pty_res: OpenptyResult,
     cmd: RunCommand,
-    quit_cb: Box<dyn Fn(PaneId) + Send>,
+    quit_cb: Box<dyn Fn(PaneId, i32) + Send>,
 ) -> (RawFd, RawFd) {
     // primary side of pty and child fd
     let pid_primary = open_pty_res.master;
@@ -128,11 +128,17 @@ fn handle_openpty(
 
     let child_id = child.id();
     std::thread::spawn(move || {
-        child.wait().unwrap();
+        let exit_status = child.wait().unwrap();
         handle_command_exit(child);
-        let _ = nix::unistd::close(pid_primary);
+        // the primary side stays open until the pane is closed (see `Pty::close_pane`), otherwise
+        // a new pane could be given the same fd while this one is still held open
         let _ = nix::unistd::close(pid_secondary);
-        quit_cb(PaneId::Terminal(pid_primary));
+        // processes killed by a signal have no exit code, so we report them the way shells do
+        let exit_code = exit_status
+            .code()
+            .or_else(|| exit_status.signal().map(|signal| 128 + signal))
+            .unwrap_or(-1);
+        quit_cb(PaneId::Terminal(pid_primary), exit_code);
     });
 
     (pid_primary, child_id as RawFd)
@@ -144,7 +150,7 @@ fn handle_openpty(
 fn handle_terminal(
     cmd: RunCommand,
     orig_termios: termios::Termios,
-    quit_cb: Box<dyn Fn(PaneId) + Send>,
+    quit_cb: Box<dyn Fn(PaneId, i32) + Send>,
 ) -> (RawFd, RawFd) {
     // Create a pipe to allow the child the communicate the shell's pid to it's
     // parent.
@@ -171,7 +177,7 @@ fn handle_terminal(
 pub fn spawn_terminal(
     terminal_action: TerminalAction,
     orig_termios: termios::Termios,
-    quit_cb: Box<dyn Fn(PaneId) + Send>,
+    quit_cb: Box<dyn Fn(PaneId, i32) + Send>,
 ) -> (RawFd, RawFd) {
     let cmd = match terminal_action {
         TerminalAction::OpenFile(file_to_open) => {
@@ -242,7 +248,7 @@ pub trait ServerOsApi: Send + Sync {
     fn spawn_terminal(
         &self,
         terminal_action: TerminalAction,
-        quit_cb: Box<dyn Fn(PaneId) + Send>,
+        quit_cb: Box<dyn Fn(PaneId, i32) + Send>,
     ) -> (RawFd, RawFd);
     /// Read bytes from the standard output of the virtual terminal referred to by `fd`.
     fn read_from_tty_stdout(&self, fd: RawFd, buf: &mut [u8]) -> Result<usize, nix::Error>;
@@ -256,6 +262,8 @@ pub trait ServerOsApi: Send + Sync {
     fn kill(&self, pid: Pid) -> Result<(), nix::Error>;
     /// Terminate the process with process ID `pid`. (SIGKILL)
     fn force_kill(&self, pid: Pid) -> Result<(), nix::Error>;
+    /// Close the primary side of the virtual terminal referred to by `fd`
+    fn close_pty(&self, fd: RawFd) -> Result<(), nix::Error>;
     /// Returns a [`Box`] pointer to this [`ServerOsApi`] struct.
     fn box_clone(&self) -> Box<dyn ServerOsApi>;
     fn send_to_client(&self, client_id: ClientId, msg: ServerToClientMsg);
@@ -268,6 +276,13 @@ pub trait ServerOsApi: Send + Sync {
     fn load_palette(&self) -> Palette;
     /// Returns the current working directory for a given pid
     fn get_cwd(&self, pid: Pid) -> Option<PathBuf>;
+    /// Returns the foreground process group of the terminal referred to by `fd`
+    fn get_foreground_process_group(&self, fd: RawFd) -> Option<Pid>;
+    /// Returns the session of the terminal referred to by `fd`, which is identified by the pid of
+    /// its leader (usually the shell)
+    fn get_session_id(&self, fd: RawFd) -> Option<Pid>;
+    /// Sends the signal numbered `signal` to every process in the process group `pgid`
+    fn signal_process_group(&self, pgid: Pid, signal: i32) -> Result<(), nix::Error>;
 }
 
 impl ServerOsApi for ServerOsInputOutput {
@@ -279,7 +294,7 @@ impl ServerOsApi for ServerOsInputOutput {
     fn spawn_terminal(
         &self,
         terminal_action: TerminalAction,
-        quit_cb: Box<dyn Fn(PaneId) + Send>,
+        quit_cb: Box<dyn Fn(PaneId, i32) + Send>,
     ) -> (RawFd, RawFd) {
         let orig_termios = self.orig_termios.lock().unwrap();
         spawn_terminal(terminal_action, orig_termios.clone(), quit_cb)
@@ -307,6 +322,9 @@ impl ServerOsApi for ServerOsInputOutput {
         let _ = kill(pid, Some(Signal::SIGKILL));
         Ok(())
     }
+    fn close_pty(&self, fd: RawFd) -> Result<(), nix::Error> {
+        unistd::close(fd)
+    }
     fn send_to_client(&self, client_id: ClientId, msg: ServerToClientMsg) {
         if let Some(sender) = self.client_senders.lock().unwrap().get_mut(&client_id) {
             sender.send(msg);
@@ -345,6 +363,18 @@ impl ServerOsApi for ServerOsInputOutput {
         }
         None
     }
+    fn get_foreground_process_group(&self, fd: RawFd) -> Option<Pid> {
+        unistd::tcgetpgrp(fd).ok()
+    }
+    fn signal_process_group(&self, pgid: Pid, signal: i32) -> Result<(), nix::Error> {
+        killpg(pgid, Signal::try_from(signal)?)
+    }
+    fn get_session_id(&self, fd: RawFd) -> Option<Pid> {
+        match unsafe { libc::tcgetsid(fd) } {
+            -1 => None,
+            session_id => Some(Pid::from_raw(session_id)),
+        }
+    }
 }
 
 impl Clone for Box<dyn ServerOsApi> {
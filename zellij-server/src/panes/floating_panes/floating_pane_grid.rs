@@ -112,8 +112,7 @@ impl<'a> FloatingPaneGrid<'a> {
                 {
                     new_pane_geom.x = new_pane_geom.x.saturating_sub(excess_width);
                 } else if excess_width > 0
-                    && new_pane_geom.cols.as_usize().saturating_sub(excess_width)
-                        > MIN_TERMINAL_WIDTH
+                    && new_pane_geom.cols.as_usize().saturating_sub(excess_width) > pane.min_width()
                 {
                     new_pane_geom
                         .cols
@@ -127,7 +126,7 @@ impl<'a> FloatingPaneGrid<'a> {
                     new_pane_geom.x = new_viewport.x;
                     new_pane_geom
                         .cols
-                        .set_inner(std::cmp::max(reduced_width, MIN_TERMINAL_WIDTH));
+                        .set_inner(std::cmp::max(reduced_width, pane.min_width()));
                 }
 
                 // handle shrink height
@@ -137,7 +136,7 @@ impl<'a> FloatingPaneGrid<'a> {
                     new_pane_geom.y = new_pane_geom.y.saturating_sub(excess_height);
                 } else if excess_height > 0
                     && new_pane_geom.rows.as_usize().saturating_sub(excess_height)
-                        > MIN_TERMINAL_HEIGHT
+                        > pane.min_height()
                 {
                     new_pane_geom
                         .rows
@@ -151,7 +150,7 @@ impl<'a> FloatingPaneGrid<'a> {
                     new_pane_geom.y = new_viewport.y;
                     new_pane_geom
                         .rows
-                        .set_inner(std::cmp::max(reduced_height, MIN_TERMINAL_HEIGHT));
+                        .set_inner(std::cmp::max(reduced_height, pane.min_height()));
                 }
 
                 // handle expand width
@@ -450,7 +449,7 @@ impl<'a> FloatingPaneGrid<'a> {
     ) -> Option<usize> {
         let panes = self.panes.borrow();
         let pane = panes.get(pane_id).unwrap();
-        let space_left_to_decrease = pane.cols().saturating_sub(MIN_TERMINAL_WIDTH);
+        let space_left_to_decrease = pane.cols().saturating_sub(pane.min_width());
         if space_left_to_decrease.saturating_sub(max_decrease_by) > 0 {
             Some(max_decrease_by)
         } else if space_left_to_decrease > 0 {
@@ -483,14 +482,14 @@ impl<'a> FloatingPaneGrid<'a> {
     ) -> Option<usize> {
         let panes = self.panes.borrow();
         let pane = panes.get(pane_id).unwrap();
-        let space_left_to_decrease = pane.cols().saturating_sub(MIN_TERMINAL_WIDTH);
+        let space_left_to_decrease = pane.cols().saturating_sub(pane.min_width());
         let pane_right_edge = pane.x() + pane.cols();
         if space_left_to_decrease.saturating_sub(max_decrease_by) > 0
-            && pane.x() + max_decrease_by <= pane_right_edge + MIN_TERMINAL_WIDTH
+            && pane.x() + max_decrease_by <= pane_right_edge + pane.min_width()
         {
             Some(max_decrease_by)
         } else if space_left_to_decrease > 0
-            && pane.x() + max_decrease_by <= pane_right_edge + MIN_TERMINAL_WIDTH
+            && pane.x() + max_decrease_by <= pane_right_edge + pane.min_width()
         {
             Some(space_left_to_decrease)
         } else {
@@ -521,14 +520,14 @@ impl<'a> FloatingPaneGrid<'a> {
     ) -> Option<usize> {
         let panes = self.panes.borrow();
         let pane = panes.get(pane_id).unwrap();
-        let space_left_to_decrease = pane.rows().saturating_sub(MIN_TERMINAL_HEIGHT);
+        let space_left_to_decrease = pane.rows().saturating_sub(pane.min_height());
         let pane_bottom_edge = pane.y() + pane.rows();
         if space_left_to_decrease.saturating_sub(max_decrease_by) > 0
-            && pane.y() + max_decrease_by <= pane_bottom_edge + MIN_TERMINAL_HEIGHT
+            && pane.y() + max_decrease_by <= pane_bottom_edge + pane.min_height()
         {
             Some(max_decrease_by)
         } else if space_left_to_decrease > 0
-            && pane.y() + max_decrease_by <= pane_bottom_edge + MIN_TERMINAL_HEIGHT
+            && pane.y() + max_decrease_by <= pane_bottom_edge + pane.min_height()
         {
             Some(space_left_to_decrease)
         } else {
@@ -550,7 +549,7 @@ impl<'a> FloatingPaneGrid<'a> {
     fn can_decrease_pane_size_up(&self, pane_id: &PaneId, max_decrease_by: usize) -> Option<usize> {
         let panes = self.panes.borrow();
         let pane = panes.get(pane_id).unwrap();
-        let space_left_to_decrease = pane.rows().saturating_sub(MIN_TERMINAL_HEIGHT);
+        let space_left_to_decrease = pane.rows().saturating_sub(pane.min_height());
         if space_left_to_decrease.saturating_sub(max_decrease_by) > 0 {
             Some(max_decrease_by)
         } else if space_left_to_decrease > 0 {
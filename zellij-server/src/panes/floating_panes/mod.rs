@@ -2,13 +2,15 @@ use zellij_tile::prelude::Style;
 mod floating_pane_grid;
 use zellij_utils::{position::Position, zellij_tile};
 
-use crate::tab::Pane;
+use crate::tab::{Pane, MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH};
 use floating_pane_grid::FloatingPaneGrid;
 
 use crate::{
     os_input_output::ServerOsApi,
     output::{FloatingPanesStack, Output},
     panes::PaneId,
+    tab::report_active_pane,
+    thread_bus::ThreadSenders,
     ui::pane_contents_and_ui::PaneContentsAndUi,
     ClientId,
 };
@@ -48,6 +50,9 @@ pub struct FloatingPanes {
     active_panes: HashMap<ClientId, PaneId>,
     show_panes: bool,
     pane_being_moved_with_mouse: Option<(PaneId, Position)>,
+    min_pane_width: usize,
+    min_pane_height: usize,
+    senders: ThreadSenders,
 }
 
 #[allow(clippy::borrowed_box)]
@@ -62,6 +67,7 @@ impl FloatingPanes {
         session_is_mirrored: bool,
         default_mode_info: ModeInfo,
         style: Style,
+        senders: ThreadSenders,
     ) -> Self {
         FloatingPanes {
             panes: BTreeMap::new(),
@@ -78,6 +84,9 @@ impl FloatingPanes {
             show_panes: false,
             active_panes: HashMap::new(),
             pane_being_moved_with_mouse: None,
+            min_pane_width: MIN_TERMINAL_WIDTH,
+            min_pane_height: MIN_TERMINAL_HEIGHT,
+            senders,
         }
     }
     pub fn stack(&self) -> Option<FloatingPanesStack> {
@@ -95,7 +104,15 @@ impl FloatingPanes {
     pub fn pane_ids(&self) -> impl Iterator<Item = &PaneId> {
         self.panes.keys()
     }
-    pub fn add_pane(&mut self, pane_id: PaneId, pane: Box<dyn Pane>) {
+    pub fn set_min_pane_size(&mut self, min_pane_width: usize, min_pane_height: usize) {
+        self.min_pane_width = min_pane_width;
+        self.min_pane_height = min_pane_height;
+        for pane in self.panes.values_mut() {
+            pane.set_min_size(min_pane_width, min_pane_height);
+        }
+    }
+    pub fn add_pane(&mut self, pane_id: PaneId, mut pane: Box<dyn Pane>) {
+        pane.set_min_size(self.min_pane_width, self.min_pane_height);
         self.desired_pane_positions
             .insert(pane_id, pane.position_and_size());
         self.panes.insert(pane_id, pane);
@@ -241,6 +258,21 @@ impl FloatingPanes {
         floating_pane_grid.resize(new_screen_size);
         self.set_force_render();
     }
+    // moves all panes along with the viewport they share with the tiled panes, a pane pushed past
+    // the far edge of the viewport is then brought back inside it by resize
+    pub fn offset_panes(&mut self, x: isize, y: isize) {
+        let offset = |mut geom: PaneGeom| {
+            geom.x = (geom.x as isize + x).max(0) as usize;
+            geom.y = (geom.y as isize + y).max(0) as usize;
+            geom
+        };
+        for pane in self.panes.values_mut() {
+            pane.set_geom(offset(pane.position_and_size()));
+        }
+        for geom in self.desired_pane_positions.values_mut() {
+            *geom = offset(*geom);
+        }
+    }
     pub fn resize_active_pane_left(
         &mut self,
         client_id: ClientId,
@@ -739,7 +771,6 @@ impl FloatingPanes {
             if active_pane_id == pane_id {
                 match next_active_pane {
                     Some(next_active_pane) => {
-                        self.active_panes.insert(client_id, next_active_pane);
                         self.focus_pane(next_active_pane, client_id);
                     }
                     None => {
@@ -753,7 +784,7 @@ impl FloatingPanes {
         let connected_clients: Vec<ClientId> =
             self.connected_clients.borrow().iter().copied().collect();
         for client_id in connected_clients {
-            self.active_panes.insert(client_id, pane_id);
+            self.set_active_pane(client_id, pane_id);
         }
         self.z_indices.retain(|p_id| *p_id != pane_id);
         self.z_indices.push(pane_id);
@@ -761,9 +792,19 @@ impl FloatingPanes {
         self.set_force_render();
     }
     pub fn focus_pane(&mut self, pane_id: PaneId, client_id: ClientId) {
-        self.active_panes.insert(client_id, pane_id);
+        self.set_active_pane(client_id, pane_id);
         self.focus_pane_for_all_clients(pane_id);
     }
+    // like TiledPanes::set_active_pane, plugins are only told about the change while the floating
+    // panes are visible, otherwise it happens when they are shown
+    fn set_active_pane(&mut self, client_id: ClientId, pane_id: PaneId) {
+        if self.active_panes.insert(client_id, pane_id) == Some(pane_id) || !self.show_panes {
+            return;
+        }
+        if let Some(pane) = self.panes.get(&pane_id) {
+            report_active_pane(&self.senders, client_id, pane.as_ref());
+        }
+    }
     pub fn defocus_pane(&mut self, pane_id: PaneId, client_id: ClientId) {
         self.z_indices.retain(|p_id| *p_id != pane_id);
         self.active_panes.remove(&client_id);
@@ -6,13 +6,16 @@ use std::{
     cmp::Ordering,
     collections::{BTreeSet, VecDeque},
     fmt::{self, Debug, Formatter},
+    path::PathBuf,
     str,
 };
 
 use zellij_utils::{
     consts::{DEFAULT_SCROLL_BUFFER_SIZE, SCROLL_BUFFER_SIZE},
+    lazy_static::lazy_static,
     pane_size::SizeInPixels,
     position::Position,
+    regex::Regex,
     vte, zellij_tile,
 };
 
@@ -26,9 +29,9 @@ use zellij_utils::{consts::VERSION, shared::version_number};
 use crate::output::{CharacterChunk, OutputBuffer};
 use crate::panes::alacritty_functions::{parse_number, xparse_color};
 use crate::panes::link_handler::LinkHandler;
-use crate::panes::selection::Selection;
+use crate::panes::selection::{Selection, SelectionMode};
 use crate::panes::terminal_character::{
-    AnsiCode, CharacterStyles, CharsetIndex, Cursor, CursorShape, StandardCharset,
+    AnsiCode, CharacterStyles, CharsetIndex, Cursor, CursorShape, LinkAnchor, StandardCharset,
     TerminalCharacter, EMPTY_TERMINAL_CHARACTER,
 };
 
@@ -113,6 +116,7 @@ fn transfer_rows_from_lines_above_to_viewport(
     viewport: &mut Vec<Row>,
     count: usize,
     max_viewport_width: usize,
+    scrollback_limit: usize,
 ) -> usize {
     let mut next_lines: Vec<Row> = vec![];
     let mut lines_added_to_viewport: isize = 0;
@@ -142,7 +146,7 @@ fn transfer_rows_from_lines_above_to_viewport(
     }
     if !next_lines.is_empty() {
         let excess_row = Row::from_rows(next_lines, 0);
-        bounded_push(lines_above, excess_row);
+        bounded_push(lines_above, excess_row, scrollback_limit);
     }
     match usize::try_from(lines_added_to_viewport) {
         Ok(n) => n,
@@ -155,6 +159,7 @@ fn transfer_rows_from_viewport_to_lines_above(
     lines_above: &mut VecDeque<Row>,
     count: usize,
     max_viewport_width: usize,
+    scrollback_limit: usize,
 ) -> isize {
     let mut next_lines: Vec<Row> = vec![];
     let mut transferred_rows_count: isize = 0;
@@ -175,7 +180,7 @@ fn transfer_rows_from_viewport_to_lines_above(
                 break; // no more rows
             }
         }
-        let dropped_line_width = bounded_push(lines_above, next_lines.remove(0));
+        let dropped_line_width = bounded_push(lines_above, next_lines.remove(0), scrollback_limit);
         if let Some(width) = dropped_line_width {
             transferred_rows_count -=
                 calculate_row_display_height(width, max_viewport_width) as isize;
@@ -231,9 +236,9 @@ fn transfer_rows_from_lines_below_to_viewport(
     }
 }
 
-fn bounded_push(vec: &mut VecDeque<Row>, value: Row) -> Option<usize> {
+fn bounded_push(vec: &mut VecDeque<Row>, value: Row, max_len: usize) -> Option<usize> {
     let mut dropped_line_width = None;
-    if vec.len() >= *SCROLL_BUFFER_SIZE.get().unwrap() {
+    if vec.len() >= max_len {
         let line = vec.pop_front();
         if let Some(line) = line {
             dropped_line_width = Some(line.width());
@@ -243,6 +248,15 @@ fn bounded_push(vec: &mut VecDeque<Row>, value: Row) -> Option<usize> {
     dropped_line_width
 }
 
+// falls back to the default instead of .get().unwrap() to prevent unit tests from panicking
+// where SCROLL_BUFFER_SIZE is uninitialized, without initializing it for them
+fn scroll_buffer_size() -> usize {
+    SCROLL_BUFFER_SIZE
+        .get()
+        .copied()
+        .unwrap_or(DEFAULT_SCROLL_BUFFER_SIZE)
+}
+
 pub fn create_horizontal_tabstops(columns: usize) -> BTreeSet<usize> {
     let mut i = TABSTOP_WIDTH;
     let mut horizontal_tabstops = BTreeSet::new();
@@ -271,6 +285,23 @@ fn subtract_isize_from_usize(u: usize, i: isize) -> usize {
     }
 }
 
+fn parse_working_directory_url(url: &str) -> Option<PathBuf> {
+    // eg. file://hostname/home/user/some%20folder
+    let without_scheme = url.strip_prefix("file://")?;
+    let path = &without_scheme[without_scheme.find('/')?..];
+    let mut decoded = Vec::with_capacity(path.len());
+    let mut bytes = path.bytes();
+    while let Some(byte) = bytes.next() {
+        if byte == b'%' {
+            let hex: String = bytes.by_ref().take(2).map(char::from).collect();
+            decoded.push(u8::from_str_radix(&hex, 16).ok()?);
+        } else {
+            decoded.push(byte);
+        }
+    }
+    String::from_utf8(decoded).ok().map(PathBuf::from)
+}
+
 #[derive(Clone)]
 pub struct Grid {
     lines_above: VecDeque<Row>,
@@ -305,8 +336,17 @@ pub struct Grid {
     pub selection: Selection,
     pub title: Option<String>,
     pub is_scrolled: bool,
+    scrollback_limit: usize,  // the most lines kept in lines_above
+    horizontal_scroll: usize, // columns hidden to the left of the viewport
+    line_wrap: bool, // when off, lines are clipped at the edge of the pane instead of wrapped
     pub link_handler: Rc<RefCell<LinkHandler>>,
     pub ring_bell: bool,
+    pub bell_received: bool, // like ring_bell, but cleared when reported to plugins rather than on render
+    pub progress: Option<u8>, // percent done, as reported by the program with OSC 9;4
+    pub progress_changed: bool, // cleared when reported to plugins
+    pub title_changed: bool, // set by OSC 0/2 and popping the title stack, cleared when reported to plugins
+    pub pending_clipboard: Option<String>, // set by the program with OSC52
+    pub working_directory: Option<PathBuf>, // as reported by the shell with OSC 7
     scrollback_buffer_lines: usize,
     pub mouse_mode: bool,
 }
@@ -332,12 +372,9 @@ impl Grid {
         link_handler: Rc<RefCell<LinkHandler>>,
         character_cell_size: Rc<RefCell<Option<SizeInPixels>>>,
     ) -> Self {
+        let scrollback_limit = scroll_buffer_size();
         Grid {
-            lines_above: VecDeque::with_capacity(
-                // .get_or_init() is used instead of .get().unwrap() to prevent
-                // unit tests from panicking where SCROLL_BUFFER_SIZE is uninitialized.
-                *SCROLL_BUFFER_SIZE.get_or_init(|| DEFAULT_SCROLL_BUFFER_SIZE),
-            ),
+            lines_above: VecDeque::with_capacity(scrollback_limit),
             viewport: vec![Row::new(columns).canonical()],
             lines_below: vec![],
             horizontal_tabstops: create_horizontal_tabstops(columns),
@@ -362,10 +399,19 @@ impl Grid {
             selection: Default::default(),
             title_stack: vec![],
             title: None,
+            working_directory: None,
             changed_colors: None,
             is_scrolled: false,
+            scrollback_limit,
+            horizontal_scroll: 0,
+            line_wrap: true,
             link_handler,
             ring_bell: false,
+            progress: None,
+            progress_changed: false,
+            title_changed: false,
+            bell_received: false,
+            pending_clipboard: None,
             scrollback_buffer_lines: 0,
             mouse_mode: false,
             character_cell_size,
@@ -417,10 +463,13 @@ impl Grid {
     }
     pub fn scrollback_position_and_length(&mut self) -> (usize, usize) {
         // (position, length)
-        (
-            self.lines_below.len(),
-            (self.scrollback_buffer_lines + self.lines_below.len()),
-        )
+        (self.scroll_offset(), self.scrollback_len())
+    }
+    pub fn scroll_offset(&self) -> usize {
+        self.lines_below.len()
+    }
+    pub fn scrollback_len(&self) -> usize {
+        self.scrollback_buffer_lines + self.lines_below.len()
     }
 
     fn recalculate_scrollback_buffer_count(&self) -> usize {
@@ -515,6 +564,7 @@ impl Grid {
                 &mut self.viewport,
                 1,
                 self.width,
+                self.scrollback_limit,
             );
             self.scrollback_buffer_lines = self
                 .scrollback_buffer_lines
@@ -539,7 +589,11 @@ impl Grid {
                 last_line_above
             };
 
-            let dropped_line_width = bounded_push(&mut self.lines_above, line_to_push_up);
+            let dropped_line_width = bounded_push(
+                &mut self.lines_above,
+                line_to_push_up,
+                self.scrollback_limit,
+            );
             if let Some(width) = dropped_line_width {
                 let dropped_line_height = calculate_row_display_height(width, self.width);
 
@@ -586,7 +640,23 @@ impl Grid {
             return;
         }
         self.selection.reset();
-        if new_columns != self.width && self.alternate_lines_above_viewport_and_cursor.is_none() {
+        // the rows below the viewport are rewrapped along with it, so if the user scrolled up we
+        // go back to the bottom and later scroll up again to the line that was at the top
+        let scrolled_to_canonical_line = if !self.lines_below.is_empty()
+            && self.alternate_lines_above_viewport_and_cursor.is_none()
+        {
+            let top_canonical_line_index = self.top_canonical_line_index();
+            self.reset_viewport();
+            Some(top_canonical_line_index)
+        } else {
+            None
+        };
+        // rows are also rewrapped when line wrapping was turned back on
+        let has_rows_wider_than_new_columns =
+            self.viewport.iter().any(|row| row.width() > new_columns);
+        if (new_columns != self.width || has_rows_wider_than_new_columns)
+            && self.alternate_lines_above_viewport_and_cursor.is_none()
+        {
             self.horizontal_tabstops = create_horizontal_tabstops(new_columns);
             let mut cursor_canonical_line_index = self.cursor_canonical_line_index();
             let cursor_index_in_canonical_line = self.cursor_index_in_canonical_line();
@@ -640,8 +710,13 @@ impl Grid {
                 if canonical_line.columns.is_empty() {
                     canonical_line_parts.push(Row::new(new_columns).canonical());
                 }
+                let wrap_at = if self.line_wrap {
+                    new_columns
+                } else {
+                    usize::MAX
+                };
                 while !canonical_line.columns.is_empty() {
-                    let next_wrap = canonical_line.drain_until(new_columns);
+                    let next_wrap = canonical_line.drain_until(wrap_at);
                     // If the next character is wider than the grid (i.e. there is nothing in
                     // `next_wrap`, then just abort the resizing
                     if next_wrap.is_empty() {
@@ -665,8 +740,12 @@ impl Grid {
 
             let mut new_cursor_y = self.canonical_line_y_coordinates(cursor_canonical_line_index);
 
-            let new_cursor_x = (cursor_index_in_canonical_line / new_columns)
-                + (cursor_index_in_canonical_line % new_columns);
+            let new_cursor_x = if self.line_wrap {
+                (cursor_index_in_canonical_line / new_columns)
+                    + (cursor_index_in_canonical_line % new_columns)
+            } else {
+                cursor_index_in_canonical_line
+            };
             let current_viewport_row_count = self.viewport.len();
             match current_viewport_row_count.cmp(&self.height) {
                 Ordering::Less => {
@@ -677,6 +756,7 @@ impl Grid {
                         &mut self.viewport,
                         row_count_to_transfer,
                         new_columns,
+                        self.scrollback_limit,
                     );
                     let rows_pulled = self.viewport.len() - current_viewport_row_count;
                     new_cursor_y += rows_pulled;
@@ -693,6 +773,7 @@ impl Grid {
                         &mut self.lines_above,
                         row_count_to_transfer,
                         new_columns,
+                        self.scrollback_limit,
                     );
                 }
                 Ordering::Equal => {}
@@ -720,6 +801,7 @@ impl Grid {
                         &mut self.viewport,
                         row_count_to_transfer,
                         new_columns,
+                        self.scrollback_limit,
                     );
                     let rows_pulled = self.viewport.len() - current_viewport_row_count;
                     self.cursor.y += rows_pulled;
@@ -737,6 +819,7 @@ impl Grid {
                             &mut self.lines_above,
                             row_count_to_transfer,
                             new_columns,
+                            self.scrollback_limit,
                         );
                     } else {
                         // in alternate screen, no scroll buffer, so just remove lines
@@ -752,8 +835,27 @@ impl Grid {
             self.set_scroll_region_to_viewport_size();
         }
         self.scrollback_buffer_lines = self.recalculate_scrollback_buffer_count();
+        if let Some(canonical_line_index) = scrolled_to_canonical_line {
+            while self.top_canonical_line_index() > canonical_line_index {
+                let lines_below_count = self.lines_below.len();
+                self.scroll_up_one_line();
+                if self.lines_below.len() == lines_below_count {
+                    // we cannot scroll up any further
+                    break;
+                }
+            }
+        }
         self.output_buffer.update_all_lines();
     }
+    fn top_canonical_line_index(&self) -> usize {
+        // the index of the canonical line shown at the top of the viewport, counted from the top
+        // of the scrollback - if its first part is still above the viewport, it is the last of
+        // lines_above
+        match self.viewport.first() {
+            Some(row) if !row.is_canonical => self.lines_above.len().saturating_sub(1),
+            _ => self.lines_above.len(),
+        }
+    }
     pub fn as_character_lines(&self) -> Vec<Vec<TerminalCharacter>> {
         // this is only used in the tests
         // it's not part of testing the app, but rather is used to interpret the snapshots created
@@ -778,22 +880,272 @@ impl Grid {
         }
         lines
     }
+    pub fn viewport_as_text_lines(&self) -> Vec<String> {
+        self.viewport
+            .iter()
+            .map(|row| {
+                let line: String = row
+                    .columns
+                    .iter()
+                    .map(|terminal_character| terminal_character.character)
+                    .collect();
+                line.trim_end().to_owned()
+            })
+            .collect()
+    }
+    pub fn dump(&self, include_scrollback: bool, with_ansi: bool) -> String {
+        let mut rows: Vec<&Row> = vec![];
+        if include_scrollback {
+            rows.extend(self.lines_above.iter());
+        }
+        rows.extend(self.viewport.iter());
+        if include_scrollback {
+            rows.extend(self.lines_below.iter());
+        }
+        let mut lines: Vec<String> = vec![];
+        for row in rows {
+            if row.is_synthetic {
+                // this line was not written by the program, so it's not part of its output
+                continue;
+            }
+            let mut line = String::new();
+            let mut character_styles = CharacterStyles::new();
+            for terminal_character in &row.columns {
+                if with_ansi {
+                    if let Some(new_styles) = character_styles
+                        .update_and_return_diff(&terminal_character.styles, self.changed_colors)
+                    {
+                        line.push_str(&new_styles.to_string());
+                    }
+                }
+                line.push(terminal_character.character);
+            }
+            match lines.last_mut() {
+                // rejoin wrapped lines
+                Some(previous_line) if !row.is_canonical => previous_line.push_str(&line),
+                _ => lines.push(line),
+            }
+        }
+        let lines: Vec<String> = lines
+            .iter()
+            .map(|line| {
+                if with_ansi {
+                    // reset the styles so that they don't bleed into the next line
+                    format!("{}\u{1b}[m", line.trim_end())
+                } else {
+                    line.trim_end().to_owned()
+                }
+            })
+            .collect();
+        lines.join("\n")
+    }
+    pub fn dump_viewport(&self) -> String {
+        // unlike dump, this keeps wrapped lines on separate rows, as they are rendered
+        let mut rows = self.viewport_as_text_lines();
+        rows.resize(self.height, String::new());
+        rows.join("\n")
+    }
+    pub fn search(&self, query: &str) -> Vec<(usize, usize)> {
+        // returns the (line, column) of each match, lines are counted from the top of the
+        // scrollback
+        let query: Vec<char> = query.chars().collect();
+        if query.is_empty() {
+            return vec![];
+        }
+        let mut matches = vec![];
+        let rows = self
+            .lines_above
+            .iter()
+            .chain(self.viewport.iter())
+            .chain(self.lines_below.iter());
+        for (line_index, row) in rows.enumerate() {
+            let characters: Vec<char> = row.columns.iter().map(|c| c.character).collect();
+            let mut column = 0;
+            for (index, terminal_character) in row.columns.iter().enumerate() {
+                if characters[index..].starts_with(&query) {
+                    matches.push((line_index, column));
+                }
+                column += terminal_character.width;
+            }
+        }
+        matches
+    }
+    pub fn url_at(&self, position: &Position) -> Option<String> {
+        // urls often wrap across the end of a row, so we look for them in the whole (unwrapped)
+        // line the position is a part of
+        lazy_static! {
+            static ref URL_RE: Regex =
+                Regex::new(r#"(?:https?://|mailto:)[^\s<>"'`]*[^\s<>"'`.,;:!?)\]}]"#).unwrap();
+        }
+        let row_index = self.lines_above.len() + usize::try_from(position.line.0).ok()?;
+        self.row_at(row_index)?;
+        let mut first_row_index = row_index;
+        while first_row_index > 0 && !self.row_at(first_row_index).unwrap().is_canonical {
+            first_row_index -= 1;
+        }
+        let mut last_row_index = row_index;
+        while self
+            .row_at(last_row_index + 1)
+            .map(|row| !row.is_canonical)
+            .unwrap_or(false)
+        {
+            last_row_index += 1;
+        }
+        let mut line = String::new();
+        let mut position_in_line = None;
+        for index in first_row_index..=last_row_index {
+            let mut column = 0;
+            for terminal_character in self.row_at(index).unwrap().columns.iter() {
+                if index == row_index
+                    && column <= position.column.0
+                    && position.column.0 < column + terminal_character.width.max(1)
+                {
+                    position_in_line = Some(line.len());
+                }
+                line.push(terminal_character.character);
+                column += terminal_character.width;
+            }
+        }
+        let position_in_line = position_in_line?;
+        URL_RE
+            .find_iter(&line)
+            .find(|url| url.start() <= position_in_line && position_in_line < url.end())
+            .map(|url| url.as_str().to_owned())
+    }
+    pub fn hyperlink_at(&self, position: &Position) -> Option<String> {
+        // the target of an OSC 8 hyperlink, as given by the program that printed it
+        let row_index = self.lines_above.len() + usize::try_from(position.line.0).ok()?;
+        let row = self.row_at(row_index)?;
+        let mut column = 0;
+        for terminal_character in row.columns.iter() {
+            if column <= position.column.0
+                && position.column.0 < column + terminal_character.width.max(1)
+            {
+                return match terminal_character.styles.link_anchor {
+                    Some(LinkAnchor::Start(index)) => self.link_handler.borrow().uri(index),
+                    _ => None,
+                };
+            }
+            column += terminal_character.width;
+        }
+        None
+    }
+    // the row at the given index of the scrollback, the viewport and the lines below it
+    fn row_at(&self, index: usize) -> Option<&Row> {
+        let index_in_viewport = index.checked_sub(self.lines_above.len());
+        match index_in_viewport {
+            None => self.lines_above.get(index),
+            Some(index_in_viewport) if index_in_viewport < self.viewport.len() => {
+                self.viewport.get(index_in_viewport)
+            }
+            Some(index_in_viewport) => self
+                .lines_below
+                .get(index_in_viewport - self.viewport.len()),
+        }
+    }
+    pub fn viewport_start_in_scrollback(&self) -> usize {
+        self.lines_above.len()
+    }
     pub fn read_changes(&mut self, x_offset: usize, y_offset: usize) -> Vec<CharacterChunk> {
-        let changes = self.output_buffer.changed_chunks_in_viewport(
+        // the content might have narrowed since we last scrolled
+        self.horizontal_scroll =
+            std::cmp::min(self.horizontal_scroll, self.max_horizontal_scroll());
+        let mut changes = self.output_buffer.changed_chunks_in_viewport(
             &self.viewport,
             self.width,
             self.height,
             x_offset,
             y_offset,
         );
+        if self.horizontal_scroll > 0 || !self.line_wrap {
+            for chunk in changes.iter_mut() {
+                chunk.terminal_characters =
+                    self.horizontally_scrolled_characters(&chunk.terminal_characters);
+            }
+        }
         self.output_buffer.clear();
         changes
     }
+    fn horizontally_scrolled_characters(
+        &self,
+        terminal_characters: &[TerminalCharacter],
+    ) -> Vec<TerminalCharacter> {
+        let mut skipped_width = 0;
+        let mut visible_width = 0;
+        let mut visible_characters = Vec::with_capacity(self.width);
+        for terminal_character in terminal_characters {
+            if skipped_width < self.horizontal_scroll {
+                skipped_width += terminal_character.width;
+                continue;
+            }
+            if visible_width + terminal_character.width > self.width {
+                break;
+            }
+            visible_width += terminal_character.width;
+            visible_characters.push(*terminal_character);
+        }
+        // a wide character cut at the left edge leaves a gap, as does a short line
+        while visible_width < self.width {
+            visible_characters.push(EMPTY_TERMINAL_CHARACTER);
+            visible_width += 1;
+        }
+        visible_characters
+    }
+    fn max_horizontal_scroll(&self) -> usize {
+        let widest_line = self
+            .viewport
+            .iter()
+            .map(|row| row.width())
+            .max()
+            .unwrap_or(0);
+        widest_line.saturating_sub(self.width)
+    }
+    pub fn is_alternate_screen_active(&self) -> bool {
+        self.alternate_lines_above_viewport_and_cursor.is_some()
+    }
+    pub fn horizontal_scroll(&self) -> usize {
+        self.horizontal_scroll
+    }
+    pub fn line_wrap(&self) -> bool {
+        self.line_wrap
+    }
+    pub fn set_line_wrap(&mut self, line_wrap: bool) {
+        if line_wrap == self.line_wrap {
+            return;
+        }
+        self.line_wrap = line_wrap;
+        if line_wrap {
+            // rewrap the lines that were clipped while wrapping was off
+            self.change_size(self.height, self.width);
+            self.horizontal_scroll = 0;
+        }
+        self.output_buffer.update_all_lines();
+        self.mark_for_rerender();
+    }
+    pub fn scroll_left(&mut self, count: usize) {
+        let horizontal_scroll = self.horizontal_scroll.saturating_sub(count);
+        self.set_horizontal_scroll(horizontal_scroll);
+    }
+    pub fn scroll_right(&mut self, count: usize) {
+        let horizontal_scroll =
+            std::cmp::min(self.horizontal_scroll + count, self.max_horizontal_scroll());
+        self.set_horizontal_scroll(horizontal_scroll);
+    }
+    fn set_horizontal_scroll(&mut self, horizontal_scroll: usize) {
+        if horizontal_scroll != self.horizontal_scroll {
+            self.horizontal_scroll = horizontal_scroll;
+            self.output_buffer.update_all_lines();
+            self.mark_for_rerender();
+        }
+    }
     pub fn cursor_coordinates(&self) -> Option<(usize, usize)> {
-        if self.cursor.is_hidden {
+        if self.cursor.is_hidden
+            || self.cursor.x < self.horizontal_scroll
+            || self.cursor.x > self.horizontal_scroll + self.width
+        {
             None
         } else {
-            Some((self.cursor.x, self.cursor.y))
+            Some((self.cursor.x - self.horizontal_scroll, self.cursor.y))
         }
     }
     pub fn move_viewport_up(&mut self, count: usize) {
@@ -932,6 +1284,45 @@ impl Grid {
             self.output_buffer.update_line(self.cursor.y);
         }
     }
+    pub fn insert_synthetic_line(&mut self, text: &str) {
+        if self.alternate_lines_above_viewport_and_cursor.is_some() {
+            // we don't want to interfere with full screen applications
+            return;
+        }
+        let styles = CharacterStyles::new()
+            .foreground(Some(self.colors.orange.into()))
+            .italic(Some(AnsiCode::On));
+        let columns: VecDeque<TerminalCharacter> = text
+            .chars()
+            .map(|character| TerminalCharacter {
+                character,
+                width: character.width().unwrap_or(0),
+                styles,
+            })
+            .collect();
+        let mut synthetic_row = Row::from_columns(columns).canonical().synthetic();
+        synthetic_row.truncate(self.width);
+
+        // we insert the line right above the line the cursor is on (or above the line it wraps
+        // from) so that from the point of view of the program everything stays where it was
+        let mut insert_at = std::cmp::min(self.cursor.y, self.viewport.len());
+        while insert_at > 0
+            && insert_at < self.viewport.len()
+            && !self.viewport[insert_at].is_canonical
+        {
+            insert_at -= 1;
+        }
+        let cursor_line_exists = self.cursor.y < self.viewport.len();
+        self.viewport.insert(insert_at, synthetic_row);
+        if self.viewport.len() > self.height {
+            self.transfer_rows_to_lines_above(1);
+            self.selection.move_up(1);
+        } else if cursor_line_exists {
+            self.cursor.y += 1;
+        }
+        self.output_buffer.update_all_lines();
+        self.mark_for_rerender();
+    }
     pub fn move_cursor_to_beginning_of_line(&mut self) {
         self.cursor.x = 0;
     }
@@ -993,6 +1384,12 @@ impl Grid {
             if self.disable_linewrap {
                 return;
             }
+            if !self.line_wrap {
+                // the line grows past the edge of the pane, scrolling horizontally reveals it
+                self.add_character_at_cursor_position(terminal_character);
+                self.cursor.x += character_width;
+                return;
+            }
             // line wrap
             self.cursor.x = 0;
             if self.cursor.y == self.height - 1 {
@@ -1028,7 +1425,7 @@ impl Grid {
         self.viewport.get(y).unwrap().absolute_character_index(x)
     }
     pub fn move_cursor_forward_until_edge(&mut self, count: usize) {
-        let count_to_move = std::cmp::min(count, self.width - self.cursor.x);
+        let count_to_move = std::cmp::min(count, self.width.saturating_sub(self.cursor.x));
         self.cursor.x += count_to_move;
     }
     pub fn replace_characters_in_line_after_cursor(&mut self, replace_with: TerminalCharacter) {
@@ -1075,6 +1472,28 @@ impl Grid {
         }
         self.output_buffer.update_all_lines();
     }
+    pub fn clear_screen_and_scrollback(&mut self) {
+        self.reset_selection();
+        self.lines_above.clear();
+        self.lines_below.clear();
+        self.viewport = vec![Row::new(self.width).canonical()];
+        self.is_scrolled = false;
+        self.scrollback_buffer_lines = 0;
+        self.cursor.x = 0;
+        self.cursor.y = 0;
+        self.clear_viewport_before_rendering = true;
+        self.output_buffer.update_all_lines();
+        self.mark_for_rerender();
+    }
+    pub fn set_scrollback_limit(&mut self, lines: usize) {
+        self.scrollback_limit = lines;
+        if self.lines_above.len() > lines {
+            let lines_to_drop = self.lines_above.len() - lines;
+            self.lines_above.drain(..lines_to_drop);
+            self.scrollback_buffer_lines = self.recalculate_scrollback_buffer_count();
+            self.output_buffer.update_all_lines();
+        }
+    }
     fn clear_lines_above(&mut self) {
         self.lines_above.clear();
         self.scrollback_buffer_lines = self.recalculate_scrollback_buffer_count();
@@ -1188,6 +1607,9 @@ impl Grid {
     pub fn show_cursor(&mut self) {
         self.cursor.is_hidden = false;
     }
+    pub fn cursor_is_hidden(&self) -> bool {
+        self.cursor.is_hidden
+    }
     pub fn set_scroll_region(&mut self, top_line_index: usize, bottom_line_index: Option<usize>) {
         let bottom_line_index = bottom_line_index.unwrap_or(self.height);
         self.scroll_region = Some((top_line_index, bottom_line_index));
@@ -1299,7 +1721,7 @@ impl Grid {
         self.should_render = true;
     }
     fn reset_terminal_state(&mut self) {
-        self.lines_above = VecDeque::with_capacity(*SCROLL_BUFFER_SIZE.get().unwrap());
+        self.lines_above = VecDeque::with_capacity(scroll_buffer_size());
         self.lines_below = vec![];
         self.viewport = vec![Row::new(self.width).canonical()];
         self.alternate_lines_above_viewport_and_cursor = None;
@@ -1320,8 +1742,12 @@ impl Grid {
         self.preceding_char = Some(terminal_character);
     }
     pub fn start_selection(&mut self, start: &Position) {
+        self.start_selection_in_mode(start, SelectionMode::Stream);
+    }
+    pub fn start_selection_in_mode(&mut self, start: &Position, mode: SelectionMode) {
         let old_selection = self.selection;
         self.selection.start(*start);
+        self.selection.mode = mode;
         self.update_selected_lines(&old_selection, &self.selection.clone());
         self.mark_for_rerender();
     }
@@ -1354,18 +1780,26 @@ impl Grid {
         let sorted_selection = self.selection.sorted();
         let (start, end) = (sorted_selection.start, sorted_selection.end);
 
+        let is_block_selection = sorted_selection.mode == SelectionMode::Block;
+
         for l in sorted_selection.line_indices() {
             let mut line_selection = String::new();
 
-            // on the first line of the selection, use the selection start column
-            // otherwise, start at the beginning of the line
-            let start_column = if l == start.line.0 { start.column.0 } else { 0 };
-
-            // same thing on the last line, but with the selection end column
-            let end_column = if l == end.line.0 {
-                end.column.0
+            let (start_column, end_column) = if is_block_selection {
+                // a block selection uses the same columns on every line
+                sorted_selection.block_columns()
             } else {
-                self.width
+                // on the first line of the selection, use the selection start column
+                // otherwise, start at the beginning of the line
+                let start_column = if l == start.line.0 { start.column.0 } else { 0 };
+
+                // same thing on the last line, but with the selection end column
+                let end_column = if l == end.line.0 {
+                    end.column.0
+                } else {
+                    self.width
+                };
+                (start_column, end_column)
             };
 
             if start_column == end_column {
@@ -1395,6 +1829,11 @@ impl Grid {
                 continue;
             };
 
+            if row.is_synthetic {
+                // this line was not written by the program, so it's not part of its output
+                continue;
+            }
+
             let mut terminal_col = 0;
             for terminal_character in &row.columns {
                 if (start_column..end_column).contains(&terminal_col) {
@@ -1404,7 +1843,7 @@ impl Grid {
                 terminal_col += terminal_character.width;
             }
 
-            if row.is_canonical {
+            if row.is_canonical || is_block_selection {
                 selection.push(line_selection);
             } else {
                 // rejoin wrapped lines if possible
@@ -1433,6 +1872,9 @@ impl Grid {
         }
     }
     fn set_title(&mut self, title: String) {
+        if self.title.as_ref() != Some(&title) {
+            self.title_changed = true;
+        }
         self.title = Some(title);
     }
     fn push_current_title_to_stack(&mut self) {
@@ -1445,7 +1887,7 @@ impl Grid {
     }
     fn pop_title_from_stack(&mut self) {
         if let Some(popped_title) = self.title_stack.pop() {
-            self.title = Some(popped_title);
+            self.set_title(popped_title);
         }
     }
     fn transfer_rows_to_lines_above(&mut self, count: usize) {
@@ -1454,6 +1896,7 @@ impl Grid {
             &mut self.lines_above,
             count,
             self.width,
+            self.scrollback_limit,
         );
 
         self.scrollback_buffer_lines =
@@ -1467,11 +1910,15 @@ impl Perform for Grid {
 
         // apparently, building TerminalCharacter like this without a "new" method
         // is a little faster
-        let terminal_character = TerminalCharacter {
+        let mut terminal_character = TerminalCharacter {
             character: c,
             width: c.width().unwrap_or(0),
             styles: self.cursor.pending_styles,
         };
+        if let Some(LinkAnchor::Start(_)) = terminal_character.styles.link_anchor {
+            // so that hyperlinks stand out even in terminals that do not mark them
+            terminal_character.styles.underline = Some(AnsiCode::On);
+        }
         self.set_preceding_character(terminal_character);
         self.add_character(terminal_character);
     }
@@ -1480,6 +1927,7 @@ impl Perform for Grid {
         match byte {
             7 => {
                 self.ring_bell = true;
+                self.bell_received = true;
             }
             8 => {
                 // backspace
@@ -1558,6 +2006,20 @@ impl Perform for Grid {
                 }
             }
 
+            // Set current working directory.
+            b"7" => {
+                if params.len() >= 2 {
+                    let url = params[1..]
+                        .iter()
+                        .flat_map(|x| str::from_utf8(x))
+                        .collect::<Vec<&str>>()
+                        .join(";");
+                    if let Some(working_directory) = parse_working_directory_url(&url) {
+                        self.working_directory = Some(working_directory);
+                    }
+                }
+            }
+
             // define hyperlink
             b"8" => {
                 if params.len() < 3 {
@@ -1567,6 +2029,25 @@ impl Perform for Grid {
                     self.link_handler.borrow_mut().dispatch_osc8(params);
             }
 
+            // Report progress (ConEmu), the state is 0 when done, 1 for a normal, 2 for an error,
+            // 3 for an indeterminate and 4 for a paused progress
+            b"9" => {
+                if params.len() >= 3 && params[1] == b"4" {
+                    let progress = match params[2] {
+                        b"0" | b"3" => None,
+                        _ => params
+                            .get(3)
+                            .and_then(|percent| parse_number(percent))
+                            .map(|percent| std::cmp::min(percent, 100))
+                            .or(self.progress),
+                    };
+                    if progress != self.progress {
+                        self.progress = progress;
+                        self.progress_changed = true;
+                    }
+                }
+            }
+
             // Get/set Foreground, Background, Cursor colors.
             b"10" | b"11" | b"12" => {
                 if params.len() >= 2 {
@@ -1629,9 +2110,13 @@ impl Perform for Grid {
                     b"?" => {
                         // TBD: paste from own clipboard - currently unsupported
                     }
-                    _base64 => {
-                        // TBD: copy to own clipboard - currently unsupported
-                    }
+                    base64_content => match base64::decode(base64_content)
+                        .ok()
+                        .and_then(|content| String::from_utf8(content).ok())
+                    {
+                        Some(content) => self.pending_clipboard = Some(content),
+                        None => log::error!("Failed to decode clipboard content from OSC52"),
+                    },
                 }
             }
 
@@ -1826,7 +2311,7 @@ impl Perform for Grid {
                         // enter alternate buffer
                         let current_lines_above = std::mem::replace(
                             &mut self.lines_above,
-                            VecDeque::with_capacity(*SCROLL_BUFFER_SIZE.get().unwrap()),
+                            VecDeque::with_capacity(scroll_buffer_size()),
                         );
                         let current_viewport = std::mem::replace(
                             &mut self.viewport,
@@ -2132,6 +2617,7 @@ impl Perform for Grid {
 pub struct Row {
     pub columns: VecDeque<TerminalCharacter>,
     pub is_canonical: bool,
+    pub is_synthetic: bool, // inserted by us rather than by the program running in the terminal
     width: Option<usize>,
 }
 
@@ -2149,6 +2635,7 @@ impl Row {
         Row {
             columns: VecDeque::with_capacity(width),
             is_canonical: false,
+            is_synthetic: false,
             width: None,
         }
     }
@@ -2156,6 +2643,7 @@ impl Row {
         Row {
             columns,
             is_canonical: false,
+            is_synthetic: false,
             width: None,
         }
     }
@@ -2179,6 +2667,10 @@ impl Row {
         self.is_canonical = true;
         self
     }
+    pub fn synthetic(mut self) -> Self {
+        self.is_synthetic = true;
+        self
+    }
     pub fn width_cached(&mut self) -> usize {
         if self.width.is_some() {
             self.width.unwrap()
@@ -2462,6 +2954,9 @@ impl Row {
         if !parts.is_empty() && self.is_canonical {
             parts.get_mut(0).unwrap().is_canonical = true;
         }
+        for part in parts.iter_mut() {
+            part.is_synthetic = self.is_synthetic;
+        }
         if parts.is_empty() {
             parts.push(self.clone());
         }
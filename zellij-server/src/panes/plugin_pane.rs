@@ -3,12 +3,13 @@ use std::sync::mpsc::channel;
 use std::time::Instant;
 
 use crate::output::CharacterChunk;
-use crate::panes::PaneId;
+use crate::panes::{selection::SelectionMode, PaneId};
 use crate::pty::VteBytes;
-use crate::tab::Pane;
+use crate::tab::{Pane, MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH};
 use crate::ui::pane_boundaries_frame::{FrameParams, PaneFrame};
 use crate::wasm_vm::PluginInstruction;
 use crate::ClientId;
+use zellij_utils::input::layout::Run;
 use zellij_utils::pane_size::Offset;
 use zellij_utils::position::Position;
 use zellij_utils::shared::ansi_len;
@@ -32,6 +33,9 @@ pub(crate) struct PluginPane {
     pub pane_name: String,
     frame: bool,
     borderless: bool,
+    min_width: usize,
+    min_height: usize,
+    invoked_with: Option<Run>,
 }
 
 impl PluginPane {
@@ -54,7 +58,10 @@ impl PluginPane {
             content_offset: Offset::default(),
             pane_title: title,
             borderless: false,
+            min_width: MIN_TERMINAL_WIDTH,
+            min_height: MIN_TERMINAL_HEIGHT,
             pane_name,
+            invoked_with: None,
         }
     }
 }
@@ -231,10 +238,8 @@ impl Pane for PluginPane {
                 && frame_params.is_main_client
             {
                 String::from("Enter name...")
-            } else if self.pane_name.is_empty() {
-                self.pane_title.clone()
             } else {
-                self.pane_name.clone()
+                self.title()
             };
             let frame = PaneFrame::new(
                 self.current_geom().into(),
@@ -278,6 +283,20 @@ impl Pane for PluginPane {
             }
         }
     }
+    fn set_title(&mut self, title: String) {
+        self.pane_name = title;
+        self.should_render = true;
+    }
+    fn title(&self) -> String {
+        if self.pane_name.is_empty() {
+            self.pane_title.clone()
+        } else {
+            self.pane_name.clone()
+        }
+    }
+    fn pane_name(&self) -> String {
+        self.pane_name.clone()
+    }
     fn pid(&self) -> PaneId {
         PaneId::Plugin(self.pid)
     }
@@ -342,7 +361,12 @@ impl Pane for PluginPane {
     fn clear_scroll(&mut self) {
         // noop
     }
-    fn start_selection(&mut self, start: &Position, client_id: ClientId) {
+    fn start_selection(
+        &mut self,
+        start: &Position,
+        _selection_mode: SelectionMode,
+        client_id: ClientId,
+    ) {
         self.send_plugin_instructions
             .send(PluginInstruction::Update(
                 Some(self.pid),
@@ -392,6 +416,22 @@ impl Pane for PluginPane {
     fn borderless(&self) -> bool {
         self.borderless
     }
+    fn min_width(&self) -> usize {
+        self.min_width
+    }
+    fn min_height(&self) -> usize {
+        self.min_height
+    }
+    fn set_min_size(&mut self, min_width: usize, min_height: usize) {
+        self.min_width = min_width;
+        self.min_height = min_height;
+    }
+    fn set_invoked_with(&mut self, run: Option<Run>) {
+        self.invoked_with = run;
+    }
+    fn invoked_with(&self) -> Option<Run> {
+        self.invoked_with.clone()
+    }
     fn handle_right_click(&mut self, to: &Position, client_id: ClientId) {
         self.send_plugin_instructions
             .send(PluginInstruction::Update(
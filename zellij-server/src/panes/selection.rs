@@ -2,12 +2,25 @@ use std::{collections::HashSet, ops::Range};
 
 use zellij_utils::position::Position;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionMode {
+    Stream, // everything between start and end, wrapping around lines
+    Block,  // only the columns between start and end on each line
+}
+
+impl Default for SelectionMode {
+    fn default() -> Self {
+        SelectionMode::Stream
+    }
+}
+
 // The selection is empty when start == end
 // it includes the character at start, and everything before end.
 #[derive(Debug, Clone, Copy)]
 pub struct Selection {
     pub start: Position,
     pub end: Position,
+    pub mode: SelectionMode,
     active: bool, // used to handle moving the selection up and down
 }
 
@@ -16,6 +29,7 @@ impl Default for Selection {
         Self {
             start: Position::new(0, 0),
             end: Position::new(0, 0),
+            mode: SelectionMode::default(),
             active: false,
         }
     }
@@ -45,6 +59,13 @@ impl Selection {
             (self.end, self.start)
         };
 
+        if self.mode == SelectionMode::Block {
+            let (start_column, end_column) = self.block_columns();
+            return start.line.0 <= row
+                && row <= end.line.0
+                && start_column <= col
+                && col < end_column;
+        }
         if (start.line.0) < row && row < end.line.0 {
             return true;
         }
@@ -85,10 +106,20 @@ impl Selection {
         Self {
             start,
             end,
+            mode: self.mode,
             active: self.active,
         }
     }
 
+    pub fn block_columns(&self) -> (usize, usize) {
+        // the start and end columns of a block selection, regardless of the direction it was made in
+        let (start_column, end_column) = (self.start.column.0, self.end.column.0);
+        (
+            std::cmp::min(start_column, end_column),
+            std::cmp::max(start_column, end_column),
+        )
+    }
+
     pub fn line_indices(&self) -> std::ops::RangeInclusive<isize> {
         let sorted = self.sorted();
         sorted.start.line.0..=sorted.end.line.0
@@ -115,6 +146,13 @@ impl Selection {
         self
     }
 
+    // the columns of the selection as they are shown in a pane scrolled this far to the right
+    pub fn scrolled_right(mut self, columns: usize) -> Self {
+        self.start.column.0 = self.start.column.0.saturating_sub(columns);
+        self.end.column.0 = self.end.column.0.saturating_sub(columns);
+        self
+    }
+
     /// Return an iterator over the line indices, up to max, that are not present in both self and other,
     /// except for the indices of the first and last line of both self and s2, that are always included.
     pub fn diff(&self, other: &Self, max: usize) -> impl Iterator<Item = isize> {
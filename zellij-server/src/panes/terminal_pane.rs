@@ -1,27 +1,32 @@
+use crate::os_input_output::ServerOsApi;
 use crate::output::CharacterChunk;
 use crate::panes::{
     grid::Grid,
+    selection::SelectionMode,
     terminal_character::{CursorShape, TerminalCharacter, EMPTY_TERMINAL_CHARACTER},
 };
 use crate::panes::{AnsiCode, LinkHandler};
 use crate::pty::VteBytes;
-use crate::tab::Pane;
+use crate::tab::{Pane, MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH};
 use crate::ClientId;
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::os::unix::io::RawFd;
+use std::path::PathBuf;
 use std::rc::Rc;
-use std::time::{self, Instant};
+use std::time::{self, Duration, Instant};
+use unicode_width::UnicodeWidthStr;
 use zellij_tile::prelude::Style;
 use zellij_utils::pane_size::Offset;
 use zellij_utils::{
+    input::layout::Run,
     pane_size::SizeInPixels,
     pane_size::{Dimension, PaneGeom},
     position::Position,
     shared::make_terminal_title,
     vte,
-    zellij_tile::data::{InputMode, PaletteColor},
+    zellij_tile::data::{InputMode, Palette, PaletteColor},
 };
 
 pub const SELECTION_SCROLL_INTERVAL_MS: u64 = 10;
@@ -51,7 +56,23 @@ pub struct TerminalPane {
     pane_name: String,
     frame: HashMap<ClientId, PaneFrame>,
     borderless: bool,
+    min_width: usize,
+    min_height: usize,
     fake_cursor_locations: HashSet<(usize, usize)>, // (x, y) - these hold a record of previous fake cursors which we need to clear on render
+    highlighted_lines: HashSet<usize>, // viewport lines to render with a highlighted background
+    raw_view: Option<RawView>, // when this is set, we render the raw bytes instead of the grid
+    palette_override: Option<Palette>, // when this is set, it is used instead of the colors of the style
+    border_color: Option<PaletteColor>, // when this is set, it is used for the frame while the pane is not focused
+    read_only: bool,                    // typed input is not sent to the pane
+    search_matches: Vec<(usize, usize)>, // (line, column) in scrollback coordinates
+    search_match_width: usize,
+    active_search_match: Option<usize>, // index into search_matches
+    exit_status: Option<i32>,
+    has_activity: bool, // set when we receive bytes, cleared when it is reported
+    last_output_at: Instant,
+    silence_threshold: Option<Duration>,
+    invoked_with: Option<Run>,
+    os_api: Option<Box<dyn ServerOsApi>>, // to look up what runs in the foreground of the terminal
 }
 
 impl Pane for TerminalPane {
@@ -101,6 +122,11 @@ impl Pane for TerminalPane {
         for &byte in &bytes {
             self.vte_parser.advance(&mut self.grid, byte);
         }
+        if let Some(raw_view) = self.raw_view.as_mut() {
+            raw_view.write_bytes(&bytes);
+        }
+        self.has_activity = true;
+        self.last_output_at = Instant::now();
         self.set_should_render(true);
     }
     fn cursor_coordinates(&self) -> Option<(usize, usize)> {
@@ -110,6 +136,9 @@ impl Pane for TerminalPane {
             .cursor_coordinates()
             .map(|(x, y)| (x + left, y + top))
     }
+    fn cursor_is_hidden(&self) -> bool {
+        self.grid.cursor_is_hidden()
+    }
     fn adjust_input_to_terminal(&self, input_bytes: Vec<u8>) -> Vec<u8> {
         // there are some cases in which the terminal state means that input sent to it
         // needs to be adjusted.
@@ -210,20 +239,50 @@ impl Pane for TerminalPane {
             let content_x = self.get_content_x();
             let content_y = self.get_content_y();
 
+            if let Some(raw_view) = self.raw_view.as_mut() {
+                let character_chunks = raw_view.grid.read_changes(content_x, content_y);
+                self.set_should_render(false);
+                return Some((character_chunks, None));
+            }
+            let colors = self.colors();
             let mut character_chunks = self.grid.read_changes(content_x, content_y);
             for character_chunk in character_chunks.iter_mut() {
                 character_chunk.add_changed_colors(self.grid.changed_colors);
+                if let Some(palette_override) = self.palette_override {
+                    for terminal_character in character_chunk.terminal_characters.iter_mut() {
+                        let styles = &mut terminal_character.styles;
+                        if matches!(styles.foreground, None | Some(AnsiCode::Reset)) {
+                            styles.foreground = Some(palette_override.fg.into());
+                        }
+                        if matches!(styles.background, None | Some(AnsiCode::Reset)) {
+                            styles.background = Some(palette_override.bg.into());
+                        }
+                    }
+                }
+                self.highlight_search_matches(character_chunk, content_x, content_y, &colors);
+                if self
+                    .highlighted_lines
+                    .contains(&character_chunk.y.saturating_sub(content_y))
+                {
+                    let highlight_color: AnsiCode = colors.diff_highlight.into();
+                    for terminal_character in character_chunk.terminal_characters.iter_mut() {
+                        terminal_character.styles =
+                            terminal_character.styles.background(Some(highlight_color));
+                    }
+                }
                 if self
                     .grid
                     .selection
                     .contains_row(character_chunk.y.saturating_sub(content_y))
                 {
-                    let background_color = match self.style.colors.bg {
+                    let background_color = match colors.bg {
                         PaletteColor::Rgb(rgb) => AnsiCode::RgbCode(rgb),
                         PaletteColor::EightBit(col) => AnsiCode::ColorIndex(col),
                     };
                     character_chunk.add_selection_and_background(
-                        self.grid.selection,
+                        self.grid
+                            .selection
+                            .scrolled_right(self.grid.horizontal_scroll()),
                         background_color,
                         content_x,
                         content_y,
@@ -253,13 +312,10 @@ impl Pane for TerminalPane {
             && frame_params.is_main_client
         {
             String::from("Enter name...")
-        } else if self.pane_name.is_empty() {
-            self.grid
-                .title
-                .clone()
-                .unwrap_or_else(|| self.pane_title.clone())
+        } else if self.read_only {
+            format!("[RO] {}", self.title())
         } else {
-            self.pane_name.clone()
+            self.title()
         };
         let frame = PaneFrame::new(
             self.current_geom().into(),
@@ -343,6 +399,50 @@ impl Pane for TerminalPane {
             }
         }
     }
+    fn set_title(&mut self, title: String) {
+        self.pane_name = title;
+        self.set_should_render(true);
+    }
+    fn title(&self) -> String {
+        if self.pane_name.is_empty() {
+            self.grid
+                .title
+                .clone()
+                .unwrap_or_else(|| self.pane_title.clone())
+        } else {
+            self.pane_name.clone()
+        }
+    }
+    fn pane_name(&self) -> String {
+        self.pane_name.clone()
+    }
+    fn bracketed_paste_active(&self) -> bool {
+        self.grid.bracketed_paste_mode
+    }
+    fn url_at(&self, position: &Position) -> Option<String> {
+        self.grid.url_at(position)
+    }
+    fn hyperlink_at(&self, position: &Position) -> Option<String> {
+        self.grid.hyperlink_at(position)
+    }
+    fn current_working_directory(&self) -> Option<PathBuf> {
+        self.grid.working_directory.clone()
+    }
+    fn set_exited(&mut self, status: i32) {
+        self.exit_status = Some(status);
+        // written through the parser so that it wraps and scrolls like the rest of the output
+        let message = format!(
+            "\r\n\u{1b}[?25l\u{1b}[2m[Process exited with code {} — press Enter to close]\u{1b}[22m",
+            status
+        );
+        for &byte in message.as_bytes() {
+            self.vte_parser.advance(&mut self.grid, byte);
+        }
+        self.set_should_render(true);
+    }
+    fn exit_status(&self) -> Option<i32> {
+        self.exit_status
+    }
     fn pid(&self) -> PaneId {
         PaneId::Terminal(self.pid)
     }
@@ -398,9 +498,75 @@ impl Pane for TerminalPane {
         self.grid.reset_viewport();
         self.set_should_render(true);
     }
+    fn set_scrollback_limit(&mut self, lines: usize) {
+        self.grid.set_scrollback_limit(lines);
+    }
+    fn clear_all(&mut self) {
+        self.grid.clear_screen_and_scrollback();
+        self.set_should_render(true);
+    }
+    fn take_bell(&mut self) -> bool {
+        std::mem::take(&mut self.grid.bell_received)
+    }
+    fn take_activity(&mut self) -> bool {
+        std::mem::take(&mut self.has_activity)
+    }
+    fn progress(&self) -> Option<u8> {
+        self.grid.progress
+    }
+    fn take_progress_change(&mut self) -> bool {
+        std::mem::take(&mut self.grid.progress_changed)
+    }
+    fn take_title_change(&mut self) -> bool {
+        std::mem::take(&mut self.grid.title_changed)
+    }
+    fn take_pending_clipboard(&mut self) -> Option<String> {
+        self.grid.pending_clipboard.take()
+    }
+    fn last_output_at(&self) -> Option<Instant> {
+        Some(self.last_output_at)
+    }
+    fn silence_threshold(&self) -> Option<Duration> {
+        self.silence_threshold
+    }
+    fn set_silence_threshold(&mut self, threshold: Option<Duration>) {
+        self.silence_threshold = threshold;
+    }
+    fn scroll_left(&mut self, count: usize) {
+        self.grid.scroll_left(count);
+        self.set_should_render(true);
+    }
+    fn scroll_right(&mut self, count: usize) {
+        self.grid.scroll_right(count);
+        self.set_should_render(true);
+    }
+    fn horizontal_scroll(&self) -> usize {
+        self.grid.horizontal_scroll()
+    }
+    fn relative_position(&self, position_on_screen: &Position) -> Position {
+        // selections, mouse events and links refer to the columns of the whole line, including
+        // those scrolled out of view to the left
+        let mut relative_position =
+            position_on_screen.relative_to(self.get_content_y(), self.get_content_x());
+        relative_position.column.0 += self.grid.horizontal_scroll();
+        relative_position
+    }
+    fn set_line_wrap(&mut self, wrap: bool) {
+        self.grid.set_line_wrap(wrap);
+        self.set_should_render(true);
+    }
+    fn line_wrap(&self) -> bool {
+        self.grid.line_wrap()
+    }
     fn is_scrolled(&self) -> bool {
         self.grid.is_scrolled
     }
+    fn scrollback_len(&self) -> usize {
+        self.grid.scrollback_len()
+    }
+    fn scroll_offset(&self) -> usize {
+        self.grid.scroll_offset()
+    }
 
     fn active_at(&self) -> Instant {
         self.active_at
@@ -424,8 +590,13 @@ impl Pane for TerminalPane {
         self.grid.pending_messages_to_pty.drain(..).collect()
     }
 
-    fn start_selection(&mut self, start: &Position, _client_id: ClientId) {
-        self.grid.start_selection(start);
+    fn start_selection(
+        &mut self,
+        start: &Position,
+        selection_mode: SelectionMode,
+        _client_id: ClientId,
+    ) {
+        self.grid.start_selection_in_mode(start, selection_mode);
         self.set_should_render(true);
     }
 
@@ -474,10 +645,144 @@ impl Pane for TerminalPane {
     fn borderless(&self) -> bool {
         self.borderless
     }
+    fn min_width(&self) -> usize {
+        self.min_width
+    }
+    fn min_height(&self) -> usize {
+        self.min_height
+    }
+    fn set_min_size(&mut self, min_width: usize, min_height: usize) {
+        self.min_width = min_width;
+        self.min_height = min_height;
+    }
 
+    fn set_invoked_with(&mut self, run: Option<Run>) {
+        self.invoked_with = run;
+    }
+    fn invoked_with(&self) -> Option<Run> {
+        self.invoked_with.clone()
+    }
+    fn has_running_process(&self) -> bool {
+        // the shell leads the session of its terminal, when it runs a command in the foreground it
+        // gives that command its own process group
+        let os_api = match &self.os_api {
+            Some(os_api) => os_api,
+            None => return false,
+        };
+        match (
+            os_api.get_foreground_process_group(self.pid),
+            os_api.get_session_id(self.pid),
+        ) {
+            (Some(foreground_process_group), Some(session_id)) => {
+                foreground_process_group != session_id
+            }
+            _ => false,
+        }
+    }
+    fn is_alternate_screen_active(&self) -> bool {
+        self.grid.is_alternate_screen_active()
+    }
     fn mouse_mode(&self) -> bool {
         self.grid.mouse_mode
     }
+    fn visible_text_lines(&self) -> Vec<String> {
+        self.grid.viewport_as_text_lines()
+    }
+    fn toggle_raw_view(&mut self) {
+        match self.raw_view.take() {
+            Some(_) => {
+                // the grid kept updating underneath, so we only need to render all of it again
+                self.grid.render_full_viewport();
+            }
+            None => {
+                // raw bytes are not kept while the view is closed, so it shows what the pane
+                // receives from the moment it is opened
+                let mut raw_view = RawView::new(Grid::new(
+                    self.get_content_rows(),
+                    self.get_content_columns(),
+                    self.style.colors,
+                    self.grid.link_handler.clone(),
+                    Rc::new(RefCell::new(None)),
+                ));
+                raw_view.grid.render_full_viewport();
+                self.raw_view = Some(raw_view);
+            }
+        }
+        self.set_should_render(true);
+    }
+    fn dump(&self, include_scrollback: bool, with_ansi: bool) -> Option<String> {
+        Some(self.grid.dump(include_scrollback, with_ansi))
+    }
+    fn dump_screen(&self) -> String {
+        self.grid.dump_viewport()
+    }
+    fn search(&mut self, query: &str) -> Vec<(usize, usize)> {
+        self.search_matches = self.grid.search(query);
+        // matches are highlighted by column, so wide characters take up more than one
+        self.search_match_width = query.width();
+        self.active_search_match = None;
+        self.set_should_render(true);
+        self.render_full_viewport();
+        self.search_matches.clone()
+    }
+    fn scroll_to_search_match(&mut self, forward: bool, client_id: ClientId) {
+        if self.search_matches.is_empty() {
+            return;
+        }
+        let match_count = self.search_matches.len();
+        let next_match = match (self.active_search_match, forward) {
+            (Some(active_match), true) => (active_match + 1) % match_count,
+            (Some(active_match), false) => (active_match + match_count - 1) % match_count,
+            (None, true) => 0,
+            (None, false) => match_count - 1,
+        };
+        self.active_search_match = Some(next_match);
+        let (match_line, _) = self.search_matches[next_match];
+        let viewport_start = self.grid.viewport_start_in_scrollback();
+        let viewport_end = viewport_start + self.grid.height;
+        if match_line < viewport_start {
+            self.scroll_up(viewport_start - match_line, client_id);
+        } else if match_line >= viewport_end {
+            self.scroll_down(match_line + 1 - viewport_end, client_id);
+        }
+        self.render_full_viewport();
+    }
+    fn set_palette_override(&mut self, palette: Option<Palette>) {
+        self.palette_override = palette;
+        self.frame.clear();
+        self.set_should_render(true);
+        self.render_full_viewport();
+    }
+    fn palette_override(&self) -> Option<Palette> {
+        self.palette_override
+    }
+    fn set_border_color(&mut self, color: Option<PaletteColor>) {
+        self.border_color = color;
+        self.frame.clear();
+        self.set_should_render(true);
+    }
+    fn border_color(&self) -> Option<PaletteColor> {
+        self.border_color
+    }
+    fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+        self.frame.clear();
+        self.set_should_render(true);
+    }
+    fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+    fn insert_synthetic_line(&mut self, text: &str) {
+        self.grid.insert_synthetic_line(text);
+        self.set_should_render(true);
+    }
+    fn set_highlighted_lines(&mut self, highlighted_lines: HashSet<usize>) {
+        if self.highlighted_lines != highlighted_lines {
+            self.highlighted_lines = highlighted_lines;
+            self.grid.render_full_viewport();
+            self.set_should_render(true);
+        }
+    }
 }
 
 impl TerminalPane {
@@ -513,9 +818,28 @@ impl TerminalPane {
             pane_title: initial_pane_title,
             pane_name,
             borderless: false,
+            min_width: MIN_TERMINAL_WIDTH,
+            min_height: MIN_TERMINAL_HEIGHT,
             fake_cursor_locations: HashSet::new(),
+            highlighted_lines: HashSet::new(),
+            raw_view: None,
+            palette_override: None,
+            border_color: None,
+            read_only: false,
+            search_matches: vec![],
+            search_match_width: 0,
+            active_search_match: None,
+            exit_status: None,
+            has_activity: false,
+            last_output_at: Instant::now(),
+            silence_threshold: None,
+            invoked_with: None,
+            os_api: None,
         }
     }
+    pub fn set_os_api(&mut self, os_api: Box<dyn ServerOsApi>) {
+        self.os_api = Some(os_api);
+    }
     pub fn get_x(&self) -> usize {
         match self.geom_override {
             Some(position_and_size_override) => position_and_size_override.x,
@@ -544,6 +868,9 @@ impl TerminalPane {
         let rows = self.get_content_rows();
         let cols = self.get_content_columns();
         self.grid.change_size(rows, cols);
+        if let Some(raw_view) = self.raw_view.as_mut() {
+            raw_view.grid.change_size(rows, cols);
+        }
         self.set_should_render(true);
     }
     pub fn read_buffer_as_lines(&self) -> Vec<Vec<TerminalCharacter>> {
@@ -553,6 +880,114 @@ impl TerminalPane {
         // (x, y)
         self.grid.cursor_coordinates()
     }
+    fn highlight_search_matches(
+        &self,
+        character_chunk: &mut CharacterChunk,
+        content_x: usize,
+        content_y: usize,
+        colors: &Palette,
+    ) {
+        if self.search_matches.is_empty() {
+            return;
+        }
+        let line =
+            self.grid.viewport_start_in_scrollback() + character_chunk.y.saturating_sub(content_y);
+        let matches_in_line: Vec<(usize, bool)> = self
+            .search_matches
+            .iter()
+            .enumerate()
+            .filter(|(_, (match_line, _))| *match_line == line)
+            .map(|(index, (_, match_column))| {
+                (*match_column, self.active_search_match == Some(index))
+            })
+            .collect();
+        if matches_in_line.is_empty() {
+            return;
+        }
+        let mut column = character_chunk.x.saturating_sub(content_x);
+        for terminal_character in character_chunk.terminal_characters.iter_mut() {
+            let is_in_match = matches_in_line.iter().find(|(match_column, _)| {
+                *match_column <= column && column < match_column + self.search_match_width
+            });
+            if let Some((_, is_active_match)) = is_in_match {
+                let highlight_color: AnsiCode = if *is_active_match {
+                    colors.orange.into()
+                } else {
+                    colors.yellow.into()
+                };
+                terminal_character.styles =
+                    terminal_character.styles.background(Some(highlight_color));
+            }
+            column += terminal_character.width;
+        }
+    }
+    fn colors(&self) -> Palette {
+        self.palette_override.unwrap_or(self.style.colors)
+    }
+}
+
+fn visualize_raw_bytes(bytes: &[u8]) -> String {
+    // similar to `cat -v`, but we keep the line breaks so that the output stays readable
+    let mut visualized = String::new();
+    for character in String::from_utf8_lossy(bytes).chars() {
+        match character {
+            '\n' => visualized.push_str("\\n\r\n"),
+            '\r' => visualized.push_str("\\r"),
+            '\u{7f}' => visualized.push_str("^?"),
+            c if (c as u32) < 0x20 => {
+                visualized.push('^');
+                visualized.push((c as u8 + 64) as char);
+            }
+            c => visualized.push(c),
+        }
+    }
+    visualized
+}
+
+fn incomplete_utf8_suffix_len(bytes: &[u8]) -> usize {
+    // the number of bytes at the end of a read that start a character whose remaining bytes
+    // have not arrived yet
+    for (offset, byte) in bytes.iter().rev().take(3).enumerate() {
+        let character_len = match byte {
+            0x80..=0xbf => continue, // continuation byte
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf7 => 4,
+            _ => return 0,
+        };
+        return if character_len > offset + 1 {
+            offset + 1
+        } else {
+            0
+        };
+    }
+    0
+}
+
+struct RawView {
+    grid: Grid,
+    vte_parser: vte::Parser,
+    incomplete_utf8: Vec<u8>, // the start of a character split between two reads of the pty
+}
+
+impl RawView {
+    fn new(grid: Grid) -> Self {
+        RawView {
+            grid,
+            vte_parser: vte::Parser::new(),
+            incomplete_utf8: vec![],
+        }
+    }
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        let mut bytes_to_visualize = std::mem::take(&mut self.incomplete_utf8);
+        bytes_to_visualize.extend_from_slice(bytes);
+        let complete_len =
+            bytes_to_visualize.len() - incomplete_utf8_suffix_len(&bytes_to_visualize);
+        self.incomplete_utf8 = bytes_to_visualize.split_off(complete_len);
+        for byte in visualize_raw_bytes(&bytes_to_visualize).bytes() {
+            self.vte_parser.advance(&mut self.grid, byte);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -5,10 +5,15 @@ use zellij_tile::prelude::Style;
 use zellij_utils::zellij_tile;
 
 use crate::tab::{Pane, MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH};
-use tiled_pane_grid::{split, TiledPaneGrid};
+use tiled_pane_grid::{
+    preset_geoms, split, split_with_fixed_size, squarest_split_direction, TiledPaneGrid,
+    RESIZE_PERCENT,
+};
 
+use crate::ui::boundaries::BoundarySegment;
 use crate::{
-    os_input_output::ServerOsApi, output::Output, panes::PaneId, ui::boundaries::Boundaries,
+    os_input_output::ServerOsApi, output::Output, panes::PaneId, tab::report_active_pane,
+    thread_bus::ThreadSenders, ui::boundaries::Boundaries,
     ui::pane_contents_and_ui::PaneContentsAndUi, ClientId,
 };
 use std::cell::RefCell;
@@ -16,9 +21,14 @@ use std::collections::{BTreeMap, HashMap, HashSet};
 use std::rc::Rc;
 use std::time::Instant;
 use zellij_tile::data::ModeInfo;
+use zellij_utils::position::Position;
 use zellij_utils::{
-    input::layout::Direction,
-    pane_size::{Offset, PaneGeom, Size, SizeInPixels, Viewport},
+    input::{
+        actions,
+        layout::{Direction, Layout, LayoutPreset, SplitSize},
+        options::CloseGravity,
+    },
+    pane_size::{Constraint, Dimension, Offset, PaneGeom, Size, SizeInPixels, Viewport},
 };
 
 macro_rules! resize_pty {
@@ -35,22 +45,35 @@ macro_rules! resize_pty {
     };
 }
 
+fn split_pane_geom(
+    direction: Direction,
+    rect: &PaneGeom,
+    fixed_size: Option<usize>,
+    min_size: (usize, usize), // (min_width, min_height)
+) -> Option<(PaneGeom, PaneGeom)> {
+    match fixed_size {
+        Some(fixed_size) => split_with_fixed_size(direction, rect, fixed_size, min_size),
+        None => split(direction, rect),
+    }
+}
+
 fn pane_content_offset(position_and_size: &PaneGeom, viewport: &Viewport) -> (usize, usize) {
     // (columns_offset, rows_offset)
     // if the pane is not on the bottom or right edge on the screen, we need to reserve one space
     // from its content to leave room for the boundary between it and the next pane (if it doesn't
     // draw its own frame)
-    let columns_offset = if position_and_size.x + position_and_size.cols.as_usize() < viewport.cols
-    {
-        1
-    } else {
-        0
-    };
-    let rows_offset = if position_and_size.y + position_and_size.rows.as_usize() < viewport.rows {
-        1
-    } else {
-        0
-    };
+    let columns_offset =
+        if position_and_size.x + position_and_size.cols.as_usize() < viewport.x + viewport.cols {
+            1
+        } else {
+            0
+        };
+    let rows_offset =
+        if position_and_size.y + position_and_size.rows.as_usize() < viewport.y + viewport.rows {
+            1
+        } else {
+            0
+        };
     (columns_offset, rows_offset)
 }
 
@@ -66,10 +89,40 @@ pub struct TiledPanes {
     style: Style,
     session_is_mirrored: bool,
     active_panes: HashMap<ClientId, PaneId>,
+    previously_active_panes: HashMap<ClientId, PaneId>, // for focus_last_pane
+    pinned_panes: HashSet<PaneId>, // still rendered (on top) while another pane is fullscreen
     draw_pane_frames: bool,
+    hide_single_pane_frame: bool, // do not draw a frame while there is only one selectable pane
     panes_to_hide: HashSet<PaneId>,
-    fullscreen_is_active: bool,
+    fullscreen_is_active: bool, // the user made the active pane fullscreen (or maximized it in one axis)
+    panes_hidden_for_lack_of_space: bool, // some panes were hidden because they no longer fit
+    maximized_axis: Option<MaximizedAxis>, // fullscreen in only one axis, the other panes keep their geometry
+    geoms_before_fullscreen: HashMap<PaneId, PaneGeom>,
+    display_area_before_fullscreen: Size,
     os_api: Box<dyn ServerOsApi>,
+    senders: ThreadSenders,
+    dragged_border: Option<DraggedBorder>,
+    pane_order: Vec<PaneId>, // the order in which focus_next_pane cycles through panes and render draws them, focus_pane_by_index counts by position instead
+    close_gravity: CloseGravity,
+    last_rendered_boundaries: HashMap<ClientId, Boundaries>,
+    boundaries_dirty: bool, // render the boundaries even if they did not change (eg. after the display was cleared)
+    min_pane_width: usize,
+    min_pane_height: usize,
+}
+
+// the axis a pane spans the whole tab in while the panes it overlaps in that axis are hidden
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MaximizedAxis {
+    Width,
+    Height,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct DraggedBorder {
+    pane_before: PaneId,  // the pane to the left of (or above) the border
+    pane_after: PaneId,   // the pane to the right of (or below) the border
+    direction: Direction, // Horizontal if the border is dragged left and right
+    grab_offset: isize,   // where the border was grabbed, relative to the start of pane_after
 }
 
 impl TiledPanes {
@@ -83,9 +136,11 @@ impl TiledPanes {
         character_cell_size: Rc<RefCell<Option<SizeInPixels>>>,
         session_is_mirrored: bool,
         draw_pane_frames: bool,
+        hide_single_pane_frame: bool,
         default_mode_info: ModeInfo,
         style: Style,
         os_api: Box<dyn ServerOsApi>,
+        senders: ThreadSenders,
     ) -> Self {
         TiledPanes {
             panes: BTreeMap::new(),
@@ -99,16 +154,44 @@ impl TiledPanes {
             style,
             session_is_mirrored,
             active_panes: HashMap::new(),
+            previously_active_panes: HashMap::new(),
+            pinned_panes: HashSet::new(),
             draw_pane_frames,
+            hide_single_pane_frame,
             panes_to_hide: HashSet::new(),
             fullscreen_is_active: false,
+            panes_hidden_for_lack_of_space: false,
+            maximized_axis: None,
+            geoms_before_fullscreen: HashMap::new(),
+            display_area_before_fullscreen: Size::default(),
             os_api,
+            senders,
+            dragged_border: None,
+            pane_order: vec![],
+            close_gravity: CloseGravity::default(),
+            last_rendered_boundaries: HashMap::new(),
+            boundaries_dirty: true,
+            min_pane_width: MIN_TERMINAL_WIDTH,
+            min_pane_height: MIN_TERMINAL_HEIGHT,
+        }
+    }
+    pub fn set_min_pane_size(&mut self, min_pane_width: usize, min_pane_height: usize) {
+        self.min_pane_width = min_pane_width;
+        self.min_pane_height = min_pane_height;
+        for pane in self.panes.values_mut() {
+            pane.set_min_size(min_pane_width, min_pane_height);
         }
     }
-    pub fn add_pane_with_existing_geom(&mut self, pane_id: PaneId, pane: Box<dyn Pane>) {
+    fn min_pane_size(&self) -> (usize, usize) {
+        (self.min_pane_width, self.min_pane_height)
+    }
+    pub fn add_pane_with_existing_geom(&mut self, pane_id: PaneId, mut pane: Box<dyn Pane>) {
+        pane.set_min_size(self.min_pane_width, self.min_pane_height);
         self.panes.insert(pane_id, pane);
+        self.pane_order.push(pane_id);
     }
     pub fn insert_pane(&mut self, pane_id: PaneId, mut pane: Box<dyn Pane>) {
+        pane.set_min_size(self.min_pane_width, self.min_pane_height);
         let cursor_height_width_ratio = self.cursor_height_width_ratio();
         let pane_grid = TiledPaneGrid::new(
             &mut self.panes,
@@ -125,6 +208,7 @@ impl TiledPanes {
                 pane_to_split.set_geom(first_geom);
                 pane.set_geom(second_geom);
                 self.panes.insert(pane_id, pane);
+                self.pane_order.push(pane_id);
                 self.relayout(!split_direction);
             }
         }
@@ -180,8 +264,18 @@ impl TiledPanes {
         }
         self.set_pane_frames(self.draw_pane_frames);
     }
+    fn pane_frames_are_drawn(&self) -> bool {
+        self.draw_pane_frames
+            && !(self.hide_single_pane_frame
+                && self.panes.values().filter(|p| p.selectable()).count() == 1)
+    }
+    pub fn toggle_hide_single_pane_frame(&mut self) {
+        self.hide_single_pane_frame = !self.hide_single_pane_frame;
+        self.set_pane_frames(self.draw_pane_frames);
+    }
     pub fn set_pane_frames(&mut self, draw_pane_frames: bool) {
         self.draw_pane_frames = draw_pane_frames;
+        let draw_pane_frames = self.pane_frames_are_drawn();
         let viewport = *self.viewport.borrow();
         for pane in self.panes.values_mut() {
             if !pane.borderless() {
@@ -213,27 +307,68 @@ impl TiledPanes {
             resize_pty!(pane, self.os_api);
         }
     }
-    pub fn can_split_pane_horizontally(&mut self, client_id: ClientId) -> bool {
+    pub fn smart_split_direction(
+        &mut self,
+        client_id: ClientId,
+        cursor_height_width_ratio: Option<usize>,
+    ) -> Option<Direction> {
+        let active_pane = self
+            .active_panes
+            .get(&client_id)
+            .and_then(|active_pane_id| self.panes.get(active_pane_id))?;
+        let preferred = squarest_split_direction(
+            active_pane.cols(),
+            active_pane.rows(),
+            cursor_height_width_ratio.or_else(|| self.cursor_height_width_ratio()),
+        );
+        [preferred, !preferred]
+            .into_iter()
+            .find(|direction| match direction {
+                Direction::Horizontal => self.can_split_pane_horizontally(client_id, None),
+                Direction::Vertical => self.can_split_pane_vertically(client_id, None),
+            })
+    }
+    pub fn can_split_pane_horizontally(
+        &mut self,
+        client_id: ClientId,
+        fixed_size: Option<usize>,
+    ) -> bool {
         if let Some(active_pane_id) = &self.active_panes.get(&client_id) {
             if let Some(active_pane) = self.panes.get_mut(active_pane_id) {
                 let full_pane_size = active_pane.position_and_size();
-                if full_pane_size.rows.as_usize() < MIN_TERMINAL_HEIGHT * 2 {
+                if full_pane_size.rows.as_usize() < self.min_pane_height * 2 {
                     return false;
                 } else {
-                    return split(Direction::Horizontal, &full_pane_size).is_some();
+                    return split_pane_geom(
+                        Direction::Horizontal,
+                        &full_pane_size,
+                        fixed_size,
+                        self.min_pane_size(),
+                    )
+                    .is_some();
                 }
             }
         }
         false
     }
-    pub fn can_split_pane_vertically(&mut self, client_id: ClientId) -> bool {
+    pub fn can_split_pane_vertically(
+        &mut self,
+        client_id: ClientId,
+        fixed_size: Option<usize>,
+    ) -> bool {
         if let Some(active_pane_id) = &self.active_panes.get(&client_id) {
             if let Some(active_pane) = self.panes.get_mut(active_pane_id) {
                 let full_pane_size = active_pane.position_and_size();
-                if full_pane_size.cols.as_usize() < MIN_TERMINAL_WIDTH * 2 {
+                if full_pane_size.cols.as_usize() < self.min_pane_width * 2 {
                     return false;
                 }
-                return split(Direction::Vertical, &full_pane_size).is_some();
+                return split_pane_geom(
+                    Direction::Vertical,
+                    &full_pane_size,
+                    fixed_size,
+                    self.min_pane_size(),
+                )
+                .is_some();
             }
         }
         false
@@ -243,14 +378,23 @@ impl TiledPanes {
         pid: PaneId,
         mut new_pane: Box<dyn Pane>,
         client_id: ClientId,
+        fixed_size: Option<usize>,
     ) {
+        new_pane.set_min_size(self.min_pane_width, self.min_pane_height);
+        let min_pane_size = self.min_pane_size();
         let active_pane_id = &self.active_panes.get(&client_id).unwrap();
         let active_pane = self.panes.get_mut(active_pane_id).unwrap();
         let full_pane_size = active_pane.position_and_size();
-        if let Some((top_winsize, bottom_winsize)) = split(Direction::Horizontal, &full_pane_size) {
+        if let Some((top_winsize, bottom_winsize)) = split_pane_geom(
+            Direction::Horizontal,
+            &full_pane_size,
+            fixed_size,
+            min_pane_size,
+        ) {
             active_pane.set_geom(top_winsize);
             new_pane.set_geom(bottom_winsize);
             self.panes.insert(pid, new_pane);
+            self.pane_order.push(pid);
             self.relayout(Direction::Vertical);
         }
     }
@@ -259,26 +403,76 @@ impl TiledPanes {
         pid: PaneId,
         mut new_pane: Box<dyn Pane>,
         client_id: ClientId,
+        fixed_size: Option<usize>,
     ) {
+        new_pane.set_min_size(self.min_pane_width, self.min_pane_height);
+        let min_pane_size = self.min_pane_size();
         let active_pane_id = &self.active_panes.get(&client_id).unwrap();
         let active_pane = self.panes.get_mut(active_pane_id).unwrap();
         let full_pane_size = active_pane.position_and_size();
-        if let Some((left_winsize, right_winsize)) = split(Direction::Vertical, &full_pane_size) {
+        if let Some((left_winsize, right_winsize)) = split_pane_geom(
+            Direction::Vertical,
+            &full_pane_size,
+            fixed_size,
+            min_pane_size,
+        ) {
             active_pane.set_geom(left_winsize);
             new_pane.set_geom(right_winsize);
             self.panes.insert(pid, new_pane);
+            self.pane_order.push(pid);
             self.relayout(Direction::Horizontal);
         }
     }
     pub fn focus_pane(&mut self, pane_id: PaneId, client_id: ClientId) {
-        self.active_panes.insert(client_id, pane_id);
+        self.set_active_pane(client_id, pane_id);
         if self.session_is_mirrored {
             // move all clients
             let connected_clients: Vec<ClientId> =
                 self.connected_clients.borrow().iter().copied().collect();
             for client_id in connected_clients {
-                self.active_panes.insert(client_id, pane_id);
+                self.set_active_pane(client_id, pane_id);
+            }
+        }
+    }
+    // every change of the active pane of a client goes through here, so that plugins are told
+    // about it once
+    fn set_active_pane(&mut self, client_id: ClientId, pane_id: PaneId) {
+        let previously_active_pane_id = self.active_panes.insert(client_id, pane_id);
+        if previously_active_pane_id == Some(pane_id) {
+            return;
+        }
+        if let Some(previously_active_pane_id) = previously_active_pane_id {
+            self.previously_active_panes
+                .insert(client_id, previously_active_pane_id);
+        }
+        if let Some(pane) = self.panes.get(&pane_id) {
+            report_active_pane(&self.senders, client_id, pane.as_ref());
+        }
+    }
+    // returns a boolean that indicates whether the focus moved
+    pub fn focus_last_pane(&mut self, client_id: ClientId) -> bool {
+        let last_pane_id = self
+            .previously_active_panes
+            .get(&client_id)
+            .copied()
+            .filter(|pane_id| {
+                self.panes
+                    .get(pane_id)
+                    .map(|pane| pane.selectable())
+                    .unwrap_or(false)
+                    && !self.panes_to_hide.contains(pane_id)
+            });
+        match last_pane_id {
+            Some(last_pane_id) => {
+                // this also makes the currently active pane the last one, so that calling this
+                // again toggles back
+                self.focus_pane(last_pane_id, client_id);
+                self.set_pane_active_at(last_pane_id);
+                // so that the frame of the previously active pane does not remain actively colored
+                self.set_force_render();
+                true
             }
+            None => false,
         }
     }
     pub fn clear_active_panes(&mut self) {
@@ -315,6 +509,10 @@ impl TiledPanes {
             pane.set_should_render_boundaries(true);
             pane.render_full_viewport();
         }
+        self.boundaries_dirty = true;
+    }
+    pub fn set_boundaries_dirty(&mut self) {
+        self.boundaries_dirty = true;
     }
     pub fn has_active_panes(&self) -> bool {
         !self.active_panes.is_empty()
@@ -326,6 +524,7 @@ impl TiledPanes {
         let connected_clients: Vec<ClientId> =
             { self.connected_clients.borrow().iter().copied().collect() };
         let multiple_users_exist_in_session = { self.connected_clients_in_app.borrow().len() > 1 };
+        let pane_frames_are_drawn = self.pane_frames_are_drawn();
         let mut client_id_to_boundaries: HashMap<ClientId, Boundaries> = HashMap::new();
         let active_panes = if self.session_is_mirrored || floating_panes_are_visible {
             HashMap::new()
@@ -336,8 +535,33 @@ impl TiledPanes {
                 .map(|(client_id, pane_id)| (*client_id, *pane_id))
                 .collect()
         };
-        for (kind, pane) in self.panes.iter_mut() {
-            if !self.panes_to_hide.contains(&pane.pid()) {
+        // pinned panes stay out of the fullscreen layout (they are in panes_to_hide) but are
+        // rendered last, on top of the fullscreen pane
+        let fullscreen_pane_is_dirty = self.fullscreen_is_active
+            && self.panes.iter().any(|(pane_id, pane)| {
+                !self.panes_to_hide.contains(pane_id) && pane.should_render()
+            });
+        // in the pane order, with panes that were added without going through it and then the
+        // pinned panes last
+        let mut panes: Vec<(&PaneId, &mut Box<dyn Pane>)> = self.panes.iter_mut().collect();
+        panes.sort_by_key(|(pane_id, _)| {
+            (
+                self.pinned_panes.contains(pane_id),
+                self.pane_order
+                    .iter()
+                    .position(|p| p == *pane_id)
+                    .unwrap_or(usize::MAX),
+            )
+        });
+        for (kind, pane) in panes {
+            let is_pinned_over_fullscreen =
+                self.fullscreen_is_active && self.pinned_panes.contains(kind);
+            if is_pinned_over_fullscreen && fullscreen_pane_is_dirty {
+                // whatever the fullscreen pane rendered below it might have overwritten it
+                pane.set_should_render(true);
+                pane.render_full_viewport();
+            }
+            if !self.panes_to_hide.contains(&pane.pid()) || is_pinned_over_fullscreen {
                 let mut pane_contents_and_ui = PaneContentsAndUi::new(
                     pane,
                     output,
@@ -356,7 +580,7 @@ impl TiledPanes {
                     if let PaneId::Plugin(..) = kind {
                         pane_contents_and_ui.render_pane_contents_for_client(*client_id);
                     }
-                    if self.draw_pane_frames {
+                    if pane_frames_are_drawn {
                         pane_contents_and_ui.render_pane_frame(
                             *client_id,
                             client_mode,
@@ -385,16 +609,102 @@ impl TiledPanes {
                 }
             }
         }
-        // render boundaries if needed
-        for (client_id, boundaries) in &mut client_id_to_boundaries {
-            // TODO: add some conditional rendering here so this isn't rendered for every character
-            output.add_character_chunks_to_client(*client_id, boundaries.render(), None);
+        // render boundaries if needed, they only change when panes are added, removed, resized or
+        // focused, so most of the time we can skip them
+        for (client_id, boundaries) in &client_id_to_boundaries {
+            let boundaries_changed =
+                self.last_rendered_boundaries.get(client_id) != Some(boundaries);
+            if self.boundaries_dirty || boundaries_changed {
+                output.add_character_chunks_to_client(*client_id, boundaries.render(), None);
+            }
         }
+        self.last_rendered_boundaries = client_id_to_boundaries;
+        self.boundaries_dirty = false;
+    }
+    pub fn boundary_segments(&self) -> Vec<BoundarySegment> {
+        // with pane frames every pane draws its own outline, otherwise neighbouring panes share
+        // the boundaries between them
+        let boundaries = Boundaries::new(*self.viewport.borrow());
+        let pane_frames_are_drawn = self.pane_frames_are_drawn();
+        let mut segments: Vec<BoundarySegment> = self
+            .panes
+            .values()
+            .filter(|pane| !self.panes_to_hide.contains(&pane.pid()))
+            .flat_map(|pane| {
+                if pane_frames_are_drawn && pane.borderless() {
+                    vec![]
+                } else if pane_frames_are_drawn {
+                    let (first_col, last_col) = (pane.x(), pane.x() + pane.cols() - 1);
+                    let (first_row, last_row) = (pane.y(), pane.y() + pane.rows() - 1);
+                    vec![
+                        BoundarySegment::horizontal(first_row, first_col, last_col),
+                        BoundarySegment::horizontal(last_row, first_col, last_col),
+                        BoundarySegment::vertical(first_col, first_row, last_row),
+                        BoundarySegment::vertical(last_col, first_row, last_row),
+                    ]
+                } else {
+                    boundaries.segments(pane.as_ref())
+                }
+            })
+            .collect();
+        segments.sort_unstable();
+        segments.dedup();
+        segments
     }
     pub fn get_panes(&self) -> impl Iterator<Item = (&PaneId, &Box<dyn Pane>)> {
         self.panes.iter()
     }
     pub fn resize(&mut self, new_screen_size: Size) {
+        if self.panes_hidden_for_lack_of_space || self.maximized_axis.is_some() {
+            // bring the hidden panes back, if they still do not fit they are hidden again below
+            self.show_hidden_panes();
+        }
+        if !self.resize_panes(new_screen_size) && !self.fullscreen_is_active {
+            self.hide_panes_for_lack_of_space(new_screen_size);
+        }
+    }
+    fn hide_panes_for_lack_of_space(&mut self, new_screen_size: Size) {
+        // rather than squeezing panes below their minimum size, the least recently active panes
+        // are hidden one at a time (their neighbours taking over their space) until the rest fit
+        let viewport = *self.viewport.borrow();
+        let mut panes_to_hide: Vec<(PaneId, Instant)> = self
+            .panes
+            .iter()
+            .filter(|(pane_id, pane)| {
+                !self
+                    .active_panes
+                    .values()
+                    .any(|active_pane_id| active_pane_id == *pane_id)
+                    && is_inside_viewport(&viewport, pane)
+            })
+            .map(|(pane_id, pane)| (*pane_id, pane.active_at()))
+            .collect();
+        panes_to_hide.sort_by_key(|(_, active_at)| *active_at);
+        self.save_geoms_before_hiding_panes();
+        for (pane_id, _) in panes_to_hide {
+            let filled_space_over_pane = {
+                let panes = self
+                    .panes
+                    .iter_mut()
+                    .filter(|(pid, _)| !self.panes_to_hide.contains(pid));
+                let mut pane_grid =
+                    TiledPaneGrid::new(panes, *self.display_area.borrow(), viewport);
+                pane_grid.fill_space_over_pane(pane_id, self.close_gravity, None)
+            };
+            if !filled_space_over_pane {
+                continue;
+            }
+            self.panes_to_hide.insert(pane_id);
+            self.panes_hidden_for_lack_of_space = true;
+            self.set_force_render();
+            if self.resize_panes(new_screen_size) {
+                break;
+            }
+        }
+    }
+    // returns false if the panes could not be laid out in the new size
+    fn resize_panes(&mut self, new_screen_size: Size) -> bool {
+        let mut resized = true;
         // this is blocked out to appease the borrow checker
         {
             let mut display_area = self.display_area.borrow_mut();
@@ -412,6 +722,7 @@ impl TiledPanes {
                 display_area.cols = cols;
             } else {
                 log::error!("Failed to horizontally resize the tab!!!");
+                resized = false;
             }
             if pane_grid.layout(Direction::Vertical, rows).is_ok() {
                 let row_difference = rows as isize - display_area.rows as isize;
@@ -419,61 +730,156 @@ impl TiledPanes {
                 display_area.rows = rows;
             } else {
                 log::error!("Failed to vertically resize the tab!!!");
+                resized = false;
             }
         }
+        self.normalize_percentages(Direction::Horizontal);
+        self.normalize_percentages(Direction::Vertical);
         self.set_pane_frames(self.draw_pane_frames);
+        resized
     }
-    pub fn resize_active_pane_left(&mut self, client_id: ClientId) {
-        if let Some(active_pane_id) = self.get_active_pane_id(client_id) {
-            let mut pane_grid = TiledPaneGrid::new(
-                &mut self.panes,
-                *self.display_area.borrow(),
-                *self.viewport.borrow(),
-            );
-            pane_grid.resize_pane_left(&active_pane_id);
-            for pane in self.panes.values_mut() {
-                resize_pty!(pane, self.os_api);
-            }
+    fn normalize_percentages(&mut self, direction: Direction) {
+        // repeated resizes accumulate floating point errors in the pane percentages, here we
+        // make sure they keep adding up to the whole tab
+        let panes = self
+            .panes
+            .iter_mut()
+            .filter(|(pid, _)| !self.panes_to_hide.contains(pid));
+        let mut pane_grid =
+            TiledPaneGrid::new(panes, *self.display_area.borrow(), *self.viewport.borrow());
+        pane_grid.rebalance_percentages(direction);
+    }
+    pub fn rebalance(&mut self) {
+        {
+            let panes = self
+                .panes
+                .iter_mut()
+                .filter(|(pid, _)| !self.panes_to_hide.contains(pid));
+            let mut pane_grid =
+                TiledPaneGrid::new(panes, *self.display_area.borrow(), *self.viewport.borrow());
+            pane_grid.rebalance_percentages(Direction::Horizontal);
+            pane_grid.rebalance_percentages(Direction::Vertical);
+        }
+        let display_area = *self.display_area.borrow();
+        self.resize_panes(display_area);
+        for pane in self.panes.values_mut() {
+            resize_pty!(pane, self.os_api);
         }
     }
-    pub fn resize_active_pane_right(&mut self, client_id: ClientId) {
-        if let Some(active_pane_id) = self.get_active_pane_id(client_id) {
-            let mut pane_grid = TiledPaneGrid::new(
-                &mut self.panes,
-                *self.display_area.borrow(),
-                *self.viewport.borrow(),
-            );
-            pane_grid.resize_pane_right(&active_pane_id);
-            for pane in self.panes.values_mut() {
-                resize_pty!(pane, self.os_api);
+    pub fn equalize_panes(&mut self) {
+        // the second pass picks up siblings whose spans only line up after the first
+        for _ in 0..2 {
+            {
+                let panes = self
+                    .panes
+                    .iter_mut()
+                    .filter(|(pid, _)| !self.panes_to_hide.contains(pid));
+                let mut pane_grid =
+                    TiledPaneGrid::new(panes, *self.display_area.borrow(), *self.viewport.borrow());
+                pane_grid.equalize_percentages(Direction::Horizontal);
+                pane_grid.equalize_percentages(Direction::Vertical);
             }
+            let display_area = *self.display_area.borrow();
+            self.resize_panes(display_area);
+        }
+        for pane in self.panes.values_mut() {
+            resize_pty!(pane, self.os_api);
         }
     }
-    pub fn resize_active_pane_up(&mut self, client_id: ClientId) {
+    pub fn resize_active_pane_by(
+        &mut self,
+        client_id: ClientId,
+        direction: actions::Direction,
+        percent: f64,
+    ) -> f64 {
+        // returns the amount (in percent) we actually managed to resize by
+        match self.get_active_pane_id(client_id) {
+            Some(active_pane_id) => self.resize_pane_by(active_pane_id, direction, percent),
+            None => 0.0,
+        }
+    }
+    pub fn resize_pane_by(
+        &mut self,
+        pane_id: PaneId,
+        direction: actions::Direction,
+        percent: f64,
+    ) -> f64 {
+        if !self.panes.contains_key(&pane_id) {
+            return 0.0;
+        }
+        let mut pane_grid = TiledPaneGrid::new(
+            &mut self.panes,
+            *self.display_area.borrow(),
+            *self.viewport.borrow(),
+        );
+        let resized_by = pane_grid.resize_pane_by(&pane_id, &direction, percent);
+        for pane in self.panes.values_mut() {
+            resize_pty!(pane, self.os_api);
+        }
+        resized_by
+    }
+    pub fn set_active_pane_size(
+        &mut self,
+        client_id: ClientId,
+        cols_percent: Option<f64>,
+        rows_percent: Option<f64>,
+    ) {
         if let Some(active_pane_id) = self.get_active_pane_id(client_id) {
-            let mut pane_grid = TiledPaneGrid::new(
-                &mut self.panes,
-                *self.display_area.borrow(),
-                *self.viewport.borrow(),
-            );
-            pane_grid.resize_pane_up(&active_pane_id);
-            for pane in self.panes.values_mut() {
-                resize_pty!(pane, self.os_api);
-            }
+            self.set_pane_size(active_pane_id, cols_percent, rows_percent);
         }
     }
+    pub fn set_pane_size(
+        &mut self,
+        pane_id: PaneId,
+        cols_percent: Option<f64>,
+        rows_percent: Option<f64>,
+    ) {
+        let mut pane_grid = TiledPaneGrid::new(
+            &mut self.panes,
+            *self.display_area.borrow(),
+            *self.viewport.borrow(),
+        );
+        pane_grid.resize_pane_to_percent(&pane_id, cols_percent, rows_percent);
+        for pane in self.panes.values_mut() {
+            resize_pty!(pane, self.os_api);
+        }
+    }
+    pub fn resize_active_pane_left(&mut self, client_id: ClientId) {
+        self.resize_active_pane_by(client_id, actions::Direction::Left, RESIZE_PERCENT);
+    }
+    pub fn resize_active_pane_right(&mut self, client_id: ClientId) {
+        self.resize_active_pane_by(client_id, actions::Direction::Right, RESIZE_PERCENT);
+    }
+    pub fn resize_active_pane_up(&mut self, client_id: ClientId) {
+        self.resize_active_pane_by(client_id, actions::Direction::Up, RESIZE_PERCENT);
+    }
     pub fn resize_active_pane_down(&mut self, client_id: ClientId) {
-        if let Some(active_pane_id) = self.get_active_pane_id(client_id) {
-            let mut pane_grid = TiledPaneGrid::new(
-                &mut self.panes,
-                *self.display_area.borrow(),
-                *self.viewport.borrow(),
-            );
-            pane_grid.resize_pane_down(&active_pane_id);
-            for pane in self.panes.values_mut() {
-                resize_pty!(pane, self.os_api);
+        self.resize_active_pane_by(client_id, actions::Direction::Down, RESIZE_PERCENT);
+    }
+    // grow the active pane in this direction until the panes it pushes are at their minimum size
+    pub fn maximize_active_pane(&mut self, client_id: ClientId, direction: actions::Direction) {
+        let active_pane_id = match self.get_active_pane_id(client_id) {
+            Some(active_pane_id) => active_pane_id,
+            None => return,
+        };
+        let mut pane_grid = TiledPaneGrid::new(
+            &mut self.panes,
+            *self.display_area.borrow(),
+            *self.viewport.borrow(),
+        );
+        // each successful step grows the pane by at least a sliver of the screen, so this bound
+        // is only there to guarantee we stop
+        let max_steps = (100.0 / RESIZE_PERCENT) as usize * 2;
+        for _ in 0..max_steps {
+            let increased_by =
+                pane_grid.increase_pane_by(&active_pane_id, &direction, RESIZE_PERCENT);
+            if increased_by <= 0.0 {
+                break;
             }
         }
+        for pane in self.panes.values_mut() {
+            resize_pty!(pane, self.os_api);
+        }
     }
     pub fn resize_active_pane_increase(&mut self, client_id: ClientId) {
         if let Some(active_pane_id) = self.get_active_pane_id(client_id) {
@@ -501,35 +907,186 @@ impl TiledPanes {
             }
         }
     }
+    // the geometry of the panes in the order focus_pane_by_index counts them
+    pub fn selectable_pane_geoms_in_order(&self) -> Vec<PaneGeom> {
+        self.selectable_pane_ids_by_position()
+            .iter()
+            .filter_map(|pane_id| self.panes.get(pane_id))
+            .map(|pane| pane.position_and_size())
+            .collect()
+    }
+    fn selectable_pane_ids_by_position(&self) -> Vec<PaneId> {
+        // top-left to bottom-right
+        let mut panes: Vec<(&PaneId, &Box<dyn Pane>)> = self
+            .panes
+            .iter()
+            .filter(|(pane_id, p)| p.selectable() && !self.panes_to_hide.contains(pane_id))
+            .collect();
+        panes.sort_by(|(_a_id, a_pane), (_b_id, b_pane)| {
+            if a_pane.y() == b_pane.y() {
+                a_pane.x().cmp(&b_pane.x())
+            } else {
+                a_pane.y().cmp(&b_pane.y())
+            }
+        });
+        panes.iter().map(|(pane_id, _)| **pane_id).collect()
+    }
+    fn selectable_pane_ids_in_order(&self) -> Vec<PaneId> {
+        // the order move_pane_to_front/move_pane_to_back arrange, panes that were added without
+        // going through this struct come last
+        let mut pane_ids: Vec<PaneId> = self
+            .pane_order
+            .iter()
+            .copied()
+            .filter(|pane_id| self.panes.contains_key(pane_id))
+            .collect();
+        pane_ids.extend(
+            self.panes
+                .keys()
+                .copied()
+                .filter(|pane_id| !self.pane_order.contains(pane_id)),
+        );
+        pane_ids.retain(|pane_id| {
+            self.panes.get(pane_id).unwrap().selectable() && !self.panes_to_hide.contains(pane_id)
+        });
+        pane_ids
+    }
+    // re-arranges the panes in the viewport, returns false if they don't fit in the preset
+    pub fn apply_preset_layout(&mut self, preset: LayoutPreset, client_id: ClientId) -> bool {
+        if self.fullscreen_is_active() {
+            self.unset_fullscreen();
+        }
+        let viewport = *self.viewport.borrow();
+        let active_pane_id = self.get_active_pane_id(client_id);
+        let mut pane_ids: Vec<PaneId> = self
+            .pane_order
+            .iter()
+            .copied()
+            .filter(|pane_id| self.panes.contains_key(pane_id))
+            .collect();
+        pane_ids.extend(
+            self.panes
+                .keys()
+                .copied()
+                .filter(|pane_id| !self.pane_order.contains(pane_id)),
+        );
+        pane_ids.retain(|pane_id| is_inside_viewport(&viewport, self.panes.get(pane_id).unwrap()));
+        // the active pane is the main pane
+        if let Some(position) = pane_ids
+            .iter()
+            .position(|pane_id| Some(*pane_id) == active_pane_id)
+        {
+            let active_pane_id = pane_ids.remove(position);
+            pane_ids.insert(0, active_pane_id);
+        }
+        let mut cols = Dimension::percent(100.0);
+        cols.set_inner(viewport.cols);
+        let mut rows = Dimension::percent(100.0);
+        rows.set_inner(viewport.rows);
+        let viewport_geom = PaneGeom {
+            x: viewport.x,
+            y: viewport.y,
+            cols,
+            rows,
+        };
+        let geoms = match preset_geoms(preset, &viewport_geom, pane_ids.len(), self.min_pane_size())
+        {
+            Some(geoms) => geoms,
+            None => return false,
+        };
+        for (pane_id, geom) in pane_ids.iter().zip(geoms) {
+            if let Some(pane) = self.panes.get_mut(pane_id) {
+                pane.set_geom(geom);
+                pane.set_should_render(true);
+            }
+        }
+        self.set_force_render();
+        true
+    }
     pub fn focus_next_pane(&mut self, client_id: ClientId) {
-        let connected_clients: Vec<ClientId> =
-            { self.connected_clients.borrow().iter().copied().collect() };
         let active_pane_id = self.get_active_pane_id(client_id).unwrap();
+        let pane_ids = self.selectable_pane_ids_in_order();
+        let next_active_pane_id = pane_ids
+            .iter()
+            .position(|pane_id| *pane_id == active_pane_id)
+            .and_then(|position| pane_ids.get(position + 1))
+            .or_else(|| pane_ids.first())
+            .copied();
+        if let Some(next_active_pane_id) = next_active_pane_id {
+            self.focus_pane_for_all_clients(next_active_pane_id);
+        }
+    }
+    pub fn focus_next_pane_in_direction(
+        &mut self,
+        client_id: ClientId,
+        direction: &actions::Direction,
+    ) {
+        let active_pane_id = self.get_active_pane_id(client_id).unwrap();
+        let panes_to_hide = &self.panes_to_hide;
         let pane_grid = TiledPaneGrid::new(
-            &mut self.panes,
+            self.panes
+                .iter_mut()
+                .filter(|(pane_id, _)| !panes_to_hide.contains(pane_id)),
             *self.display_area.borrow(),
             *self.viewport.borrow(),
         );
-        let next_active_pane_id = pane_grid.next_selectable_pane_id(&active_pane_id);
-        for client_id in connected_clients {
-            self.active_panes.insert(client_id, next_active_pane_id);
+        let pane_ids = pane_grid.spatial_focus_cycle(direction);
+        let next_active_pane_id = pane_ids
+            .iter()
+            .position(|pane_id| *pane_id == active_pane_id)
+            .and_then(|position| pane_ids.get(position + 1))
+            .or_else(|| pane_ids.first())
+            .copied();
+        if let Some(next_active_pane_id) = next_active_pane_id {
+            self.focus_pane_for_all_clients(next_active_pane_id);
         }
-        self.set_pane_active_at(next_active_pane_id);
     }
     pub fn focus_previous_pane(&mut self, client_id: ClientId) {
+        let active_pane_id = self.get_active_pane_id(client_id).unwrap();
+        let pane_ids = self.selectable_pane_ids_in_order();
+        let previous_active_pane_id = pane_ids
+            .iter()
+            .position(|pane_id| *pane_id == active_pane_id)
+            .and_then(|position| position.checked_sub(1))
+            .and_then(|position| pane_ids.get(position))
+            .or_else(|| pane_ids.last())
+            .copied();
+        if let Some(previous_active_pane_id) = previous_active_pane_id {
+            self.focus_pane_for_all_clients(previous_active_pane_id);
+        }
+    }
+    // counts the panes from the top left to the bottom right rather than in pane_order, so the
+    // number of a pane does not change when it is moved to the front or back of the focus order
+    pub fn focus_pane_by_index(&mut self, index: usize) -> bool {
+        match self.selectable_pane_ids_by_position().get(index) {
+            Some(pane_id) => {
+                self.focus_pane_for_all_clients(*pane_id);
+                // so that the frames of previously active panes do not remain actively colored
+                self.set_force_render();
+                true
+            }
+            None => false,
+        }
+    }
+    fn focus_pane_for_all_clients(&mut self, pane_id: PaneId) {
         let connected_clients: Vec<ClientId> =
             { self.connected_clients.borrow().iter().copied().collect() };
-        let active_pane_id = self.get_active_pane_id(client_id).unwrap();
-        let pane_grid = TiledPaneGrid::new(
-            &mut self.panes,
-            *self.display_area.borrow(),
-            *self.viewport.borrow(),
-        );
-        let next_active_pane_id = pane_grid.previous_selectable_pane_id(&active_pane_id);
         for client_id in connected_clients {
-            self.active_panes.insert(client_id, next_active_pane_id);
+            self.set_active_pane(client_id, pane_id);
+        }
+        self.set_pane_active_at(pane_id);
+    }
+    pub fn move_pane_to_front(&mut self, pane_id: PaneId) {
+        if self.panes.contains_key(&pane_id) {
+            self.pane_order.retain(|p| *p != pane_id);
+            self.pane_order.insert(0, pane_id);
+        }
+    }
+    pub fn move_pane_to_back(&mut self, pane_id: PaneId) {
+        if self.panes.contains_key(&pane_id) {
+            self.pane_order.retain(|p| *p != pane_id);
+            self.pane_order.push(pane_id);
         }
-        self.set_pane_active_at(next_active_pane_id);
     }
     fn set_pane_active_at(&mut self, pane_id: PaneId) {
         if let Some(pane) = self.get_pane_mut(pane_id) {
@@ -542,11 +1099,54 @@ impl TiledPanes {
             (size_in_pixels.height as f64 / size_in_pixels.width as f64).round() as usize
         })
     }
+    pub fn active_pane_is_at_edge(
+        &self,
+        client_id: ClientId,
+        direction: actions::Direction,
+    ) -> bool {
+        // true if there is no selectable pane to move focus to in this direction
+        let active_pane_id = match self.get_active_pane_id(client_id) {
+            Some(active_pane_id) => active_pane_id,
+            None => return true,
+        };
+        let active_pane = match self.panes.get(&active_pane_id) {
+            Some(active_pane) => Box::as_ref(active_pane),
+            None => return true,
+        };
+        !self.panes.iter().any(|(pane_id, pane)| {
+            *pane_id != active_pane_id
+                && pane.selectable()
+                && !self.panes_to_hide.contains(pane_id)
+                && match direction {
+                    actions::Direction::Left => {
+                        pane.is_directly_left_of(active_pane)
+                            && pane.horizontally_overlaps_with(active_pane)
+                    }
+                    actions::Direction::Right => {
+                        pane.is_directly_right_of(active_pane)
+                            && pane.horizontally_overlaps_with(active_pane)
+                    }
+                    actions::Direction::Up => {
+                        pane.is_directly_above(active_pane)
+                            && pane.vertically_overlaps_with(active_pane)
+                    }
+                    actions::Direction::Down => {
+                        pane.is_directly_below(active_pane)
+                            && pane.vertically_overlaps_with(active_pane)
+                    }
+                }
+        })
+    }
     pub fn move_focus_left(&mut self, client_id: ClientId) -> bool {
         match self.get_active_pane_id(client_id) {
             Some(active_pane_id) => {
+                // the panes hidden because they did not fit or by a pane maximized in one axis
+                // can't be focused
+                let panes_to_hide = &self.panes_to_hide;
                 let pane_grid = TiledPaneGrid::new(
-                    &mut self.panes,
+                    self.panes
+                        .iter_mut()
+                        .filter(|(pane_id, _)| !panes_to_hide.contains(pane_id)),
                     *self.display_area.borrow(),
                     *self.viewport.borrow(),
                 );
@@ -582,11 +1182,55 @@ impl TiledPanes {
             None => false,
         }
     }
+    pub fn move_focus_wrapping(
+        &mut self,
+        client_id: ClientId,
+        direction: &actions::Direction,
+    ) -> bool {
+        // moves focus to the pane on the opposite edge of the tab, to be used when there is no
+        // pane to move focus to in this direction
+        match self.get_active_pane_id(client_id) {
+            Some(active_pane_id) => {
+                let panes_to_hide = &self.panes_to_hide;
+                let pane_grid = TiledPaneGrid::new(
+                    self.panes
+                        .iter_mut()
+                        .filter(|(pane_id, _)| !panes_to_hide.contains(pane_id)),
+                    *self.display_area.borrow(),
+                    *self.viewport.borrow(),
+                );
+                let next_index = pane_grid.wrapped_selectable_pane_id(&active_pane_id, direction);
+                match next_index {
+                    Some(p) => {
+                        // render previously active pane so that its frame does not remain actively
+                        // colored
+                        let previously_active_pane = self.panes.get_mut(&active_pane_id).unwrap();
+                        previously_active_pane.set_should_render(true);
+                        previously_active_pane.render_full_viewport();
+
+                        let next_active_pane = self.panes.get_mut(&p).unwrap();
+                        next_active_pane.set_should_render(true);
+                        next_active_pane.render_full_viewport();
+
+                        self.focus_pane(p, client_id);
+                        self.set_pane_active_at(p);
+
+                        true
+                    }
+                    None => false,
+                }
+            }
+            None => false,
+        }
+    }
     pub fn move_focus_down(&mut self, client_id: ClientId) -> bool {
         match self.get_active_pane_id(client_id) {
             Some(active_pane_id) => {
+                let panes_to_hide = &self.panes_to_hide;
                 let pane_grid = TiledPaneGrid::new(
-                    &mut self.panes,
+                    self.panes
+                        .iter_mut()
+                        .filter(|(pane_id, _)| !panes_to_hide.contains(pane_id)),
                     *self.display_area.borrow(),
                     *self.viewport.borrow(),
                 );
@@ -625,8 +1269,11 @@ impl TiledPanes {
     pub fn move_focus_up(&mut self, client_id: ClientId) -> bool {
         match self.get_active_pane_id(client_id) {
             Some(active_pane_id) => {
+                let panes_to_hide = &self.panes_to_hide;
                 let pane_grid = TiledPaneGrid::new(
-                    &mut self.panes,
+                    self.panes
+                        .iter_mut()
+                        .filter(|(pane_id, _)| !panes_to_hide.contains(pane_id)),
                     *self.display_area.borrow(),
                     *self.viewport.borrow(),
                 );
@@ -665,8 +1312,11 @@ impl TiledPanes {
     pub fn move_focus_right(&mut self, client_id: ClientId) -> bool {
         match self.get_active_pane_id(client_id) {
             Some(active_pane_id) => {
+                let panes_to_hide = &self.panes_to_hide;
                 let pane_grid = TiledPaneGrid::new(
-                    &mut self.panes,
+                    self.panes
+                        .iter_mut()
+                        .filter(|(pane_id, _)| !panes_to_hide.contains(pane_id)),
                     *self.display_area.borrow(),
                     *self.viewport.borrow(),
                 );
@@ -868,6 +1518,174 @@ impl TiledPanes {
             }
         }
     }
+    pub fn move_active_pane_to_direction(
+        &mut self,
+        client_id: ClientId,
+        direction: &actions::Direction,
+    ) {
+        let active_pane_id = match self.get_active_pane_id(client_id) {
+            Some(active_pane_id) => active_pane_id,
+            None => return,
+        };
+        let target_pane_id = {
+            let pane_grid = TiledPaneGrid::new(
+                &mut self.panes,
+                *self.display_area.borrow(),
+                *self.viewport.borrow(),
+            );
+            match direction {
+                actions::Direction::Left => {
+                    pane_grid.next_selectable_pane_id_to_the_left(&active_pane_id)
+                }
+                actions::Direction::Right => {
+                    pane_grid.next_selectable_pane_id_to_the_right(&active_pane_id)
+                }
+                actions::Direction::Up => pane_grid.next_selectable_pane_id_above(&active_pane_id),
+                actions::Direction::Down => {
+                    pane_grid.next_selectable_pane_id_below(&active_pane_id)
+                }
+            }
+        };
+        let target_pane_id = match target_pane_id {
+            Some(target_pane_id) => target_pane_id,
+            None => return,
+        };
+        let split_direction = match direction {
+            actions::Direction::Left | actions::Direction::Right => Direction::Vertical,
+            actions::Direction::Up | actions::Direction::Down => Direction::Horizontal,
+        };
+        let target_geom = self.panes.get(&target_pane_id).unwrap().position_and_size();
+        let min_pane_size = self.min_pane_size();
+        if split_pane_geom(split_direction, &target_geom, None, min_pane_size).is_none() {
+            // the target is too small to host the pane, leave everything as is
+            return;
+        }
+        let was_pinned = self.pinned_panes.contains(&active_pane_id);
+        let mut moved_pane = match self.extract_pane(active_pane_id) {
+            Some(moved_pane) => moved_pane,
+            None => return,
+        };
+        // the target can only have grown when the space of the moved pane was reclaimed
+        let target_pane = self.panes.get_mut(&target_pane_id).unwrap();
+        let target_geom = target_pane.position_and_size();
+        match split_pane_geom(split_direction, &target_geom, None, min_pane_size) {
+            Some((first_geom, second_geom)) => {
+                let (target_geom, moved_geom) = match direction {
+                    actions::Direction::Left | actions::Direction::Up => (second_geom, first_geom),
+                    actions::Direction::Right | actions::Direction::Down => {
+                        (first_geom, second_geom)
+                    }
+                };
+                target_pane.set_geom(target_geom);
+                moved_pane.set_geom(moved_geom);
+                self.panes.insert(active_pane_id, moved_pane);
+                self.pane_order.push(active_pane_id);
+                self.relayout(!split_direction);
+            }
+            None => self.insert_pane(active_pane_id, moved_pane),
+        }
+        if was_pinned {
+            self.pinned_panes.insert(active_pane_id);
+        }
+        self.focus_pane(active_pane_id, client_id);
+        // this also resizes the ptys
+        self.set_pane_frames(self.draw_pane_frames);
+        self.set_force_render();
+    }
+    pub fn next_selectable_pane_id_in_direction(
+        &mut self,
+        client_id: ClientId,
+        direction: &actions::Direction,
+    ) -> Option<PaneId> {
+        let active_pane_id = self.get_active_pane_id(client_id)?;
+        let pane_grid = TiledPaneGrid::new(
+            &mut self.panes,
+            *self.display_area.borrow(),
+            *self.viewport.borrow(),
+        );
+        match direction {
+            actions::Direction::Left => {
+                pane_grid.next_selectable_pane_id_to_the_left(&active_pane_id)
+            }
+            actions::Direction::Right => {
+                pane_grid.next_selectable_pane_id_to_the_right(&active_pane_id)
+            }
+            actions::Direction::Up => pane_grid.next_selectable_pane_id_above(&active_pane_id),
+            actions::Direction::Down => pane_grid.next_selectable_pane_id_below(&active_pane_id),
+        }
+    }
+    pub fn swap_pane(&mut self, client_id: ClientId, direction: &actions::Direction) {
+        if let Some(active_pane_id @ PaneId::Terminal(_)) = self.get_active_pane_id(client_id) {
+            if let Some(pane_id_to_swap @ PaneId::Terminal(_)) =
+                self.next_selectable_pane_id_in_direction(client_id, direction)
+            {
+                self.swap_pane_geoms(active_pane_id, pane_id_to_swap);
+            }
+        }
+    }
+    pub fn rotate_panes(&mut self, reverse: bool) {
+        // every pane moves to the slot of the next one (top to bottom, left to right), the slots
+        // themselves stay where they are and each pane keeps its own geom override
+        let viewport = *self.viewport.borrow();
+        let mut pane_ids: Vec<PaneId> = self
+            .panes
+            .iter()
+            .filter(|(pane_id, pane)| {
+                pane.selectable()
+                    && is_inside_viewport(&viewport, pane)
+                    && !self.panes_to_hide.contains(pane_id)
+            })
+            .map(|(pane_id, _)| *pane_id)
+            .collect();
+        if pane_ids.len() < 2 {
+            return;
+        }
+        pane_ids.sort_by_key(|pane_id| {
+            let geom = self.panes.get(pane_id).unwrap().position_and_size();
+            (geom.y, geom.x)
+        });
+        let slots: Vec<PaneGeom> = pane_ids
+            .iter()
+            .map(|pane_id| self.panes.get(pane_id).unwrap().position_and_size())
+            .collect();
+        let slot_count = slots.len();
+        for (index, pane_id) in pane_ids.iter().enumerate() {
+            let next_index = if reverse {
+                (index + slot_count - 1) % slot_count
+            } else {
+                (index + 1) % slot_count
+            };
+            let pane = self.panes.get_mut(pane_id).unwrap();
+            pane.set_geom(slots[next_index]);
+            resize_pty!(pane, self.os_api);
+            pane.set_should_render(true);
+        }
+    }
+    fn swap_pane_geoms(&mut self, first_pane_id: PaneId, second_pane_id: PaneId) {
+        let first_pane = self.panes.get(&first_pane_id).unwrap();
+        let first_geom = first_pane.position_and_size();
+        let first_geom_override = first_pane.geom_override();
+
+        let second_pane = self.panes.get_mut(&second_pane_id).unwrap();
+        let second_geom = second_pane.position_and_size();
+        let second_geom_override = second_pane.geom_override();
+        second_pane.set_geom(first_geom);
+        match first_geom_override {
+            Some(geom) => second_pane.get_geom_override(geom),
+            None => second_pane.reset_size_and_position_override(),
+        }
+        resize_pty!(second_pane, self.os_api);
+        second_pane.set_should_render(true);
+
+        let first_pane = self.panes.get_mut(&first_pane_id).unwrap();
+        first_pane.set_geom(second_geom);
+        match second_geom_override {
+            Some(geom) => first_pane.get_geom_override(geom),
+            None => first_pane.reset_size_and_position_override(),
+        }
+        resize_pty!(first_pane, self.os_api);
+        first_pane.set_should_render(true);
+    }
     pub fn move_clients_out_of_pane(&mut self, pane_id: PaneId) {
         let active_panes: Vec<(ClientId, PaneId)> = self
             .active_panes
@@ -878,12 +1696,12 @@ impl TiledPanes {
             .panes
             .iter()
             .find(|(p_id, p)| **p_id != pane_id && p.selectable())
-            .map(|(p_id, _p)| p_id)
+            .map(|(p_id, _p)| *p_id)
         {
             Some(next_active_pane) => {
                 for (client_id, active_pane_id) in active_panes {
                     if active_pane_id == pane_id {
-                        self.active_panes.insert(client_id, *next_active_pane);
+                        self.set_active_pane(client_id, next_active_pane);
                     }
                 }
             }
@@ -891,65 +1709,247 @@ impl TiledPanes {
         }
     }
     pub fn remove_pane(&mut self, pane_id: PaneId) -> Option<Box<dyn Pane>> {
+        let closed_pane = self.extract_pane(pane_id);
+        if closed_pane.is_none() {
+            self.panes.remove(&pane_id);
+            self.pane_order.retain(|p| *p != pane_id);
+            self.previously_active_panes.retain(|_, p| *p != pane_id);
+            self.pinned_panes.remove(&pane_id);
+            // this is a bit of a roundabout way to say: this is the last pane and so the tab
+            // should be destroyed
+            self.active_panes.clear();
+        }
+        closed_pane
+    }
+    pub fn extract_pane(&mut self, pane_id: PaneId) -> Option<Box<dyn Pane>> {
+        // returns None and leaves everything as is if there is nothing to fill the space over
+        // the pane (eg. it is the last one)
+        if !self.panes.contains_key(&pane_id) {
+            return None;
+        }
+        let previously_active_pane = self
+            .active_panes
+            .iter()
+            .find(|(_, active_pane_id)| **active_pane_id == pane_id)
+            .and_then(|(client_id, _)| self.previously_active_panes.get(client_id))
+            .copied();
         let mut pane_grid = TiledPaneGrid::new(
             &mut self.panes,
             *self.display_area.borrow(),
             *self.viewport.borrow(),
         );
-        if pane_grid.fill_space_over_pane(pane_id) {
+        if pane_grid.fill_space_over_pane(pane_id, self.close_gravity, previously_active_pane) {
             // successfully filled space over pane
-            let closed_pane = self.panes.remove(&pane_id);
+            let extracted_pane = self.panes.remove(&pane_id);
+            self.pane_order.retain(|p| *p != pane_id);
+            self.previously_active_panes.retain(|_, p| *p != pane_id);
+            self.pinned_panes.remove(&pane_id);
             self.move_clients_out_of_pane(pane_id);
-            for pane in self.panes.values_mut() {
-                resize_pty!(pane, self.os_api);
-            }
-            closed_pane
+            // this also resizes the ptys, and hides the frame if only a single pane is left
+            self.set_pane_frames(self.draw_pane_frames);
+            extracted_pane
         } else {
-            self.panes.remove(&pane_id);
-            // this is a bit of a roundabout way to say: this is the last pane and so the tab
-            // should be destroyed
-            self.active_panes.clear();
             None
         }
     }
+    pub fn set_close_gravity(&mut self, close_gravity: CloseGravity) {
+        self.close_gravity = close_gravity;
+    }
     pub fn panes_to_hide_contains(&self, pane_id: PaneId) -> bool {
         self.panes_to_hide.contains(&pane_id)
     }
+    pub fn start_dragging_border_at(&mut self, position: &Position) -> bool {
+        // returns true if there is a border between two panes at this position
+        self.dragged_border = self.border_at(position);
+        self.dragged_border.is_some()
+    }
+    pub fn border_is_being_dragged(&self) -> bool {
+        self.dragged_border.is_some()
+    }
+    pub fn drag_border_to(&mut self, position: &Position) {
+        let dragged_border = match self.dragged_border {
+            Some(dragged_border) => dragged_border,
+            None => return,
+        };
+        let (border_position, mouse_position, space) = {
+            let pane_after = match self.panes.get(&dragged_border.pane_after) {
+                Some(pane_after) => pane_after,
+                None => {
+                    self.dragged_border = None;
+                    return;
+                }
+            };
+            let display_area = self.display_area.borrow();
+            match dragged_border.direction {
+                Direction::Horizontal => (
+                    pane_after.x() as isize,
+                    position.column() as isize,
+                    display_area.cols,
+                ),
+                Direction::Vertical => {
+                    (pane_after.y() as isize, position.line(), display_area.rows)
+                }
+            }
+        };
+        let delta = mouse_position - dragged_border.grab_offset - border_position;
+        if delta == 0 {
+            return;
+        }
+        let percent = (delta.abs() as f64 / space as f64) * 100.0;
+        // we always grow the pane on the side the border is moving towards, so that the border
+        // is the only thing that moves
+        let (pane_id, direction) = match (dragged_border.direction, delta > 0) {
+            (Direction::Horizontal, true) => {
+                (dragged_border.pane_before, actions::Direction::Right)
+            }
+            (Direction::Horizontal, false) => (dragged_border.pane_after, actions::Direction::Left),
+            (Direction::Vertical, true) => (dragged_border.pane_before, actions::Direction::Down),
+            (Direction::Vertical, false) => (dragged_border.pane_after, actions::Direction::Up),
+        };
+        let mut pane_grid = TiledPaneGrid::new(
+            &mut self.panes,
+            *self.display_area.borrow(),
+            *self.viewport.borrow(),
+        );
+        pane_grid.increase_pane_by(&pane_id, &direction, percent);
+        for pane in self.panes.values_mut() {
+            resize_pty!(pane, self.os_api);
+        }
+    }
+    pub fn stop_dragging_border(&mut self) {
+        self.dragged_border = None;
+    }
+    fn border_at(&self, position: &Position) -> Option<DraggedBorder> {
+        let (line, column) = (position.line(), position.column());
+        if line < 0 {
+            return None;
+        }
+        let line = line as usize;
+        let overlaps_line = |pane: &dyn Pane| pane.y() <= line && line < pane.y() + pane.rows();
+        let overlaps_column =
+            |pane: &dyn Pane| pane.x() <= column && column < pane.x() + pane.cols();
+        let visible_panes = || {
+            self.panes
+                .iter()
+                .filter(|(pid, _)| !self.panes_to_hide.contains(pid))
+                .map(|(pid, pane)| (*pid, pane.as_ref()))
+        };
+        let (pid, pane) = visible_panes().find(|(_, pane)| pane.contains(position))?;
+        // the border is whatever part of the pane is not its content (its frame or boundary)
+        let (pane_before, pane_after, direction) =
+            if column >= pane.get_content_x() + pane.get_content_columns() {
+                let (pid_after, _) = visible_panes()
+                    .find(|(_, p)| p.x() == pane.x() + pane.cols() && overlaps_line(*p))?;
+                (pid, pid_after, Direction::Horizontal)
+            } else if column < pane.get_content_x() {
+                let (pid_before, _) = visible_panes()
+                    .find(|(_, p)| p.x() + p.cols() == pane.x() && overlaps_line(*p))?;
+                (pid_before, pid, Direction::Horizontal)
+            } else if line >= pane.get_content_y() + pane.get_content_rows() {
+                let (pid_after, _) = visible_panes()
+                    .find(|(_, p)| p.y() == pane.y() + pane.rows() && overlaps_column(*p))?;
+                (pid, pid_after, Direction::Vertical)
+            } else if line < pane.get_content_y() {
+                let (pid_before, _) = visible_panes()
+                    .find(|(_, p)| p.y() + p.rows() == pane.y() && overlaps_column(*p))?;
+                (pid_before, pid, Direction::Vertical)
+            } else {
+                return None;
+            };
+        let border_position = {
+            let pane_after = self.panes.get(&pane_after)?;
+            match direction {
+                Direction::Horizontal => pane_after.x() as isize,
+                Direction::Vertical => pane_after.y() as isize,
+            }
+        };
+        let mouse_position = match direction {
+            Direction::Horizontal => column as isize,
+            Direction::Vertical => line as isize,
+        };
+        Some(DraggedBorder {
+            pane_before,
+            pane_after,
+            direction,
+            grab_offset: mouse_position - border_position,
+        })
+    }
+    // moves all panes and the viewport, eg. when space at the edge of the tab is reserved
+    pub fn offset_panes(&mut self, x: isize, y: isize) {
+        for pane in self.panes.values_mut() {
+            let mut geom = pane.position_and_size();
+            geom.x = (geom.x as isize + x).max(0) as usize;
+            geom.y = (geom.y as isize + y).max(0) as usize;
+            pane.set_geom(geom);
+        }
+        let mut viewport = self.viewport.borrow_mut();
+        viewport.x = (viewport.x as isize + x).max(0) as usize;
+        viewport.y = (viewport.y as isize + y).max(0) as usize;
+    }
+    // only when the user asked for it, panes hidden because they did not fit don't count
     pub fn fullscreen_is_active(&self) -> bool {
         self.fullscreen_is_active
     }
+    // as opposed to the active pane being maximized in one axis, in which case the rest of the
+    // panes can still be focused
+    pub fn active_pane_is_fullscreen(&self) -> bool {
+        self.fullscreen_is_active && self.maximized_axis.is_none()
+    }
+    fn save_geoms_before_hiding_panes(&mut self) {
+        // the geometry from before the first panes were hidden is the one we go back to, so it is
+        // kept when the user goes fullscreen after some panes were hidden for lack of space
+        if self.fullscreen_is_active || self.panes_hidden_for_lack_of_space {
+            return;
+        }
+        self.geoms_before_fullscreen = self
+            .panes
+            .iter()
+            .map(|(&id, pane)| (id, pane.position_and_size()))
+            .collect();
+        self.display_area_before_fullscreen = *self.display_area.borrow();
+    }
     pub fn unset_fullscreen(&mut self) {
-        if self.fullscreen_is_active {
-            let first_client_id = {
-                let connected_clients = self.connected_clients.borrow();
-                *connected_clients.iter().next().unwrap()
-            };
-            let active_pane_id = self.get_active_pane_id(first_client_id).unwrap();
+        let panes_were_hidden_for_lack_of_space = self.panes_hidden_for_lack_of_space;
+        self.show_hidden_panes();
+        if panes_were_hidden_for_lack_of_space {
+            // the panes that did not fit before going fullscreen still don't
+            let display_area = *self.display_area.borrow();
+            if !self.resize_panes(display_area) {
+                self.hide_panes_for_lack_of_space(display_area);
+            }
+        }
+    }
+    fn show_hidden_panes(&mut self) {
+        let panes_are_hidden = self.fullscreen_is_active || self.panes_hidden_for_lack_of_space;
+        self.panes_hidden_for_lack_of_space = false;
+        self.maximized_axis = None;
+        if panes_are_hidden {
             let panes_to_hide: Vec<_> = self.panes_to_hide.iter().copied().collect();
             for pane_id in panes_to_hide {
                 let pane = self.get_pane_mut(pane_id).unwrap();
                 pane.set_should_render(true);
                 pane.set_should_render_boundaries(true);
             }
-            let viewport_pane_ids: Vec<_> = self
-                .panes
-                .keys()
-                .copied()
-                .into_iter()
-                .filter(|id| {
-                    !is_inside_viewport(&*self.viewport.borrow(), self.get_pane(*id).unwrap())
-                })
-                .collect();
-            for pid in viewport_pane_ids {
-                let viewport_pane = self.get_pane_mut(pid).unwrap();
-                viewport_pane.reset_size_and_position_override();
+            // the panes outside of the viewport and the fullscreen (or maximized) pane have an
+            // override, focus might have moved away from the latter in the meantime
+            for pane in self.panes.values_mut() {
+                pane.reset_size_and_position_override();
             }
             self.panes_to_hide.clear();
-            let active_terminal = self.get_pane_mut(active_pane_id).unwrap();
-            active_terminal.reset_size_and_position_override();
+            // restore the exact geometry from before going fullscreen, the hidden panes were not
+            // resized along with the tab in the meantime so their own geometry is stale
+            for (pane_id, geom) in self.geoms_before_fullscreen.drain() {
+                if let Some(pane) = self.panes.get_mut(&pane_id) {
+                    pane.set_geom(geom);
+                }
+            }
             self.set_force_render();
             let display_area = *self.display_area.borrow();
-            self.resize(display_area);
+            if display_area != self.display_area_before_fullscreen {
+                self.resize_panes(display_area);
+            } else {
+                self.set_pane_frames(self.draw_pane_frames);
+            }
             self.fullscreen_is_active = false;
         }
     }
@@ -972,6 +1972,7 @@ impl TiledPanes {
                     // nothing to do, pane is already as fullscreen as it can be, let's bail
                     return;
                 } else {
+                    self.save_geoms_before_hiding_panes();
                     // For all of the panes outside of the viewport staying on the fullscreen
                     // screen, switch them to using override positions as well so that the resize
                     // system doesn't get confused by viewport and old panes that no longer line up
@@ -1007,14 +2008,151 @@ impl TiledPanes {
                 }
                 self.set_force_render();
                 let display_area = *self.display_area.borrow();
-                self.resize(display_area);
+                self.resize_panes(display_area);
                 self.fullscreen_is_active = true;
             }
         }
     }
+    pub fn toggle_active_pane_max_width(&mut self, client_id: ClientId) {
+        self.toggle_active_pane_maximized_axis(client_id, MaximizedAxis::Width);
+    }
+    pub fn toggle_active_pane_max_height(&mut self, client_id: ClientId) {
+        self.toggle_active_pane_maximized_axis(client_id, MaximizedAxis::Height);
+    }
+    fn toggle_active_pane_maximized_axis(&mut self, client_id: ClientId, axis: MaximizedAxis) {
+        // like fullscreen, but only the panes overlapping the active one in the other axis are
+        // hidden and the rest of the tab keeps its layout
+        let active_pane_id = match self.get_active_pane_id(client_id) {
+            Some(active_pane_id) => active_pane_id,
+            None => return,
+        };
+        if self.fullscreen_is_active {
+            let was_maximized_in_this_axis = self.maximized_axis == Some(axis);
+            self.unset_fullscreen();
+            if was_maximized_in_this_axis {
+                return;
+            }
+        }
+        let viewport = *self.viewport.borrow();
+        let active_geom = self.panes.get(&active_pane_id).unwrap().position_and_size();
+        let overlaps = |geom: &PaneGeom| match axis {
+            MaximizedAxis::Width => {
+                geom.y < active_geom.y + active_geom.rows.as_usize()
+                    && active_geom.y < geom.y + geom.rows.as_usize()
+            }
+            MaximizedAxis::Height => {
+                geom.x < active_geom.x + active_geom.cols.as_usize()
+                    && active_geom.x < geom.x + geom.cols.as_usize()
+            }
+        };
+        let panes_to_hide: HashSet<PaneId> = self
+            .panes
+            .iter()
+            .filter(|(id, pane)| {
+                **id != active_pane_id
+                    && is_inside_viewport(&viewport, pane)
+                    && overlaps(&pane.position_and_size())
+            })
+            .map(|(id, _)| *id)
+            .collect();
+        if panes_to_hide.is_empty() {
+            // the pane already spans the whole tab in this axis
+            return;
+        }
+        self.save_geoms_before_hiding_panes();
+        // the panes hidden for lack of space (if any) stay hidden
+        self.panes_to_hide.extend(panes_to_hide);
+        let maximized_geom = match axis {
+            MaximizedAxis::Width => PaneGeom {
+                x: viewport.x,
+                cols: Dimension::fixed(viewport.cols),
+                ..active_geom
+            },
+            MaximizedAxis::Height => PaneGeom {
+                y: viewport.y,
+                rows: Dimension::fixed(viewport.rows),
+                ..active_geom
+            },
+        };
+        self.panes
+            .get_mut(&active_pane_id)
+            .unwrap()
+            .get_geom_override(maximized_geom);
+        self.fullscreen_is_active = true;
+        self.maximized_axis = Some(axis);
+        self.set_pane_frames(self.draw_pane_frames);
+        let active_pane = self.panes.get_mut(&active_pane_id).unwrap();
+        resize_pty!(active_pane, self.os_api);
+        self.set_force_render();
+    }
+    pub fn toggle_pane_pinned(&mut self, pane_id: PaneId) {
+        if !self.panes.contains_key(&pane_id) {
+            return;
+        }
+        if !self.pinned_panes.remove(&pane_id) {
+            self.pinned_panes.insert(pane_id);
+        }
+        self.set_force_render();
+    }
+    // pinned panes are drawn over the fullscreen pane, so they also take its clicks
+    pub fn pinned_pane_id_at(&self, point: &Position, search_selectable: bool) -> Option<PaneId> {
+        if !self.fullscreen_is_active {
+            return None;
+        }
+        self.pinned_panes
+            .iter()
+            .copied()
+            .find(|pane_id| match self.panes.get(pane_id) {
+                Some(pane) => pane.contains(point) && (!search_selectable || pane.selectable()),
+                None => false,
+            })
+    }
     pub fn panes_to_hide_count(&self) -> usize {
         self.panes_to_hide.len()
     }
+    pub fn snapshot_layout(&self) -> Option<Layout> {
+        let active_pane_id = self.first_active_pane_id();
+        let leaves = self
+            .panes
+            .values()
+            .map(|pane| {
+                let pane_name = pane.pane_name();
+                let layout = Layout {
+                    direction: Direction::Horizontal,
+                    pane_name: if pane_name.is_empty() {
+                        None
+                    } else {
+                        Some(pane_name)
+                    },
+                    parts: vec![],
+                    split_size: None,
+                    run: pane.invoked_with(),
+                    borderless: pane.borderless(),
+                    focus: if Some(pane.pid()) == active_pane_id {
+                        Some(true)
+                    } else {
+                        None
+                    },
+                };
+                (pane.position_and_size(), layout)
+            })
+            .collect();
+        let layout = split_tree(leaves)?;
+        if layout.parts.is_empty() {
+            // a layout needs at least one part to place a pane in
+            Some(Layout {
+                direction: Direction::Horizontal,
+                pane_name: None,
+                parts: vec![layout],
+                split_size: None,
+                run: None,
+                borderless: false,
+                focus: None,
+            })
+        } else {
+            Some(layout)
+        }
+    }
 }
 
 #[allow(clippy::borrowed_box)]
@@ -1031,3 +2169,90 @@ pub fn pane_geom_is_inside_viewport(viewport: &Viewport, geom: &PaneGeom) -> boo
         && geom.x >= viewport.x
         && geom.x + geom.cols.as_usize() <= viewport.x + viewport.cols
 }
+
+// reconstructs the splits that created these panes, returns None if they can't be cut all the way
+// through in either direction (this can't happen with panes created by splitting)
+fn split_tree(mut leaves: Vec<(PaneGeom, Layout)>) -> Option<Layout> {
+    if leaves.len() == 1 {
+        return leaves.pop().map(|(_, layout)| layout);
+    }
+    for direction in [Direction::Vertical, Direction::Horizontal] {
+        let groups = split_leaves_along(direction, leaves.clone());
+        if groups.len() > 1 {
+            let mut parts = vec![];
+            for group in groups {
+                let split_size = split_size_of(direction, &group);
+                let mut part = split_tree(group)?;
+                part.split_size = split_size;
+                parts.push(part);
+            }
+            return Some(Layout {
+                direction,
+                pane_name: None,
+                parts,
+                split_size: None,
+                run: None,
+                borderless: false,
+                focus: None,
+            });
+        }
+    }
+    None
+}
+
+fn split_leaves_along(
+    direction: Direction,
+    mut leaves: Vec<(PaneGeom, Layout)>,
+) -> Vec<Vec<(PaneGeom, Layout)>> {
+    let span = |geom: &PaneGeom| match direction {
+        Direction::Vertical => (geom.x, geom.x + geom.cols.as_usize()),
+        Direction::Horizontal => (geom.y, geom.y + geom.rows.as_usize()),
+    };
+    leaves.sort_by_key(|(geom, _)| span(geom).0);
+    let mut groups: Vec<Vec<(PaneGeom, Layout)>> = vec![];
+    let mut group_end = 0;
+    for leaf in leaves {
+        let (start, end) = span(&leaf.0);
+        match groups.last_mut() {
+            Some(group) if start < group_end => group.push(leaf),
+            _ => groups.push(vec![leaf]),
+        }
+        group_end = std::cmp::max(group_end, end);
+    }
+    groups
+}
+
+fn split_size_of(direction: Direction, leaves: &[(PaneGeom, Layout)]) -> Option<SplitSize> {
+    // the size of a group of panes is that of any chain of panes going across it
+    let dimension = |geom: &PaneGeom| match direction {
+        Direction::Vertical => (geom.x, geom.cols),
+        Direction::Horizontal => (geom.y, geom.rows),
+    };
+    let start = leaves.iter().map(|(geom, _)| dimension(geom).0).min()?;
+    let end = leaves
+        .iter()
+        .map(|(geom, _)| {
+            let (position, size) = dimension(geom);
+            position + size.as_usize()
+        })
+        .max()?;
+    let mut position = start;
+    let mut percent = 0.0;
+    let mut fixed = 0;
+    while position < end {
+        let (_, size) = leaves
+            .iter()
+            .map(|(geom, _)| dimension(geom))
+            .find(|(pane_position, _)| *pane_position == position)?;
+        match size.constraint {
+            Constraint::Percent(p) => percent += p,
+            Constraint::Fixed(f) => fixed += f,
+        }
+        position += size.as_usize();
+    }
+    if percent > 0.0 {
+        Some(SplitSize::Percent(percent))
+    } else {
+        Some(SplitSize::Fixed(fixed))
+    }
+}
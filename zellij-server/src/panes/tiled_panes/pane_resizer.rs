@@ -47,7 +47,18 @@ impl<'a> PaneResizer<'a> {
     pub fn layout(&mut self, direction: Direction, space: usize) -> Result<(), String> {
         self.solver.reset();
         let grid = self.solve(direction, space)?;
-        let spans = self.discretize_spans(grid, space)?;
+        // panes don't start at the edge of the screen if the tab reserved some space there
+        let origin = self
+            .panes
+            .borrow()
+            .values()
+            .map(|p| match direction {
+                Direction::Horizontal => p.current_geom().x,
+                Direction::Vertical => p.current_geom().y,
+            })
+            .min()
+            .unwrap_or(0);
+        let spans = self.discretize_spans(grid, space, origin)?;
         self.apply_spans(spans);
         Ok(())
     }
@@ -71,7 +82,12 @@ impl<'a> PaneResizer<'a> {
         Ok(grid)
     }
 
-    fn discretize_spans(&mut self, mut grid: Grid, space: usize) -> Result<Vec<Span>, String> {
+    fn discretize_spans(
+        &mut self,
+        mut grid: Grid,
+        space: usize,
+        origin: usize,
+    ) -> Result<Vec<Span>, String> {
         let mut rounded_sizes: HashMap<_, _> = grid
             .iter()
             .flatten()
@@ -85,20 +101,26 @@ impl<'a> PaneResizer<'a> {
 
         // Round f64 pane sizes to usize without gaps or overlap
         let mut finalised = Vec::new();
-        for spans in &mut grid {
+        for spans in &grid {
             let rounded_size: isize = spans.iter().map(|s| rounded_sizes[&s.size_var]).sum();
             let mut error = space as isize - rounded_size;
             let mut flex_spans: Vec<_> = spans
-                .iter_mut()
+                .iter()
                 .filter(|s| !s.size.is_fixed() && !finalised.contains(&s.pid))
+                .map(|s| s.size_var)
                 .collect();
-            flex_spans.sort_by_key(|s| rounded_sizes[&s.size_var]);
+            flex_spans.sort_by_key(|size_var| rounded_sizes[size_var]);
             if error < 0 {
                 flex_spans.reverse();
             }
-            for span in flex_spans {
+            // spans finalised in a previous boundary can't absorb any of the error, so there can
+            // be more of it than there are flex spans left - we go around until all of it is used
+            for size_var in flex_spans.iter().cycle() {
+                if error == 0 {
+                    break;
+                }
                 rounded_sizes
-                    .entry(span.size_var)
+                    .entry(*size_var)
                     .and_modify(|s| *s += error.signum());
                 error -= error.signum();
             }
@@ -107,7 +129,7 @@ impl<'a> PaneResizer<'a> {
 
         // Update span positions based on their rounded sizes
         for spans in &mut grid {
-            let mut offset = 0;
+            let mut offset = origin;
             for span in spans {
                 span.pos = offset;
                 let sz = rounded_sizes[&span.size_var];
@@ -117,6 +139,10 @@ impl<'a> PaneResizer<'a> {
                 span.size.set_inner(sz as usize);
                 offset += span.size.as_usize();
             }
+            if offset - origin != space {
+                // we'd rather not resize at all than leave gaps or overlapping panes
+                return Err("Spans do not fill the available space".into());
+            }
         }
 
         Ok(grid.into_iter().flatten().collect())
@@ -1,18 +1,22 @@
 use super::is_inside_viewport;
 use super::pane_resizer::PaneResizer;
-use crate::tab::{MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH};
 use crate::{panes::PaneId, tab::Pane};
-use std::cmp::Reverse;
+use std::cmp::{Ordering, Reverse};
 use std::collections::{HashMap, HashSet};
 use zellij_utils::{
-    input::layout::Direction,
+    input::{
+        actions,
+        layout::{Direction, LayoutPreset},
+        options::CloseGravity,
+    },
     pane_size::{Dimension, PaneGeom, Size, Viewport},
 };
 
 use std::cell::RefCell;
 use std::rc::Rc;
 
-const RESIZE_PERCENT: f64 = 5.0;
+pub const RESIZE_PERCENT: f64 = 5.0;
+const RESIZE_PRECISION_PERCENT: f64 = 0.1;
 const DEFAULT_CURSOR_HEIGHT_WIDTH_RATIO: usize = 4;
 
 type BorderAndPaneIds = (usize, Vec<PaneId>);
@@ -41,33 +45,394 @@ impl<'a> TiledPaneGrid<'a> {
         let mut pane_resizer = PaneResizer::new(self.panes.clone());
         pane_resizer.layout(direction, space)
     }
-    pub fn resize_pane_left(&mut self, pane_id: &PaneId) {
-        // TODO: find out by how much we actually reduced and only reduce by that much
-        if self.try_increase_pane_and_surroundings_left(pane_id, RESIZE_PERCENT) {
-            return;
+    pub fn rebalance_percentages(&mut self, direction: Direction) {
+        // corrects rounding drift so that the percent sizes of the panes in each row (or column)
+        // of the tab sum up to exactly 100%, the remainder is given to the largest pane
+        let mut panes = self.panes.borrow_mut();
+        let perpendicular_span = |geom: PaneGeom| match direction {
+            Direction::Horizontal => (geom.y, geom.y + geom.rows.as_usize()),
+            Direction::Vertical => (geom.x, geom.x + geom.cols.as_usize()),
+        };
+        let size_percent = |geom: PaneGeom| match direction {
+            Direction::Horizontal => geom.cols.as_percent(),
+            Direction::Vertical => geom.rows.as_percent(),
+        };
+        let mut edges: Vec<usize> = panes
+            .values()
+            .map(|p| perpendicular_span(p.current_geom()).1)
+            .collect();
+        edges.sort_unstable();
+        edges.dedup();
+        let mut rebalanced_panes: HashSet<PaneId> = HashSet::new();
+        let mut band_start = 0;
+        for band_end in edges {
+            let panes_in_band: Vec<(PaneId, f64)> = panes
+                .iter()
+                .filter_map(|(pid, p)| {
+                    let geom = p.current_geom();
+                    let (start, end) = perpendicular_span(geom);
+                    if start <= band_start && band_start < end {
+                        size_percent(geom).map(|percent| (*pid, percent))
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+            band_start = band_end;
+            let total: f64 = panes_in_band.iter().map(|(_, percent)| percent).sum();
+            let drift = 100.0 - total;
+            // panes spanning several bands are only corrected in the first one, so that we don't
+            // undo that correction in the next
+            // (f64::total_cmp is newer than our toolchain, a NaN percent is treated as equal
+            // rather than panicking)
+            let largest_pane = panes_in_band
+                .iter()
+                .filter(|(pid, _)| !rebalanced_panes.contains(pid))
+                .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+            if let Some((pid, _)) = largest_pane {
+                if drift != 0.0 {
+                    let pane = panes.get_mut(pid).unwrap();
+                    match direction {
+                        Direction::Horizontal if drift > 0.0 => pane.increase_width(drift),
+                        Direction::Horizontal => pane.reduce_width(-drift),
+                        Direction::Vertical if drift > 0.0 => pane.increase_height(drift),
+                        Direction::Vertical => pane.reduce_height(-drift),
+                    }
+                }
+            }
+            rebalanced_panes.extend(panes_in_band.iter().map(|(pid, _)| *pid));
         }
-        self.try_reduce_pane_and_surroundings_left(pane_id, RESIZE_PERCENT);
     }
-    pub fn resize_pane_right(&mut self, pane_id: &PaneId) {
-        // TODO: find out by how much we actually reduced and only reduce by that much
-        if self.try_increase_pane_and_surroundings_right(pane_id, RESIZE_PERCENT) {
-            return;
+    pub fn equalize_percentages(&mut self, direction: Direction) {
+        // gives every pane in a run of siblings (panes sharing the same perpendicular span and
+        // adjacent to each other) an equal share of the percent size of that run, fixed panes
+        // keep their size
+        let mut panes = self.panes.borrow_mut();
+        let mut siblings: HashMap<(usize, usize), Vec<(usize, usize, PaneId)>> = HashMap::new();
+        for (pid, pane) in panes.iter() {
+            let geom = pane.position_and_size();
+            let (perpendicular_span, start, size) = match direction {
+                Direction::Horizontal => ((geom.y, geom.rows.as_usize()), geom.x, geom.cols),
+                Direction::Vertical => ((geom.x, geom.cols.as_usize()), geom.y, geom.rows),
+            };
+            siblings.entry(perpendicular_span).or_default().push((
+                start,
+                start + size.as_usize(),
+                *pid,
+            ));
+        }
+        for mut panes_in_span in siblings.into_values() {
+            panes_in_span.sort_unstable();
+            let mut runs: Vec<Vec<PaneId>> = vec![];
+            let mut previous_end = None;
+            for (start, end, pid) in panes_in_span {
+                match runs.last_mut() {
+                    Some(run) if previous_end == Some(start) => run.push(pid),
+                    _ => runs.push(vec![pid]),
+                }
+                previous_end = Some(end);
+            }
+            for run in runs {
+                let percent_panes: Vec<(PaneId, f64)> = run
+                    .iter()
+                    .filter_map(|pid| {
+                        let geom = panes.get(pid).unwrap().position_and_size();
+                        let size = match direction {
+                            Direction::Horizontal => geom.cols,
+                            Direction::Vertical => geom.rows,
+                        };
+                        size.as_percent().map(|percent| (*pid, percent))
+                    })
+                    .collect();
+                if percent_panes.len() < 2 {
+                    continue;
+                }
+                let total: f64 = percent_panes.iter().map(|(_, percent)| percent).sum();
+                let equal_share = total / percent_panes.len() as f64;
+                for (pid, _) in percent_panes {
+                    let pane = panes.get_mut(&pid).unwrap();
+                    let mut geom = pane.position_and_size();
+                    let size = match direction {
+                        Direction::Horizontal => &mut geom.cols,
+                        Direction::Vertical => &mut geom.rows,
+                    };
+                    let inner = size.as_usize();
+                    *size = Dimension::percent(equal_share);
+                    size.set_inner(inner);
+                    pane.set_geom(geom);
+                }
+            }
         }
-        self.try_reduce_pane_and_surroundings_right(pane_id, RESIZE_PERCENT);
     }
-    pub fn resize_pane_down(&mut self, pane_id: &PaneId) {
-        // TODO: find out by how much we actually reduced and only reduce by that much
-        if self.try_increase_pane_and_surroundings_down(pane_id, RESIZE_PERCENT) {
-            return;
+    pub fn resize_pane_by(
+        &mut self,
+        pane_id: &PaneId,
+        direction: &actions::Direction,
+        percent: f64,
+    ) -> f64 {
+        // returns the amount (in percent) we actually resized by
+        if !percent.is_finite() || percent <= 0.0 {
+            return 0.0;
+        }
+        if self.try_resize_pane_by(pane_id, direction, percent) {
+            return percent;
+        }
+        // find the largest amount we can resize by without pushing any of the panes involved
+        // below their minimum size
+        let (mut can_resize_by, mut cannot_resize_by) = (0.0, percent);
+        while cannot_resize_by - can_resize_by > RESIZE_PRECISION_PERCENT {
+            let resize_by = (can_resize_by + cannot_resize_by) / 2.0;
+            if self.can_resize_pane_by(pane_id, direction, resize_by) {
+                can_resize_by = resize_by;
+            } else {
+                cannot_resize_by = resize_by;
+            }
+        }
+        if can_resize_by > 0.0 && self.try_resize_pane_by(pane_id, direction, can_resize_by) {
+            can_resize_by
+        } else {
+            0.0
         }
-        self.try_reduce_pane_and_surroundings_down(pane_id, RESIZE_PERCENT);
     }
-    pub fn resize_pane_up(&mut self, pane_id: &PaneId) {
-        // TODO: find out by how much we actually reduced and only reduce by that much
-        if self.try_increase_pane_and_surroundings_up(pane_id, RESIZE_PERCENT) {
-            return;
+    fn can_resize_pane_by(
+        &self,
+        pane_id: &PaneId,
+        direction: &actions::Direction,
+        percent: f64,
+    ) -> bool {
+        match direction {
+            actions::Direction::Left => {
+                self.can_increase_pane_and_surroundings_left(pane_id, percent)
+                    || self.can_reduce_pane_and_surroundings_left(pane_id, percent)
+            }
+            actions::Direction::Right => {
+                self.can_increase_pane_and_surroundings_right(pane_id, percent)
+                    || self.can_reduce_pane_and_surroundings_right(pane_id, percent)
+            }
+            actions::Direction::Up => {
+                self.can_increase_pane_and_surroundings_up(pane_id, percent)
+                    || self.can_reduce_pane_and_surroundings_up(pane_id, percent)
+            }
+            actions::Direction::Down => {
+                self.can_increase_pane_and_surroundings_down(pane_id, percent)
+                    || self.can_reduce_pane_and_surroundings_down(pane_id, percent)
+            }
+        }
+    }
+    pub fn increase_pane_by(
+        &mut self,
+        pane_id: &PaneId,
+        direction: &actions::Direction,
+        percent: f64,
+    ) -> f64 {
+        // like resize_pane_by, but only ever grows the pane so that only its border in this
+        // direction moves (eg. when it is dragged with the mouse)
+        if percent <= 0.0 {
+            return 0.0;
+        }
+        if self.try_increase_pane_and_surroundings(pane_id, direction, percent) {
+            return percent;
+        }
+        let (mut can_increase_by, mut cannot_increase_by) = (0.0, percent);
+        while cannot_increase_by - can_increase_by > RESIZE_PRECISION_PERCENT {
+            let increase_by = (can_increase_by + cannot_increase_by) / 2.0;
+            if self.can_increase_pane_and_surroundings(pane_id, direction, increase_by) {
+                can_increase_by = increase_by;
+            } else {
+                cannot_increase_by = increase_by;
+            }
+        }
+        if can_increase_by > 0.0
+            && self.try_increase_pane_and_surroundings(pane_id, direction, can_increase_by)
+        {
+            can_increase_by
+        } else {
+            0.0
+        }
+    }
+    pub fn reduce_pane_by(
+        &mut self,
+        pane_id: &PaneId,
+        direction: &actions::Direction,
+        percent: f64,
+    ) -> f64 {
+        // like increase_pane_by, but only ever shrinks the pane by moving its border on the
+        // opposite side of the direction (eg. reducing it to the left moves its right border)
+        if percent <= 0.0 {
+            return 0.0;
+        }
+        if self.try_reduce_pane_and_surroundings(pane_id, direction, percent) {
+            return percent;
+        }
+        let (mut can_reduce_by, mut cannot_reduce_by) = (0.0, percent);
+        while cannot_reduce_by - can_reduce_by > RESIZE_PRECISION_PERCENT {
+            let reduce_by = (can_reduce_by + cannot_reduce_by) / 2.0;
+            if self.can_reduce_pane_and_surroundings(pane_id, direction, reduce_by) {
+                can_reduce_by = reduce_by;
+            } else {
+                cannot_reduce_by = reduce_by;
+            }
+        }
+        if can_reduce_by > 0.0
+            && self.try_reduce_pane_and_surroundings(pane_id, direction, can_reduce_by)
+        {
+            can_reduce_by
+        } else {
+            0.0
+        }
+    }
+    pub fn resize_pane_to_percent(
+        &mut self,
+        pane_id: &PaneId,
+        cols_percent: Option<f64>,
+        rows_percent: Option<f64>,
+    ) {
+        // grows or shrinks the pane towards the requested share of the tab, stopping short where
+        // this would push any of the panes involved below their minimum size
+        let (cols, rows) = match self.panes.borrow().get(pane_id) {
+            Some(pane) => {
+                let geom = pane.current_geom();
+                (geom.cols.as_percent(), geom.rows.as_percent())
+            }
+            None => return,
+        };
+        if let (Some(current), Some(requested)) = (cols, cols_percent) {
+            let requested = requested.max(RESIZE_PERCENT).min(100.0);
+            self.resize_pane_along_axis(
+                pane_id,
+                requested - current,
+                [actions::Direction::Right, actions::Direction::Left],
+            );
+        }
+        if let (Some(current), Some(requested)) = (rows, rows_percent) {
+            let requested = requested.max(RESIZE_PERCENT).min(100.0);
+            self.resize_pane_along_axis(
+                pane_id,
+                requested - current,
+                [actions::Direction::Down, actions::Direction::Up],
+            );
+        }
+    }
+    fn resize_pane_along_axis(
+        &mut self,
+        pane_id: &PaneId,
+        change_by: f64,
+        directions: [actions::Direction; 2],
+    ) {
+        // if the pane cannot move its border in one direction (eg. it is on the edge of the
+        // tab), we move the other one
+        let mut remaining = change_by.abs();
+        for direction in &directions {
+            if remaining <= RESIZE_PRECISION_PERCENT {
+                break;
+            }
+            remaining -= if change_by > 0.0 {
+                self.increase_pane_by(pane_id, direction, remaining)
+            } else {
+                self.reduce_pane_by(pane_id, direction, remaining)
+            };
+        }
+    }
+    fn can_reduce_pane_and_surroundings(
+        &self,
+        pane_id: &PaneId,
+        direction: &actions::Direction,
+        percent: f64,
+    ) -> bool {
+        match direction {
+            actions::Direction::Left => {
+                self.can_reduce_pane_and_surroundings_left(pane_id, percent)
+            }
+            actions::Direction::Right => {
+                self.can_reduce_pane_and_surroundings_right(pane_id, percent)
+            }
+            actions::Direction::Up => self.can_reduce_pane_and_surroundings_up(pane_id, percent),
+            actions::Direction::Down => {
+                self.can_reduce_pane_and_surroundings_down(pane_id, percent)
+            }
+        }
+    }
+    fn try_reduce_pane_and_surroundings(
+        &mut self,
+        pane_id: &PaneId,
+        direction: &actions::Direction,
+        percent: f64,
+    ) -> bool {
+        match direction {
+            actions::Direction::Left => {
+                self.try_reduce_pane_and_surroundings_left(pane_id, percent)
+            }
+            actions::Direction::Right => {
+                self.try_reduce_pane_and_surroundings_right(pane_id, percent)
+            }
+            actions::Direction::Up => self.try_reduce_pane_and_surroundings_up(pane_id, percent),
+            actions::Direction::Down => {
+                self.try_reduce_pane_and_surroundings_down(pane_id, percent)
+            }
+        }
+    }
+    fn can_increase_pane_and_surroundings(
+        &self,
+        pane_id: &PaneId,
+        direction: &actions::Direction,
+        percent: f64,
+    ) -> bool {
+        match direction {
+            actions::Direction::Left => {
+                self.can_increase_pane_and_surroundings_left(pane_id, percent)
+            }
+            actions::Direction::Right => {
+                self.can_increase_pane_and_surroundings_right(pane_id, percent)
+            }
+            actions::Direction::Up => self.can_increase_pane_and_surroundings_up(pane_id, percent),
+            actions::Direction::Down => {
+                self.can_increase_pane_and_surroundings_down(pane_id, percent)
+            }
+        }
+    }
+    fn try_increase_pane_and_surroundings(
+        &mut self,
+        pane_id: &PaneId,
+        direction: &actions::Direction,
+        percent: f64,
+    ) -> bool {
+        match direction {
+            actions::Direction::Left => {
+                self.try_increase_pane_and_surroundings_left(pane_id, percent)
+            }
+            actions::Direction::Right => {
+                self.try_increase_pane_and_surroundings_right(pane_id, percent)
+            }
+            actions::Direction::Up => self.try_increase_pane_and_surroundings_up(pane_id, percent),
+            actions::Direction::Down => {
+                self.try_increase_pane_and_surroundings_down(pane_id, percent)
+            }
+        }
+    }
+    fn try_resize_pane_by(
+        &mut self,
+        pane_id: &PaneId,
+        direction: &actions::Direction,
+        percent: f64,
+    ) -> bool {
+        match direction {
+            actions::Direction::Left => {
+                self.try_increase_pane_and_surroundings_left(pane_id, percent)
+                    || self.try_reduce_pane_and_surroundings_left(pane_id, percent)
+            }
+            actions::Direction::Right => {
+                self.try_increase_pane_and_surroundings_right(pane_id, percent)
+                    || self.try_reduce_pane_and_surroundings_right(pane_id, percent)
+            }
+            actions::Direction::Up => {
+                self.try_increase_pane_and_surroundings_up(pane_id, percent)
+                    || self.try_reduce_pane_and_surroundings_up(pane_id, percent)
+            }
+            actions::Direction::Down => {
+                self.try_increase_pane_and_surroundings_down(pane_id, percent)
+                    || self.try_reduce_pane_and_surroundings_down(pane_id, percent)
+            }
         }
-        self.try_reduce_pane_and_surroundings_up(pane_id, RESIZE_PERCENT);
     }
     pub fn resize_increase(&mut self, pane_id: &PaneId) {
         if self.try_increase_pane_and_surroundings_right_and_down(pane_id) {
@@ -1312,30 +1677,6 @@ impl<'a> TiledPaneGrid<'a> {
             .unwrap();
         next_active_pane_id
     }
-    pub fn previous_selectable_pane_id(&self, current_pane_id: &PaneId) -> PaneId {
-        let panes = self.panes.borrow();
-        let mut panes: Vec<(&PaneId, &&mut Box<dyn Pane>)> =
-            panes.iter().filter(|(_, p)| p.selectable()).collect();
-        panes.sort_by(|(_a_id, a_pane), (_b_id, b_pane)| {
-            if a_pane.y() == b_pane.y() {
-                a_pane.x().cmp(&b_pane.x())
-            } else {
-                a_pane.y().cmp(&b_pane.y())
-            }
-        });
-        let last_pane = panes.last().unwrap();
-        let active_pane_position = panes
-            .iter()
-            .position(|(id, _)| *id == current_pane_id) // TODO: better
-            .unwrap();
-
-        let previous_active_pane_id = if active_pane_position == 0 {
-            *last_pane.0
-        } else {
-            *panes.get(active_pane_position - 1).unwrap().0
-        };
-        previous_active_pane_id
-    }
     pub fn next_selectable_pane_id_to_the_left(&self, current_pane_id: &PaneId) -> Option<PaneId> {
         let panes = self.panes.borrow();
         let current_pane = panes.get(current_pane_id)?;
@@ -1356,6 +1697,39 @@ impl<'a> TiledPaneGrid<'a> {
             .copied();
         next_index
     }
+    pub fn wrapped_selectable_pane_id(
+        &self,
+        current_pane_id: &PaneId,
+        direction: &actions::Direction,
+    ) -> Option<PaneId> {
+        // the pane on the opposite edge of the tab (in the same row or column band as the current
+        // pane) that we should move to when there's nothing left in this direction
+        let panes = self.panes.borrow();
+        let current_pane = panes.get(current_pane_id)?;
+        let candidates = panes.iter().filter(|(p_id, p)| {
+            *p_id != current_pane_id
+                && p.selectable()
+                && match direction {
+                    actions::Direction::Left | actions::Direction::Right => {
+                        p.horizontally_overlaps_with(Box::as_ref(current_pane))
+                    }
+                    actions::Direction::Up | actions::Direction::Down => {
+                        p.vertically_overlaps_with(Box::as_ref(current_pane))
+                    }
+                }
+        });
+        let next_pane = match direction {
+            actions::Direction::Left => candidates.max_by_key(|(_, p)| (p.x(), p.active_at())),
+            actions::Direction::Right => {
+                candidates.max_by_key(|(_, p)| (Reverse(p.x()), p.active_at()))
+            }
+            actions::Direction::Up => candidates.max_by_key(|(_, p)| (p.y(), p.active_at())),
+            actions::Direction::Down => {
+                candidates.max_by_key(|(_, p)| (Reverse(p.y()), p.active_at()))
+            }
+        };
+        next_pane.map(|(p_id, _)| *p_id)
+    }
     pub fn next_selectable_pane_id_below(&self, current_pane_id: &PaneId) -> Option<PaneId> {
         let panes = self.panes.borrow();
         let current_pane = panes.get(current_pane_id)?;
@@ -1416,6 +1790,60 @@ impl<'a> TiledPaneGrid<'a> {
             .copied();
         next_index
     }
+    pub fn spatial_focus_cycle(&self, direction: &actions::Direction) -> Vec<PaneId> {
+        // walks the panes from the top left, following their neighbours to the right (or below)
+        // and continuing with the first pane not yet visited in reading order (or column order)
+        // when there are none, so that every selectable pane appears exactly once
+        let panes = self.panes.borrow();
+        let moves_vertically =
+            matches!(direction, actions::Direction::Up | actions::Direction::Down);
+        let mut pane_ids_in_order: Vec<PaneId> = panes
+            .iter()
+            .filter(|(_, p)| p.selectable())
+            .map(|(p_id, _)| *p_id)
+            .collect();
+        pane_ids_in_order.sort_by_key(|p_id| {
+            let pane = panes.get(p_id).unwrap();
+            if moves_vertically {
+                (pane.x(), pane.y())
+            } else {
+                (pane.y(), pane.x())
+            }
+        });
+        let next_neighbour = |current: &PaneId, visited: &[PaneId]| -> Option<PaneId> {
+            let current_pane = panes.get(current).unwrap();
+            pane_ids_in_order
+                .iter()
+                .filter(|p_id| !visited.contains(p_id))
+                .find(|p_id| {
+                    let candidate = panes.get(p_id).unwrap();
+                    if moves_vertically {
+                        candidate.is_directly_below(Box::as_ref(current_pane))
+                            && candidate.vertically_overlaps_with(Box::as_ref(current_pane))
+                    } else {
+                        candidate.is_directly_right_of(Box::as_ref(current_pane))
+                            && candidate.horizontally_overlaps_with(Box::as_ref(current_pane))
+                    }
+                })
+                .copied()
+        };
+        let mut cycle: Vec<PaneId> = vec![];
+        while let Some(mut current) = pane_ids_in_order
+            .iter()
+            .find(|p_id| !cycle.contains(p_id))
+            .copied()
+        {
+            cycle.push(current);
+            while let Some(next) = next_neighbour(&current, &cycle) {
+                cycle.push(next);
+                current = next;
+            }
+        }
+        if matches!(direction, actions::Direction::Left | actions::Direction::Up) {
+            cycle.reverse();
+        }
+        cycle
+    }
     fn horizontal_borders(&self, pane_ids: &[PaneId]) -> HashSet<usize> {
         pane_ids.iter().fold(HashSet::new(), |mut borders, p| {
             let panes = self.panes.borrow();
@@ -1546,22 +1974,68 @@ impl<'a> TiledPaneGrid<'a> {
         }
         None
     }
-    fn find_panes_to_grow(&self, id: PaneId) -> Option<(Vec<PaneId>, Direction)> {
-        if let Some(panes) = self
-            .panes_to_the_left_between_aligning_borders(id)
-            .or_else(|| self.panes_to_the_right_between_aligning_borders(id))
-        {
-            return Some((panes, Direction::Horizontal));
-        }
-
-        if let Some(panes) = self
-            .panes_above_between_aligning_borders(id)
-            .or_else(|| self.panes_below_between_aligning_borders(id))
-        {
-            return Some((panes, Direction::Vertical));
-        }
-
-        None
+    fn find_panes_to_grow(
+        &self,
+        id: PaneId,
+        gravity: CloseGravity,
+        preferred_pane: Option<PaneId>,
+    ) -> Option<(Vec<PaneId>, Direction)> {
+        // the candidates are in the order of CloseGravity::Fixed
+        let candidates: Vec<(Vec<PaneId>, Direction)> = vec![
+            (
+                self.panes_to_the_left_between_aligning_borders(id),
+                Direction::Horizontal,
+            ),
+            (
+                self.panes_to_the_right_between_aligning_borders(id),
+                Direction::Horizontal,
+            ),
+            (
+                self.panes_above_between_aligning_borders(id),
+                Direction::Vertical,
+            ),
+            (
+                self.panes_below_between_aligning_borders(id),
+                Direction::Vertical,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(panes, direction)| panes.map(|panes| (panes, direction)))
+        .collect();
+        let preferred_index = match gravity {
+            CloseGravity::Fixed => None,
+            CloseGravity::PreviouslyActive => preferred_pane.and_then(|preferred_pane| {
+                candidates
+                    .iter()
+                    .position(|(panes, _)| panes.contains(&preferred_pane))
+            }),
+            CloseGravity::Largest => {
+                let panes = self.panes.borrow();
+                let area = |pane_ids: &[PaneId]| -> usize {
+                    pane_ids
+                        .iter()
+                        .filter_map(|pane_id| panes.get(pane_id))
+                        .map(|pane| {
+                            let geom = pane.position_and_size();
+                            geom.cols.as_usize() * geom.rows.as_usize()
+                        })
+                        .sum()
+                };
+                let mut largest: Option<(usize, usize)> = None; // (index, area)
+                for (index, (pane_ids, _)) in candidates.iter().enumerate() {
+                    let candidate_area = area(pane_ids);
+                    if largest
+                        .map(|(_, area)| candidate_area > area)
+                        .unwrap_or(true)
+                    {
+                        largest = Some((index, candidate_area));
+                    }
+                }
+                largest.map(|(index, _)| index)
+            }
+        };
+        let index = preferred_index.unwrap_or(0);
+        candidates.into_iter().nth(index)
     }
     fn grow_panes(&mut self, panes: &[PaneId], direction: Direction, (width, height): (f64, f64)) {
         match direction {
@@ -1577,34 +2051,99 @@ impl<'a> TiledPaneGrid<'a> {
             }
         };
     }
-    pub fn fill_space_over_pane(&mut self, id: PaneId) -> bool {
+    pub fn fill_space_over_pane(
+        &mut self,
+        id: PaneId,
+        gravity: CloseGravity,
+        preferred_pane: Option<PaneId>,
+    ) -> bool {
+        // gravity decides which of the panes around this one get its space, preferred_pane is
+        // the one CloseGravity::PreviouslyActive gives it to
         // true => successfully filled space over pane
         // false => didn't succeed, so didn't do anything
         let (freed_width, freed_height) = {
             let panes = self.panes.borrow_mut();
             let pane_to_close = panes.get(&id).unwrap();
             let freed_space = pane_to_close.position_and_size();
-            let freed_width = freed_space.cols.as_percent();
-            let freed_height = freed_space.rows.as_percent();
+            // a fixed size does not take up any percent of the flexible space, so once it's gone
+            // the relayout will have the remaining percent based panes absorb it
+            let freed_width = freed_space.cols.as_percent().unwrap_or(0.0);
+            let freed_height = freed_space.rows.as_percent().unwrap_or(0.0);
             (freed_width, freed_height)
         };
-        if let (Some(freed_width), Some(freed_height)) = (freed_width, freed_height) {
-            if let Some((panes_to_grow, direction)) = self.find_panes_to_grow(id) {
-                self.grow_panes(&panes_to_grow, direction, (freed_width, freed_height));
-                let side_length = match direction {
-                    Direction::Vertical => self.display_area.rows,
-                    Direction::Horizontal => self.display_area.cols,
-                };
+        if let Some((panes_to_grow, direction)) =
+            self.find_panes_to_grow(id, gravity, preferred_pane)
+        {
+            self.grow_panes(&panes_to_grow, direction, (freed_width, freed_height));
+            let side_length = match direction {
+                Direction::Vertical => self.display_area.rows,
+                Direction::Horizontal => self.display_area.cols,
+            };
+            {
+                let mut panes = self.panes.borrow_mut();
+                (*panes).remove(&id);
+            }
+            let mut pane_resizer = PaneResizer::new(self.panes.clone());
+            let _ = pane_resizer.layout(direction, side_length);
+            return true;
+        }
+        self.expand_neighbor_over_pane(id)
+    }
+    fn expand_neighbor_over_pane(&mut self, id: PaneId) -> bool {
+        // no panes line up with the pane we're closing, so instead we expand its largest neighbor
+        // over the box containing both of them and crop whichever panes it now overlaps - this
+        // only works if what is left of each of those is still a rectangle
+        let new_geoms = {
+            let panes = self.panes.borrow();
+            let closed_geom = match panes.get(&id) {
+                Some(pane) => pane.position_and_size(),
+                None => return false,
+            };
+            let mut neighbors: Vec<(PaneId, PaneGeom)> = panes
+                .iter()
+                .filter(|(pid, pane)| {
+                    **pid != id
+                        && pane.selectable()
+                        && geoms_are_adjacent(&pane.position_and_size(), &closed_geom)
+                })
+                .map(|(pid, pane)| (*pid, pane.position_and_size()))
+                .collect();
+            neighbors.sort_by_key(|(pid, geom)| {
+                (Reverse(geom.cols.as_usize() * geom.rows.as_usize()), *pid)
+            });
+            neighbors.iter().find_map(|(neighbor_id, neighbor_geom)| {
+                let expanded_geom = bounding_geom(neighbor_geom, &closed_geom)?;
+                let mut new_geoms = vec![(*neighbor_id, expanded_geom)];
+                for (pid, pane) in panes.iter() {
+                    if *pid == id || pid == neighbor_id {
+                        continue;
+                    }
+                    let geom = pane.position_and_size();
+                    if geoms_overlap(&geom, &expanded_geom) {
+                        new_geoms.push((*pid, crop_geom(&geom, &expanded_geom)?));
+                    }
+                }
+                Some(new_geoms)
+            })
+        };
+        match new_geoms {
+            Some(new_geoms) => {
                 {
                     let mut panes = self.panes.borrow_mut();
-                    (*panes).remove(&id);
+                    for (pid, geom) in new_geoms {
+                        if let Some(pane) = panes.get_mut(&pid) {
+                            pane.set_geom(geom);
+                        }
+                    }
+                    panes.remove(&id);
                 }
                 let mut pane_resizer = PaneResizer::new(self.panes.clone());
-                let _ = pane_resizer.layout(direction, side_length);
-                return true;
+                let _ = pane_resizer.layout(Direction::Horizontal, self.display_area.cols);
+                let _ = pane_resizer.layout(Direction::Vertical, self.display_area.rows);
+                true
             }
+            None => false,
         }
-        false
     }
     pub fn find_room_for_new_pane(
         &self,
@@ -1620,8 +2159,8 @@ impl<'a> TiledPaneGrid<'a> {
                 let pane_size = (pane_to_check.rows()
                     * cursor_height_width_ratio.unwrap_or(DEFAULT_CURSOR_HEIGHT_WIDTH_RATIO))
                     * pane_to_check.cols();
-                let pane_can_be_split = pane_to_check.cols() >= MIN_TERMINAL_WIDTH
-                    && pane_to_check.rows() >= MIN_TERMINAL_HEIGHT
+                let pane_can_be_split = pane_to_check.cols() >= pane_to_check.min_width()
+                    && pane_to_check.rows() >= pane_to_check.min_height()
                     && ((pane_to_check.cols() > pane_to_check.min_width() * 2)
                         || (pane_to_check.rows() > pane_to_check.min_height() * 2));
                 if pane_can_be_split && pane_size > current_largest_pane_size {
@@ -1650,6 +2189,24 @@ impl<'a> TiledPaneGrid<'a> {
     }
 }
 
+pub fn squarest_split_direction(
+    cols: usize,
+    rows: usize,
+    cursor_height_width_ratio: Option<usize>,
+) -> Direction {
+    // compare the aspect ratio of the two halves each split would produce, measuring height in
+    // cell widths so that a "square" pane looks square on screen
+    let ratio = cursor_height_width_ratio.unwrap_or(DEFAULT_CURSOR_HEIGHT_WIDTH_RATIO) as f64;
+    let aspect = |width: f64, height: f64| width.max(height) / width.min(height).max(1.0);
+    let vertical_halves = aspect(cols as f64 / 2.0, rows as f64 * ratio);
+    let horizontal_halves = aspect(cols as f64, rows as f64 * ratio / 2.0);
+    if horizontal_halves < vertical_halves {
+        Direction::Horizontal
+    } else {
+        Direction::Vertical
+    }
+}
+
 pub fn split(direction: Direction, rect: &PaneGeom) -> Option<(PaneGeom, PaneGeom)> {
     let space = match direction {
         Direction::Vertical => rect.cols,
@@ -1683,3 +2240,214 @@ pub fn split(direction: Direction, rect: &PaneGeom) -> Option<(PaneGeom, PaneGeo
         None
     }
 }
+
+// divides the rect into parts of (almost) the same size, the first parts get the extra cells
+fn split_evenly(direction: Direction, rect: &PaneGeom, count: usize) -> Vec<PaneGeom> {
+    let (space, position) = match direction {
+        Direction::Vertical => (rect.cols, rect.x),
+        Direction::Horizontal => (rect.rows, rect.y),
+    };
+    let percent = space.as_percent().unwrap_or(100.0) / count as f64;
+    let cells = space.as_usize();
+    let mut parts = Vec::with_capacity(count);
+    let mut offset = 0;
+    for i in 0..count {
+        let part_cells = cells / count + if i < cells % count { 1 } else { 0 };
+        let mut dimension = Dimension::percent(percent);
+        dimension.set_inner(part_cells);
+        let part = match direction {
+            Direction::Vertical => PaneGeom {
+                x: position + offset,
+                cols: dimension,
+                ..*rect
+            },
+            Direction::Horizontal => PaneGeom {
+                y: position + offset,
+                rows: dimension,
+                ..*rect
+            },
+        };
+        parts.push(part);
+        offset += part_cells;
+    }
+    parts
+}
+
+// the geometry of each of pane_count panes arranged in the rect, the main pane comes first.
+// Returns None if some of the panes would be too small
+pub fn preset_geoms(
+    preset: LayoutPreset,
+    rect: &PaneGeom,
+    pane_count: usize,
+    min_size: (usize, usize), // (min_width, min_height)
+) -> Option<Vec<PaneGeom>> {
+    if pane_count == 0 {
+        return Some(vec![]);
+    }
+    let main_and_rest = |direction: Direction| -> Vec<PaneGeom> {
+        if pane_count == 1 {
+            return vec![*rect];
+        }
+        let halves = split_evenly(direction, rect, 2);
+        let mut geoms = vec![halves[0]];
+        geoms.append(&mut split_evenly(!direction, &halves[1], pane_count - 1));
+        geoms
+    };
+    let geoms = match preset {
+        LayoutPreset::EvenHorizontal => split_evenly(Direction::Vertical, rect, pane_count),
+        LayoutPreset::EvenVertical => split_evenly(Direction::Horizontal, rect, pane_count),
+        LayoutPreset::MainVertical => main_and_rest(Direction::Vertical),
+        LayoutPreset::MainHorizontal => main_and_rest(Direction::Horizontal),
+        LayoutPreset::Tiled => {
+            let mut columns = 1;
+            while columns * columns < pane_count {
+                columns += 1;
+            }
+            split_evenly(Direction::Vertical, rect, columns)
+                .iter()
+                .enumerate()
+                .flat_map(|(i, column)| {
+                    // the panes that don't divide evenly go in the first columns
+                    let panes_in_column =
+                        pane_count / columns + if i < pane_count % columns { 1 } else { 0 };
+                    split_evenly(Direction::Horizontal, column, panes_in_column)
+                })
+                .collect()
+        }
+    };
+    let too_small = geoms
+        .iter()
+        .any(|geom| geom.cols.as_usize() < min_size.0 || geom.rows.as_usize() < min_size.1);
+    if too_small {
+        None
+    } else {
+        Some(geoms)
+    }
+}
+
+pub fn split_with_fixed_size(
+    direction: Direction,
+    rect: &PaneGeom,
+    fixed_size: usize,
+    min_size: (usize, usize), // (min_width, min_height)
+) -> Option<(PaneGeom, PaneGeom)> {
+    // the first pane keeps the (percent based) share of the original pane, the second one gets the
+    // fixed size, which the relayout then takes out of the flexible space
+    let (space, min_size) = match direction {
+        Direction::Vertical => (rect.cols, min_size.0),
+        Direction::Horizontal => (rect.rows, min_size.1),
+    };
+    if space.as_percent().is_none()
+        || fixed_size < min_size
+        || space.as_usize().saturating_sub(fixed_size) < min_size
+    {
+        return None;
+    }
+    let first_size = space.as_usize() - fixed_size;
+    let mut first_dimension = space;
+    first_dimension.set_inner(first_size);
+    let mut fixed_dimension = Dimension::fixed(fixed_size);
+    fixed_dimension.set_inner(fixed_size);
+    let (first_rect, second_rect) = match direction {
+        Direction::Vertical => (
+            PaneGeom {
+                cols: first_dimension,
+                ..*rect
+            },
+            PaneGeom {
+                x: rect.x + first_size,
+                cols: fixed_dimension,
+                ..*rect
+            },
+        ),
+        Direction::Horizontal => (
+            PaneGeom {
+                rows: first_dimension,
+                ..*rect
+            },
+            PaneGeom {
+                y: rect.y + first_size,
+                rows: fixed_dimension,
+                ..*rect
+            },
+        ),
+    };
+    Some((first_rect, second_rect))
+}
+
+fn geoms_are_adjacent(a: &PaneGeom, b: &PaneGeom) -> bool {
+    let overlap_horizontally = a.x < b.x + b.cols.as_usize() && b.x < a.x + a.cols.as_usize();
+    let overlap_vertically = a.y < b.y + b.rows.as_usize() && b.y < a.y + a.rows.as_usize();
+    let touch_horizontally = a.x + a.cols.as_usize() == b.x || b.x + b.cols.as_usize() == a.x;
+    let touch_vertically = a.y + a.rows.as_usize() == b.y || b.y + b.rows.as_usize() == a.y;
+    (touch_horizontally && overlap_vertically) || (touch_vertically && overlap_horizontally)
+}
+
+fn geoms_overlap(a: &PaneGeom, b: &PaneGeom) -> bool {
+    a.x < b.x + b.cols.as_usize()
+        && b.x < a.x + a.cols.as_usize()
+        && a.y < b.y + b.rows.as_usize()
+        && b.y < a.y + a.rows.as_usize()
+}
+
+fn resize_dimension(dimension: Dimension, size: usize) -> Option<Dimension> {
+    // keeps the ratio between percent and cells, so that the pane takes up the same share of the
+    // tab per cell it did before
+    if size == dimension.as_usize() {
+        return Some(dimension);
+    }
+    let percent = dimension.as_percent()?;
+    if dimension.as_usize() == 0 || size == 0 {
+        return None;
+    }
+    let mut resized = Dimension::percent(percent * size as f64 / dimension.as_usize() as f64);
+    resized.set_inner(size);
+    Some(resized)
+}
+
+fn bounding_geom(a: &PaneGeom, b: &PaneGeom) -> Option<PaneGeom> {
+    let x = std::cmp::min(a.x, b.x);
+    let y = std::cmp::min(a.y, b.y);
+    let right = std::cmp::max(a.x + a.cols.as_usize(), b.x + b.cols.as_usize());
+    let bottom = std::cmp::max(a.y + a.rows.as_usize(), b.y + b.rows.as_usize());
+    Some(PaneGeom {
+        x,
+        y,
+        cols: resize_dimension(a.cols, right - x)?,
+        rows: resize_dimension(a.rows, bottom - y)?,
+    })
+}
+
+fn crop_geom(geom: &PaneGeom, by: &PaneGeom) -> Option<PaneGeom> {
+    // removes the part of geom covered by the other geom, returns None if what remains is not a
+    // rectangle
+    let (right, bottom) = (geom.x + geom.cols.as_usize(), geom.y + geom.rows.as_usize());
+    let (by_right, by_bottom) = (by.x + by.cols.as_usize(), by.y + by.rows.as_usize());
+    let covers_width = by.x <= geom.x && by_right >= right;
+    let covers_height = by.y <= geom.y && by_bottom >= bottom;
+    if covers_width && by.y <= geom.y && by_bottom < bottom {
+        Some(PaneGeom {
+            y: by_bottom,
+            rows: resize_dimension(geom.rows, bottom - by_bottom)?,
+            ..*geom
+        })
+    } else if covers_width && by.y > geom.y && by_bottom >= bottom {
+        Some(PaneGeom {
+            rows: resize_dimension(geom.rows, by.y - geom.y)?,
+            ..*geom
+        })
+    } else if covers_height && by.x <= geom.x && by_right < right {
+        Some(PaneGeom {
+            x: by_right,
+            cols: resize_dimension(geom.cols, right - by_right)?,
+            ..*geom
+        })
+    } else if covers_height && by.x > geom.x && by_right >= right {
+        Some(PaneGeom {
+            cols: resize_dimension(geom.cols, by.x - geom.x)?,
+            ..*geom
+        })
+    } else {
+        None
+    }
+}
@@ -1,5 +1,7 @@
-use super::super::Grid;
+use super::super::{Grid, Row};
 use crate::panes::link_handler::LinkHandler;
+use crate::panes::selection::SelectionMode;
+use crate::panes::terminal_character::TerminalCharacter;
 use ::insta::assert_snapshot;
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -908,6 +910,111 @@ fn copy_selected_text_from_viewport() {
     );
 }
 
+#[test]
+fn insert_synthetic_line_above_cursor() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = Grid::new(
+        10,
+        20,
+        Palette::default(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+    );
+    for byte in "line1\r\nline2\r\n".as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    grid.insert_synthetic_line("--- done ---");
+    for byte in "line3".as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(
+        grid.viewport_as_text_lines(),
+        vec!["line1", "line2", "--- done ---", "line3"],
+        "synthetic line inserted above the cursor line"
+    );
+
+    grid.start_selection(&Position::new(0, 0));
+    grid.end_selection(&Position::new(3, 5));
+    let text = grid.get_selected_text().unwrap();
+    assert!(
+        !text.contains("done"),
+        "synthetic line is not part of the copied text"
+    );
+}
+
+#[test]
+fn copy_block_selected_text_from_viewport() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = Grid::new(
+        10,
+        40,
+        Palette::default(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+    );
+    for byte in "name   size  owner\r\nfoo    12    root\r\nbar    345   user".as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    grid.start_selection_in_mode(&Position::new(2, 13), SelectionMode::Block);
+    grid.end_selection(&Position::new(0, 7));
+    assert_eq!(
+        grid.get_selected_text(),
+        Some(String::from("size\n12\n345")),
+        "only the selected columns are copied from each line"
+    );
+}
+
+#[test]
+fn dump_grid_contents() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = Grid::new(
+        2,
+        10,
+        Palette::default(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+    );
+    for byte in "line1\r\nline2\r\n\u{1b}[31mred\u{1b}[m".as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(grid.dump(false, false), "line2\nred", "only the viewport");
+    assert_eq!(
+        grid.dump(true, false),
+        "line1\nline2\nred",
+        "viewport and scrollback"
+    );
+    assert!(
+        grid.dump(false, true).contains("\u{1b}[31mred"),
+        "styles are preserved"
+    );
+}
+
+#[test]
+fn url_at_position_wrapped_across_rows() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = Grid::new(
+        3,
+        20,
+        Palette::default(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+    );
+    for byte in "see https://example.com/a/long/path ok".as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(
+        grid.url_at(&Position::new(0, 6)),
+        Some(String::from("https://example.com/a/long/path")),
+        "url found from its first row"
+    );
+    assert_eq!(
+        grid.url_at(&Position::new(1, 2)),
+        Some(String::from("https://example.com/a/long/path")),
+        "url found from the row it wrapped to"
+    );
+    assert_eq!(grid.url_at(&Position::new(1, 17)), None, "no url here");
+}
+
 #[test]
 fn copy_wrapped_selected_text_from_viewport() {
     let mut vte_parser = vte::Parser::new();
@@ -1792,3 +1899,253 @@ fn terminal_pixel_size_reports_in_unsupported_terminals() {
         expected,
     );
 }
+
+#[test]
+fn working_directory_reported_with_osc7() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = Grid::new(
+        10,
+        80,
+        Palette::default(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+    );
+    for byte in "\u{1b}]7;file://hostname/home/user/some%20folder\u{1b}\\".as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(
+        grid.working_directory,
+        Some(std::path::PathBuf::from("/home/user/some folder")),
+        "working directory was decoded"
+    );
+}
+
+#[test]
+fn dump_viewport_as_rendered() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = Grid::new(
+        4,
+        10,
+        Palette::default(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+    );
+    for byte in "line0\r\nline1\r\nline2\r\nwrapped line\u{1b}[31m!\u{1b}[m  ".as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(
+        grid.dump_viewport(),
+        "line1\nline2\nwrapped li\nne!",
+        "visible rows without scrollback, styles or trailing spaces"
+    );
+}
+
+#[test]
+fn clear_screen_and_scrollback() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = Grid::new(
+        4,
+        10,
+        Palette::default(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+    );
+    for byte in "line0\r\nline1\r\nline2\r\nline3\r\nline4".as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    grid.move_viewport_up(1);
+    grid.clear_screen_and_scrollback();
+    assert_eq!(grid.dump_viewport(), "\n\n\n", "screen is empty");
+    assert_eq!(grid.scrollback_len(), 0, "scrollback is empty");
+    assert!(!grid.is_scrolled, "grid is no longer scrolled");
+    assert_eq!(
+        grid.cursor_coordinates(),
+        Some((0, 0)),
+        "cursor is at the top left"
+    );
+}
+
+#[test]
+fn lowering_scrollback_limit_trims_oldest_lines() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = Grid::new(
+        2,
+        10,
+        Palette::default(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+    );
+    for byte in "line0\r\nline1\r\nline2\r\nline3\r\nline4\r\nline5".as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(
+        grid.scrollback_len(),
+        4,
+        "history before lowering the limit"
+    );
+    grid.set_scrollback_limit(2);
+    assert_eq!(grid.scrollback_len(), 2, "oldest lines were trimmed");
+    for byte in "\r\nline6".as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(grid.scrollback_len(), 2, "new history respects the limit");
+    grid.move_viewport_up(2);
+    assert_eq!(
+        grid.dump_viewport(),
+        "line3\nline4",
+        "the most recent history is kept"
+    );
+}
+
+#[test]
+fn horizontal_scroll_reveals_lines_wider_than_the_grid() {
+    let mut grid = Grid::new(
+        2,
+        5,
+        Palette::default(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+    );
+    // rows only get wider than the grid when line wrapping is off
+    grid.viewport = vec![Row::from_columns(
+        "abcdefgh".chars().map(TerminalCharacter::new).collect(),
+    )];
+    let first_line = |grid: &mut Grid| -> String {
+        grid.render_full_viewport();
+        grid.read_changes(0, 0)[0]
+            .terminal_characters
+            .iter()
+            .map(|c| c.character)
+            .collect()
+    };
+    assert_eq!(first_line(&mut grid), "abcde", "starts at the left edge");
+    grid.scroll_right(10);
+    assert_eq!(grid.horizontal_scroll(), 3, "stops at the widest line");
+    assert_eq!(first_line(&mut grid), "defgh", "shows the end of the line");
+    grid.scroll_left(1);
+    assert_eq!(
+        first_line(&mut grid),
+        "cdefg",
+        "scrolled back by one column"
+    );
+}
+
+#[test]
+fn hyperlink_at_position() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = Grid::new(
+        3,
+        20,
+        Palette::default(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+    );
+    let content = "see \u{1b}]8;;https://example.com\u{1b}\\here\u{1b}]8;;\u{1b}\\ ok";
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(
+        grid.hyperlink_at(&Position::new(0, 5)),
+        Some(String::from("https://example.com"))
+    );
+    assert_eq!(
+        grid.hyperlink_at(&Position::new(0, 1)),
+        None,
+        "before the link"
+    );
+    assert_eq!(
+        grid.hyperlink_at(&Position::new(0, 9)),
+        None,
+        "after the link"
+    );
+    assert!(
+        grid.dump(false, true).contains("\u{1b}[4m"),
+        "hyperlinks are underlined"
+    );
+}
+
+#[test]
+fn lines_are_clipped_instead_of_wrapped_when_line_wrap_is_off() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = Grid::new(
+        3,
+        5,
+        Palette::default(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+    );
+    grid.set_line_wrap(false);
+    for byte in "abcdefgh".as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    let row_text = |row: &Row| -> String { row.columns.iter().map(|c| c.character).collect() };
+    let first_line = |grid: &mut Grid| -> String {
+        grid.render_full_viewport();
+        grid.read_changes(0, 0)[0]
+            .terminal_characters
+            .iter()
+            .map(|c| c.character)
+            .collect()
+    };
+    assert_eq!(
+        row_text(&grid.viewport[0]),
+        "abcdefgh",
+        "line was not wrapped"
+    );
+    assert_eq!(
+        first_line(&mut grid),
+        "abcde",
+        "line is clipped at the edge"
+    );
+    grid.scroll_right(3);
+    assert_eq!(
+        first_line(&mut grid),
+        "defgh",
+        "the rest is revealed by scrolling"
+    );
+    grid.change_size(3, 6);
+    assert!(!grid.line_wrap(), "wrap mode survives a resize");
+    assert_eq!(
+        row_text(&grid.viewport[0]),
+        "abcdefgh",
+        "line was not rewrapped"
+    );
+    grid.set_line_wrap(true);
+    assert_eq!(row_text(&grid.viewport[0]), "abcdef");
+    assert_eq!(row_text(&grid.viewport[1]), "gh", "line was wrapped again");
+    assert_eq!(grid.horizontal_scroll(), 0);
+}
+
+#[test]
+fn progress_is_parsed_from_osc_9_4() {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = Grid::new(
+        3,
+        20,
+        Palette::default(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+    );
+    let mut feed = |grid: &mut Grid, content: &str| {
+        for byte in content.as_bytes() {
+            vte_parser.advance(grid, *byte);
+        }
+    };
+    assert_eq!(grid.progress, None, "no progress before it is reported");
+    feed(&mut grid, "\u{1b}]9;4;1;73\u{7}");
+    assert_eq!(grid.progress, Some(73));
+    assert!(grid.progress_changed);
+    grid.progress_changed = false;
+    feed(&mut grid, "\u{1b}]9;4;2\u{1b}\\");
+    assert_eq!(
+        grid.progress,
+        Some(73),
+        "an error state without a percentage keeps the last one"
+    );
+    assert!(!grid.progress_changed);
+    feed(&mut grid, "\u{1b}]9;4;1;250\u{7}");
+    assert_eq!(grid.progress, Some(100), "clamped to 100");
+    feed(&mut grid, "\u{1b}]9;4;0\u{7}");
+    assert_eq!(grid.progress, None, "cleared once done");
+    assert!(grid.progress_changed);
+}
@@ -42,6 +42,7 @@ fn contains() {
     let selection = Selection {
         start: Position::new(10, 5),
         end: Position::new(40, 20),
+        mode: SelectionMode::Stream,
         active: false,
     };
 
@@ -92,6 +93,7 @@ fn sorted() {
     let selection = Selection {
         start: Position::new(1, 1),
         end: Position::new(10, 2),
+        mode: SelectionMode::Stream,
         active: false,
     };
     let sorted_selection = selection.sorted();
@@ -101,6 +103,7 @@ fn sorted() {
     let selection = Selection {
         start: Position::new(10, 2),
         end: Position::new(1, 1),
+        mode: SelectionMode::Stream,
         active: false,
     };
     let sorted_selection = selection.sorted();
@@ -113,6 +116,7 @@ fn line_indices() {
     let selection = Selection {
         start: Position::new(1, 1),
         end: Position::new(10, 2),
+        mode: SelectionMode::Stream,
         active: false,
     };
 
@@ -126,6 +130,7 @@ fn move_up_inactive() {
     let mut inactive_selection = Selection {
         start,
         end,
+        mode: SelectionMode::Stream,
         active: false,
     };
 
@@ -144,6 +149,7 @@ fn move_up_active() {
     let mut inactive_selection = Selection {
         start,
         end,
+        mode: SelectionMode::Stream,
         active: true,
     };
 
@@ -159,6 +165,7 @@ fn move_down_inactive() {
     let mut inactive_selection = Selection {
         start,
         end,
+        mode: SelectionMode::Stream,
         active: false,
     };
 
@@ -177,6 +184,7 @@ fn move_down_active() {
     let mut inactive_selection = Selection {
         start,
         end,
+        mode: SelectionMode::Stream,
         active: true,
     };
 
@@ -184,3 +192,20 @@ fn move_down_active() {
     assert_eq!(inactive_selection.start, Position::new(12, 1));
     assert_eq!(inactive_selection.end, end);
 }
+
+#[test]
+fn contains_block() {
+    let selection = Selection {
+        start: Position::new(20, 15),
+        end: Position::new(10, 5),
+        mode: SelectionMode::Block,
+        active: false,
+    };
+
+    assert!(selection.contains(10, 5));
+    assert!(selection.contains(15, 14));
+    assert!(selection.contains(20, 10));
+    assert!(!selection.contains(15, 4));
+    assert!(!selection.contains(15, 15));
+    assert!(!selection.contains(21, 10));
+}
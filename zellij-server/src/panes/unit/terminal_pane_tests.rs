@@ -1,9 +1,10 @@
 use super::super::TerminalPane;
-use crate::panes::LinkHandler;
+use crate::panes::{AnsiCode, LinkHandler};
 use crate::tab::Pane;
 use ::insta::assert_snapshot;
 use std::cell::RefCell;
 use std::rc::Rc;
+use zellij_tile::data::{Palette, PaletteColor};
 use zellij_tile::prelude::Style;
 use zellij_utils::pane_size::PaneGeom;
 
@@ -39,3 +40,344 @@ pub fn scrolling_inside_a_pane() {
     terminal_pane.clear_scroll();
     assert_snapshot!(format!("{:?}", terminal_pane.grid));
 }
+
+#[test]
+pub fn raw_view_visualizes_control_characters() {
+    let visualized = super::visualize_raw_bytes(b"\x1b[31mred\x07\r\n\x7f");
+    assert_eq!(visualized, "^[[31mred^G\\r\\n\r\n^?");
+}
+
+#[test]
+pub fn toggle_raw_view() {
+    let mut fake_win_size = PaneGeom::default();
+    fake_win_size.cols.set_inner(121);
+    fake_win_size.rows.set_inner(20);
+
+    let pid = 1;
+    let style = Style::default();
+    let mut terminal_pane = TerminalPane::new(
+        pid,
+        fake_win_size,
+        style,
+        0,
+        String::new(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+    ); // 0 is the pane index
+    let rendered_first_line = |terminal_pane: &mut TerminalPane| -> String {
+        let (character_chunks, _) = terminal_pane.render(None).unwrap();
+        let line: String = character_chunks[0]
+            .terminal_characters
+            .iter()
+            .map(|terminal_character| terminal_character.character)
+            .collect();
+        line.trim_end().to_owned()
+    };
+    terminal_pane.toggle_raw_view();
+    terminal_pane.handle_pty_bytes("\u{1b}[31mred".as_bytes().to_vec());
+    assert_eq!(
+        rendered_first_line(&mut terminal_pane),
+        "^[[31mred",
+        "raw view toggled on"
+    );
+    terminal_pane.handle_pty_bytes("\r\nmore".as_bytes().to_vec());
+    terminal_pane.toggle_raw_view();
+    assert_eq!(
+        rendered_first_line(&mut terminal_pane),
+        "red",
+        "raw view toggled off"
+    );
+    assert_eq!(
+        terminal_pane.grid.viewport_as_text_lines(),
+        vec!["red", "more"],
+        "grid kept updating underneath the raw view"
+    );
+}
+
+#[test]
+pub fn highlighted_lines_use_the_diff_highlight_color() {
+    let mut fake_win_size = PaneGeom::default();
+    fake_win_size.cols.set_inner(121);
+    fake_win_size.rows.set_inner(20);
+
+    let pid = 1;
+    let style = Style {
+        colors: Palette {
+            diff_highlight: PaletteColor::Rgb((0, 0, 95)),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut terminal_pane = TerminalPane::new(
+        pid,
+        fake_win_size,
+        style,
+        0,
+        String::new(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+    ); // 0 is the pane index
+    terminal_pane.handle_pty_bytes("same\r\ndiffers".as_bytes().to_vec());
+    terminal_pane.set_highlighted_lines(vec![1].into_iter().collect());
+    let (character_chunks, _) = terminal_pane.render(None).unwrap();
+    assert_ne!(
+        character_chunks[0].terminal_characters[0].styles.background,
+        Some(AnsiCode::RgbCode((0, 0, 95))),
+        "lines that are not highlighted keep their background"
+    );
+    assert_eq!(
+        character_chunks[1].terminal_characters[0].styles.background,
+        Some(AnsiCode::RgbCode((0, 0, 95))),
+        "highlighted lines use the diff_highlight color"
+    );
+}
+
+#[test]
+pub fn palette_override_sets_default_colors() {
+    let mut fake_win_size = PaneGeom::default();
+    fake_win_size.cols.set_inner(121);
+    fake_win_size.rows.set_inner(20);
+
+    let pid = 1;
+    let style = Style::default();
+    let mut terminal_pane = TerminalPane::new(
+        pid,
+        fake_win_size,
+        style,
+        0,
+        String::new(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+    ); // 0 is the pane index
+    let palette = Palette {
+        fg: PaletteColor::Rgb((0, 0, 0)),
+        bg: PaletteColor::Rgb((255, 255, 255)),
+        ..Default::default()
+    };
+    terminal_pane.handle_pty_bytes("default \u{1b}[31mred".as_bytes().to_vec());
+    terminal_pane.set_palette_override(Some(palette));
+    let (character_chunks, _) = terminal_pane.render(None).unwrap();
+    let first_line = &character_chunks[0].terminal_characters;
+    assert_eq!(
+        first_line[0].styles.foreground,
+        Some(AnsiCode::RgbCode((0, 0, 0))),
+        "default foreground comes from the override"
+    );
+    assert_eq!(
+        first_line[0].styles.background,
+        Some(AnsiCode::RgbCode((255, 255, 255))),
+        "default background comes from the override"
+    );
+    assert_ne!(
+        first_line[8].styles.foreground,
+        Some(AnsiCode::RgbCode((0, 0, 0))),
+        "explicit colors are kept"
+    );
+}
+
+#[test]
+pub fn search_and_scroll_to_matches() {
+    let mut fake_win_size = PaneGeom::default();
+    fake_win_size.cols.set_inner(121);
+    fake_win_size.rows.set_inner(5);
+
+    let pid = 1;
+    let style = Style::default();
+    let mut terminal_pane = TerminalPane::new(
+        pid,
+        fake_win_size,
+        style,
+        0,
+        String::new(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+    ); // 0 is the pane index
+    let mut pane_content = String::new();
+    for i in 0..20 {
+        writeln!(&mut pane_content, "line {}\r", i).unwrap();
+    }
+    terminal_pane.handle_pty_bytes(pane_content.into_bytes());
+    assert_eq!(
+        terminal_pane.search("line 1"),
+        vec![
+            (1, 0),
+            (10, 0),
+            (11, 0),
+            (12, 0),
+            (13, 0),
+            (14, 0),
+            (15, 0),
+            (16, 0),
+            (17, 0),
+            (18, 0),
+            (19, 0)
+        ],
+        "matches in scrollback coordinates"
+    );
+    terminal_pane.scroll_to_search_match(true, 1);
+    assert!(
+        terminal_pane.is_scrolled(),
+        "scrolled up to the first match"
+    );
+    assert_eq!(
+        terminal_pane.grid.viewport_start_in_scrollback(),
+        1,
+        "first match is at the top of the viewport"
+    );
+}
+
+#[test]
+pub fn search_matches_of_wide_characters_are_highlighted_in_full() {
+    let mut fake_win_size = PaneGeom::default();
+    fake_win_size.cols.set_inner(121);
+    fake_win_size.rows.set_inner(5);
+
+    let pid = 1;
+    let style = Style::default();
+    let mut terminal_pane = TerminalPane::new(
+        pid,
+        fake_win_size,
+        style,
+        0,
+        String::new(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+    ); // 0 is the pane index
+    terminal_pane.handle_pty_bytes("日本語".as_bytes().to_vec());
+    assert_eq!(terminal_pane.search("日本"), vec![(0, 0)]);
+    assert_eq!(
+        terminal_pane.search_match_width, 4,
+        "match spans the columns of both characters"
+    );
+}
+
+#[test]
+pub fn bell_and_activity_are_cleared_when_taken() {
+    let mut fake_win_size = PaneGeom::default();
+    fake_win_size.cols.set_inner(121);
+    fake_win_size.rows.set_inner(5);
+
+    let pid = 1;
+    let style = Style::default();
+    let mut terminal_pane = TerminalPane::new(
+        pid,
+        fake_win_size,
+        style,
+        0,
+        String::new(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+    ); // 0 is the pane index
+    assert!(!terminal_pane.take_activity(), "no activity before output");
+    terminal_pane.handle_pty_bytes("output".as_bytes().to_vec());
+    assert!(
+        !terminal_pane.take_bell(),
+        "output alone does not ring the bell"
+    );
+    assert!(terminal_pane.take_activity(), "output is activity");
+    assert!(
+        !terminal_pane.take_activity(),
+        "activity is cleared once taken"
+    );
+    terminal_pane.handle_pty_bytes(vec![7]);
+    assert!(terminal_pane.take_bell(), "bell was rung");
+    assert!(!terminal_pane.take_bell(), "bell is cleared once taken");
+}
+
+#[test]
+pub fn osc52_copy_is_kept_until_taken() {
+    let mut fake_win_size = PaneGeom::default();
+    fake_win_size.cols.set_inner(121);
+    fake_win_size.rows.set_inner(5);
+
+    let pid = 1;
+    let style = Style::default();
+    let mut terminal_pane = TerminalPane::new(
+        pid,
+        fake_win_size,
+        style,
+        0,
+        String::new(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+    ); // 0 is the pane index
+    assert_eq!(terminal_pane.take_pending_clipboard(), None);
+    terminal_pane.handle_pty_bytes("\u{1b}]52;c;?\u{7}".as_bytes().to_vec());
+    assert_eq!(
+        terminal_pane.take_pending_clipboard(),
+        None,
+        "clipboard paste requests are ignored"
+    );
+    terminal_pane.handle_pty_bytes("\u{1b}]52;c;aGVsbG8=\u{7}".as_bytes().to_vec());
+    assert_eq!(
+        terminal_pane.take_pending_clipboard(),
+        Some(String::from("hello"))
+    );
+    assert_eq!(
+        terminal_pane.take_pending_clipboard(),
+        None,
+        "clipboard content is cleared once taken"
+    );
+}
+
+#[test]
+pub fn cursor_shape_follows_decscusr() {
+    let mut fake_win_size = PaneGeom::default();
+    fake_win_size.cols.set_inner(121);
+    fake_win_size.rows.set_inner(5);
+
+    let pid = 1;
+    let style = Style::default();
+    let mut terminal_pane = TerminalPane::new(
+        pid,
+        fake_win_size,
+        style,
+        0,
+        String::new(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+    ); // 0 is the pane index
+    assert_eq!(terminal_pane.cursor_shape_csi(), "\u{1b}[0 q");
+    terminal_pane.handle_pty_bytes("\u{1b}[5 q".as_bytes().to_vec());
+    assert_eq!(
+        terminal_pane.cursor_shape_csi(),
+        "\u{1b}[5 q",
+        "blinking bar requested by the application"
+    );
+    terminal_pane.handle_pty_bytes("\u{1b}[2 q".as_bytes().to_vec());
+    assert_eq!(terminal_pane.cursor_shape_csi(), "\u{1b}[2 q");
+}
+
+#[test]
+pub fn raw_view_keeps_characters_split_between_reads() {
+    let mut fake_win_size = PaneGeom::default();
+    fake_win_size.cols.set_inner(121);
+    fake_win_size.rows.set_inner(20);
+
+    let pid = 1;
+    let style = Style::default();
+    let mut terminal_pane = TerminalPane::new(
+        pid,
+        fake_win_size,
+        style,
+        0,
+        String::new(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+    ); // 0 is the pane index
+    terminal_pane.toggle_raw_view();
+    let bytes = "日本".as_bytes();
+    terminal_pane.handle_pty_bytes(bytes[..2].to_vec());
+    terminal_pane.handle_pty_bytes(bytes[2..4].to_vec());
+    terminal_pane.handle_pty_bytes(bytes[4..].to_vec());
+    assert_eq!(
+        terminal_pane
+            .raw_view
+            .as_ref()
+            .unwrap()
+            .grid
+            .viewport_as_text_lines()[0],
+        "日本",
+        "characters split between reads are shown whole"
+    );
+}
@@ -11,10 +11,11 @@ use async_std::{
     task::{self, JoinHandle},
 };
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     env,
     os::unix::io::RawFd,
     path::PathBuf,
+    sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
 use zellij_utils::nix::unistd::Pid;
@@ -73,6 +74,7 @@ pub(crate) struct Pty {
     pub id_to_child_pid: HashMap<RawFd, RawFd>, // pty_primary => child raw fd
     debug_to_file: bool,
     task_handles: HashMap<RawFd, JoinHandle<()>>,
+    exited_panes: Arc<Mutex<HashSet<RawFd>>>, // pty_primary of panes whose child was reaped
 }
 
 use std::convert::TryFrom;
@@ -83,11 +85,16 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<LayoutFromYaml>) {
         err_ctx.add_call(ContextType::Pty((&event).into()));
         match event {
             PtyInstruction::SpawnTerminal(terminal_action, client_or_tab_index) => {
+                let run_command = match &terminal_action {
+                    Some(TerminalAction::RunCommand(run_command)) => Some(run_command.clone()),
+                    _ => None,
+                };
                 let pid = pty.spawn_terminal(terminal_action, client_or_tab_index);
                 pty.bus
                     .senders
                     .send_to_screen(ScreenInstruction::NewPane(
                         PaneId::Terminal(pid),
+                        run_command,
                         client_or_tab_index,
                     ))
                     .unwrap();
@@ -227,7 +234,8 @@ fn stream_terminal_bytes(
 
             // After a successful read, we keep on reading additional data up to a duration of
             // `RENDER_PAUSE`. This is in order to batch up PtyBytes before rendering them.
-            // Once `render_deadline` has elapsed, we send Render.
+            // Once `render_deadline` has elapsed, we send MaybeRender, which screen coalesces with
+            // the output of the other panes.
             const RENDER_PAUSE: Duration = Duration::from_millis(30);
             let mut render_deadline = None;
             // Keep track of the last render time so we can render immediately if something shows
@@ -240,7 +248,7 @@ fn stream_terminal_bytes(
                 match deadline_read(async_reader.as_mut(), render_deadline, &mut buf).await {
                     ReadResult::Ok(0) | ReadResult::Err(_) => break, // EOF or error
                     ReadResult::Timeout => {
-                        async_send_to_screen(senders.clone(), ScreenInstruction::Render).await;
+                        async_send_to_screen(senders.clone(), ScreenInstruction::MaybeRender).await;
                         // next read does not need a deadline as we just rendered everything
                         render_deadline = None;
                         last_render = Instant::now();
@@ -274,6 +282,7 @@ impl Pty {
             id_to_child_pid: HashMap::new(),
             debug_to_file,
             task_handles: HashMap::new(),
+            exited_panes: Arc::new(Mutex::new(HashSet::new())),
         }
     }
     pub fn get_default_terminal(&self) -> TerminalAction {
@@ -302,6 +311,16 @@ impl Pty {
             };
         };
     }
+    fn quit_cb(&self) -> Box<dyn Fn(PaneId, i32) + Send> {
+        let senders = self.bus.senders.clone();
+        let exited_panes = self.exited_panes.clone();
+        Box::new(move |pane_id, exit_status| {
+            if let PaneId::Terminal(id) = pane_id {
+                exited_panes.lock().unwrap().insert(id);
+            }
+            let _ = senders.send_to_screen(ScreenInstruction::PaneExited(pane_id, exit_status));
+        })
+    }
     pub fn spawn_terminal(
         &mut self,
         terminal_action: Option<TerminalAction>,
@@ -318,12 +337,7 @@ impl Pty {
                 terminal_action.unwrap_or_else(|| self.get_default_terminal())
             }
         };
-        let quit_cb = Box::new({
-            let senders = self.bus.senders.clone();
-            move |pane_id| {
-                let _ = senders.send_to_screen(ScreenInstruction::ClosePane(pane_id, None));
-            }
-        });
+        let quit_cb = self.quit_cb();
         let (pid_primary, child_fd): (RawFd, RawFd) = self
             .bus
             .os_input
@@ -351,12 +365,7 @@ impl Pty {
         let extracted_run_instructions = layout.extract_run_instructions();
         let mut new_pane_pids = vec![];
         for run_instruction in extracted_run_instructions {
-            let quit_cb = Box::new({
-                let senders = self.bus.senders.clone();
-                move |pane_id| {
-                    let _ = senders.send_to_screen(ScreenInstruction::ClosePane(pane_id, None));
-                }
-            });
+            let quit_cb = self.quit_cb();
             match run_instruction {
                 Some(Run::Command(command)) => {
                     let cmd = TerminalAction::RunCommand(command);
@@ -405,15 +414,31 @@ impl Pty {
         match id {
             PaneId::Terminal(id) => {
                 let child_fd = self.id_to_child_pid.remove(&id).unwrap();
-                self.task_handles.remove(&id).unwrap();
+                let task_handle = self.task_handles.remove(&id).unwrap();
+                let has_exited = self.exited_panes.lock().unwrap().remove(&id);
                 task::block_on(async {
-                    self.bus
-                        .os_input
-                        .as_mut()
-                        .unwrap()
-                        .kill(Pid::from_raw(child_fd))
-                        .unwrap();
+                    // stop reading before the fd is closed and can be handed out again
+                    task_handle.cancel().await;
+                    // the child of an exited pane was already reaped, so its pid might have been
+                    // reused by an unrelated process
+                    if !has_exited {
+                        self.bus
+                            .os_input
+                            .as_mut()
+                            .unwrap()
+                            .kill(Pid::from_raw(child_fd))
+                            .unwrap();
+                    }
                 });
+                if let Err(e) = self.bus.os_input.as_ref().unwrap().close_pty(id) {
+                    log::error!("Failed to close pty {}: {}", id, e);
+                }
+                // everything the pane's reader sent is now queued before this, so screen can
+                // drop whatever it still holds for the fd
+                let _ = self
+                    .bus
+                    .senders
+                    .send_to_screen(ScreenInstruction::PtyClosed(id));
             }
             PaneId::Plugin(pid) => drop(
                 self.bus
@@ -442,3 +467,7 @@ impl Drop for Pty {
         }
     }
 }
+
+#[cfg(test)]
+#[path = "./unit/pty_tests.rs"]
+mod pty_tests;
@@ -336,6 +336,18 @@ fn route_action(
                 .send_to_screen(ScreenInstruction::LeftClick(point, client_id))
                 .unwrap();
         }
+        Action::CtrlLeftClick(point) => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::CtrlLeftClick(point, client_id))
+                .unwrap();
+        }
+        Action::AltLeftClick(point) => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::AltLeftClick(point, client_id))
+                .unwrap();
+        }
         Action::RightClick(point) => {
             session
                 .senders
@@ -355,6 +367,275 @@ fn route_action(
                 .send_to_screen(ScreenInstruction::MouseHold(point, client_id))
                 .unwrap();
         }
+        Action::BreakPane => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::BreakPane(client_id))
+                .unwrap();
+        }
+        Action::SwapPane(direction) => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::SwapPane(direction, client_id))
+                .unwrap();
+        }
+        Action::ToggleDiffPane(direction) => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::ToggleDiffPane(direction, client_id))
+                .unwrap();
+        }
+        Action::ResizeBy(direction, percent) => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::ResizeActivePaneBy(
+                    direction, percent, client_id,
+                ))
+                .unwrap();
+        }
+        Action::ToggleRawView => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::ToggleActivePaneRawView(client_id))
+                .unwrap();
+        }
+        Action::MoveFocusWrapping(direction) => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::MoveFocusWrapping(direction, client_id))
+                .unwrap();
+        }
+        Action::RebalancePanes => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::RebalancePanes(client_id))
+                .unwrap();
+        }
+        Action::DumpPane(path, include_scrollback, with_ansi) => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::DumpActivePaneToFile(
+                    path,
+                    include_scrollback,
+                    with_ansi,
+                    client_id,
+                ))
+                .unwrap();
+        }
+        Action::DumpScreen(path) => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::DumpActivePaneScreenToFile(
+                    path, client_id,
+                ))
+                .unwrap();
+        }
+        Action::Search(query) => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::SearchActivePane(query, client_id))
+                .unwrap();
+        }
+        Action::SearchNext => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::ScrollToNextSearchMatch(client_id))
+                .unwrap();
+        }
+        Action::SearchPrevious => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::ScrollToPreviousSearchMatch(client_id))
+                .unwrap();
+        }
+        Action::CopyScrollback => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::CopyActivePaneScrollback(client_id))
+                .unwrap();
+        }
+        Action::DumpLayout(path) => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::DumpLayoutToFile(path, client_id))
+                .unwrap();
+        }
+        Action::Paste(text) => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::ClearScroll(client_id))
+                .unwrap();
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::Paste(text, client_id))
+                .unwrap();
+        }
+        Action::MovePaneToFront => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::MoveActivePaneToFront(client_id))
+                .unwrap();
+        }
+        Action::MovePaneToBack => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::MoveActivePaneToBack(client_id))
+                .unwrap();
+        }
+        Action::ToggleSinglePaneFrames => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::ToggleSinglePaneFrames)
+                .unwrap();
+        }
+        Action::FocusLastPane => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::FocusLastPane(client_id))
+                .unwrap();
+        }
+        Action::SetPaneSize(cols_percent, rows_percent) => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::SetActivePaneSize(
+                    cols_percent,
+                    rows_percent,
+                    client_id,
+                ))
+                .unwrap();
+        }
+        Action::EqualizePanes => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::EqualizePanes(client_id))
+                .unwrap();
+        }
+        Action::FocusNextPaneInDirection(direction) => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::FocusNextPaneInDirection(
+                    direction, client_id,
+                ))
+                .unwrap();
+        }
+        Action::ClearScreen => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::ClearActivePane(client_id))
+                .unwrap();
+        }
+        Action::TogglePanePinned => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::ToggleActivePanePinned(client_id))
+                .unwrap();
+        }
+        Action::ScrollLeft => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::ScrollLeft(client_id))
+                .unwrap();
+        }
+        Action::ScrollRight => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::ScrollRight(client_id))
+                .unwrap();
+        }
+        Action::RelocatePane(direction) => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::MoveActivePaneToDirection(
+                    direction, client_id,
+                ))
+                .unwrap();
+        }
+        Action::ReserveViewport(top, bottom, left, right) => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::ReserveViewport(
+                    top, bottom, left, right, client_id,
+                ))
+                .unwrap();
+        }
+        Action::SwapLayout => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::SwapLayout(client_id))
+                .unwrap();
+        }
+        Action::UndoSplit => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::UndoLastLayoutOp(client_id))
+                .unwrap();
+        }
+        Action::DuplicatePane => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::DuplicateActivePane(client_id))
+                .unwrap();
+        }
+        Action::ShowPaneNumbers => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::ShowPaneNumbers(client_id))
+                .unwrap();
+        }
+        Action::CloseOtherPanes(close_plugins) => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::CloseOtherPanes(close_plugins, client_id))
+                .unwrap();
+        }
+        Action::RotatePanes(reverse) => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::RotatePanes(reverse, client_id))
+                .unwrap();
+        }
+        Action::StartPaneLogging(path) => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::StartActivePaneLogging(path, client_id))
+                .unwrap();
+        }
+        Action::StopPaneLogging => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::StopActivePaneLogging(client_id))
+                .unwrap();
+        }
+        Action::ToggleMaxWidth => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::ToggleActivePaneMaxWidth(client_id))
+                .unwrap();
+        }
+        Action::ToggleMaxHeight => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::ToggleActivePaneMaxHeight(client_id))
+                .unwrap();
+        }
+        Action::MaximizePane(direction) => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::MaximizeActivePane(direction, client_id))
+                .unwrap();
+        }
+        Action::ToggleLineWrap => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::ToggleActivePaneLineWrap(client_id))
+                .unwrap();
+        }
+        Action::SendSignal(signal) => {
+            session
+                .senders
+                .send_to_screen(ScreenInstruction::SendSignalToActivePane(signal, client_id))
+                .unwrap();
+        }
         Action::Copy => {
             session
                 .senders
@@ -1,41 +1,61 @@
 //! Things related to [`Screen`]s.
 
 use std::cell::RefCell;
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::os::unix::io::RawFd;
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::str;
+use std::time::{Duration, Instant};
 
 use zellij_tile::prelude::Style;
-use zellij_utils::input::options::Clipboard;
+use zellij_utils::input::options::{Clipboard, CloseGravity, PasteNewlines};
 use zellij_utils::pane_size::{Size, SizeInPixels};
 use zellij_utils::{
-    input::command::TerminalAction, input::layout::Layout, position::Position, zellij_tile,
+    input::actions::Direction,
+    input::command::{RunCommand, TerminalAction},
+    input::layout::Layout,
+    position::Position,
+    zellij_tile,
 };
 
 use crate::{
     output::Output,
-    panes::PaneId,
+    panes::{selection::SelectionMode, PaneId},
     pty::{ClientOrTabIndex, PtyInstruction, VteBytes},
-    tab::Tab,
+    tab::{CloseResult, Tab, MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH},
     thread_bus::Bus,
     ui::overlay::{Overlay, OverlayWindow, Overlayable},
     wasm_vm::PluginInstruction,
     ClientId, ServerInstruction,
 };
-use zellij_tile::data::{Event, InputMode, ModeInfo, PluginCapabilities, TabInfo};
+use zellij_tile::data::{
+    Event, InputMode, ModeInfo, Palette, PaletteColor, PaneGeomInfo, PluginCapabilities, TabInfo,
+};
 use zellij_utils::{
     errors::{ContextType, ScreenContext},
     input::{get_mode_info, options::Options},
     ipc::{ClientAttributes, PixelDimensions},
 };
 
+/// How many bytes we keep for a pty whose pane does not exist yet, older bytes are dropped.
+const MAX_PENDING_PTY_BYTES: usize = 1024 * 1024;
+
+/// The shortest time between two renders triggered by pty output.
+const RENDER_THROTTLE: Duration = Duration::from_millis(16);
+
+/// How many lines of scrollback are copied at most, some terminals reject OSC52 payloads that are too large.
+const DEFAULT_SCROLLBACK_COPY_LINE_LIMIT: usize = 10000;
+
 /// Instructions that can be sent to the [`Screen`].
 #[derive(Debug, Clone)]
 pub enum ScreenInstruction {
     PtyBytes(RawFd, VteBytes),
     Render,
-    NewPane(PaneId, ClientOrTabIndex),
+    MaybeRender,
+    DeferredRender,
+    PtyClosed(RawFd),
+    NewPane(PaneId, Option<RunCommand>, ClientOrTabIndex), // the command if it is not the default shell
     TogglePaneEmbedOrFloating(ClientId),
     ToggleFloatingPanes(ClientId, Option<TerminalAction>),
     HorizontalSplit(PaneId, ClientId),
@@ -77,6 +97,9 @@ pub enum ScreenInstruction {
     TogglePaneFrames,
     SetSelectable(PaneId, bool, usize),
     ClosePane(PaneId, Option<ClientId>),
+    PaneExited(PaneId, i32),
+    ResizePane(PaneId, Direction, f64),
+    FocusPane(PaneId, ClientId),
     UpdatePaneName(Vec<u8>, ClientId),
     NewTab(Layout, Vec<RawFd>, ClientId),
     SwitchTabNext(ClientId),
@@ -89,10 +112,68 @@ pub enum ScreenInstruction {
     TerminalResize(Size),
     TerminalPixelDimensions(PixelDimensions),
     ChangeMode(ModeInfo, ClientId),
+    BreakPane(ClientId),
+    SwapPane(Direction, ClientId),
+    ToggleDiffPane(Direction, ClientId),
+    ResizeActivePaneBy(Direction, f64, ClientId),
+    ToggleActivePaneRawView(ClientId),
+    MoveFocusWrapping(Direction, ClientId),
+    RebalancePanes(ClientId),
+    RenamePane(PaneId, String),
+    SetPanePalette(PaneId, Option<Palette>),
+    DumpActivePaneToFile(PathBuf, bool, bool, ClientId),
+    DumpActivePaneScreenToFile(PathBuf, ClientId),
+    SearchActivePane(String, ClientId),
+    ScrollToNextSearchMatch(ClientId),
+    ScrollToPreviousSearchMatch(ClientId),
+    CopyActivePaneScrollback(ClientId),
+    SetPaneSyncExcluded(PaneId, bool),
+    DumpLayoutToFile(PathBuf, ClientId),
+    Paste(String, ClientId),
+    MoveActivePaneToFront(ClientId),
+    MoveActivePaneToBack(ClientId),
+    ToggleSinglePaneFrames,
+    FocusLastPane(ClientId),
+    SetActivePaneSize(Option<f64>, Option<f64>, ClientId),
+    AddPaneToGroup(PaneId, String),
+    RemovePaneFromGroup(PaneId, String),
+    WriteToGroup(String, Vec<u8>, ClientId),
+    EqualizePanes(ClientId),
+    FocusNextPaneInDirection(Direction, ClientId),
+    ClearActivePane(ClientId),
+    SetPaneScrollbackLimit(PaneId, usize),
+    ToggleActivePanePinned(ClientId),
+    ScrollLeft(ClientId),
+    ScrollRight(ClientId),
+    MoveActivePaneToDirection(Direction, ClientId),
+    SetPaneSilenceThreshold(PaneId, Option<Duration>),
+    ReserveViewport(usize, usize, usize, usize, ClientId),
+    SwapLayout(ClientId),
+    UndoLastLayoutOp(ClientId),
+    SetPaneBorderColor(PaneId, Option<PaletteColor>),
+    DuplicateActivePane(ClientId),
+    ShowPaneNumbers(ClientId),
+    CloseOtherPanes(bool, ClientId),
+    SetPaneReadOnly(PaneId, bool),
+    RotatePanes(bool, ClientId),
+    StartActivePaneLogging(PathBuf, ClientId),
+    StopActivePaneLogging(ClientId),
+    RequestPaneContentSize(PaneId, usize, usize),
+    ToggleActivePaneMaxWidth(ClientId),
+    ToggleActivePaneMaxHeight(ClientId),
+    MaximizeActivePane(Direction, ClientId),
+    ToggleActivePaneLineWrap(ClientId),
+    SendSignalToActivePane(i32, ClientId),
+    InsertSyntheticLine(PaneId, String, ClientId),
+    RequestPaneBoundaries(u32, ClientId), // u32 is the id of the plugin asking
+    RequestPaneGeoms(u32, ClientId),      // u32 is the id of the plugin asking
     LeftClick(Position, ClientId),
+    CtrlLeftClick(Position, ClientId),
+    AltLeftClick(Position, ClientId),
     RightClick(Position, ClientId),
     MouseRelease(Position, ClientId),
     MouseHold(Position, ClientId),
+    ScrollSelectionAtEdge(ClientId),
     Copy(ClientId),
     AddClient(ClientId),
     RemoveClient(ClientId),
@@ -107,6 +188,9 @@ impl From<&ScreenInstruction> for ScreenContext {
         match *screen_instruction {
             ScreenInstruction::PtyBytes(..) => ScreenContext::HandlePtyBytes,
             ScreenInstruction::Render => ScreenContext::Render,
+            ScreenInstruction::MaybeRender => ScreenContext::MaybeRender,
+            ScreenInstruction::DeferredRender => ScreenContext::DeferredRender,
+            ScreenInstruction::PtyClosed(..) => ScreenContext::PtyClosed,
             ScreenInstruction::NewPane(..) => ScreenContext::NewPane,
             ScreenInstruction::TogglePaneEmbedOrFloating(..) => {
                 ScreenContext::TogglePaneEmbedOrFloating
@@ -155,6 +239,9 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::TogglePaneFrames => ScreenContext::TogglePaneFrames,
             ScreenInstruction::SetSelectable(..) => ScreenContext::SetSelectable,
             ScreenInstruction::ClosePane(..) => ScreenContext::ClosePane,
+            ScreenInstruction::PaneExited(..) => ScreenContext::PaneExited,
+            ScreenInstruction::ResizePane(..) => ScreenContext::ResizePane,
+            ScreenInstruction::FocusPane(..) => ScreenContext::FocusPane,
             ScreenInstruction::UpdatePaneName(..) => ScreenContext::UpdatePaneName,
             ScreenInstruction::NewTab(..) => ScreenContext::NewTab,
             ScreenInstruction::SwitchTabNext(..) => ScreenContext::SwitchTabNext,
@@ -170,10 +257,90 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::ToggleActiveSyncTab(..) => ScreenContext::ToggleActiveSyncTab,
             ScreenInstruction::ScrollUpAt(..) => ScreenContext::ScrollUpAt,
             ScreenInstruction::ScrollDownAt(..) => ScreenContext::ScrollDownAt,
+            ScreenInstruction::BreakPane(..) => ScreenContext::BreakPane,
+            ScreenInstruction::SwapPane(..) => ScreenContext::SwapPane,
+            ScreenInstruction::ToggleDiffPane(..) => ScreenContext::ToggleDiffPane,
+            ScreenInstruction::ResizeActivePaneBy(..) => ScreenContext::ResizeActivePaneBy,
+            ScreenInstruction::ToggleActivePaneRawView(..) => {
+                ScreenContext::ToggleActivePaneRawView
+            }
+            ScreenInstruction::MoveFocusWrapping(..) => ScreenContext::MoveFocusWrapping,
+            ScreenInstruction::RebalancePanes(..) => ScreenContext::RebalancePanes,
+            ScreenInstruction::RenamePane(..) => ScreenContext::RenamePane,
+            ScreenInstruction::SetPanePalette(..) => ScreenContext::SetPanePalette,
+            ScreenInstruction::DumpActivePaneToFile(..) => ScreenContext::DumpActivePaneToFile,
+            ScreenInstruction::DumpActivePaneScreenToFile(..) => {
+                ScreenContext::DumpActivePaneScreenToFile
+            }
+            ScreenInstruction::SearchActivePane(..) => ScreenContext::SearchActivePane,
+            ScreenInstruction::ScrollToNextSearchMatch(..) => {
+                ScreenContext::ScrollToNextSearchMatch
+            }
+            ScreenInstruction::ScrollToPreviousSearchMatch(..) => {
+                ScreenContext::ScrollToPreviousSearchMatch
+            }
+            ScreenInstruction::CopyActivePaneScrollback(..) => {
+                ScreenContext::CopyActivePaneScrollback
+            }
+            ScreenInstruction::SetPaneSyncExcluded(..) => ScreenContext::SetPaneSyncExcluded,
+            ScreenInstruction::DumpLayoutToFile(..) => ScreenContext::DumpLayoutToFile,
+            ScreenInstruction::Paste(..) => ScreenContext::Paste,
+            ScreenInstruction::MoveActivePaneToFront(..) => ScreenContext::MoveActivePaneToFront,
+            ScreenInstruction::MoveActivePaneToBack(..) => ScreenContext::MoveActivePaneToBack,
+            ScreenInstruction::ToggleSinglePaneFrames => ScreenContext::ToggleSinglePaneFrames,
+            ScreenInstruction::FocusLastPane(..) => ScreenContext::FocusLastPane,
+            ScreenInstruction::SetActivePaneSize(..) => ScreenContext::SetActivePaneSize,
+            ScreenInstruction::AddPaneToGroup(..) => ScreenContext::AddPaneToGroup,
+            ScreenInstruction::RemovePaneFromGroup(..) => ScreenContext::RemovePaneFromGroup,
+            ScreenInstruction::WriteToGroup(..) => ScreenContext::WriteToGroup,
+            ScreenInstruction::EqualizePanes(..) => ScreenContext::EqualizePanes,
+            ScreenInstruction::FocusNextPaneInDirection(..) => {
+                ScreenContext::FocusNextPaneInDirection
+            }
+            ScreenInstruction::ClearActivePane(..) => ScreenContext::ClearActivePane,
+            ScreenInstruction::SetPaneScrollbackLimit(..) => ScreenContext::SetPaneScrollbackLimit,
+            ScreenInstruction::ToggleActivePanePinned(..) => ScreenContext::ToggleActivePanePinned,
+            ScreenInstruction::ScrollLeft(..) => ScreenContext::ScrollLeft,
+            ScreenInstruction::ScrollRight(..) => ScreenContext::ScrollRight,
+            ScreenInstruction::MoveActivePaneToDirection(..) => {
+                ScreenContext::MoveActivePaneToDirection
+            }
+            ScreenInstruction::SetPaneSilenceThreshold(..) => {
+                ScreenContext::SetPaneSilenceThreshold
+            }
+            ScreenInstruction::ReserveViewport(..) => ScreenContext::ReserveViewport,
+            ScreenInstruction::SwapLayout(..) => ScreenContext::SwapLayout,
+            ScreenInstruction::UndoLastLayoutOp(..) => ScreenContext::UndoLastLayoutOp,
+            ScreenInstruction::SetPaneBorderColor(..) => ScreenContext::SetPaneBorderColor,
+            ScreenInstruction::DuplicateActivePane(..) => ScreenContext::DuplicateActivePane,
+            ScreenInstruction::ShowPaneNumbers(..) => ScreenContext::ShowPaneNumbers,
+            ScreenInstruction::CloseOtherPanes(..) => ScreenContext::CloseOtherPanes,
+            ScreenInstruction::SetPaneReadOnly(..) => ScreenContext::SetPaneReadOnly,
+            ScreenInstruction::RotatePanes(..) => ScreenContext::RotatePanes,
+            ScreenInstruction::StartActivePaneLogging(..) => ScreenContext::StartActivePaneLogging,
+            ScreenInstruction::StopActivePaneLogging(..) => ScreenContext::StopActivePaneLogging,
+            ScreenInstruction::RequestPaneContentSize(..) => ScreenContext::RequestPaneContentSize,
+            ScreenInstruction::ToggleActivePaneMaxWidth(..) => {
+                ScreenContext::ToggleActivePaneMaxWidth
+            }
+            ScreenInstruction::ToggleActivePaneMaxHeight(..) => {
+                ScreenContext::ToggleActivePaneMaxHeight
+            }
+            ScreenInstruction::MaximizeActivePane(..) => ScreenContext::MaximizeActivePane,
+            ScreenInstruction::ToggleActivePaneLineWrap(..) => {
+                ScreenContext::ToggleActivePaneLineWrap
+            }
+            ScreenInstruction::SendSignalToActivePane(..) => ScreenContext::SendSignalToActivePane,
+            ScreenInstruction::InsertSyntheticLine(..) => ScreenContext::InsertSyntheticLine,
+            ScreenInstruction::RequestPaneBoundaries(..) => ScreenContext::RequestPaneBoundaries,
+            ScreenInstruction::RequestPaneGeoms(..) => ScreenContext::RequestPaneGeoms,
             ScreenInstruction::LeftClick(..) => ScreenContext::LeftClick,
+            ScreenInstruction::CtrlLeftClick(..) => ScreenContext::CtrlLeftClick,
+            ScreenInstruction::AltLeftClick(..) => ScreenContext::AltLeftClick,
             ScreenInstruction::RightClick(..) => ScreenContext::RightClick,
             ScreenInstruction::MouseRelease(..) => ScreenContext::MouseRelease,
             ScreenInstruction::MouseHold(..) => ScreenContext::MouseHold,
+            ScreenInstruction::ScrollSelectionAtEdge(..) => ScreenContext::ScrollSelectionAtEdge,
             ScreenInstruction::Copy(..) => ScreenContext::Copy,
             ScreenInstruction::ToggleTab(..) => ScreenContext::ToggleTab,
             ScreenInstruction::AddClient(..) => ScreenContext::AddClient,
@@ -191,6 +358,8 @@ pub(crate) struct CopyOptions {
     pub command: Option<String>,
     pub clipboard: Clipboard,
     pub copy_on_select: bool,
+    pub copy_from_panes: bool,
+    pub scrollback_line_limit: usize,
 }
 
 impl CopyOptions {
@@ -198,11 +367,15 @@ impl CopyOptions {
         copy_command: Option<String>,
         copy_clipboard: Clipboard,
         copy_on_select: bool,
+        copy_from_panes: bool,
+        scrollback_line_limit: usize,
     ) -> Self {
         Self {
             command: copy_command,
             clipboard: copy_clipboard,
             copy_on_select,
+            copy_from_panes,
+            scrollback_line_limit,
         }
     }
 
@@ -212,6 +385,8 @@ impl CopyOptions {
             command: None,
             clipboard: Clipboard::default(),
             copy_on_select: true,
+            copy_from_panes: false,
+            scrollback_line_limit: DEFAULT_SCROLLBACK_COPY_LINE_LIMIT,
         }
     }
 }
@@ -239,20 +414,41 @@ pub(crate) struct Screen {
     default_mode_info: ModeInfo, // TODO: restructure ModeInfo to prevent this duplication
     style: Style,
     draw_pane_frames: bool,
+    hide_single_pane_frame: bool,
+    hold_exited_panes: bool,
     session_is_mirrored: bool,
     copy_options: CopyOptions,
+    min_pane_width: usize,
+    min_pane_height: usize,
+    paste_newlines: PasteNewlines,
+    reject_panes_over_max: bool,
+    close_gravity: CloseGravity,
+    cursor_height_width_ratio: Option<usize>,
+    /// Bytes that arrived from a pty before its pane was added to a [`Tab`].
+    pending_pty_bytes: HashMap<RawFd, VteBytes>,
+    last_render: Instant,
+    deferred_render_pending: bool,
 }
 
 impl Screen {
     /// Creates and returns a new [`Screen`].
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         bus: Bus<ScreenInstruction>,
         client_attributes: &ClientAttributes,
         max_panes: Option<usize>,
         mode_info: ModeInfo,
         draw_pane_frames: bool,
+        hide_single_pane_frame: bool,
+        hold_exited_panes: bool,
         session_is_mirrored: bool,
         copy_options: CopyOptions,
+        min_pane_width: usize,
+        min_pane_height: usize,
+        paste_newlines: PasteNewlines,
+        reject_panes_over_max: bool,
+        close_gravity: CloseGravity,
+        cursor_height_width_ratio: Option<usize>,
     ) -> Self {
         Screen {
             bus,
@@ -269,8 +465,19 @@ impl Screen {
             mode_info: BTreeMap::new(),
             default_mode_info: mode_info,
             draw_pane_frames,
+            hide_single_pane_frame,
+            hold_exited_panes,
             session_is_mirrored,
             copy_options,
+            min_pane_width,
+            min_pane_height,
+            paste_newlines,
+            reject_panes_over_max,
+            close_gravity,
+            cursor_height_width_ratio,
+            pending_pty_bytes: HashMap::new(),
+            last_render: Instant::now(),
+            deferred_render_pending: false,
         }
     }
 
@@ -483,13 +690,54 @@ impl Screen {
         }
     }
 
+    /// Passes the bytes a pty produced to the pane that displays it, or buffers them if that pane
+    /// was not created yet (the pty thread can start reading before the pane reaches us).
+    pub fn handle_pty_bytes(&mut self, pid: RawFd, vte_bytes: VteBytes) {
+        if let Some(tab) = self.tabs.values_mut().find(|tab| tab.has_terminal_pid(pid)) {
+            tab.handle_pty_bytes(pid, vte_bytes);
+        } else {
+            let pending_bytes = self.pending_pty_bytes.entry(pid).or_default();
+            pending_bytes.extend(vte_bytes);
+            if pending_bytes.len() > MAX_PENDING_PTY_BYTES {
+                let overflow = pending_bytes.len() - MAX_PENDING_PTY_BYTES;
+                pending_bytes.drain(..overflow);
+            }
+        }
+    }
+
+    /// Drops the bytes buffered for a pty that was closed, its fd might be reused by a new pane
+    /// which should not get them.
+    pub fn discard_pending_pty_bytes(&mut self, pid: RawFd) {
+        self.pending_pty_bytes.remove(&pid);
+    }
+
+    fn flush_pending_pty_bytes(&mut self) {
+        let pids: Vec<RawFd> = self.pending_pty_bytes.keys().copied().collect();
+        for pid in pids {
+            if let Some(tab) = self.tabs.values_mut().find(|tab| tab.has_terminal_pid(pid)) {
+                if let Some(pending_bytes) = self.pending_pty_bytes.remove(&pid) {
+                    tab.handle_pty_bytes(pid, pending_bytes);
+                }
+            }
+        }
+    }
+
     /// Renders this [`Screen`], which amounts to rendering its active [`Tab`].
     pub fn render(&mut self) {
+        // new panes are always rendered right after they are created, so this is where we catch
+        // up on the output they produced before they existed
+        if !self.pending_pty_bytes.is_empty() {
+            self.flush_pending_pty_bytes();
+        }
         let mut output = Output::default();
         let mut tabs_to_close = vec![];
         let size = self.size;
         let overlay = self.overlay.clone();
         for (tab_index, tab) in &mut self.tabs {
+            // tabs no client is looking at don't render, but their panes can still go silent or
+            // have activity
+            tab.report_silent_panes();
+            tab.update_pane_activity_in_plugins();
             if tab.has_selectable_tiled_panes() {
                 let vte_overlay = overlay.generate_overlay(size);
                 tab.render(&mut output, Some(vte_overlay));
@@ -505,6 +753,37 @@ impl Screen {
             .senders
             .send_to_server(ServerInstruction::Render(Some(serialized_output)))
             .unwrap();
+        self.last_render = Instant::now();
+    }
+
+    /// Renders if a [`Tab`] got pty output since the last render, but no more than once every
+    /// [`RENDER_THROTTLE`]. Output arriving sooner is picked up by a single deferred render.
+    pub fn maybe_render(&mut self) {
+        // bytes still waiting for their pane don't need a render until the pane exists
+        let needs_render = self.tabs.values().any(|tab| {
+            tab.needs_render()
+                || self
+                    .pending_pty_bytes
+                    .keys()
+                    .any(|pid| tab.has_terminal_pid(*pid))
+        });
+        if !needs_render {
+            return;
+        }
+        let since_last_render = self.last_render.elapsed();
+        if since_last_render >= RENDER_THROTTLE {
+            self.render();
+        } else if !self.deferred_render_pending {
+            self.deferred_render_pending = true;
+            self.bus.senders.send_to_screen_after(
+                ScreenInstruction::DeferredRender,
+                RENDER_THROTTLE - since_last_render,
+            );
+        }
+    }
+    fn deferred_render(&mut self) {
+        self.deferred_render_pending = false;
+        self.maybe_render();
     }
 
     /// Returns a mutable reference to this [`Screen`]'s tabs.
@@ -537,6 +816,13 @@ impl Screen {
         }
     }
 
+    /// Returns a mutable reference to the [`Tab`] containing the given pane.
+    pub fn get_tab_with_pane_mut(&mut self, pane_id: PaneId) -> Option<&mut Tab> {
+        self.tabs
+            .values_mut()
+            .find(|tab| tab.get_all_pane_ids().contains(&pane_id))
+    }
+
     /// Returns a mutable reference to this [`Screen`]'s active [`Overlays`].
     pub fn get_active_overlays_mut(&mut self) -> &mut Vec<Overlay> {
         &mut self.overlay.overlay_stack
@@ -550,6 +836,35 @@ impl Screen {
     /// Creates a new [`Tab`] in this [`Screen`], applying the specified [`Layout`]
     /// and switching to it.
     pub fn new_tab(&mut self, layout: Layout, new_pids: Vec<RawFd>, client_id: ClientId) {
+        let mut tab = self.create_tab(client_id);
+        let tab_index = tab.index;
+        tab.apply_layout(layout, new_pids, tab_index, client_id);
+        self.add_tab(tab, client_id);
+    }
+
+    /// Moves the focused pane of this client out of its tab and into a new tab of its own
+    pub fn break_pane_to_new_tab(&mut self, client_id: ClientId) {
+        let extracted_pane = self.get_active_tab_mut(client_id).and_then(|active_tab| {
+            active_tab
+                .get_active_pane_id(client_id)
+                .and_then(|pane_id| active_tab.extract_pane(pane_id).map(|pane| (pane_id, pane)))
+        });
+        match extracted_pane {
+            Some((pane_id, pane)) => {
+                let mut tab = self.create_tab(client_id);
+                tab.add_extracted_pane(pane_id, pane, client_id);
+                self.add_tab(tab, client_id);
+            }
+            None => {
+                log::error!(
+                    "No pane to break out of the active tab of client: {:?}",
+                    client_id
+                );
+            }
+        }
+    }
+
+    fn create_tab(&self, client_id: ClientId) -> Tab {
         let tab_index = self.get_new_tab_index();
         let position = self.tabs.len();
         let client_mode_info = self
@@ -563,18 +878,29 @@ impl Screen {
             String::new(),
             self.size,
             self.character_cell_size.clone(),
+            self.cursor_height_width_ratio,
             self.bus.os_input.as_ref().unwrap().clone(),
             self.bus.senders.clone(),
             self.max_panes,
             self.style,
             client_mode_info,
             self.draw_pane_frames,
+            self.hide_single_pane_frame,
             self.connected_clients.clone(),
             self.session_is_mirrored,
             client_id,
             self.copy_options.clone(),
+            self.min_pane_width,
+            self.min_pane_height,
         );
-        tab.apply_layout(layout, new_pids, tab_index, client_id);
+        tab.set_paste_newlines(self.paste_newlines);
+        tab.reject_new_panes_over_max(self.reject_panes_over_max);
+        tab.set_close_gravity(self.close_gravity);
+        tab
+    }
+
+    fn add_tab(&mut self, mut tab: Tab, client_id: ClientId) {
+        let tab_index = tab.index;
         if self.session_is_mirrored {
             if let Some(active_tab) = self.get_active_tab_mut(client_id) {
                 let client_mode_infos_in_source_tab = active_tab.drain_connected_clients(None);
@@ -669,6 +995,8 @@ impl Screen {
                     is_sync_panes_active: tab.is_sync_panes_active(),
                     are_floating_panes_visible: tab.are_floating_panes_visible(),
                     other_focused_clients,
+                    pane_count: tab.pane_count(),
+                    selectable_pane_count: tab.selectable_pane_count(),
                 });
             }
             self.bus
@@ -727,8 +1055,10 @@ impl Screen {
     }
     pub fn move_focus_left_or_previous_tab(&mut self, client_id: ClientId) {
         if let Some(active_tab) = self.get_active_tab_mut(client_id) {
-            if !active_tab.move_focus_left(client_id) {
-                println!("can has true");
+            // only roll over to the previous tab from the left edge of this one
+            if !active_tab.move_focus_left(client_id)
+                && active_tab.active_pane_is_at_edge(client_id, Direction::Left)
+            {
                 self.switch_tab_prev(client_id);
             }
         } else {
@@ -737,7 +1067,9 @@ impl Screen {
     }
     pub fn move_focus_right_or_next_tab(&mut self, client_id: ClientId) {
         if let Some(active_tab) = self.get_active_tab_mut(client_id) {
-            if !active_tab.move_focus_right(client_id) {
+            if !active_tab.move_focus_right(client_id)
+                && active_tab.active_pane_is_at_edge(client_id, Direction::Right)
+            {
                 self.switch_tab_next(client_id);
             }
         } else {
@@ -767,11 +1099,17 @@ pub(crate) fn screen_thread_main(
 ) {
     let capabilities = config_options.simplified_ui;
     let draw_pane_frames = config_options.pane_frames.unwrap_or(true);
+    let hide_single_pane_frame = config_options.hide_single_pane_frame.unwrap_or(false);
+    let hold_exited_panes = config_options.hold_exited_panes.unwrap_or(false);
     let session_is_mirrored = config_options.mirror_session.unwrap_or(false);
     let copy_options = CopyOptions::new(
         config_options.copy_command,
         config_options.copy_clipboard.unwrap_or_default(),
         config_options.copy_on_select.unwrap_or(true),
+        config_options.copy_from_panes.unwrap_or(false),
+        config_options
+            .scrollback_copy_line_limit
+            .unwrap_or(DEFAULT_SCROLLBACK_COPY_LINE_LIMIT),
     );
 
     let mut screen = Screen::new(
@@ -786,8 +1124,18 @@ pub(crate) fn screen_thread_main(
             },
         ),
         draw_pane_frames,
+        hide_single_pane_frame,
+        hold_exited_panes,
         session_is_mirrored,
         copy_options,
+        config_options.min_pane_width.unwrap_or(MIN_TERMINAL_WIDTH),
+        config_options
+            .min_pane_height
+            .unwrap_or(MIN_TERMINAL_HEIGHT),
+        config_options.paste_newlines.unwrap_or_default(),
+        config_options.reject_panes_over_max.unwrap_or(false),
+        config_options.close_gravity.unwrap_or_default(),
+        config_options.cursor_height_width_ratio,
     );
     loop {
         let (event, mut err_ctx) = screen
@@ -797,22 +1145,26 @@ pub(crate) fn screen_thread_main(
         err_ctx.add_call(ContextType::Screen((&event).into()));
         match event {
             ScreenInstruction::PtyBytes(pid, vte_bytes) => {
-                let all_tabs = screen.get_tabs_mut();
-                for tab in all_tabs.values_mut() {
-                    if tab.has_terminal_pid(pid) {
-                        tab.handle_pty_bytes(pid, vte_bytes);
-                        break;
-                    }
-                }
+                screen.handle_pty_bytes(pid, vte_bytes);
             }
             ScreenInstruction::Render => {
                 screen.render();
             }
-            ScreenInstruction::NewPane(pid, client_or_tab_index) => {
+            ScreenInstruction::MaybeRender => {
+                screen.maybe_render();
+            }
+            ScreenInstruction::DeferredRender => {
+                screen.deferred_render();
+            }
+            ScreenInstruction::PtyClosed(pid) => {
+                screen.discard_pending_pty_bytes(pid);
+            }
+            ScreenInstruction::NewPane(pid, run_command, client_or_tab_index) => {
                 match client_or_tab_index {
                     ClientOrTabIndex::ClientId(client_id) => {
                         if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
-                            active_tab.new_pane(pid, Some(client_id));
+                            active_tab.smart_split_active(pid, client_id);
+                            active_tab.set_pane_run_command(pid, run_command);
                         } else {
                             log::error!("Active tab not found for client id: {:?}", client_id);
                         }
@@ -820,6 +1172,7 @@ pub(crate) fn screen_thread_main(
                     ClientOrTabIndex::TabIndex(tab_index) => {
                         if let Some(active_tab) = screen.tabs.get_mut(&tab_index) {
                             active_tab.new_pane(pid, None);
+                            active_tab.set_pane_run_command(pid, run_command);
                         } else {
                             log::error!("Tab index not found: {:?}", tab_index);
                         }
@@ -1174,7 +1527,12 @@ pub(crate) fn screen_thread_main(
             }
             ScreenInstruction::CloseFocusedPane(client_id) => {
                 if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
-                    active_tab.close_focused_pane(client_id);
+                    if active_tab.close_focused_pane(client_id) == CloseResult::NeedsConfirmation {
+                        active_tab.show_overlay_message(
+                            "A process is running in this pane, close it again to confirm".into(),
+                            Duration::from_secs(3),
+                        );
+                    }
                 } else {
                     log::error!("Active tab not found for client id: {:?}", client_id);
                 }
@@ -1195,6 +1553,9 @@ pub(crate) fn screen_thread_main(
                 screen.render();
             }
             ScreenInstruction::ClosePane(id, client_id) => {
+                if let PaneId::Terminal(pid) = id {
+                    screen.discard_pending_pty_bytes(pid);
+                }
                 match client_id {
                     Some(client_id) => {
                         screen
@@ -1216,6 +1577,52 @@ pub(crate) fn screen_thread_main(
                 }
                 screen.update_tabs();
             }
+            ScreenInstruction::PaneExited(id, exit_status) => {
+                let hold_exited_panes = screen.hold_exited_panes;
+                if !hold_exited_panes {
+                    if let PaneId::Terminal(pid) = id {
+                        screen.discard_pending_pty_bytes(pid);
+                    }
+                }
+                for tab in screen.tabs.values_mut() {
+                    if tab.get_all_pane_ids().contains(&id) {
+                        if hold_exited_panes {
+                            tab.mark_pane_exited(id, exit_status);
+                        } else {
+                            tab.close_pane(id);
+                            // lets the pty thread release the fd of the pane
+                            screen
+                                .bus
+                                .senders
+                                .send_to_pty(PtyInstruction::ClosePane(id))
+                                .unwrap();
+                        }
+                        break;
+                    }
+                }
+                screen.update_tabs();
+                screen.render();
+            }
+            ScreenInstruction::ResizePane(id, direction, percent) => {
+                match screen.get_tab_with_pane_mut(id) {
+                    Some(tab) => {
+                        tab.resize_pane_with_id(id, direction, percent);
+                    }
+                    None => log::error!("Failed to resize pane {:?}: pane not found", id),
+                }
+                screen.render();
+            }
+            ScreenInstruction::FocusPane(id, client_id) => {
+                match screen.get_tab_with_pane_mut(id) {
+                    Some(tab) => {
+                        if !tab.focus_pane(id, client_id) {
+                            log::error!("Failed to focus pane {:?}: pane is not selectable", id);
+                        }
+                    }
+                    None => log::error!("Failed to focus pane {:?}: pane not found", id),
+                }
+                screen.render();
+            }
             ScreenInstruction::UpdatePaneName(c, client_id) => {
                 if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
                     active_tab.update_active_pane_name(c, client_id);
@@ -1324,6 +1731,536 @@ pub(crate) fn screen_thread_main(
 
                 screen.render();
             }
+            ScreenInstruction::BreakPane(client_id) => {
+                screen.break_pane_to_new_tab(client_id);
+            }
+            ScreenInstruction::SwapPane(direction, client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.swap_pane(client_id, direction);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::ToggleDiffPane(direction, client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.toggle_diff_pane(client_id, direction);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::ResizeActivePaneBy(direction, percent, client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.resize_active_pane_by(client_id, direction, percent);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::ToggleActivePaneRawView(client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    if let Some(pane_id) = active_tab.get_active_pane_id(client_id) {
+                        active_tab.toggle_raw_view(pane_id);
+                    }
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::MoveFocusWrapping(direction, client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    match direction {
+                        Direction::Left => active_tab.move_focus_left_wrapping(client_id),
+                        Direction::Right => active_tab.move_focus_right_wrapping(client_id),
+                        Direction::Up => active_tab.move_focus_up_wrapping(client_id),
+                        Direction::Down => active_tab.move_focus_down_wrapping(client_id),
+                    };
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::RebalancePanes(client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.rebalance();
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::RenamePane(id, name) => {
+                match screen.get_tab_with_pane_mut(id) {
+                    Some(tab) => {
+                        tab.set_pane_name(id, name);
+                    }
+                    None => log::error!("Failed to rename pane {:?}: pane not found", id),
+                }
+                screen.render();
+            }
+            ScreenInstruction::SetPanePalette(id, palette) => {
+                match screen.get_tab_with_pane_mut(id) {
+                    Some(tab) => {
+                        tab.set_pane_palette(id, palette);
+                    }
+                    None => {
+                        log::error!("Failed to set the palette of pane {:?}: pane not found", id)
+                    }
+                }
+                screen.render();
+            }
+            ScreenInstruction::DumpActivePaneToFile(
+                path,
+                include_scrollback,
+                with_ansi,
+                client_id,
+            ) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.dump_active_pane_to_file(
+                        &path,
+                        include_scrollback,
+                        with_ansi,
+                        client_id,
+                    );
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::DumpActivePaneScreenToFile(path, client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.dump_active_pane_screen_to_file(&path, client_id);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::SearchActivePane(query, client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.search_active_terminal(&query, client_id);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::ScrollToNextSearchMatch(client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.scroll_to_next_match(client_id);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::ScrollToPreviousSearchMatch(client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.scroll_to_previous_match(client_id);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::CopyActivePaneScrollback(client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.copy_active_pane_scrollback(client_id);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::SetPaneSyncExcluded(id, excluded) => {
+                match screen.get_tab_with_pane_mut(id) {
+                    Some(tab) => {
+                        tab.set_pane_sync_excluded(id, excluded);
+                    }
+                    None => {
+                        log::error!(
+                            "Failed to set the sync exclusion of pane {:?}: pane not found",
+                            id
+                        )
+                    }
+                }
+                screen.render();
+            }
+            ScreenInstruction::DumpLayoutToFile(path, client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.dump_layout_to_file(&path);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::Paste(text, client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    match active_tab.is_sync_panes_active() {
+                        true => active_tab.paste_to_terminals_on_current_tab(&text),
+                        false => active_tab.paste_to_active_terminal(&text, client_id),
+                    }
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+            }
+            ScreenInstruction::MoveActivePaneToFront(client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.move_active_pane_to_front(client_id);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::MoveActivePaneToBack(client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.move_active_pane_to_back(client_id);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::ToggleSinglePaneFrames => {
+                screen.hide_single_pane_frame = !screen.hide_single_pane_frame;
+                for tab in screen.tabs.values_mut() {
+                    tab.toggle_pane_frames_for_single_pane();
+                }
+                screen.render();
+            }
+            ScreenInstruction::FocusLastPane(client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.focus_last_pane(client_id);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::SetActivePaneSize(cols_percent, rows_percent, client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.set_active_pane_size(cols_percent, rows_percent, client_id);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::AddPaneToGroup(id, group) => {
+                match screen.get_tab_with_pane_mut(id) {
+                    Some(tab) => {
+                        tab.add_pane_to_group(&group, id);
+                    }
+                    None => log::error!(
+                        "Failed to add pane {:?} to group {}: pane not found",
+                        id,
+                        group
+                    ),
+                }
+            }
+            ScreenInstruction::RemovePaneFromGroup(id, group) => {
+                match screen.get_tab_with_pane_mut(id) {
+                    Some(tab) => {
+                        tab.remove_pane_from_group(&group, id);
+                    }
+                    None => log::error!(
+                        "Failed to remove pane {:?} from group {}: pane not found",
+                        id,
+                        group
+                    ),
+                }
+            }
+            ScreenInstruction::WriteToGroup(group, bytes, client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.write_to_group(&group, bytes);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+            }
+            ScreenInstruction::EqualizePanes(client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.equalize_panes();
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::FocusNextPaneInDirection(direction, client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.focus_next_pane_in_direction(client_id, direction);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::ClearActivePane(client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.clear_active_pane(client_id);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::SetPaneScrollbackLimit(id, lines) => {
+                match screen.get_tab_with_pane_mut(id) {
+                    Some(tab) => {
+                        tab.set_pane_scrollback_limit(id, lines);
+                    }
+                    None => log::error!(
+                        "Failed to set the scrollback limit of pane {:?}: pane not found",
+                        id
+                    ),
+                }
+                screen.render();
+            }
+            ScreenInstruction::ToggleActivePanePinned(client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    if let Some(pane_id) = active_tab.get_active_pane_id(client_id) {
+                        active_tab.toggle_pane_pinned(pane_id);
+                    }
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::ScrollLeft(client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.scroll_active_terminal_left(client_id);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::ScrollRight(client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.scroll_active_terminal_right(client_id);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::MoveActivePaneToDirection(direction, client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.move_active_pane_to_direction(client_id, direction);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::SetPaneSilenceThreshold(id, threshold) => {
+                match screen.get_tab_with_pane_mut(id) {
+                    Some(tab) => {
+                        tab.set_pane_silence_threshold(id, threshold);
+                    }
+                    None => log::error!(
+                        "Failed to set the silence threshold of pane {:?}: pane not found",
+                        id
+                    ),
+                }
+                screen.render();
+            }
+            ScreenInstruction::ReserveViewport(top, bottom, left, right, client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.reserve_viewport(top, bottom, left, right);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::SwapLayout(client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.swap_layout(client_id);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::UndoLastLayoutOp(client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.undo_last_layout_op();
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::SetPaneBorderColor(id, color) => {
+                match screen.get_tab_with_pane_mut(id) {
+                    Some(tab) => {
+                        tab.set_pane_border_color(id, color);
+                    }
+                    None => log::error!(
+                        "Failed to set the border color of pane {:?}: pane not found",
+                        id
+                    ),
+                }
+                screen.render();
+            }
+            ScreenInstruction::DuplicateActivePane(client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.duplicate_active_pane(client_id);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::ShowPaneNumbers(client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.show_pane_numbers();
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::CloseOtherPanes(close_plugins, client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.close_other_panes(client_id, close_plugins);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.update_tabs(); // update_tabs eventually calls render through the plugin thread
+            }
+            ScreenInstruction::SetPaneReadOnly(id, read_only) => {
+                match screen.get_tab_with_pane_mut(id) {
+                    Some(tab) => {
+                        tab.set_pane_read_only(id, read_only);
+                    }
+                    None => log::error!(
+                        "Failed to set the read-only mode of pane {:?}: pane not found",
+                        id
+                    ),
+                }
+                screen.render();
+            }
+            ScreenInstruction::RotatePanes(reverse, client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.rotate_panes(reverse);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::StartActivePaneLogging(path, client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    if let Some(pane_id) = active_tab.get_active_pane_id(client_id) {
+                        active_tab.start_pane_logging(pane_id, path);
+                    }
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+            }
+            ScreenInstruction::StopActivePaneLogging(client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    if let Some(pane_id) = active_tab.get_active_pane_id(client_id) {
+                        active_tab.stop_pane_logging(pane_id);
+                    }
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+            }
+            ScreenInstruction::RequestPaneContentSize(id, cols, rows) => {
+                match screen.get_tab_with_pane_mut(id) {
+                    Some(tab) => {
+                        tab.request_pane_content_size(id, cols, rows);
+                    }
+                    None => log::error!(
+                        "Failed to resize the content of pane {:?}: pane not found",
+                        id
+                    ),
+                }
+                screen.render();
+            }
+            ScreenInstruction::ToggleActivePaneMaxWidth(client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.toggle_active_pane_max_width(client_id);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::ToggleActivePaneMaxHeight(client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.toggle_active_pane_max_height(client_id);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::MaximizeActivePane(direction, client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.maximize_active_pane(client_id, direction);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::ToggleActivePaneLineWrap(client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.toggle_active_pane_line_wrap(client_id);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::SendSignalToActivePane(signal, client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.send_signal_to_active_pane(client_id, signal);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+                screen.render();
+            }
+            ScreenInstruction::InsertSyntheticLine(id, text, client_id) => {
+                match screen.get_tab_with_pane_mut(id) {
+                    Some(tab) => {
+                        tab.insert_synthetic_line(id, text, client_id);
+                    }
+                    None => {
+                        log::error!("Failed to insert a line into pane {:?}: pane not found", id)
+                    }
+                }
+                screen.render();
+            }
+            ScreenInstruction::RequestPaneBoundaries(plugin_id, client_id) => {
+                if let Some(active_tab) = screen.get_active_tab(client_id) {
+                    screen
+                        .bus
+                        .senders
+                        .send_to_plugin(PluginInstruction::Update(
+                            Some(plugin_id),
+                            Some(client_id),
+                            Event::PaneBoundaries(active_tab.boundary_segments()),
+                        ))
+                        .unwrap();
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+            }
+            ScreenInstruction::RequestPaneGeoms(plugin_id, client_id) => {
+                if let Some(active_tab) = screen.get_active_tab(client_id) {
+                    let pane_geoms = active_tab
+                        .pane_geoms()
+                        .into_iter()
+                        .map(|(pane_id, geom)| {
+                            let (id, is_plugin) = match pane_id {
+                                PaneId::Terminal(pid) => (pid as u32, false),
+                                PaneId::Plugin(pid) => (pid, true),
+                            };
+                            PaneGeomInfo {
+                                id,
+                                is_plugin,
+                                x: geom.x,
+                                y: geom.y,
+                                cols: geom.cols.as_usize(),
+                                rows: geom.rows.as_usize(),
+                                is_hidden: active_tab.is_pane_hidden(pane_id),
+                            }
+                        })
+                        .collect();
+                    screen
+                        .bus
+                        .senders
+                        .send_to_plugin(PluginInstruction::Update(
+                            Some(plugin_id),
+                            Some(client_id),
+                            Event::PaneGeoms(pane_geoms),
+                        ))
+                        .unwrap();
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+            }
             ScreenInstruction::LeftClick(point, client_id) => {
                 if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
                     active_tab.handle_left_click(&point, client_id);
@@ -1334,6 +2271,32 @@ pub(crate) fn screen_thread_main(
                 screen.update_tabs();
                 screen.render();
             }
+            ScreenInstruction::CtrlLeftClick(point, client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    if !active_tab.open_url_at(&point) {
+                        active_tab.handle_left_click(&point, client_id);
+                    }
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+
+                screen.update_tabs();
+                screen.render();
+            }
+            ScreenInstruction::AltLeftClick(point, client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.handle_left_click_with_selection_mode(
+                        &point,
+                        SelectionMode::Block,
+                        client_id,
+                    );
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+
+                screen.update_tabs();
+                screen.render();
+            }
             ScreenInstruction::RightClick(point, client_id) => {
                 if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
                     active_tab.handle_right_click(&point, client_id);
@@ -1362,6 +2325,15 @@ pub(crate) fn screen_thread_main(
 
                 screen.render();
             }
+            ScreenInstruction::ScrollSelectionAtEdge(client_id) => {
+                if let Some(active_tab) = screen.get_active_tab_mut(client_id) {
+                    active_tab.scroll_selection_at_edge(client_id);
+                } else {
+                    log::error!("Active tab not found for client id: {:?}", client_id);
+                }
+
+                screen.render();
+            }
             ScreenInstruction::Copy(client_id) => {
                 if let Some(active_tab) = screen.get_active_tab(client_id) {
                     active_tab.copy_selection(client_id);
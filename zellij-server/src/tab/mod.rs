@@ -10,37 +10,47 @@ use zellij_utils::position::{Column, Line};
 use zellij_utils::{position::Position, serde, zellij_tile};
 
 use crate::screen::CopyOptions;
+use crate::ui::boundaries::BoundarySegment;
 use crate::ui::pane_boundaries_frame::FrameParams;
 
 use self::clipboard::ClipboardProvider;
 use crate::{
     os_input_output::ServerOsApi,
     output::{CharacterChunk, Output},
+    panes::{selection::SelectionMode, LinkHandler, PaneId, PluginPane, TerminalPane},
     panes::{FloatingPanes, TiledPanes},
-    panes::{LinkHandler, PaneId, PluginPane, TerminalPane},
     pty::{ClientOrTabIndex, PtyInstruction, VteBytes},
+    screen::ScreenInstruction,
     thread_bus::ThreadSenders,
     wasm_vm::PluginInstruction,
     ClientId, ServerInstruction,
 };
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::os::unix::io::RawFd;
 use std::rc::Rc;
 use std::sync::mpsc::channel;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use std::{
     collections::{HashMap, HashSet},
+    fs,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
     str,
 };
-use zellij_tile::data::{Event, InputMode, ModeInfo, Palette, PaletteColor};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use zellij_tile::data::{Event, InputMode, ModeInfo, Palette, PaletteColor, PaneInfo, ScrollInfo};
 use zellij_utils::{
     input::{
-        command::TerminalAction,
-        layout::{Layout, Run},
+        actions::Direction,
+        command::{RunCommand, TerminalAction},
+        layout::{Direction as SplitDirection, Layout, LayoutPreset, Run},
+        options::{CloseGravity, PasteNewlines},
         parse_keys,
     },
-    pane_size::{Offset, PaneGeom, Size, SizeInPixels, Viewport},
+    pane_size::{Dimension, Offset, PaneGeom, Size, SizeInPixels, Viewport},
+    serde_yaml,
 };
 
 macro_rules! resize_pty {
@@ -57,11 +67,33 @@ macro_rules! resize_pty {
     };
 }
 
-// FIXME: This should be replaced by `RESIZE_PERCENT` at some point
+// the default minimum pane size, tabs can be configured with their own (see Tab::new)
 pub const MIN_TERMINAL_HEIGHT: usize = 5;
 pub const MIN_TERMINAL_WIDTH: usize = 5;
 
 const MAX_PENDING_VTE_EVENTS: usize = 7000;
+// how long a second attempt to close a pane with a running process counts as a confirmation
+const CLOSE_CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(3);
+
+// how often the selection scrolls while the mouse is held past the top or bottom of a pane
+const SELECTION_EDGE_SCROLL_INTERVAL: Duration = Duration::from_millis(50);
+
+// how long after a layout change it can still be undone
+const LAYOUT_OP_UNDO_TIMEOUT: Duration = Duration::from_secs(10);
+// how many layout changes we keep around to undo, older ones are dropped even if still recent
+const MAX_LAYOUT_OP_HISTORY: usize = 32;
+
+// the only urls a click hands to the system opener
+const OPENABLE_URL_SCHEMES: [&str; 3] = ["http://", "https://", "mailto:"];
+
+// a geometry-affecting change to the tab that can be undone
+enum TabOp {
+    Split {
+        new_pane_id: PaneId,
+        geoms_before: HashMap<PaneId, PaneGeom>,
+        created_at: Instant,
+    },
+}
 
 pub(crate) struct Tab {
     pub index: usize,
@@ -70,12 +102,17 @@ pub(crate) struct Tab {
     tiled_panes: TiledPanes,
     floating_panes: FloatingPanes,
     max_panes: Option<usize>,
+    reject_panes_over_max: bool, // refuse new panes at max_panes rather than closing existing ones
+    layout_op_history: Vec<TabOp>,
     viewport: Rc<RefCell<Viewport>>, // includes all non-UI panes
     display_area: Rc<RefCell<Size>>, // includes all panes (including eg. the status bar and tab bar in the default layout)
     character_cell_size: Rc<RefCell<Option<SizeInPixels>>>,
+    cursor_height_width_ratio: Option<usize>,
     os_api: Box<dyn ServerOsApi>,
     pub senders: ThreadSenders,
     synchronize_is_active: bool,
+    sync_excluded_panes: HashSet<PaneId>, // panes that do not receive synchronized input
+    pane_groups: HashMap<String, HashSet<PaneId>>, // named groups of panes input can be broadcast to
     should_clear_display_before_rendering: bool,
     mode_info: Rc<RefCell<HashMap<ClientId, ModeInfo>>>,
     default_mode_info: ModeInfo,
@@ -84,12 +121,36 @@ pub(crate) struct Tab {
     draw_pane_frames: bool,
     pending_vte_events: HashMap<RawFd, Vec<VteBytes>>,
     pub selecting_with_mouse: bool, // this is only pub for the tests TODO: remove this once we combine write_text_to_clipboard with render
+    selection_edge_scroll: Option<Position>, // where the mouse is held past the edge of the selected pane
+    selection_scroll_tick_pending: bool,
     link_handler: Rc<RefCell<LinkHandler>>,
     clipboard_provider: ClipboardProvider,
     // TODO: used only to focus the pane when the layout is loaded
     // it seems that optimization is possible using `active_panes`
     focus_pane_id: Option<PaneId>,
     copy_on_select: bool,
+    copy_from_panes: bool, // whether programs in panes can set the clipboard with OSC52
+    min_pane_width: usize,
+    min_pane_height: usize,
+    diff_panes: Option<(PaneId, PaneId)>,
+    overlay_message: Option<(String, Instant)>, // (text, expires_at)
+    pane_numbers_shown: bool, // the next key press focuses the pane with that number
+    needs_render: bool,       // a pane got output since the last render
+    pane_logs: HashMap<PaneId, BufWriter<fs::File>>, // files the raw output of panes is copied to
+    paste_newlines: PasteNewlines,
+    scrollback_copy_line_limit: usize,
+    scroll_offsets_in_plugins: HashMap<ClientId, (PaneId, usize)>, // the scroll offsets of active panes plugins were last told about
+    pane_pending_close: Option<(PaneId, Instant)>,                 // (pane, close requested at)
+    reported_silence: HashMap<PaneId, Instant>, // the last output of panes plugins were told went silent
+    silence_check_at: Option<Instant>,
+    reserved_viewport: Offset, // space at the edges of the screen kept free of panes
+    preset_layout: Option<LayoutPreset>, // the last preset the panes were arranged in
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloseResult {
+    Closed,
+    NeedsConfirmation, // a process is running in the pane, closing it again will kill it
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -143,6 +204,9 @@ pub trait Pane {
     ) -> Option<String>;
     fn render_terminal_title(&mut self, _input_mode: InputMode) -> String;
     fn update_name(&mut self, name: &str);
+    fn set_title(&mut self, title: String);
+    fn title(&self) -> String;
+    fn pane_name(&self) -> String;
     fn pid(&self) -> PaneId;
     fn reduce_height(&mut self, percent: f64);
     fn increase_height(&mut self, percent: f64);
@@ -155,6 +219,24 @@ pub trait Pane {
     fn scroll_up(&mut self, count: usize, client_id: ClientId);
     fn scroll_down(&mut self, count: usize, client_id: ClientId);
     fn clear_scroll(&mut self);
+    // clears the screen and discards the scrollback, leaving the cursor at the top left
+    fn clear_all(&mut self) {}
+    // reveals content hidden past the left or right edge of the pane, only meaningful when it has
+    // lines wider than itself
+    fn scroll_left(&mut self, _count: usize) {}
+    fn scroll_right(&mut self, _count: usize) {}
+    // columns hidden to the left of the pane by scrolling it to the right
+    fn horizontal_scroll(&self) -> usize {
+        0
+    }
+    // with line wrapping off, lines wider than the pane are clipped at its edge
+    fn set_line_wrap(&mut self, _wrap: bool) {}
+    fn line_wrap(&self) -> bool {
+        true
+    }
+    // the most lines of history kept for this pane, the oldest lines are dropped immediately if
+    // there are more
+    fn set_scrollback_limit(&mut self, _lines: usize) {}
     fn is_scrolled(&self) -> bool;
     fn active_at(&self) -> Instant;
     fn set_active_at(&mut self, instant: Instant);
@@ -163,13 +245,23 @@ pub trait Pane {
     fn cursor_shape_csi(&self) -> String {
         "\u{1b}[0 q".to_string() // default to non blinking block
     }
+    // whether the application running in the pane hid the cursor (DECTCEM)
+    fn cursor_is_hidden(&self) -> bool {
+        false
+    }
     fn contains(&self, position: &Position) -> bool {
         match self.geom_override() {
             Some(position_and_size) => position_and_size.contains(position),
             None => self.position_and_size().contains(position),
         }
     }
-    fn start_selection(&mut self, _start: &Position, _client_id: ClientId) {}
+    fn start_selection(
+        &mut self,
+        _start: &Position,
+        _selection_mode: SelectionMode,
+        _client_id: ClientId,
+    ) {
+    }
     fn update_selection(&mut self, _position: &Position, _client_id: ClientId) {}
     fn end_selection(&mut self, _end: &Position, _client_id: ClientId) {}
     fn reset_selection(&mut self) {}
@@ -241,11 +333,44 @@ pub trait Pane {
     fn min_height(&self) -> usize {
         MIN_TERMINAL_HEIGHT
     }
+    // panes are given the minimum size of their tab when they are added to it
+    fn set_min_size(&mut self, _min_width: usize, _min_height: usize) {}
     fn drain_messages_to_pty(&mut self) -> Vec<Vec<u8>> {
         // TODO: this is only relevant to terminal panes
         // we should probably refactor away from this trait at some point
         vec![]
     }
+    // whether the pane rang the bell / received output since the last call
+    fn take_bell(&mut self) -> bool {
+        false
+    }
+    fn take_activity(&mut self) -> bool {
+        false
+    }
+    // how far along (0-100) the program in the pane reported it is with OSC 9;4, if it did
+    fn progress(&self) -> Option<u8> {
+        None
+    }
+    // whether the reported progress changed since the last call
+    fn take_progress_change(&mut self) -> bool {
+        false
+    }
+    // whether the program in the pane changed its title since the last call
+    fn take_title_change(&mut self) -> bool {
+        false
+    }
+    // text the program in the pane asked to copy to the clipboard (with OSC52)
+    fn take_pending_clipboard(&mut self) -> Option<String> {
+        None
+    }
+    fn last_output_at(&self) -> Option<Instant> {
+        None
+    }
+    // how long the pane can go without output before plugins are told it went silent
+    fn silence_threshold(&self) -> Option<Duration> {
+        None
+    }
+    fn set_silence_threshold(&mut self, _threshold: Option<Duration>) {}
     fn render_full_viewport(&mut self) {}
     fn relative_position(&self, position_on_screen: &Position) -> Position {
         position_on_screen.relative_to(self.get_content_y(), self.get_content_x())
@@ -262,6 +387,75 @@ pub trait Pane {
     fn borderless(&self) -> bool;
     fn handle_right_click(&mut self, _to: &Position, _client_id: ClientId) {}
     fn mouse_mode(&self) -> bool;
+    // the alternate screen (eg. of vim or less) has no scrollback of its own
+    fn is_alternate_screen_active(&self) -> bool {
+        false
+    }
+    // plugin panes have no grid, so the text of a pane (visible_text_lines, dump, search...) is
+    // only ever found in terminal panes
+    fn visible_text_lines(&self) -> Vec<String> {
+        vec![]
+    }
+    fn set_highlighted_lines(&mut self, _highlighted_lines: HashSet<usize>) {}
+    fn insert_synthetic_line(&mut self, _text: &str) {}
+    fn toggle_raw_view(&mut self) {}
+    fn dump(&self, _include_scrollback: bool, _with_ansi: bool) -> Option<String> {
+        None
+    }
+    fn dump_screen(&self) -> String {
+        String::new()
+    }
+    fn search(&mut self, _query: &str) -> Vec<(usize, usize)> {
+        vec![]
+    }
+    fn scroll_to_search_match(&mut self, _forward: bool, _client_id: ClientId) {}
+    fn set_palette_override(&mut self, _palette: Option<Palette>) {}
+    fn palette_override(&self) -> Option<Palette> {
+        None
+    }
+    fn set_border_color(&mut self, _color: Option<PaletteColor>) {}
+    fn border_color(&self) -> Option<PaletteColor> {
+        None
+    }
+    fn set_read_only(&mut self, _read_only: bool) {}
+    fn is_read_only(&self) -> bool {
+        false
+    }
+    fn bracketed_paste_active(&self) -> bool {
+        false
+    }
+    fn url_at(&self, _position: &Position) -> Option<String> {
+        None
+    }
+    // the target of an OSC 8 hyperlink the program printed at this position
+    fn hyperlink_at(&self, _position: &Position) -> Option<String> {
+        None
+    }
+    // the process of this pane exited but the pane is held open
+    fn set_exited(&mut self, _status: i32) {}
+    fn exit_status(&self) -> Option<i32> {
+        None
+    }
+    // as reported by the process running in the pane, if it does
+    fn current_working_directory(&self) -> Option<PathBuf> {
+        None
+    }
+    fn scrollback_len(&self) -> usize {
+        0
+    }
+    // how many lines the pane is scrolled up from the bottom
+    fn scroll_offset(&self) -> usize {
+        0
+    }
+    // what this pane was started with in a layout, if anything
+    fn set_invoked_with(&mut self, _run: Option<Run>) {}
+    fn invoked_with(&self) -> Option<Run> {
+        None
+    }
+    // a process other than the shell runs in the foreground of this pane
+    fn has_running_process(&self) -> bool {
+        false
+    }
 }
 
 impl Tab {
@@ -273,16 +467,20 @@ impl Tab {
         name: String,
         display_area: Size,
         character_cell_size: Rc<RefCell<Option<SizeInPixels>>>,
+        cursor_height_width_ratio: Option<usize>,
         os_api: Box<dyn ServerOsApi>,
         senders: ThreadSenders,
         max_panes: Option<usize>,
         style: Style,
         default_mode_info: ModeInfo,
         draw_pane_frames: bool,
+        hide_single_pane_frame: bool,
         connected_clients_in_app: Rc<RefCell<HashSet<ClientId>>>,
         session_is_mirrored: bool,
         client_id: ClientId,
         copy_options: CopyOptions,
+        min_pane_width: usize,
+        min_pane_height: usize,
     ) -> Self {
         let name = if name.is_empty() {
             format!("Tab #{}", index + 1)
@@ -298,7 +496,7 @@ impl Tab {
         let connected_clients = Rc::new(RefCell::new(connected_clients));
         let mode_info = Rc::new(RefCell::new(HashMap::new()));
 
-        let tiled_panes = TiledPanes::new(
+        let mut tiled_panes = TiledPanes::new(
             display_area.clone(),
             viewport.clone(),
             connected_clients.clone(),
@@ -307,11 +505,13 @@ impl Tab {
             character_cell_size.clone(),
             session_is_mirrored,
             draw_pane_frames,
+            hide_single_pane_frame,
             default_mode_info.clone(),
             style,
             os_api.clone(),
+            senders.clone(),
         );
-        let floating_panes = FloatingPanes::new(
+        let mut floating_panes = FloatingPanes::new(
             display_area.clone(),
             viewport.clone(),
             connected_clients.clone(),
@@ -320,7 +520,10 @@ impl Tab {
             session_is_mirrored,
             default_mode_info.clone(),
             style,
+            senders.clone(),
         );
+        tiled_panes.set_min_pane_size(min_pane_width, min_pane_height);
+        floating_panes.set_min_pane_size(min_pane_width, min_pane_height);
 
         let clipboard_provider = match copy_options.command {
             Some(command) => ClipboardProvider::Command(CopyCommand::new(command)),
@@ -334,10 +537,15 @@ impl Tab {
             floating_panes,
             name,
             max_panes,
+            reject_panes_over_max: false,
+            layout_op_history: vec![],
             viewport,
             display_area,
             character_cell_size,
+            cursor_height_width_ratio,
             synchronize_is_active: false,
+            sync_excluded_panes: HashSet::new(),
+            pane_groups: HashMap::new(),
             os_api,
             senders,
             should_clear_display_before_rendering: false,
@@ -348,10 +556,28 @@ impl Tab {
             pending_vte_events: HashMap::new(),
             connected_clients,
             selecting_with_mouse: false,
+            selection_edge_scroll: None,
+            selection_scroll_tick_pending: false,
             link_handler: Rc::new(RefCell::new(LinkHandler::new())),
             clipboard_provider,
             focus_pane_id: None,
             copy_on_select: copy_options.copy_on_select,
+            copy_from_panes: copy_options.copy_from_panes,
+            min_pane_width,
+            min_pane_height,
+            diff_panes: None,
+            overlay_message: None,
+            pane_numbers_shown: false,
+            needs_render: false,
+            pane_logs: HashMap::new(),
+            paste_newlines: PasteNewlines::default(),
+            scrollback_copy_line_limit: copy_options.scrollback_line_limit,
+            scroll_offsets_in_plugins: HashMap::new(),
+            pane_pending_close: None,
+            reported_silence: HashMap::new(),
+            silence_check_at: None,
+            reserved_viewport: Offset::default(),
+            preset_layout: None,
         }
     }
 
@@ -362,6 +588,7 @@ impl Tab {
         tab_index: usize,
         client_id: ClientId,
     ) {
+        self.layout_op_history.clear();
         if self.tiled_panes.has_panes() {
             log::error!(
                 "Applying a layout to a tab with existing panes - this is not yet supported!"
@@ -404,6 +631,7 @@ impl Tab {
                     layout.pane_name.clone().unwrap_or_default(),
                 );
                 new_plugin.set_borderless(layout.borderless);
+                new_plugin.set_invoked_with(layout.run.clone());
                 self.tiled_panes
                     .add_pane_with_existing_geom(PaneId::Plugin(pid), Box::new(new_plugin));
                 set_focus_pane_id(layout, PaneId::Plugin(pid));
@@ -421,6 +649,8 @@ impl Tab {
                     self.character_cell_size.clone(),
                 );
                 new_pane.set_borderless(layout.borderless);
+                new_pane.set_invoked_with(layout.run.clone());
+                new_pane.set_os_api(self.os_api.clone());
                 self.tiled_panes
                     .add_pane_with_existing_geom(PaneId::Terminal(*pid), Box::new(new_pane));
                 set_focus_pane_id(layout, PaneId::Terminal(*pid));
@@ -436,11 +666,8 @@ impl Tab {
         }
         // FIXME: This is another hack to crop the viewport to fixed-size panes. Once you can have
         // non-fixed panes that are part of the viewport, get rid of this!
-        let display_area = {
-            let display_area = self.display_area.borrow();
-            *display_area
-        };
-        self.resize_whole_tab(display_area);
+        let screen_size = self.screen_size();
+        self.resize_whole_tab(screen_size);
         let boundary_geoms = self.tiled_panes.fixed_pane_geoms();
         for geom in boundary_geoms {
             self.offset_viewport(&geom)
@@ -532,6 +759,9 @@ impl Tab {
     pub fn remove_client(&mut self, client_id: ClientId) {
         self.focus_pane_id = None;
         self.connected_clients.borrow_mut().remove(&client_id);
+        if self.has_no_connected_clients() {
+            self.clear_all_selections();
+        }
         self.set_force_render();
     }
     pub fn drain_connected_clients(
@@ -545,6 +775,10 @@ impl Tab {
         for client_id in clients_to_drain {
             client_ids_to_mode_infos.push(self.drain_single_client(client_id));
         }
+        if self.has_no_connected_clients() {
+            // so that no stale selection is highlighted when a client attaches again
+            self.clear_all_selections();
+        }
         client_ids_to_mode_infos
     }
     pub fn drain_single_client(&mut self, client_id: ClientId) -> (ClientId, ModeInfo) {
@@ -571,9 +805,9 @@ impl Tab {
                     self.tiled_panes
                         .insert_pane(focused_floating_pane_id, floating_pane_to_embed);
                     self.should_clear_display_before_rendering = true;
+                    self.set_floating_panes_visibility(false);
                     self.tiled_panes
                         .focus_pane(focused_floating_pane_id, client_id);
-                    self.floating_panes.toggle_show_panes(false);
                 }
             }
         } else if let Some(focused_pane_id) = self.tiled_panes.focused_pane_id(client_id) {
@@ -589,7 +823,7 @@ impl Tab {
                     self.floating_panes
                         .add_pane(focused_pane_id, embedded_pane_to_float);
                     self.floating_panes.focus_pane(focused_pane_id, client_id);
-                    self.floating_panes.toggle_show_panes(true);
+                    self.set_floating_panes_visibility(true);
                 }
             }
         }
@@ -600,10 +834,10 @@ impl Tab {
         default_shell: Option<TerminalAction>,
     ) {
         if self.floating_panes.panes_are_visible() {
-            self.floating_panes.toggle_show_panes(false);
+            self.set_floating_panes_visibility(false);
             self.set_force_render();
         } else {
-            self.floating_panes.toggle_show_panes(true);
+            self.set_floating_panes_visibility(true);
             match self.floating_panes.first_floating_pane_id() {
                 Some(first_floating_pane_id) => {
                     if !self.floating_panes.active_panes_contain(&client_id) {
@@ -633,8 +867,27 @@ impl Tab {
         }
         self.set_force_render();
     }
-    pub fn new_pane(&mut self, pid: PaneId, client_id: Option<ClientId>) {
-        self.close_down_to_max_terminals();
+    // the pane remembers the command it runs so that it ends up in layouts made from this tab
+    pub fn set_pane_run_command(&mut self, pid: PaneId, command: Option<RunCommand>) {
+        let command = match command {
+            Some(command) => command,
+            None => return,
+        };
+        if let Some(pane) = self
+            .tiled_panes
+            .get_pane_mut(pid)
+            .or_else(|| self.floating_panes.get_pane_mut(pid))
+        {
+            pane.set_invoked_with(Some(Run::Command(command)));
+        }
+    }
+    // returns true if the pane was created
+    // this places the pane wherever there is the most room (or as a floating pane), panes opened
+    // by a client should go through open_pane, which splits that client's active pane
+    pub fn new_pane(&mut self, pid: PaneId, client_id: Option<ClientId>) -> bool {
+        if !self.make_room_for_new_pane(pid) {
+            return false;
+        }
         if self.floating_panes.panes_are_visible() {
             if let Some(new_pane_geom) = self.floating_panes.find_room_for_new_pane() {
                 let next_terminal_position = self.get_next_terminal_position();
@@ -649,9 +902,11 @@ impl Tab {
                         self.character_cell_size.clone(),
                     );
                     new_pane.set_content_offset(Offset::frame(1)); // floating panes always have a frame
+                    new_pane.set_os_api(self.os_api.clone());
                     resize_pty!(new_pane, self.os_api);
                     self.floating_panes.add_pane(pid, Box::new(new_pane));
                     self.floating_panes.focus_pane_for_all_clients(pid);
+                    return true;
                 }
             }
         } else {
@@ -661,7 +916,7 @@ impl Tab {
             if self.tiled_panes.has_room_for_new_pane() {
                 if let PaneId::Terminal(term_pid) = pid {
                     let next_terminal_position = self.get_next_terminal_position();
-                    let new_terminal = TerminalPane::new(
+                    let mut new_terminal = TerminalPane::new(
                         term_pid,
                         PaneGeom::default(), // the initial size will be set later
                         self.style,
@@ -670,27 +925,82 @@ impl Tab {
                         self.link_handler.clone(),
                         self.character_cell_size.clone(),
                     );
+                    new_terminal.set_os_api(self.os_api.clone());
                     self.tiled_panes.insert_pane(pid, Box::new(new_terminal));
                     self.should_clear_display_before_rendering = true;
                     if let Some(client_id) = client_id {
                         self.tiled_panes.focus_pane(pid, client_id);
                     }
+                    return true;
                 }
             }
         }
+        false
+    }
+    // returns true if the pane was created
+    // the new pane always splits the active pane of client_id: direction forces the orientation
+    // of the split, while None splits in whichever direction leaves both halves closest to square,
+    // falling back to new_pane (the largest pane) if the active one is too small to split
+    // without a client, or while floating panes are visible, this is the same as new_pane
+    // note: opening a pane used to split the largest pane rather than the active one
+    pub fn open_pane(
+        &mut self,
+        pid: PaneId,
+        client_id: Option<ClientId>,
+        direction: Option<SplitDirection>,
+    ) -> bool {
+        let client_id = match client_id {
+            Some(client_id) => client_id,
+            None => return self.new_pane(pid, None),
+        };
+        let direction = direction.or_else(|| {
+            if self.floating_panes.panes_are_visible() || self.tiled_panes.fullscreen_is_active() {
+                None
+            } else {
+                self.tiled_panes
+                    .smart_split_direction(client_id, self.cursor_height_width_ratio)
+            }
+        });
+        match direction {
+            Some(SplitDirection::Horizontal) => {
+                self.horizontal_split_with_fixed_size(pid, client_id, None)
+            }
+            Some(SplitDirection::Vertical) => {
+                self.vertical_split_with_fixed_size(pid, client_id, None)
+            }
+            None => self.new_pane(pid, Some(client_id)),
+        }
     }
-    pub fn horizontal_split(&mut self, pid: PaneId, client_id: ClientId) {
+    pub fn smart_split_active(&mut self, pid: PaneId, client_id: ClientId) {
+        self.open_pane(pid, Some(client_id), None);
+    }
+    pub fn horizontal_split(&mut self, pid: PaneId, client_id: ClientId) -> bool {
+        self.open_pane(pid, Some(client_id), Some(SplitDirection::Horizontal))
+    }
+    pub fn horizontal_split_with_fixed_size(
+        &mut self,
+        pid: PaneId,
+        client_id: ClientId,
+        fixed_size: Option<usize>,
+    ) -> bool {
+        // fixed_size: the new pane keeps this exact size when the tab is resized
         if self.floating_panes.panes_are_visible() {
-            return;
+            return false;
+        }
+        if !self.make_room_for_new_pane(pid) {
+            return false;
         }
-        self.close_down_to_max_terminals();
         if self.tiled_panes.fullscreen_is_active() {
             self.toggle_active_pane_fullscreen(client_id);
         }
-        if self.tiled_panes.can_split_pane_horizontally(client_id) {
+        if self
+            .tiled_panes
+            .can_split_pane_horizontally(client_id, fixed_size)
+        {
             if let PaneId::Terminal(term_pid) = pid {
+                let geoms_before = self.tiled_pane_geoms();
                 let next_terminal_position = self.get_next_terminal_position();
-                let new_terminal = TerminalPane::new(
+                let mut new_terminal = TerminalPane::new(
                     term_pid,
                     PaneGeom::default(), // the initial size will be set later
                     self.style,
@@ -699,25 +1009,48 @@ impl Tab {
                     self.link_handler.clone(),
                     self.character_cell_size.clone(),
                 );
-                self.tiled_panes
-                    .split_pane_horizontally(pid, Box::new(new_terminal), client_id);
+                new_terminal.set_os_api(self.os_api.clone());
+                self.tiled_panes.split_pane_horizontally(
+                    pid,
+                    Box::new(new_terminal),
+                    client_id,
+                    fixed_size,
+                );
+                self.record_split(pid, geoms_before);
                 self.should_clear_display_before_rendering = true;
                 self.tiled_panes.focus_pane(pid, client_id);
+                return true;
             }
         }
+        false
+    }
+    pub fn vertical_split(&mut self, pid: PaneId, client_id: ClientId) -> bool {
+        self.open_pane(pid, Some(client_id), Some(SplitDirection::Vertical))
     }
-    pub fn vertical_split(&mut self, pid: PaneId, client_id: ClientId) {
+    pub fn vertical_split_with_fixed_size(
+        &mut self,
+        pid: PaneId,
+        client_id: ClientId,
+        fixed_size: Option<usize>,
+    ) -> bool {
+        // fixed_size: the new pane keeps this exact size when the tab is resized
         if self.floating_panes.panes_are_visible() {
-            return;
+            return false;
+        }
+        if !self.make_room_for_new_pane(pid) {
+            return false;
         }
-        self.close_down_to_max_terminals();
         if self.tiled_panes.fullscreen_is_active() {
             self.toggle_active_pane_fullscreen(client_id);
         }
-        if self.tiled_panes.can_split_pane_vertically(client_id) {
+        if self
+            .tiled_panes
+            .can_split_pane_vertically(client_id, fixed_size)
+        {
             if let PaneId::Terminal(term_pid) = pid {
+                let geoms_before = self.tiled_pane_geoms();
                 let next_terminal_position = self.get_next_terminal_position();
-                let new_terminal = TerminalPane::new(
+                let mut new_terminal = TerminalPane::new(
                     term_pid,
                     PaneGeom::default(), // the initial size will be set later
                     self.style,
@@ -726,11 +1059,82 @@ impl Tab {
                     self.link_handler.clone(),
                     self.character_cell_size.clone(),
                 );
-                self.tiled_panes
-                    .split_pane_vertically(pid, Box::new(new_terminal), client_id);
+                new_terminal.set_os_api(self.os_api.clone());
+                self.tiled_panes.split_pane_vertically(
+                    pid,
+                    Box::new(new_terminal),
+                    client_id,
+                    fixed_size,
+                );
+                self.record_split(pid, geoms_before);
                 self.should_clear_display_before_rendering = true;
                 self.tiled_panes.focus_pane(pid, client_id);
+                return true;
+            }
+        }
+        false
+    }
+    fn tiled_pane_geoms(&self) -> HashMap<PaneId, PaneGeom> {
+        self.get_tiled_panes()
+            .map(|(pane_id, pane)| (*pane_id, pane.position_and_size()))
+            .collect()
+    }
+    fn record_split(&mut self, new_pane_id: PaneId, geoms_before: HashMap<PaneId, PaneGeom>) {
+        // ops that can no longer be undone are dropped here, otherwise the history of a
+        // long-lived tab would grow with every split
+        self.layout_op_history.retain(|op| match op {
+            TabOp::Split { created_at, .. } => created_at.elapsed() <= LAYOUT_OP_UNDO_TIMEOUT,
+        });
+        if self.layout_op_history.len() >= MAX_LAYOUT_OP_HISTORY {
+            let excess_ops = self.layout_op_history.len() + 1 - MAX_LAYOUT_OP_HISTORY;
+            self.layout_op_history.drain(..excess_ops);
+        }
+        self.layout_op_history.push(TabOp::Split {
+            new_pane_id,
+            geoms_before,
+            created_at: Instant::now(),
+        });
+    }
+    // reverses the most recent split by closing the pane it created and restoring the geometry
+    // the other panes had before it, returns false if there was nothing (recent enough) to undo
+    pub fn undo_last_layout_op(&mut self) -> bool {
+        match self.layout_op_history.pop() {
+            Some(TabOp::Split {
+                new_pane_id,
+                geoms_before,
+                created_at,
+            }) => {
+                if created_at.elapsed() > LAYOUT_OP_UNDO_TIMEOUT {
+                    // anything older than this op is too old as well
+                    self.layout_op_history.clear();
+                    return false;
+                }
+                if self.tiled_panes.get_pane(new_pane_id).is_none() {
+                    return false;
+                }
+                self.senders
+                    .send_to_pty(PtyInstruction::ClosePane(new_pane_id))
+                    .unwrap();
+                self.close_pane(new_pane_id);
+                // if other panes were closed in the meantime the old geometry no longer fits
+                let remaining_panes = self.tiled_pane_geoms();
+                if remaining_panes.len() == geoms_before.len()
+                    && geoms_before
+                        .keys()
+                        .all(|pane_id| remaining_panes.contains_key(pane_id))
+                {
+                    for (pane_id, geom) in geoms_before {
+                        if let Some(pane) = self.tiled_panes.get_pane_mut(pane_id) {
+                            pane.set_geom(geom);
+                            resize_pty!(pane, self.os_api);
+                        }
+                    }
+                }
+                self.should_clear_display_before_rendering = true;
+                self.set_force_render();
+                true
             }
+            None => false,
         }
     }
     pub fn get_active_pane(&self, client_id: ClientId) -> Option<&dyn Pane> {
@@ -742,6 +1146,88 @@ impl Tab {
             }
         })
     }
+    pub fn get_active_pane_cwd(&self, client_id: ClientId) -> Option<PathBuf> {
+        let active_pane = self.get_active_pane(client_id)?;
+        active_pane
+            .current_working_directory()
+            .or_else(|| match active_pane.pid() {
+                // not all shells report their working directory, so we fall back to the one of
+                // the process in the foreground of the terminal
+                PaneId::Terminal(fd) => self
+                    .os_api
+                    .get_foreground_process_group(fd)
+                    .and_then(|pid| self.os_api.get_cwd(pid)),
+                PaneId::Plugin(..) => None,
+            })
+    }
+    // opens another pane running the same command as the active one (in its current directory),
+    // or another instance of the same plugin, and a plain shell if we don't know what it runs
+    pub fn duplicate_active_pane(&mut self, client_id: ClientId) {
+        let invoked_with = match self.get_active_pane(client_id) {
+            Some(active_pane) => active_pane.invoked_with(),
+            None => return,
+        };
+        match invoked_with {
+            Some(Run::Plugin(run)) => {
+                if self.floating_panes.panes_are_visible()
+                    || !self.tiled_panes.has_room_for_new_pane()
+                {
+                    log::error!("No room to duplicate plugin pane {:?}", run.location);
+                    return;
+                }
+                let (pid_tx, pid_rx) = channel();
+                let pane_title = run.location.to_string();
+                self.senders
+                    .send_to_plugin(PluginInstruction::Load(
+                        pid_tx,
+                        run.clone(),
+                        self.index,
+                        client_id,
+                    ))
+                    .unwrap();
+                let pid = pid_rx.recv().unwrap();
+                if !self.make_room_for_new_pane(PaneId::Plugin(pid)) {
+                    // the pty thread unloads the plugin we just loaded
+                    return;
+                }
+                let mut new_plugin = PluginPane::new(
+                    pid,
+                    PaneGeom::default(), // the initial size will be set later
+                    self.senders.to_plugin.as_ref().unwrap().clone(),
+                    pane_title,
+                    String::new(),
+                );
+                new_plugin.set_invoked_with(Some(Run::Plugin(run)));
+                if self.tiled_panes.fullscreen_is_active() {
+                    self.tiled_panes.unset_fullscreen();
+                }
+                self.tiled_panes
+                    .insert_pane(PaneId::Plugin(pid), Box::new(new_plugin));
+                self.tiled_panes.focus_pane(PaneId::Plugin(pid), client_id);
+                self.should_clear_display_before_rendering = true;
+            }
+            Some(Run::Command(mut run_command)) => {
+                if let Some(cwd) = self.get_active_pane_cwd(client_id) {
+                    run_command.cwd = Some(cwd);
+                }
+                self.senders
+                    .send_to_pty(PtyInstruction::SpawnTerminal(
+                        Some(TerminalAction::RunCommand(run_command)),
+                        ClientOrTabIndex::ClientId(client_id),
+                    ))
+                    .unwrap();
+            }
+            None => {
+                // the pty opens the default shell in the directory of the active pane
+                self.senders
+                    .send_to_pty(PtyInstruction::SpawnTerminal(
+                        None,
+                        ClientOrTabIndex::ClientId(client_id),
+                    ))
+                    .unwrap();
+            }
+        }
+    }
     pub fn get_active_pane_mut(&mut self, client_id: ClientId) -> Option<&mut Box<dyn Pane>> {
         self.get_active_pane_id(client_id).and_then(|ap| {
             if self.floating_panes.panes_are_visible() {
@@ -780,6 +1266,7 @@ impl Tab {
             || self.floating_panes.panes_contain(&PaneId::Terminal(pid))
     }
     pub fn handle_pty_bytes(&mut self, pid: RawFd, bytes: VteBytes) {
+        self.log_pane_output(PaneId::Terminal(pid), &bytes);
         if let Some(terminal_output) = self
             .tiled_panes
             .get_pane_mut(PaneId::Terminal(pid))
@@ -800,6 +1287,40 @@ impl Tab {
         }
         self.process_pty_bytes(pid, bytes);
     }
+    pub fn start_pane_logging(&mut self, pane_id: PaneId, path: PathBuf) {
+        // the raw pty bytes are appended to the file, so it can be replayed with eg. cat
+        if !self.get_all_pane_ids().contains(&pane_id) {
+            log::error!("Failed to find pane with id {:?} to log", pane_id);
+            return;
+        }
+        match fs::OpenOptions::new().create(true).append(true).open(&path) {
+            Ok(file) => {
+                if let Some(mut previous_log) = self.pane_logs.insert(pane_id, BufWriter::new(file))
+                {
+                    if let Err(e) = previous_log.flush() {
+                        log::error!("Failed to flush pane log: {}", e);
+                    }
+                }
+            }
+            Err(e) => log::error!("Failed to open pane log {:?}: {}", path, e),
+        }
+    }
+    pub fn stop_pane_logging(&mut self, pane_id: PaneId) {
+        // dropping the writer closes the file
+        if let Some(mut pane_log) = self.pane_logs.remove(&pane_id) {
+            if let Err(e) = pane_log.flush() {
+                log::error!("Failed to flush pane log: {}", e);
+            }
+        }
+    }
+    fn log_pane_output(&mut self, pane_id: PaneId, bytes: &[u8]) {
+        if let Some(pane_log) = self.pane_logs.get_mut(&pane_id) {
+            if let Err(e) = pane_log.write_all(bytes) {
+                log::error!("Failed to write to pane log, stopping it: {}", e);
+                self.pane_logs.remove(&pane_id);
+            }
+        }
+    }
     pub fn process_pending_vte_events(&mut self, pid: RawFd) {
         if let Some(pending_vte_events) = self.pending_vte_events.get_mut(&pid) {
             let vte_events: Vec<VteBytes> = pending_vte_events.drain(..).collect();
@@ -815,19 +1336,62 @@ impl Tab {
             .or_else(|| self.floating_panes.get_pane_mut(PaneId::Terminal(pid)))
         {
             terminal_output.handle_pty_bytes(bytes);
+            self.needs_render = true;
+            let title_changed = terminal_output.take_title_change();
             let messages_to_pty = terminal_output.drain_messages_to_pty();
+            let pending_clipboard = terminal_output.take_pending_clipboard();
             for message in messages_to_pty {
                 self.write_to_pane_id(message, PaneId::Terminal(pid));
             }
+            if let Some(pending_clipboard) = pending_clipboard {
+                if self.copy_from_panes {
+                    self.write_selection_to_clipboard(&pending_clipboard);
+                } else {
+                    log::info!("Ignoring a clipboard copy from a pane, copy_from_panes is off");
+                }
+            }
+            if title_changed {
+                self.report_pane_title_change(PaneId::Terminal(pid));
+            }
         }
     }
     pub fn write_to_terminals_on_current_tab(&mut self, input_bytes: Vec<u8>) {
-        let pane_ids = self.get_static_and_floating_pane_ids();
-        pane_ids.iter().for_each(|&pane_id| {
+        for pane_id in self.synchronized_pane_ids() {
             self.write_to_pane_id(input_bytes.clone(), pane_id);
-        });
+        }
+    }
+    pub fn paste_to_terminals_on_current_tab(&mut self, text: &str) {
+        // like paste_to_active_terminal, each pane gets the paste bracketed if it asked for it
+        for pane_id in self.synchronized_pane_ids() {
+            let bracketed_paste_active = self
+                .tiled_panes
+                .get_pane(pane_id)
+                .or_else(|| self.floating_panes.get_pane(pane_id))
+                .map(|pane| pane.bracketed_paste_active())
+                .unwrap_or(false);
+            let input_bytes = bracket_paste(text, bracketed_paste_active);
+            let input_bytes = normalize_pasted_newlines(&input_bytes, self.paste_newlines);
+            self.write_to_pane_id(input_bytes, pane_id);
+        }
+    }
+    fn synchronized_pane_ids(&self) -> Vec<PaneId> {
+        // plugins do not receive synchronized input, they only get keys when they're focused
+        self.get_static_and_floating_pane_ids()
+            .into_iter()
+            .filter(|pane_id| matches!(pane_id, PaneId::Terminal(_)))
+            .filter(|pane_id| !self.sync_excluded_panes.contains(pane_id))
+            .filter(|pane_id| !self.pane_is_read_only(*pane_id))
+            .collect()
     }
     pub fn write_to_active_terminal(&mut self, input_bytes: Vec<u8>, client_id: ClientId) {
+        if self.pane_numbers_shown {
+            // any key hides the numbers, a digit also focuses that pane
+            self.hide_pane_numbers();
+            if let [digit @ b'1'..=b'9'] = input_bytes[..] {
+                self.focus_pane_by_index((digit - b'1') as usize);
+            }
+            return;
+        }
         let pane_id = if self.floating_panes.panes_are_visible() {
             self.floating_panes
                 .get_active_pane_id(client_id)
@@ -835,8 +1399,29 @@ impl Tab {
         } else {
             self.tiled_panes.get_active_pane_id(client_id).unwrap()
         };
+        if self.pane_is_read_only(pane_id) {
+            return;
+        }
         self.write_to_pane_id(input_bytes, pane_id);
     }
+    pub fn write_paste_to_active_terminal(&mut self, input_bytes: Vec<u8>, client_id: ClientId) {
+        // unlike typed input, pasted text often comes with foreign line endings (eg. \r\n) that
+        // make shells execute lines twice or print ^M, so we normalize them here
+        let input_bytes = normalize_pasted_newlines(&input_bytes, self.paste_newlines);
+        self.write_to_active_terminal(input_bytes, client_id);
+    }
+    pub fn paste_to_active_terminal(&mut self, text: &str, client_id: ClientId) {
+        // applications that enabled bracketed paste know not to execute pasted lines as they come
+        let bracketed_paste_active = self
+            .get_active_pane(client_id)
+            .map(|pane| pane.bracketed_paste_active())
+            .unwrap_or(false);
+        let input_bytes = bracket_paste(text, bracketed_paste_active);
+        self.write_paste_to_active_terminal(input_bytes, client_id);
+    }
+    pub fn set_paste_newlines(&mut self, paste_newlines: PasteNewlines) {
+        self.paste_newlines = paste_newlines;
+    }
     pub fn write_to_terminal_at(&mut self, input_bytes: Vec<u8>, position: &Position) {
         if self.floating_panes.panes_are_visible() {
             let pane_id = self.floating_panes.get_pane_id_at(position, false);
@@ -858,6 +1443,17 @@ impl Tab {
                     .floating_panes
                     .get(&pane_id)
                     .unwrap_or_else(|| self.tiled_panes.get_pane(pane_id).unwrap());
+                if active_terminal.exit_status().is_some() {
+                    // there's no process left to write to, the only thing we listen to is the
+                    // close key
+                    if input_bytes == b"\r" || input_bytes == b"\n" {
+                        self.close_pane(pane_id);
+                        self.senders
+                            .send_to_pty(PtyInstruction::ClosePane(pane_id))
+                            .unwrap();
+                    }
+                    return;
+                }
                 let adjusted_input = active_terminal.adjust_input_to_terminal(input_bytes);
                 if let Err(e) = self
                     .os_api
@@ -902,12 +1498,38 @@ impl Tab {
                 (x, y)
             })
     }
+    pub fn active_pane_has_cursor(&self, client_id: ClientId) -> bool {
+        !self.active_pane_cursor_is_hidden(client_id)
+            && self
+                .get_active_terminal_cursor_position(client_id)
+                .is_some()
+    }
+    pub fn active_pane_cursor_is_hidden(&self, client_id: ClientId) -> bool {
+        self.get_active_pane(client_id)
+            .map(|pane| pane.cursor_is_hidden())
+            .unwrap_or(false)
+    }
     pub fn toggle_active_pane_fullscreen(&mut self, client_id: ClientId) {
         if self.floating_panes.panes_are_visible() {
             return;
         }
         self.tiled_panes.toggle_active_pane_fullscreen(client_id);
     }
+    pub fn toggle_active_pane_max_width(&mut self, client_id: ClientId) {
+        if self.floating_panes.panes_are_visible() {
+            return;
+        }
+        self.tiled_panes.toggle_active_pane_max_width(client_id);
+        // the hidden panes may leave parts of the tab that no pane covers
+        self.should_clear_display_before_rendering = true;
+    }
+    pub fn toggle_active_pane_max_height(&mut self, client_id: ClientId) {
+        if self.floating_panes.panes_are_visible() {
+            return;
+        }
+        self.tiled_panes.toggle_active_pane_max_height(client_id);
+        self.should_clear_display_before_rendering = true;
+    }
     pub fn is_fullscreen_active(&self) -> bool {
         self.tiled_panes.fullscreen_is_active()
     }
@@ -923,6 +1545,43 @@ impl Tab {
     }
     pub fn toggle_sync_panes_is_active(&mut self) {
         self.synchronize_is_active = !self.synchronize_is_active;
+        if !self.synchronize_is_active {
+            self.sync_excluded_panes.clear();
+        }
+    }
+    pub fn set_pane_sync_excluded(&mut self, id: PaneId, excluded: bool) {
+        if excluded {
+            self.sync_excluded_panes.insert(id);
+        } else {
+            self.sync_excluded_panes.remove(&id);
+        }
+    }
+    pub fn add_pane_to_group(&mut self, group: &str, id: PaneId) {
+        if self.get_all_pane_ids().contains(&id) {
+            self.pane_groups
+                .entry(group.to_owned())
+                .or_insert_with(HashSet::new)
+                .insert(id);
+        }
+    }
+    pub fn remove_pane_from_group(&mut self, group: &str, id: PaneId) {
+        if let Some(pane_ids) = self.pane_groups.get_mut(group) {
+            pane_ids.remove(&id);
+            if pane_ids.is_empty() {
+                self.pane_groups.remove(group);
+            }
+        }
+    }
+    pub fn write_to_group(&mut self, group: &str, input_bytes: Vec<u8>) {
+        let pane_ids: Vec<PaneId> = match self.pane_groups.get(group) {
+            Some(pane_ids) => pane_ids.iter().copied().collect(),
+            None => return,
+        };
+        for pane_id in pane_ids {
+            if !self.pane_is_read_only(pane_id) {
+                self.write_to_pane_id(input_bytes.clone(), pane_id);
+            }
+        }
     }
     pub fn mark_active_pane_for_rerender(&mut self, client_id: ClientId) {
         if let Some(active_pane) = self.get_active_pane_mut(client_id) {
@@ -943,79 +1602,383 @@ impl Tab {
                 .unwrap();
         }
     }
-    pub fn render(&mut self, output: &mut Output, overlay: Option<String>) {
-        let connected_clients: HashSet<ClientId> =
+    fn report_pane_title_change(&self, pane_id: PaneId) {
+        // the title is part of what plugins are told about the active pane
+        let connected_clients: Vec<ClientId> =
             { self.connected_clients.borrow().iter().copied().collect() };
-        if connected_clients.is_empty() || !self.tiled_panes.has_active_panes() {
-            return;
-        }
-        self.update_active_panes_in_pty_thread();
-
-        let floating_panes_stack = self.floating_panes.stack();
-        output.add_clients(
-            &connected_clients,
-            self.link_handler.clone(),
-            floating_panes_stack,
-        );
-
-        self.hide_cursor_and_clear_display_as_needed(output);
-        self.tiled_panes
-            .render(output, self.floating_panes.panes_are_visible());
-        if self.floating_panes.panes_are_visible() && self.floating_panes.has_active_panes() {
-            self.floating_panes.render(output);
-        }
-
-        // FIXME: Once clients can be distinguished
-        if let Some(overlay_vte) = &overlay {
-            output.add_post_vte_instruction_to_multiple_clients(
-                connected_clients.iter().copied(),
-                overlay_vte,
-            );
+        for client_id in connected_clients {
+            if self.get_active_pane_id(client_id) != Some(pane_id) {
+                continue;
+            }
+            if let Some(active_pane) = self.get_active_pane(client_id) {
+                report_active_pane(&self.senders, client_id, active_pane);
+            }
         }
-
-        self.render_cursor(output);
     }
-    fn hide_cursor_and_clear_display_as_needed(&mut self, output: &mut Output) {
-        let hide_cursor = "\u{1b}[?25l";
+    fn set_floating_panes_visibility(&mut self, should_show_floating_panes: bool) {
+        // showing or hiding the floating panes changes the active pane of every client
         let connected_clients: Vec<ClientId> =
             { self.connected_clients.borrow().iter().copied().collect() };
-        output.add_pre_vte_instruction_to_multiple_clients(
-            connected_clients.iter().copied(),
-            hide_cursor,
-        );
-        if self.should_clear_display_before_rendering {
-            let clear_display = "\u{1b}[2J";
+        let active_pane_ids: Vec<Option<PaneId>> = connected_clients
+            .iter()
+            .map(|client_id| self.get_active_pane_id(*client_id))
+            .collect();
+        self.floating_panes
+            .toggle_show_panes(should_show_floating_panes);
+        for (client_id, active_pane_id) in connected_clients.into_iter().zip(active_pane_ids) {
+            if self.get_active_pane_id(client_id) == active_pane_id {
+                continue;
+            }
+            if let Some(active_pane) = self.get_active_pane(client_id) {
+                report_active_pane(&self.senders, client_id, active_pane);
+            }
+        }
+    }
+    pub fn update_pane_activity_in_plugins(&mut self) {
+        // like focus changes, we check for these once per render so that a busy pane sends at
+        // most one event per frame, and only panes no client is focused on are reported
+        // this is called by the screen for every tab, since tabs no client is looking at don't
+        // render
+        let focused_panes: HashSet<PaneId> = {
+            let connected_clients: Vec<ClientId> =
+                { self.connected_clients.borrow().iter().copied().collect() };
+            connected_clients
+                .iter()
+                .filter_map(|client_id| self.get_active_pane_id(*client_id))
+                .collect()
+        };
+        let pane_ids: Vec<PaneId> = self
+            .tiled_panes
+            .get_panes()
+            .chain(self.floating_panes.get_panes())
+            .map(|(pane_id, _)| *pane_id)
+            .collect();
+        for pane_id in pane_ids {
+            let pane = match self
+                .tiled_panes
+                .get_pane_mut(pane_id)
+                .or_else(|| self.floating_panes.get_pane_mut(pane_id))
+            {
+                Some(pane) => pane,
+                None => continue,
+            };
+            // we take these either way so that output in a focused pane is not reported later
+            let rang_bell = pane.take_bell();
+            let had_activity = pane.take_activity();
+            // progress is reported for focused panes too, eg. for a progress indicator
+            let progress_changed = pane.take_progress_change();
+            let is_focused = focused_panes.contains(&pane_id);
+            if !progress_changed && (is_focused || !(rang_bell || had_activity)) {
+                continue;
+            }
+            let pane_info = pane_info(pane_id, pane.as_ref());
+            if progress_changed {
+                self.senders
+                    .send_to_plugin(PluginInstruction::Update(
+                        None,
+                        None,
+                        Event::PaneProgress(pane_info.clone(), pane.progress()),
+                    ))
+                    .unwrap();
+            }
+            if is_focused {
+                continue;
+            }
+            if rang_bell {
+                self.senders
+                    .send_to_plugin(PluginInstruction::Update(
+                        None,
+                        None,
+                        Event::PaneBell(pane_info.clone()),
+                    ))
+                    .unwrap();
+            }
+            if had_activity {
+                self.senders
+                    .send_to_plugin(PluginInstruction::Update(
+                        None,
+                        None,
+                        Event::PaneActivity(pane_info),
+                    ))
+                    .unwrap();
+            }
+        }
+    }
+    fn update_scroll_info_in_plugins(&mut self) {
+        let connected_clients: Vec<ClientId> =
+            { self.connected_clients.borrow().iter().copied().collect() };
+        for client_id in connected_clients {
+            let (active_pane_id, scroll_info) = match (
+                self.get_active_pane_id(client_id),
+                self.active_pane_scroll_info(client_id),
+            ) {
+                (Some(active_pane_id), Some(scroll_info)) => (active_pane_id, scroll_info),
+                _ => continue,
+            };
+            // the scrollback grows with every line of output, which is not a scroll
+            let scroll_offset = (active_pane_id, scroll_info.offset);
+            if self.scroll_offsets_in_plugins.get(&client_id) == Some(&scroll_offset) {
+                continue;
+            }
+            self.senders
+                .send_to_plugin(PluginInstruction::Update(
+                    None,
+                    Some(client_id),
+                    Event::ActivePaneScrolled(scroll_info),
+                ))
+                .unwrap();
+            self.scroll_offsets_in_plugins
+                .insert(client_id, scroll_offset);
+        }
+    }
+    pub fn active_pane_scroll_info(&self, client_id: ClientId) -> Option<ScrollInfo> {
+        self.get_active_pane(client_id)
+            .map(|active_pane| ScrollInfo {
+                offset: active_pane.scroll_offset(),
+                scrollback_len: active_pane.scrollback_len(),
+            })
+    }
+    pub fn needs_render(&self) -> bool {
+        self.needs_render
+    }
+    pub fn render(&mut self, output: &mut Output, overlay: Option<String>) {
+        self.needs_render = false;
+        let connected_clients: HashSet<ClientId> =
+            { self.connected_clients.borrow().iter().copied().collect() };
+        if connected_clients.is_empty() || !self.tiled_panes.has_active_panes() {
+            return;
+        }
+        self.update_active_panes_in_pty_thread();
+        self.update_scroll_info_in_plugins();
+
+        let floating_panes_stack = self.floating_panes.stack();
+        output.add_clients(
+            &connected_clients,
+            self.link_handler.clone(),
+            floating_panes_stack,
+        );
+
+        self.clear_expired_overlay_message();
+        self.hide_cursor_and_clear_display_as_needed(output);
+        self.update_diff_highlights(false);
+        self.tiled_panes
+            .render(output, self.floating_panes.panes_are_visible());
+        if self.floating_panes.panes_are_visible() && self.floating_panes.has_active_panes() {
+            self.floating_panes.render(output);
+        }
+        if let Some(pane_numbers_vte) = self.render_pane_numbers() {
+            output.add_post_vte_instruction_to_multiple_clients(
+                connected_clients.iter().copied(),
+                &pane_numbers_vte,
+            );
+        }
+        if let Some(overlay_message_vte) = self.render_overlay_message() {
+            output.add_post_vte_instruction_to_multiple_clients(
+                connected_clients.iter().copied(),
+                &overlay_message_vte,
+            );
+        }
+
+        // FIXME: Once clients can be distinguished
+        if let Some(overlay_vte) = &overlay {
+            output.add_post_vte_instruction_to_multiple_clients(
+                connected_clients.iter().copied(),
+                overlay_vte,
+            );
+        }
+
+        self.render_cursor(output);
+    }
+    pub fn show_overlay_message(&mut self, text: String, duration: Duration) {
+        self.overlay_message = Some((text, Instant::now() + duration));
+        // render again so that the expired message gets cleared
+        self.senders
+            .send_to_screen_after(ScreenInstruction::Render, duration);
+    }
+    fn clear_expired_overlay_message(&mut self) {
+        let message_expired = self
+            .overlay_message
+            .as_ref()
+            .map(|(_, expires_at)| Instant::now() >= *expires_at)
+            .unwrap_or(false);
+        if message_expired {
+            self.overlay_message = None;
+            // we force render so that the panes under the message are drawn over it
+            self.set_force_render();
+        }
+    }
+    fn render_overlay_message(&self) -> Option<String> {
+        let (text, _) = self.overlay_message.as_ref()?;
+        let viewport = self.viewport.borrow();
+        let max_width = viewport.cols.saturating_sub(2); // one space of padding on each side
+        let mut text_width = 0;
+        let text: String = text
+            .chars()
+            .take_while(|character| {
+                text_width += character.width().unwrap_or(0);
+                text_width <= max_width
+            })
+            .collect();
+        if text.is_empty() {
+            return None;
+        }
+        let text_width = text.width() + 2;
+        let x = viewport.x + (viewport.cols.saturating_sub(text_width) / 2);
+        let y = viewport.y + (viewport.rows / 2);
+        Some(format!(
+            "\u{1b}[{};{}H\u{1b}[m\u{1b}[7m {} \u{1b}[m",
+            y + 1,
+            x + 1,
+            text
+        ))
+    }
+    pub fn show_pane_numbers(&mut self) {
+        if self.tiled_panes.fullscreen_is_active() || self.floating_panes.panes_are_visible() {
+            // focus_pane_by_index only focuses tiled panes outside of fullscreen
+            return;
+        }
+        self.pane_numbers_shown = true;
+    }
+    pub fn hide_pane_numbers(&mut self) {
+        if self.pane_numbers_shown {
+            self.pane_numbers_shown = false;
+            // so that the panes are drawn over the numbers
+            self.set_force_render();
+        }
+    }
+    fn render_pane_numbers(&self) -> Option<String> {
+        // numbers start at 1 so that they match the keys on the keyboard, only the first 9 panes
+        // can be focused this way
+        if !self.pane_numbers_shown {
+            return None;
+        }
+        let mut vte_output = String::new();
+        for (index, geom) in self
+            .tiled_panes
+            .selectable_pane_geoms_in_order()
+            .iter()
+            .take(9)
+            .enumerate()
+        {
+            let x = geom.x + (geom.cols.as_usize().saturating_sub(3) / 2);
+            let y = geom.y + (geom.rows.as_usize() / 2);
+            vte_output.push_str(&format!(
+                "\u{1b}[{};{}H\u{1b}[m\u{1b}[7m {} \u{1b}[m",
+                y + 1,
+                x + 1,
+                index + 1
+            ));
+        }
+        Some(vte_output)
+    }
+    pub fn set_diff_panes(&mut self, diff_panes: Option<(PaneId, PaneId)>) {
+        if let Some((first_pane_id, second_pane_id)) = self.diff_panes.take() {
+            self.set_pane_highlighted_lines(first_pane_id, HashSet::new());
+            self.set_pane_highlighted_lines(second_pane_id, HashSet::new());
+        }
+        self.diff_panes = diff_panes;
+        self.update_diff_highlights(true);
+    }
+    pub fn toggle_diff_pane(&mut self, client_id: ClientId, direction: Direction) {
+        if self.diff_panes.is_some() {
+            self.set_diff_panes(None);
+            return;
+        }
+        if let Some(active_pane_id) = self.tiled_panes.get_active_pane_id(client_id) {
+            if let Some(neighbour_pane_id) = self
+                .tiled_panes
+                .next_selectable_pane_id_in_direction(client_id, &direction)
+            {
+                self.set_diff_panes(Some((active_pane_id, neighbour_pane_id)));
+            }
+        }
+    }
+    fn update_diff_highlights(&mut self, force: bool) {
+        let (first_pane_id, second_pane_id) = match self.diff_panes {
+            Some(diff_panes) => diff_panes,
+            None => return,
+        };
+        let first_pane = self
+            .tiled_panes
+            .get_pane(first_pane_id)
+            .or_else(|| self.floating_panes.get_pane(first_pane_id));
+        let second_pane = self
+            .tiled_panes
+            .get_pane(second_pane_id)
+            .or_else(|| self.floating_panes.get_pane(second_pane_id));
+        let (first_pane_lines, second_pane_lines) = match (first_pane, second_pane) {
+            (Some(first_pane), Some(second_pane)) => {
+                if !force && !first_pane.should_render() && !second_pane.should_render() {
+                    // content did not change since we last compared
+                    return;
+                }
+                line_diff(
+                    &first_pane.visible_text_lines(),
+                    &second_pane.visible_text_lines(),
+                )
+            }
+            _ => {
+                // one of the panes was closed, there's nothing to compare anymore
+                self.set_diff_panes(None);
+                return;
+            }
+        };
+        self.set_pane_highlighted_lines(first_pane_id, first_pane_lines);
+        self.set_pane_highlighted_lines(second_pane_id, second_pane_lines);
+    }
+    fn set_pane_highlighted_lines(&mut self, pane_id: PaneId, highlighted_lines: HashSet<usize>) {
+        if let Some(pane) = self
+            .tiled_panes
+            .get_pane_mut(pane_id)
+            .or_else(|| self.floating_panes.get_pane_mut(pane_id))
+        {
+            pane.set_highlighted_lines(highlighted_lines);
+        }
+    }
+    fn hide_cursor_and_clear_display_as_needed(&mut self, output: &mut Output) {
+        let hide_cursor = "\u{1b}[?25l";
+        let connected_clients: Vec<ClientId> =
+            { self.connected_clients.borrow().iter().copied().collect() };
+        output.add_pre_vte_instruction_to_multiple_clients(
+            connected_clients.iter().copied(),
+            hide_cursor,
+        );
+        if self.should_clear_display_before_rendering {
+            let clear_display = "\u{1b}[2J";
             output.add_pre_vte_instruction_to_multiple_clients(
                 connected_clients.iter().copied(),
                 clear_display,
             );
             self.should_clear_display_before_rendering = false;
+            self.tiled_panes.set_boundaries_dirty();
         }
     }
     fn render_cursor(&self, output: &mut Output) {
         let connected_clients: Vec<ClientId> =
             { self.connected_clients.borrow().iter().copied().collect() };
         for client_id in connected_clients {
-            match self.get_active_terminal_cursor_position(client_id) {
-                Some((cursor_position_x, cursor_position_y)) => {
-                    let show_cursor = "\u{1b}[?25h";
-                    let change_cursor_shape = self
-                        .get_active_pane(client_id)
-                        .map(|ap| ap.cursor_shape_csi())
-                        .unwrap_or_default();
-                    let goto_cursor_position = &format!(
-                        "\u{1b}[{};{}H\u{1b}[m{}",
-                        cursor_position_y + 1,
-                        cursor_position_x + 1,
-                        change_cursor_shape
-                    ); // goto row/col
-                    output.add_post_vte_instruction_to_client(client_id, show_cursor);
-                    output.add_post_vte_instruction_to_client(client_id, goto_cursor_position);
-                }
-                None => {
-                    let hide_cursor = "\u{1b}[?25l";
-                    output.add_post_vte_instruction_to_client(client_id, hide_cursor);
-                }
+            if !self.active_pane_has_cursor(client_id) {
+                // hidden by the application (DECTCEM) or scrolled out of view
+                let hide_cursor = "\u{1b}[?25l";
+                output.add_post_vte_instruction_to_client(client_id, hide_cursor);
+                continue;
+            }
+            if let Some((cursor_position_x, cursor_position_y)) =
+                self.get_active_terminal_cursor_position(client_id)
+            {
+                let show_cursor = "\u{1b}[?25h";
+                let change_cursor_shape = self
+                    .get_active_pane(client_id)
+                    .map(|ap| ap.cursor_shape_csi())
+                    .unwrap_or_default();
+                let goto_cursor_position = &format!(
+                    "\u{1b}[{};{}H\u{1b}[m{}",
+                    cursor_position_y + 1,
+                    cursor_position_x + 1,
+                    change_cursor_shape
+                ); // goto row/col
+                output.add_post_vte_instruction_to_client(client_id, show_cursor);
+                output.add_post_vte_instruction_to_client(client_id, goto_cursor_position);
             }
         }
     }
@@ -1026,23 +1989,7 @@ impl Tab {
         self.get_tiled_panes().filter(|(_, p)| p.selectable())
     }
     fn get_next_terminal_position(&self) -> usize {
-        let tiled_panes_count = self
-            .tiled_panes
-            .get_panes()
-            .filter(|(k, _)| match k {
-                PaneId::Plugin(_) => false,
-                PaneId::Terminal(_) => true,
-            })
-            .count();
-        let floating_panes_count = self
-            .floating_panes
-            .get_panes()
-            .filter(|(k, _)| match k {
-                PaneId::Plugin(_) => false,
-                PaneId::Terminal(_) => true,
-            })
-            .count();
-        tiled_panes_count + floating_panes_count + 1
+        self.get_terminal_pane_ids().len() + 1
     }
     pub fn has_selectable_panes(&self) -> bool {
         let selectable_tiled_panes = self.tiled_panes.get_panes().filter(|(_, p)| p.selectable());
@@ -1057,10 +2004,272 @@ impl Tab {
         selectable_tiled_panes.count() > 0
     }
     pub fn resize_whole_tab(&mut self, new_screen_size: Size) {
-        self.floating_panes.resize(new_screen_size);
-        self.tiled_panes.resize(new_screen_size);
+        // panes are laid out in what is left of the screen once the reserved space is taken out
+        let reserved = self.reserved_viewport;
+        let new_display_area = Size {
+            rows: new_screen_size
+                .rows
+                .saturating_sub(reserved.top + reserved.bottom),
+            cols: new_screen_size
+                .cols
+                .saturating_sub(reserved.left + reserved.right),
+        };
+        self.floating_panes.resize(new_display_area);
+        self.tiled_panes.resize(new_display_area);
+        self.should_clear_display_before_rendering = true;
+    }
+    fn screen_size(&self) -> Size {
+        let display_area = *self.display_area.borrow();
+        Size {
+            rows: display_area.rows + self.reserved_viewport.top + self.reserved_viewport.bottom,
+            cols: display_area.cols + self.reserved_viewport.left + self.reserved_viewport.right,
+        }
+    }
+    // keeps space at the edges of the screen free of panes, eg. for UI drawn by an embedder
+    pub fn reserve_viewport(&mut self, top: usize, bottom: usize, left: usize, right: usize) {
+        let reserved_viewport = Offset {
+            top,
+            bottom,
+            left,
+            right,
+        };
+        if reserved_viewport == self.reserved_viewport {
+            return;
+        }
+        let screen_size = self.screen_size();
+        if top + bottom + self.min_pane_height > screen_size.rows
+            || left + right + self.min_pane_width > screen_size.cols
+        {
+            log::error!(
+                "Not enough room to reserve {:?} of a {:?} screen",
+                reserved_viewport,
+                screen_size
+            );
+            return;
+        }
+        if self.tiled_panes.fullscreen_is_active() {
+            self.tiled_panes.unset_fullscreen();
+        }
+        let offset_x = left as isize - self.reserved_viewport.left as isize;
+        let offset_y = top as isize - self.reserved_viewport.top as isize;
+        self.tiled_panes.offset_panes(offset_x, offset_y);
+        self.floating_panes.offset_panes(offset_x, offset_y);
+        self.reserved_viewport = reserved_viewport;
+        self.resize_whole_tab(screen_size);
+        self.set_force_render();
+    }
+    fn is_position_in_reserved_viewport(&self, point: &Position) -> bool {
+        let screen_size = self.screen_size();
+        let reserved = self.reserved_viewport;
+        let line = point.line.0;
+        let column = point.column.0;
+        line < reserved.top as isize
+            || line >= (screen_size.rows - reserved.bottom) as isize
+            || column < reserved.left
+            || column >= screen_size.cols - reserved.right
+    }
+    // a layout that recreates the current tiled panes when applied to a new tab, floating panes
+    // are not included
+    pub fn snapshot_layout(&self) -> Option<Layout> {
+        let layout = self.tiled_panes.snapshot_layout();
+        if layout.is_none() {
+            log::error!("Failed to create a layout from the panes in this tab");
+        }
+        layout
+    }
+    pub fn dump_layout_to_file(&mut self, path: &Path) {
+        let layout = match self.snapshot_layout() {
+            Some(layout) => layout,
+            None => return,
+        };
+        let layout = match serde_yaml::to_string(&layout) {
+            Ok(layout) => layout,
+            Err(e) => {
+                log::error!("Failed to serialize the layout of this tab: {}", e);
+                return;
+            }
+        };
+        let message = match fs::write(path, layout) {
+            Ok(()) => format!("Layout dumped to {}", path.display()),
+            Err(e) => {
+                log::error!("Failed to dump layout to {}: {}", path.display(), e);
+                format!("Failed to dump layout to {}", path.display())
+            }
+        };
+        self.show_overlay_message(message, Duration::from_secs(3));
+    }
+    pub fn boundary_segments(&self) -> Vec<BoundarySegment> {
+        self.tiled_panes.boundary_segments()
+    }
+    pub fn apply_preset_layout(&mut self, preset: LayoutPreset, client_id: ClientId) {
+        if !self.tiled_panes.apply_preset_layout(preset, client_id) {
+            log::error!("Not enough room to arrange the panes in {:?}", preset);
+            return;
+        }
+        self.preset_layout = Some(preset);
+        let screen_size = self.screen_size();
+        self.resize_whole_tab(screen_size);
+        self.set_force_render();
+    }
+    // arranges the panes in the preset after the one they were last arranged in
+    pub fn swap_layout(&mut self, client_id: ClientId) {
+        let preset = self
+            .preset_layout
+            .map(|preset| preset.next())
+            .unwrap_or(LayoutPreset::EvenHorizontal);
+        self.apply_preset_layout(preset, client_id);
+    }
+    pub fn equalize_panes(&mut self) {
+        self.tiled_panes.equalize_panes();
+        self.set_force_render();
+    }
+    pub fn rebalance(&mut self) {
+        self.tiled_panes.rebalance();
         self.should_clear_display_before_rendering = true;
     }
+    pub fn resize_active_pane_by(
+        &mut self,
+        client_id: ClientId,
+        direction: Direction,
+        percent: f64,
+    ) -> f64 {
+        // returns the amount (in percent) we actually managed to resize by, so that the caller can
+        // retry with the remainder
+        // TODO: floating panes are resized in fixed steps, we only support tiled panes here
+        if self.floating_panes.panes_are_visible() {
+            return 0.0;
+        }
+        self.tiled_panes
+            .resize_active_pane_by(client_id, direction, percent)
+    }
+    // like resize_active_pane_by, for any tiled pane (eg. for plugins arranging panes), panes are
+    // never made smaller than their minimum size
+    pub fn resize_pane_with_id(
+        &mut self,
+        pane_id: PaneId,
+        direction: Direction,
+        percent: f64,
+    ) -> f64 {
+        if self.tiled_panes.fullscreen_is_active() || self.tiled_panes.get_pane(pane_id).is_none() {
+            return 0.0;
+        }
+        if !percent.is_finite() || percent <= 0.0 {
+            log::error!(
+                "Failed to resize pane {:?}: invalid percent {}",
+                pane_id,
+                percent
+            );
+            return 0.0;
+        }
+        let resized_by = self
+            .tiled_panes
+            .resize_pane_by(pane_id, direction, percent.min(100.0));
+        self.set_force_render();
+        resized_by
+    }
+    pub fn request_pane_content_size(
+        &mut self,
+        pane_id: PaneId,
+        cols: usize,
+        rows: usize,
+    ) -> Option<(usize, usize)> {
+        // sizes the pane so that its content area (inside the frame) is cols x rows, as closely
+        // as the tab and the minimum sizes of the other panes allow
+        // returns the content size (cols, rows) the pane ended up with
+        let content_size = |pane: &dyn Pane| (pane.get_content_columns(), pane.get_content_rows());
+        if let Some(pane) = self.floating_panes.get_pane_mut(pane_id) {
+            let viewport = *self.viewport.borrow();
+            let mut geom = pane.position_and_size();
+            let outer_cols = cols + pane.cols().saturating_sub(pane.get_content_columns());
+            let outer_rows = rows + pane.rows().saturating_sub(pane.get_content_rows());
+            let outer_cols = outer_cols.max(pane.min_width()).min(
+                viewport
+                    .cols
+                    .saturating_sub(geom.x.saturating_sub(viewport.x)),
+            );
+            let outer_rows = outer_rows.max(pane.min_height()).min(
+                viewport
+                    .rows
+                    .saturating_sub(geom.y.saturating_sub(viewport.y)),
+            );
+            geom.cols = Dimension::fixed(outer_cols);
+            geom.rows = Dimension::fixed(outer_rows);
+            pane.set_geom(geom);
+            resize_pty!(pane, self.os_api);
+            let achieved_size = content_size(pane.as_ref());
+            self.set_force_render();
+            return Some(achieved_size);
+        }
+        if self.tiled_panes.get_pane(pane_id).is_none() {
+            log::error!("Failed to find pane with id {:?} to resize", pane_id);
+            return None;
+        }
+        if !self.tiled_panes.fullscreen_is_active() {
+            // cells are rounded from percents, so we correct by what we missed a few times
+            let viewport = *self.viewport.borrow();
+            let percent_of =
+                |cells: isize, total: usize| cells as f64 / total.max(1) as f64 * 100.0;
+            for _ in 0..3 {
+                let pane = self.tiled_panes.get_pane(pane_id).unwrap();
+                let geom = pane.position_and_size();
+                let (content_cols, content_rows) = content_size(pane.as_ref());
+                let missing_cols = cols as isize - content_cols as isize;
+                let missing_rows = rows as isize - content_rows as isize;
+                if missing_cols == 0 && missing_rows == 0 {
+                    break;
+                }
+                let cols_percent = geom
+                    .cols
+                    .as_percent()
+                    .filter(|_| missing_cols != 0)
+                    .map(|current| current + percent_of(missing_cols, viewport.cols));
+                let rows_percent = geom
+                    .rows
+                    .as_percent()
+                    .filter(|_| missing_rows != 0)
+                    .map(|current| current + percent_of(missing_rows, viewport.rows));
+                self.tiled_panes
+                    .set_pane_size(pane_id, cols_percent, rows_percent);
+                let resized_geom = self
+                    .tiled_panes
+                    .get_pane(pane_id)
+                    .unwrap()
+                    .position_and_size();
+                if resized_geom == geom {
+                    // this is as close as we can get
+                    break;
+                }
+            }
+            self.set_force_render();
+        }
+        self.tiled_panes
+            .get_pane(pane_id)
+            .map(|pane| content_size(pane.as_ref()))
+    }
+    pub fn set_active_pane_size(
+        &mut self,
+        cols_percent: Option<f64>,
+        rows_percent: Option<f64>,
+        client_id: ClientId,
+    ) {
+        // sets the share of the tab (in percent) the active pane takes up, as closely as the
+        // minimum sizes of its neighbors allow
+        if self.floating_panes.panes_are_visible() || self.tiled_panes.fullscreen_is_active() {
+            return;
+        }
+        self.tiled_panes
+            .set_active_pane_size(client_id, cols_percent, rows_percent);
+        self.set_force_render();
+    }
+    pub fn maximize_active_pane(&mut self, client_id: ClientId, direction: Direction) {
+        if self.floating_panes.panes_are_visible() || self.tiled_panes.fullscreen_is_active() {
+            return;
+        }
+        self.tiled_panes.maximize_active_pane(client_id, direction);
+        let screen_size = self.screen_size();
+        self.resize_whole_tab(screen_size);
+        self.set_force_render();
+    }
     pub fn resize_left(&mut self, client_id: ClientId) {
         if self.floating_panes.panes_are_visible() {
             let successfully_resized = self
@@ -1144,20 +2353,96 @@ impl Tab {
         if !self.has_selectable_panes() {
             return;
         }
-        if self.tiled_panes.fullscreen_is_active() {
+        if self.tiled_panes.active_pane_is_fullscreen() {
             return;
         }
         self.tiled_panes.focus_next_pane(client_id);
     }
+    // like focus_next_pane, but cycles through the panes in spatial order following the neighbours
+    // of each pane in the given direction
+    pub fn focus_next_pane_in_direction(&mut self, client_id: ClientId, direction: Direction) {
+        if !self.has_selectable_panes() {
+            return;
+        }
+        if self.tiled_panes.active_pane_is_fullscreen() {
+            return;
+        }
+        self.tiled_panes
+            .focus_next_pane_in_direction(client_id, &direction);
+    }
+    pub fn move_active_pane_to_front(&mut self, client_id: ClientId) {
+        // makes the active pane the first one focus_next_pane cycles through
+        if let Some(active_pane_id) = self.tiled_panes.get_active_pane_id(client_id) {
+            self.tiled_panes.move_pane_to_front(active_pane_id);
+        }
+    }
+    pub fn move_active_pane_to_back(&mut self, client_id: ClientId) {
+        if let Some(active_pane_id) = self.tiled_panes.get_active_pane_id(client_id) {
+            self.tiled_panes.move_pane_to_back(active_pane_id);
+        }
+    }
     pub fn focus_previous_pane(&mut self, client_id: ClientId) {
         if !self.has_selectable_panes() {
             return;
         }
-        if self.tiled_panes.fullscreen_is_active() {
+        if self.tiled_panes.active_pane_is_fullscreen() {
             return;
         }
         self.tiled_panes.focus_previous_pane(client_id);
     }
+    // focuses the nth selectable pane, counting from the top left of the tab (unlike
+    // focus_next_pane, which follows the pane order)
+    // returns a boolean that indicates whether the focus moved
+    pub fn focus_pane_by_index(&mut self, index: usize) -> bool {
+        if !self.has_selectable_panes() || self.tiled_panes.active_pane_is_fullscreen() {
+            return false;
+        }
+        self.tiled_panes.focus_pane_by_index(index)
+    }
+    // returns false if there is no such selectable pane in this tab
+    pub fn focus_pane(&mut self, pane_id: PaneId, client_id: ClientId) -> bool {
+        if self
+            .tiled_panes
+            .get_pane(pane_id)
+            .map_or(false, |p| p.selectable())
+        {
+            if self.tiled_panes.active_pane_is_fullscreen()
+                || self.tiled_panes.panes_to_hide_contains(pane_id)
+            {
+                self.tiled_panes.unset_fullscreen();
+            }
+            if self.floating_panes.panes_are_visible() {
+                self.set_floating_panes_visibility(false);
+            }
+            self.tiled_panes.focus_pane(pane_id, client_id);
+        } else if self
+            .floating_panes
+            .get_pane(pane_id)
+            .map_or(false, |p| p.selectable())
+        {
+            self.floating_panes.focus_pane(pane_id, client_id);
+            if !self.floating_panes.panes_are_visible() {
+                self.set_floating_panes_visibility(true);
+            }
+        } else {
+            return false;
+        }
+        self.set_force_render();
+        true
+    }
+    pub fn get_pane_title(&self, pane_id: PaneId) -> Option<String> {
+        self.tiled_panes
+            .get_pane(pane_id)
+            .or_else(|| self.floating_panes.get_pane(pane_id))
+            .map(|pane| pane.title())
+    }
+    // toggles focus between the active pane and the one that was focused before it
+    pub fn focus_last_pane(&mut self, client_id: ClientId) {
+        if !self.has_selectable_panes() || self.tiled_panes.active_pane_is_fullscreen() {
+            return;
+        }
+        self.tiled_panes.focus_last_pane(client_id);
+    }
     // returns a boolean that indicates whether the focus moved
     pub fn move_focus_left(&mut self, client_id: ClientId) -> bool {
         if self.floating_panes.panes_are_visible() {
@@ -1169,12 +2454,44 @@ impl Tab {
             if !self.has_selectable_panes() {
                 return false;
             }
-            if self.tiled_panes.fullscreen_is_active() {
+            if self.tiled_panes.active_pane_is_fullscreen() {
                 return false;
             }
             self.tiled_panes.move_focus_left(client_id)
         }
     }
+    pub fn move_focus_left_wrapping(&mut self, client_id: ClientId) -> bool {
+        self.move_focus_left(client_id) || self.move_focus_wrapping(client_id, Direction::Left)
+    }
+    pub fn move_focus_right_wrapping(&mut self, client_id: ClientId) -> bool {
+        self.move_focus_right(client_id) || self.move_focus_wrapping(client_id, Direction::Right)
+    }
+    pub fn move_focus_up_wrapping(&mut self, client_id: ClientId) -> bool {
+        self.move_focus_up(client_id) || self.move_focus_wrapping(client_id, Direction::Up)
+    }
+    pub fn move_focus_down_wrapping(&mut self, client_id: ClientId) -> bool {
+        self.move_focus_down(client_id) || self.move_focus_wrapping(client_id, Direction::Down)
+    }
+    fn move_focus_wrapping(&mut self, client_id: ClientId, direction: Direction) -> bool {
+        // TODO: support wrapping around floating panes as well
+        if self.floating_panes.panes_are_visible()
+            || !self.has_selectable_panes()
+            || self.tiled_panes.active_pane_is_fullscreen()
+        {
+            return false;
+        }
+        self.tiled_panes.move_focus_wrapping(client_id, &direction)
+    }
+    pub fn active_pane_is_at_edge(&self, client_id: ClientId, direction: Direction) -> bool {
+        // lets the screen move on to the adjacent tab when focus can't move any further
+        // a fullscreen pane is always at the edge, and so are floating panes since focus does not
+        // move between them by position
+        if self.floating_panes.panes_are_visible() || self.tiled_panes.active_pane_is_fullscreen() {
+            return true;
+        }
+        self.tiled_panes
+            .active_pane_is_at_edge(client_id, direction)
+    }
     pub fn move_focus_down(&mut self, client_id: ClientId) -> bool {
         if self.floating_panes.panes_are_visible() {
             self.floating_panes.move_focus_down(
@@ -1185,7 +2502,7 @@ impl Tab {
             if !self.has_selectable_panes() {
                 return false;
             }
-            if self.tiled_panes.fullscreen_is_active() {
+            if self.tiled_panes.active_pane_is_fullscreen() {
                 return false;
             }
             self.tiled_panes.move_focus_down(client_id)
@@ -1201,7 +2518,7 @@ impl Tab {
             if !self.has_selectable_panes() {
                 return false;
             }
-            if self.tiled_panes.fullscreen_is_active() {
+            if self.tiled_panes.active_pane_is_fullscreen() {
                 return false;
             }
             self.tiled_panes.move_focus_up(client_id)
@@ -1218,7 +2535,7 @@ impl Tab {
             if !self.has_selectable_panes() {
                 return false;
             }
-            if self.tiled_panes.fullscreen_is_active() {
+            if self.tiled_panes.active_pane_is_fullscreen() {
                 return false;
             }
             self.tiled_panes.move_focus_right(client_id)
@@ -1289,6 +2606,74 @@ impl Tab {
             self.tiled_panes.move_active_pane_left(client_id);
         }
     }
+    // takes the active pane out and re-inserts it next to the pane in the given direction, rather
+    // than swapping places with it
+    pub fn move_active_pane_to_direction(&mut self, client_id: ClientId, direction: Direction) {
+        if self.floating_panes.panes_are_visible() {
+            return;
+        }
+        if !self.has_selectable_panes() {
+            return;
+        }
+        if self.tiled_panes.fullscreen_is_active() {
+            return;
+        }
+        self.tiled_panes
+            .move_active_pane_to_direction(client_id, &direction);
+        self.set_force_render();
+    }
+    pub fn rotate_panes(&mut self, reverse: bool) {
+        if !self.has_selectable_panes() {
+            return;
+        }
+        if self.tiled_panes.fullscreen_is_active() {
+            self.tiled_panes.unset_fullscreen();
+        }
+        self.tiled_panes.rotate_panes(reverse);
+    }
+    pub fn swap_pane(&mut self, client_id: ClientId, direction: Direction) {
+        if !self.has_selectable_panes() {
+            return;
+        }
+        if self.tiled_panes.fullscreen_is_active() {
+            self.tiled_panes.unset_fullscreen();
+        }
+        self.tiled_panes.swap_pane(client_id, &direction);
+    }
+    pub fn reject_new_panes_over_max(&mut self, reject: bool) {
+        // when false (the default) reaching max_panes closes existing panes instead
+        self.reject_panes_over_max = reject;
+    }
+    pub fn set_close_gravity(&mut self, close_gravity: CloseGravity) {
+        self.tiled_panes.set_close_gravity(close_gravity);
+    }
+    fn make_room_for_new_pane(&mut self, new_pid: PaneId) -> bool {
+        // returns false if the new pane should not be created
+        if self.reject_panes_over_max {
+            if let Some(max_panes) = self.max_panes {
+                let selectable_floating_panes = self
+                    .floating_panes
+                    .get_panes()
+                    .filter(|(_, p)| p.selectable())
+                    .count();
+                let selectable_panes =
+                    self.get_selectable_tiled_panes().count() + selectable_floating_panes;
+                if selectable_panes >= max_panes {
+                    log::info!(
+                        "Not creating a new pane, the tab already has {} panes",
+                        max_panes
+                    );
+                    self.senders
+                        .send_to_pty(PtyInstruction::ClosePane(new_pid))
+                        .unwrap();
+                    return false;
+                }
+            }
+            return true;
+        }
+        self.close_down_to_max_terminals();
+        true
+    }
     fn close_down_to_max_terminals(&mut self) {
         if let Some(max_panes) = self.max_panes {
             let terminals = self.get_tiled_pane_ids();
@@ -1307,6 +2692,16 @@ impl Tab {
         // this is here just as a naming thing to make things more explicit
         self.get_static_and_floating_pane_ids()
     }
+    pub fn pane_count(&self) -> usize {
+        self.tiled_panes.pane_ids().count() + self.floating_panes.pane_ids().count()
+    }
+    pub fn selectable_pane_count(&self) -> usize {
+        self.tiled_panes
+            .get_panes()
+            .chain(self.floating_panes.get_panes())
+            .filter(|(_, p)| p.selectable())
+            .count()
+    }
     pub fn get_static_and_floating_pane_ids(&self) -> Vec<PaneId> {
         self.tiled_panes
             .pane_ids()
@@ -1314,6 +2709,21 @@ impl Tab {
             .copied()
             .collect()
     }
+    pub fn get_terminal_pane_ids(&self) -> Vec<RawFd> {
+        self.get_static_and_floating_pane_ids()
+            .into_iter()
+            .filter_map(|pane_id| match pane_id {
+                PaneId::Terminal(pid) => Some(pid),
+                PaneId::Plugin(_) => None,
+            })
+            .collect()
+    }
+    pub fn get_plugin_pane_ids(&self) -> Vec<PaneId> {
+        self.get_static_and_floating_pane_ids()
+            .into_iter()
+            .filter(|pane_id| matches!(pane_id, PaneId::Plugin(_)))
+            .collect()
+    }
     pub fn set_pane_selectable(&mut self, id: PaneId, selectable: bool) {
         if let Some(pane) = self.tiled_panes.get_pane_mut(id) {
             pane.set_selectable(selectable);
@@ -1333,27 +2743,134 @@ impl Tab {
             }
         }
     }
-    pub fn close_pane(&mut self, id: PaneId) -> Option<Box<dyn Pane>> {
-        if self.floating_panes.panes_contain(&id) {
-            let closed_pane = self.floating_panes.remove_pane(id);
+    pub fn mark_pane_exited(&mut self, id: PaneId, status: i32) {
+        let pane = if self.floating_panes.panes_contain(&id) {
+            self.floating_panes.get_pane_mut(id)
+        } else {
+            self.tiled_panes.get_pane_mut(id)
+        };
+        match pane {
+            Some(pane) => pane.set_exited(status),
+            None => log::error!("Failed to find exited pane: {:?}", id),
+        }
+    }
+    pub fn close_pane(&mut self, id: PaneId) -> Option<Box<dyn Pane>> {
+        self.sync_excluded_panes.remove(&id);
+        self.stop_pane_logging(id);
+        for pane_ids in self.pane_groups.values_mut() {
+            pane_ids.remove(&id);
+        }
+        self.pane_groups.retain(|_, pane_ids| !pane_ids.is_empty());
+        if self.floating_panes.panes_contain(&id) {
+            let closed_pane = self.floating_panes.remove_pane(id);
+            self.floating_panes.move_clients_out_of_pane(id);
+            if !self.floating_panes.has_panes() {
+                self.set_floating_panes_visibility(false);
+            }
+            self.set_force_render();
+            self.floating_panes.set_force_render();
+            closed_pane
+        } else {
+            if self.tiled_panes.fullscreen_is_active() {
+                self.tiled_panes.unset_fullscreen();
+            }
+            let closed_pane = self.tiled_panes.remove_pane(id);
+            self.set_force_render();
+            self.tiled_panes.set_force_render();
+            closed_pane
+        }
+    }
+    pub fn extract_pane(&mut self, id: PaneId) -> Option<Box<dyn Pane>> {
+        let mut extracted_pane = if self.floating_panes.panes_contain(&id) {
+            let extracted_pane = self.floating_panes.remove_pane(id);
             self.floating_panes.move_clients_out_of_pane(id);
             if !self.floating_panes.has_panes() {
-                self.floating_panes.toggle_show_panes(false);
+                self.set_floating_panes_visibility(false);
             }
             self.set_force_render();
             self.floating_panes.set_force_render();
-            closed_pane
+            extracted_pane
         } else {
+            if self.tiled_panes.panes.len() <= 1 {
+                // this is the only pane in the tab
+                return None;
+            }
             if self.tiled_panes.fullscreen_is_active() {
                 self.tiled_panes.unset_fullscreen();
             }
-            let closed_pane = self.tiled_panes.remove_pane(id);
+            let extracted_pane = self.tiled_panes.extract_pane(id);
             self.set_force_render();
             self.tiled_panes.set_force_render();
-            closed_pane
+            extracted_pane
+        };
+        if let (Some(pane), PaneId::Terminal(pid)) = (extracted_pane.as_mut(), id) {
+            // these were buffered while the pane was scrolled, we don't want to lose them
+            if let Some(pending_vte_events) = self.pending_vte_events.remove(&pid) {
+                for vte_event in pending_vte_events {
+                    pane.handle_pty_bytes(vte_event);
+                }
+            }
+        }
+        extracted_pane
+    }
+    pub fn add_extracted_pane(
+        &mut self,
+        pane_id: PaneId,
+        mut pane: Box<dyn Pane>,
+        client_id: ClientId,
+    ) {
+        // the pane takes up the whole viewport of this (new) tab
+        let viewport = *self.viewport.borrow();
+        let mut cols = Dimension::percent(100.0);
+        cols.set_inner(viewport.cols);
+        let mut rows = Dimension::percent(100.0);
+        rows.set_inner(viewport.rows);
+        pane.set_geom(PaneGeom {
+            x: viewport.x,
+            y: viewport.y,
+            cols,
+            rows,
+        });
+        self.tiled_panes.add_pane_with_existing_geom(pane_id, pane);
+        self.tiled_panes.set_pane_frames(self.draw_pane_frames);
+        self.tiled_panes.focus_pane(pane_id, client_id);
+        self.focus_pane_id = Some(pane_id);
+        self.should_clear_display_before_rendering = true;
+        self.set_force_render();
+    }
+    pub fn close_focused_pane(&mut self, client_id: ClientId) -> CloseResult {
+        // closing a pane kills whatever runs in it, so if this is more than an idle shell we
+        // only do so if the user tries again shortly after
+        let focused_pane_id = if self.floating_panes.panes_are_visible() {
+            self.floating_panes
+                .active_pane_id(client_id)
+                .or_else(|| self.tiled_panes.get_active_pane_id(client_id))
+        } else {
+            self.tiled_panes.get_active_pane_id(client_id)
+        };
+        if let Some(focused_pane_id) = focused_pane_id {
+            let close_was_requested = self
+                .pane_pending_close
+                .map(|(pane_id, requested_at)| {
+                    pane_id == focused_pane_id
+                        && requested_at.elapsed() < CLOSE_CONFIRMATION_TIMEOUT
+                })
+                .unwrap_or(false);
+            let has_running_process = self
+                .tiled_panes
+                .get_pane(focused_pane_id)
+                .or_else(|| self.floating_panes.get_pane(focused_pane_id))
+                .map_or(false, |pane| pane.has_running_process());
+            if !close_was_requested && has_running_process {
+                self.pane_pending_close = Some((focused_pane_id, Instant::now()));
+                return CloseResult::NeedsConfirmation;
+            }
         }
+        self.force_close_focused_pane(client_id);
+        CloseResult::Closed
     }
-    pub fn close_focused_pane(&mut self, client_id: ClientId) {
+    pub fn force_close_focused_pane(&mut self, client_id: ClientId) {
+        self.pane_pending_close = None;
         if self.floating_panes.panes_are_visible() {
             if let Some(active_floating_pane_id) = self.floating_panes.active_pane_id(client_id) {
                 self.close_pane(active_floating_pane_id);
@@ -1370,11 +2887,94 @@ impl Tab {
                 .unwrap();
         }
     }
+    pub fn close_other_panes(&mut self, client_id: ClientId, close_plugins: bool) {
+        // the pane that stays has to be tiled so that it can take up the whole tab
+        if self.floating_panes.panes_are_visible()
+            && self.floating_panes.active_pane_id(client_id).is_some()
+        {
+            self.toggle_pane_embed_or_floating(client_id);
+        }
+        if self.tiled_panes.fullscreen_is_active() {
+            self.tiled_panes.unset_fullscreen();
+        }
+        let active_pane_id = match self.tiled_panes.get_active_pane_id(client_id) {
+            Some(active_pane_id) => active_pane_id,
+            None => {
+                log::error!("No active pane found for client id: {:?}", client_id);
+                return;
+            }
+        };
+        self.pane_pending_close = None;
+        let panes_to_close: Vec<PaneId> = self
+            .get_all_pane_ids()
+            .into_iter()
+            .filter(|pane_id| *pane_id != active_pane_id)
+            .filter(|pane_id| close_plugins || matches!(pane_id, PaneId::Terminal(_)))
+            .collect();
+        for pane_id in panes_to_close {
+            self.close_pane(pane_id);
+            self.senders
+                .send_to_pty(PtyInstruction::ClosePane(pane_id))
+                .unwrap();
+        }
+        self.set_force_render();
+    }
+    // signals the process in the foreground of the pane's terminal (eg. to kill a hung process
+    // without closing its pane), plugin panes have no process to signal
+    pub fn send_signal_to_pane(&mut self, pane_id: PaneId, signal: i32) {
+        let fd = match pane_id {
+            PaneId::Terminal(fd) => fd,
+            PaneId::Plugin(..) => return,
+        };
+        match self.os_api.get_foreground_process_group(fd) {
+            Some(foreground_process_group) => {
+                if let Err(e) = self
+                    .os_api
+                    .signal_process_group(foreground_process_group, signal)
+                {
+                    log::error!(
+                        "Failed to send signal {} to pane {:?}: {}",
+                        signal,
+                        pane_id,
+                        e
+                    );
+                }
+            }
+            None => log::error!(
+                "Failed to find the foreground process of pane {:?}",
+                pane_id
+            ),
+        }
+    }
+    pub fn send_signal_to_active_pane(&mut self, client_id: ClientId, signal: i32) {
+        if let Some(active_pane_id) = self.get_active_pane_id(client_id) {
+            self.send_signal_to_pane(active_pane_id, signal);
+        }
+    }
     pub fn scroll_active_terminal_up(&mut self, client_id: ClientId) {
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            if active_pane.is_alternate_screen_active() {
+                return;
+            }
             active_pane.scroll_up(1, client_id);
         }
     }
+    pub fn scroll_active_terminal_left(&mut self, client_id: ClientId) {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.scroll_left(1);
+        }
+    }
+    pub fn scroll_active_terminal_right(&mut self, client_id: ClientId) {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.scroll_right(1);
+        }
+    }
+    pub fn toggle_active_pane_line_wrap(&mut self, client_id: ClientId) {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            let line_wrap = active_pane.line_wrap();
+            active_pane.set_line_wrap(!line_wrap);
+        }
+    }
     pub fn scroll_active_terminal_down(&mut self, client_id: ClientId) {
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
             active_pane.scroll_down(1, client_id);
@@ -1387,6 +2987,9 @@ impl Tab {
     }
     pub fn scroll_active_terminal_up_page(&mut self, client_id: ClientId) {
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            if active_pane.is_alternate_screen_active() {
+                return;
+            }
             // prevent overflow when row == 0
             let scroll_rows = active_pane.rows().max(1) - 1;
             active_pane.scroll_up(scroll_rows, client_id);
@@ -1405,6 +3008,9 @@ impl Tab {
     }
     pub fn scroll_active_terminal_up_half_page(&mut self, client_id: ClientId) {
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            if active_pane.is_alternate_screen_active() {
+                return;
+            }
             // prevent overflow when row == 0
             let scroll_rows = (active_pane.rows().max(1) - 1) / 2;
             active_pane.scroll_up(scroll_rows, client_id);
@@ -1431,6 +3037,247 @@ impl Tab {
             }
         }
     }
+    pub fn search_active_terminal(
+        &mut self,
+        query: &str,
+        client_id: ClientId,
+    ) -> Vec<(usize, usize)> {
+        // returns the (line, column) of each match in the scrollback of the active pane
+        match self.get_active_pane_or_floating_pane_mut(client_id) {
+            Some(active_pane) => active_pane.search(query),
+            None => vec![],
+        }
+    }
+    pub fn scroll_to_next_match(&mut self, client_id: ClientId) {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.scroll_to_search_match(true, client_id);
+        }
+    }
+    pub fn scroll_to_previous_match(&mut self, client_id: ClientId) {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.scroll_to_search_match(false, client_id);
+        }
+    }
+    pub fn dump_active_pane_screen(&self, client_id: ClientId) -> Option<String> {
+        self.get_active_pane(client_id)
+            .map(|active_pane| active_pane.dump_screen())
+    }
+    pub fn dump_active_pane_to_file(
+        &mut self,
+        path: &Path,
+        include_scrollback: bool,
+        with_ansi: bool,
+        client_id: ClientId,
+    ) {
+        let contents = match self
+            .get_active_pane(client_id)
+            .and_then(|pane| pane.dump(include_scrollback, with_ansi))
+        {
+            Some(contents) => contents,
+            None => return,
+        };
+        self.write_pane_dump(path, contents);
+    }
+    pub fn dump_active_pane_screen_to_file(&mut self, path: &Path, client_id: ClientId) {
+        if let Some(contents) = self.dump_active_pane_screen(client_id) {
+            self.write_pane_dump(path, contents);
+        }
+    }
+    fn write_pane_dump(&mut self, path: &Path, contents: String) {
+        let message = match fs::write(path, contents + "\n") {
+            Ok(()) => format!("Pane dumped to {}", path.display()),
+            Err(e) => {
+                log::error!("Failed to dump pane to {}: {}", path.display(), e);
+                format!("Failed to dump pane to {}", path.display())
+            }
+        };
+        self.show_overlay_message(message, Duration::from_secs(3));
+    }
+    pub fn set_pane_palette(&mut self, pane_id: PaneId, palette: Option<Palette>) {
+        // None reverts the pane to the colors of the tab
+        match self
+            .tiled_panes
+            .get_pane_mut(pane_id)
+            .or_else(|| self.floating_panes.get_pane_mut(pane_id))
+        {
+            Some(pane) => pane.set_palette_override(palette),
+            None => log::error!("Failed to find pane with id {:?} to set palette", pane_id),
+        }
+    }
+    pub fn clear_all_selections(&mut self) {
+        self.selecting_with_mouse = false;
+        self.selection_edge_scroll = None;
+        for pane_id in self.get_all_pane_ids() {
+            if let Some(pane) = self
+                .tiled_panes
+                .get_pane_mut(pane_id)
+                .or_else(|| self.floating_panes.get_pane_mut(pane_id))
+            {
+                pane.reset_selection();
+                pane.set_should_render(true);
+            }
+        }
+    }
+    pub fn set_pane_read_only(&mut self, pane_id: PaneId, read_only: bool) {
+        // typed input to a read-only pane is dropped, mouse scrolling and replies the pane's
+        // terminal sends to its own process still go through
+        match self
+            .tiled_panes
+            .get_pane_mut(pane_id)
+            .or_else(|| self.floating_panes.get_pane_mut(pane_id))
+        {
+            Some(pane) => pane.set_read_only(read_only),
+            None => log::error!("Failed to find pane with id {:?} to set read-only", pane_id),
+        }
+    }
+    fn pane_is_read_only(&self, pane_id: PaneId) -> bool {
+        self.tiled_panes
+            .get_pane(pane_id)
+            .or_else(|| self.floating_panes.get_pane(pane_id))
+            .map(|pane| pane.is_read_only())
+            .unwrap_or(false)
+    }
+    pub fn set_pane_border_color(&mut self, pane_id: PaneId, color: Option<PaletteColor>) {
+        // the color is used while the pane is not focused, None reverts to the default border
+        match self
+            .tiled_panes
+            .get_pane_mut(pane_id)
+            .or_else(|| self.floating_panes.get_pane_mut(pane_id))
+        {
+            Some(pane) => pane.set_border_color(color),
+            None => log::error!(
+                "Failed to find pane with id {:?} to set border color",
+                pane_id
+            ),
+        }
+    }
+    pub fn toggle_raw_view(&mut self, pane_id: PaneId) {
+        if let Some(pane) = self
+            .tiled_panes
+            .get_pane_mut(pane_id)
+            .or_else(|| self.floating_panes.get_pane_mut(pane_id))
+        {
+            pane.toggle_raw_view();
+        }
+    }
+    pub fn insert_synthetic_line(&mut self, pane_id: PaneId, text: String, client_id: ClientId) {
+        if let PaneId::Terminal(pid) = pane_id {
+            // the line should come after everything the program has already sent us, including
+            // the bytes we buffered while the pane was scrolled, so we catch up on those and then
+            // scroll back to the lines the user was looking at
+            let (scroll_offset, scrollback_len) = match self
+                .tiled_panes
+                .get_pane_mut(pane_id)
+                .or_else(|| self.floating_panes.get_pane_mut(pane_id))
+            {
+                Some(pane) => {
+                    let scroll_offset = pane.scroll_offset();
+                    pane.clear_scroll();
+                    (scroll_offset, pane.scrollback_len())
+                }
+                None => return,
+            };
+            self.process_pending_vte_events(pid);
+            if let Some(pane) = self
+                .tiled_panes
+                .get_pane_mut(pane_id)
+                .or_else(|| self.floating_panes.get_pane_mut(pane_id))
+            {
+                pane.insert_synthetic_line(&text);
+                if scroll_offset > 0 {
+                    let added_lines = pane.scrollback_len().saturating_sub(scrollback_len);
+                    pane.scroll_up(scroll_offset + added_lines, client_id);
+                }
+            }
+        }
+    }
+    pub fn set_pane_silence_threshold(&mut self, id: PaneId, threshold: Option<Duration>) {
+        if let Some(pane) = self
+            .tiled_panes
+            .get_pane_mut(id)
+            .or_else(|| self.floating_panes.get_pane_mut(id))
+        {
+            pane.set_silence_threshold(threshold);
+            self.report_silent_panes();
+        }
+    }
+    pub fn report_silent_panes(&mut self) {
+        // each pane is reported once per silence, which ends when it gets more output
+        let now = Instant::now();
+        let tiled_panes = &self.tiled_panes;
+        let floating_panes = &self.floating_panes;
+        self.reported_silence.retain(|pane_id, _| {
+            tiled_panes.panes_contain(pane_id) || floating_panes.panes_contain(pane_id)
+        });
+        if self.silence_check_at.map(|at| at <= now).unwrap_or(false) {
+            self.silence_check_at = None;
+        }
+        let mut silent_panes = vec![];
+        let mut next_check_at: Option<Instant> = None;
+        for (pane_id, pane) in self
+            .tiled_panes
+            .get_panes()
+            .chain(self.floating_panes.get_panes())
+        {
+            let (last_output_at, threshold) =
+                match (pane.last_output_at(), pane.silence_threshold()) {
+                    (Some(last_output_at), Some(threshold)) => (last_output_at, threshold),
+                    _ => continue,
+                };
+            if self.reported_silence.get(pane_id) == Some(&last_output_at) {
+                continue;
+            }
+            let silent_at = last_output_at + threshold;
+            if silent_at <= now {
+                silent_panes.push((*pane_id, last_output_at, pane_info(*pane_id, pane.as_ref())));
+            } else if next_check_at.map(|at| silent_at < at).unwrap_or(true) {
+                next_check_at = Some(silent_at);
+            }
+        }
+        for (pane_id, last_output_at, pane_info) in silent_panes {
+            self.senders
+                .send_to_plugin(PluginInstruction::Update(
+                    None,
+                    None,
+                    Event::PaneSilence(pane_info),
+                ))
+                .unwrap();
+            self.reported_silence.insert(pane_id, last_output_at);
+        }
+        if let Some(next_check_at) = next_check_at {
+            if self
+                .silence_check_at
+                .map(|at| next_check_at < at)
+                .unwrap_or(true)
+            {
+                // nothing might render when a pane goes silent, so we make sure we look again
+                self.silence_check_at = Some(next_check_at);
+                self.senders.send_to_screen_after(
+                    ScreenInstruction::Render,
+                    next_check_at.saturating_duration_since(now),
+                );
+            }
+        }
+    }
+    pub fn set_pane_scrollback_limit(&mut self, id: PaneId, lines: usize) {
+        if let Some(pane) = self
+            .tiled_panes
+            .get_pane_mut(id)
+            .or_else(|| self.floating_panes.get_pane_mut(id))
+        {
+            pane.set_scrollback_limit(lines);
+            pane.set_should_render(true);
+        }
+    }
+    pub fn clear_active_pane(&mut self, client_id: ClientId) {
+        // output buffered while the pane was scrolled is processed first so that the pane does
+        // not miss any mode changes in it
+        self.clear_active_terminal_scroll(client_id);
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.clear_all();
+            self.set_force_render();
+        }
+    }
     pub fn clear_active_terminal_scroll(&mut self, client_id: ClientId) {
         // TODO: is this a thing?
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
@@ -1452,6 +3299,11 @@ impl Tab {
                     relative_position.line.0 + 1
                 );
                 self.write_to_terminal_at(mouse_event.into_bytes(), point);
+            } else if pane.is_alternate_screen_active() {
+                // there is nothing to scroll, so we let the program scroll instead
+                for _ in 0..lines {
+                    self.write_to_terminal_at("\u{1b}[A".as_bytes().to_vec(), point);
+                }
             } else {
                 pane.scroll_up(lines, client_id);
             }
@@ -1467,6 +3319,10 @@ impl Tab {
                     relative_position.line.0 + 1
                 );
                 self.write_to_terminal_at(mouse_event.into_bytes(), point);
+            } else if pane.is_alternate_screen_active() {
+                for _ in 0..lines {
+                    self.write_to_terminal_at("\u{1b}[B".as_bytes().to_vec(), point);
+                }
             } else {
                 pane.scroll_down(lines, client_id);
                 if !pane.is_scrolled() {
@@ -1482,6 +3338,9 @@ impl Tab {
         point: &Position,
         search_selectable: bool,
     ) -> Option<&mut Box<dyn Pane>> {
+        if self.is_position_in_reserved_viewport(point) {
+            return None;
+        }
         if self.floating_panes.panes_are_visible() {
             if let Some(pane_id) = self.floating_panes.get_pane_id_at(point, search_selectable) {
                 return self.floating_panes.get_pane_mut(pane_id);
@@ -1495,7 +3354,13 @@ impl Tab {
     }
 
     fn get_pane_id_at(&self, point: &Position, search_selectable: bool) -> Option<PaneId> {
-        if self.tiled_panes.fullscreen_is_active() && self.is_position_inside_viewport(point) {
+        if self.is_position_in_reserved_viewport(point) {
+            return None;
+        }
+        if let Some(pane_id) = self.tiled_panes.pinned_pane_id_at(point, search_selectable) {
+            return Some(pane_id);
+        }
+        if self.tiled_panes.active_pane_is_fullscreen() && self.is_position_inside_viewport(point) {
             let first_client_id = {
                 self.connected_clients
                     .borrow()
@@ -1506,17 +3371,66 @@ impl Tab {
             }; // TODO: instead of doing this, record the pane that is in fullscreen
             return self.tiled_panes.get_active_pane_id(first_client_id);
         }
+        // panes hidden because they did not fit or behind a pane maximized in one axis can't be
+        // clicked, the panes that took over their space are found instead
         if search_selectable {
             self.get_selectable_tiled_panes()
-                .find(|(_, p)| p.contains(point))
+                .find(|(&id, p)| p.contains(point) && !self.tiled_panes.panes_to_hide_contains(id))
                 .map(|(&id, _)| id)
         } else {
             self.get_tiled_panes()
-                .find(|(_, p)| p.contains(point))
+                .find(|(&id, p)| p.contains(point) && !self.tiled_panes.panes_to_hide_contains(id))
                 .map(|(&id, _)| id)
         }
     }
+    // frames are part of a pane's geometry, so a click on a pane's border is attributed to
+    // the framed pane
+    pub fn get_pane_id_at_position(&self, point: &Position) -> Option<PaneId> {
+        if self.is_position_in_reserved_viewport(point) {
+            return None;
+        }
+        if self.floating_panes.panes_are_visible() {
+            if let Some(pane_id) = self.floating_panes.get_pane_id_at(point, true) {
+                return Some(pane_id);
+            }
+        }
+        self.get_pane_id_at(point, true)
+    }
+    pub fn open_url_at(&mut self, position: &Position) -> bool {
+        // meant for clicks with a modifier held, returns false if there is no url to open so
+        // that the click can be handled as usual
+        let url = self.get_pane_at(position, false).and_then(|pane| {
+            let relative_position = pane.relative_position(position);
+            pane.hyperlink_at(&relative_position)
+                .or_else(|| pane.url_at(&relative_position))
+        });
+        // anything else could run or open a local file
+        let url = url.filter(|url| {
+            OPENABLE_URL_SCHEMES
+                .iter()
+                .any(|scheme| url.to_lowercase().starts_with(scheme))
+        });
+        match url {
+            Some(url) => {
+                self.senders
+                    .send_to_server(ServerInstruction::OpenUrl(url))
+                    .unwrap();
+                true
+            }
+            None => false,
+        }
+    }
     pub fn handle_left_click(&mut self, position: &Position, client_id: ClientId) {
+        self.handle_left_click_with_selection_mode(position, SelectionMode::Stream, client_id);
+    }
+    pub fn handle_left_click_with_selection_mode(
+        &mut self,
+        position: &Position,
+        selection_mode: SelectionMode,
+        client_id: ClientId,
+    ) {
+        // eg. a block selection while a modifier is held, mouse holds then extend the selection in
+        // the same mode
         self.focus_pane_at(position, client_id);
 
         let search_selectable = false;
@@ -1529,6 +3443,13 @@ impl Tab {
             return;
         }
 
+        if !self.floating_panes.panes_are_visible()
+            && !self.tiled_panes.fullscreen_is_active()
+            && self.tiled_panes.start_dragging_border_at(position)
+        {
+            return;
+        }
+
         if let Some(pane) = self.get_pane_at(position, false) {
             let relative_position = pane.relative_position(position);
 
@@ -1540,7 +3461,7 @@ impl Tab {
                 );
                 self.write_to_active_terminal(mouse_event.into_bytes(), client_id);
             } else {
-                pane.start_selection(&relative_position, client_id);
+                pane.start_selection(&relative_position, selection_mode, client_id);
                 self.selecting_with_mouse = true;
             }
         };
@@ -1563,23 +3484,24 @@ impl Tab {
         };
     }
     fn focus_pane_at(&mut self, point: &Position, client_id: ClientId) {
-        if self.floating_panes.panes_are_visible() {
-            if let Some(clicked_pane) = self.floating_panes.get_pane_id_at(point, true) {
-                self.floating_panes.focus_pane(clicked_pane, client_id);
-                self.set_pane_active_at(clicked_pane);
-                return;
-            }
-        }
-        if let Some(clicked_pane) = self.get_pane_id_at(point, true) {
-            self.tiled_panes.focus_pane(clicked_pane, client_id);
+        let clicked_pane = match self.get_pane_id_at_position(point) {
+            Some(clicked_pane) => clicked_pane,
+            None => return,
+        };
+        if self.floating_panes.panes_contain(&clicked_pane) {
+            self.floating_panes.focus_pane(clicked_pane, client_id);
             self.set_pane_active_at(clicked_pane);
+        } else {
             if self.floating_panes.panes_are_visible() {
-                self.floating_panes.toggle_show_panes(false);
+                self.set_floating_panes_visibility(false);
                 self.set_force_render();
             }
+            self.tiled_panes.focus_pane(clicked_pane, client_id);
+            self.set_pane_active_at(clicked_pane);
         }
     }
     pub fn handle_mouse_release(&mut self, position: &Position, client_id: ClientId) {
+        self.selection_edge_scroll = None;
         if self.floating_panes.panes_are_visible()
             && self.floating_panes.pane_is_being_moved_with_mouse()
         {
@@ -1587,6 +3509,15 @@ impl Tab {
             return;
         }
 
+        if self.tiled_panes.border_is_being_dragged() {
+            self.tiled_panes.drag_border_to(position);
+            self.tiled_panes.stop_dragging_border();
+            let screen_size = self.screen_size();
+            self.resize_whole_tab(screen_size);
+            self.set_force_render();
+            return;
+        }
+
         // read these here to avoid use of borrowed `*self`, since we are holding active_pane
         let selecting = self.selecting_with_mouse;
         let copy_on_release = self.copy_on_select;
@@ -1598,7 +3529,7 @@ impl Tab {
                 // ensure that coordinates are valid
                 let col = (relative_position.column.0 + 1)
                     .max(1)
-                    .min(active_pane.get_content_columns());
+                    .min(active_pane.get_content_columns() + active_pane.horizontal_scroll());
 
                 let line = (relative_position.line.0 + 1)
                     .max(1)
@@ -1633,7 +3564,14 @@ impl Tab {
             return;
         }
 
+        if self.tiled_panes.border_is_being_dragged() {
+            self.tiled_panes.drag_border_to(position_on_screen);
+            self.set_force_render();
+            return;
+        }
+
         let selecting = self.selecting_with_mouse;
+        let mut held_past_edge = false;
         let active_pane = self.get_active_pane_or_floating_pane_mut(client_id);
 
         if let Some(active_pane) = active_pane {
@@ -1642,7 +3580,7 @@ impl Tab {
                 // ensure that coordinates are valid
                 let col = (relative_position.column.0 + 1)
                     .max(1)
-                    .min(active_pane.get_content_columns());
+                    .min(active_pane.get_content_columns() + active_pane.horizontal_scroll());
 
                 let line = (relative_position.line.0 + 1)
                     .max(1)
@@ -1652,7 +3590,29 @@ impl Tab {
                 self.write_to_active_terminal(mouse_event.into_bytes(), client_id);
             } else if selecting {
                 active_pane.update_selection(&relative_position, client_id);
+                held_past_edge = relative_position.line.0 < 0
+                    || relative_position.line.0 >= active_pane.get_content_rows() as isize;
+            }
+        }
+        if held_past_edge {
+            // keep scrolling the selection while the mouse stays there, since the terminal does
+            // not send us any more events until it moves
+            self.selection_edge_scroll = Some(*position_on_screen);
+            if !self.selection_scroll_tick_pending {
+                self.selection_scroll_tick_pending = true;
+                self.senders.send_to_screen_after(
+                    ScreenInstruction::ScrollSelectionAtEdge(client_id),
+                    SELECTION_EDGE_SCROLL_INTERVAL,
+                );
             }
+        } else {
+            self.selection_edge_scroll = None;
+        }
+    }
+    pub fn scroll_selection_at_edge(&mut self, client_id: ClientId) {
+        self.selection_scroll_tick_pending = false;
+        if let Some(position) = self.selection_edge_scroll {
+            self.handle_mouse_hold(&position, client_id);
         }
     }
 
@@ -1672,6 +3632,29 @@ impl Tab {
         }
     }
 
+    pub fn copy_active_pane_scrollback(&self, client_id: ClientId) {
+        let scrollback = match self
+            .get_active_pane(client_id)
+            .and_then(|active_pane| active_pane.dump(true, false))
+        {
+            Some(scrollback) => scrollback,
+            None => return,
+        };
+        let lines: Vec<&str> = scrollback.lines().collect();
+        let first_line_to_copy = lines.len().saturating_sub(self.scrollback_copy_line_limit);
+        if first_line_to_copy > 0 {
+            log::info!(
+                "Scrollback has {} lines, copying only the last {}",
+                lines.len(),
+                self.scrollback_copy_line_limit
+            );
+        }
+        let scrollback = lines[first_line_to_copy..].join("\n");
+        if !scrollback.is_empty() {
+            self.write_selection_to_clipboard(&scrollback);
+        }
+    }
+
     fn write_selection_to_clipboard(&self, selection: &str) {
         let mut output = Output::default();
         let connected_clients: HashSet<ClientId> =
@@ -1724,21 +3707,30 @@ impl Tab {
     }
 
     pub fn visible(&self, visible: bool) {
-        let pids_in_this_tab = self.tiled_panes.pane_ids().filter_map(|p| match p {
-            PaneId::Plugin(pid) => Some(pid),
-            _ => None,
-        });
-        for pid in pids_in_this_tab {
-            self.senders
-                .send_to_plugin(PluginInstruction::Update(
-                    Some(*pid),
-                    None,
-                    Event::Visible(visible),
-                ))
-                .unwrap();
+        // floating plugin panes are told as well, they are part of this tab too
+        for pane_id in self.get_plugin_pane_ids() {
+            if let PaneId::Plugin(pid) = pane_id {
+                self.senders
+                    .send_to_plugin(PluginInstruction::Update(
+                        Some(pid),
+                        None,
+                        Event::Visible(visible),
+                    ))
+                    .unwrap();
+            }
         }
     }
 
+    pub fn set_pane_name(&mut self, id: PaneId, name: String) {
+        match self
+            .tiled_panes
+            .get_pane_mut(id)
+            .or_else(|| self.floating_panes.get_pane_mut(id))
+        {
+            Some(pane) => pane.set_title(name),
+            None => log::error!("Failed to find pane with id {:?} to rename", id),
+        }
+    }
     pub fn update_active_pane_name(&mut self, buf: Vec<u8>, client_id: ClientId) {
         if let Some(active_terminal_id) = self.get_active_terminal_id(client_id) {
             let active_terminal = self
@@ -1751,6 +3743,7 @@ impl Tab {
             if is_updatable {
                 let s = str::from_utf8(&buf).unwrap();
                 active_terminal.update_name(s);
+                self.report_pane_title_change(PaneId::Terminal(active_terminal_id));
             }
         }
     }
@@ -1769,14 +3762,129 @@ impl Tab {
             && column <= viewport.x + viewport.cols
     }
 
+    pub fn toggle_pane_frames_for_single_pane(&mut self) {
+        self.tiled_panes.toggle_hide_single_pane_frame();
+        self.should_clear_display_before_rendering = true;
+        self.set_force_render();
+    }
     pub fn set_pane_frames(&mut self, should_set_pane_frames: bool) {
         self.tiled_panes.set_pane_frames(should_set_pane_frames);
         self.should_clear_display_before_rendering = true;
         self.set_force_render();
     }
+    // pinned panes stay visible while another pane is fullscreen
+    pub fn toggle_pane_pinned(&mut self, pane_id: PaneId) {
+        self.tiled_panes.toggle_pane_pinned(pane_id);
+    }
     pub fn panes_to_hide_count(&self) -> usize {
         self.tiled_panes.panes_to_hide_count()
     }
+    // the geometry every pane is currently drawn at (the override if it has one, eg. when it is
+    // fullscreen), see is_pane_hidden for panes that are not drawn at all
+    pub fn pane_geoms(&self) -> BTreeMap<PaneId, PaneGeom> {
+        self.tiled_panes
+            .get_panes()
+            .chain(self.floating_panes.get_panes())
+            .map(|(pane_id, pane)| (*pane_id, pane.current_geom()))
+            .collect()
+    }
+    // hidden panes are those behind a fullscreen pane and floating panes while they are hidden
+    pub fn is_pane_hidden(&self, pane_id: PaneId) -> bool {
+        if self.floating_panes.panes_contain(&pane_id) {
+            !self.floating_panes.panes_are_visible()
+        } else {
+            self.tiled_panes.panes_to_hide_contains(pane_id)
+        }
+    }
+}
+
+// how a pane is described in the events sent to plugins
+fn pane_info(pane_id: PaneId, pane: &dyn Pane) -> PaneInfo {
+    let (id, is_plugin) = match pane_id {
+        PaneId::Terminal(pid) => (pid as u32, false),
+        PaneId::Plugin(pid) => (pid, true),
+    };
+    PaneInfo {
+        id,
+        is_plugin,
+        title: pane.title(),
+    }
+}
+
+pub(crate) fn report_active_pane(senders: &ThreadSenders, client_id: ClientId, pane: &dyn Pane) {
+    senders
+        .send_to_plugin(PluginInstruction::Update(
+            None,
+            Some(client_id),
+            Event::ActivePaneChanged(pane_info(pane.pid(), pane)),
+        ))
+        .unwrap();
+}
+
+// returns the indices of the lines on each side that are not part of the longest common
+// subsequence of both sides (i.e. the lines that differ between them)
+fn line_diff(first: &[String], second: &[String]) -> (HashSet<usize>, HashSet<usize>) {
+    let mut common_lengths = vec![vec![0; second.len() + 1]; first.len() + 1];
+    for i in (0..first.len()).rev() {
+        for j in (0..second.len()).rev() {
+            common_lengths[i][j] = if first[i] == second[j] {
+                common_lengths[i + 1][j + 1] + 1
+            } else {
+                std::cmp::max(common_lengths[i + 1][j], common_lengths[i][j + 1])
+            };
+        }
+    }
+    let mut first_differing_lines: HashSet<usize> = (0..first.len()).collect();
+    let mut second_differing_lines: HashSet<usize> = (0..second.len()).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < first.len() && j < second.len() {
+        if first[i] == second[j] {
+            first_differing_lines.remove(&i);
+            second_differing_lines.remove(&j);
+            i += 1;
+            j += 1;
+        } else if common_lengths[i + 1][j] >= common_lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    (first_differing_lines, second_differing_lines)
+}
+
+fn bracket_paste(text: &str, bracketed_paste_active: bool) -> Vec<u8> {
+    if bracketed_paste_active {
+        // strip any paste markers from the pasted text so it can't end the bracketed paste early,
+        // repeating until none are left since removing one can join the halves of another
+        let mut text = text.to_owned();
+        while text.contains("\u{1b}[200~") || text.contains("\u{1b}[201~") {
+            text = text.replace("\u{1b}[200~", "").replace("\u{1b}[201~", "");
+        }
+        format!("\u{1b}[200~{}\u{1b}[201~", text).into_bytes()
+    } else {
+        text.as_bytes().to_vec()
+    }
+}
+
+fn normalize_pasted_newlines(input_bytes: &[u8], paste_newlines: PasteNewlines) -> Vec<u8> {
+    let newline = match paste_newlines {
+        PasteNewlines::Preserve => return input_bytes.to_vec(),
+        PasteNewlines::LineFeed => b'\n',
+        PasteNewlines::CarriageReturn => b'\r',
+    };
+    let mut normalized = Vec::with_capacity(input_bytes.len());
+    let mut bytes = input_bytes.iter().peekable();
+    while let Some(byte) = bytes.next() {
+        match byte {
+            b'\r' => {
+                bytes.next_if_eq(&&b'\n');
+                normalized.push(newline);
+            }
+            b'\n' => normalized.push(newline),
+            _ => normalized.push(*byte),
+        }
+    }
+    normalized
 }
 
 #[cfg(test)]
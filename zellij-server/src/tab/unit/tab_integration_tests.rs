@@ -1,4 +1,4 @@
-use super::{Output, Tab};
+use super::{Output, Tab, MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH};
 use crate::screen::CopyOptions;
 use crate::zellij_tile::data::{ModeInfo, Palette};
 use crate::{
@@ -20,6 +20,7 @@ use std::cell::RefCell;
 use std::collections::HashSet;
 use std::os::unix::io::RawFd;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
 use zellij_utils::nix;
 
@@ -39,7 +40,7 @@ impl ServerOsApi for FakeInputOutput {
     fn spawn_terminal(
         &self,
         _file_to_open: TerminalAction,
-        _quit_cb: Box<dyn Fn(PaneId) + Send>,
+        _quit_cb: Box<dyn Fn(PaneId, i32) + Send>,
     ) -> (RawFd, RawFd) {
         unimplemented!()
     }
@@ -61,6 +62,9 @@ impl ServerOsApi for FakeInputOutput {
     fn force_kill(&self, _pid: Pid) -> Result<(), nix::Error> {
         unimplemented!()
     }
+    fn close_pty(&self, _fd: RawFd) -> Result<(), nix::Error> {
+        unimplemented!()
+    }
     fn box_clone(&self) -> Box<dyn ServerOsApi> {
         Box::new((*self).clone())
     }
@@ -83,6 +87,15 @@ impl ServerOsApi for FakeInputOutput {
     fn get_cwd(&self, _pid: Pid) -> Option<PathBuf> {
         unimplemented!()
     }
+    fn get_foreground_process_group(&self, _fd: RawFd) -> Option<Pid> {
+        unimplemented!()
+    }
+    fn get_session_id(&self, _fd: RawFd) -> Option<Pid> {
+        unimplemented!()
+    }
+    fn signal_process_group(&self, _pgid: Pid, _signal: i32) -> Result<(), nix::Error> {
+        unimplemented!()
+    }
 }
 
 // TODO: move to shared thingy with other test file
@@ -97,6 +110,7 @@ fn create_new_tab(size: Size) -> Tab {
     let mode_info = ModeInfo::default();
     let style = Style::default();
     let draw_pane_frames = true;
+    let hide_single_pane_frame = false;
     let client_id = 1;
     let session_is_mirrored = true;
     let mut connected_clients = HashSet::new();
@@ -110,16 +124,20 @@ fn create_new_tab(size: Size) -> Tab {
         name,
         size,
         character_cell_info,
+        None,
         os_api,
         senders,
         max_panes,
         style,
         mode_info,
         draw_pane_frames,
+        hide_single_pane_frame,
         connected_clients,
         session_is_mirrored,
         client_id,
         copy_options,
+        MIN_TERMINAL_WIDTH,
+        MIN_TERMINAL_HEIGHT,
     );
     tab.apply_layout(
         LayoutTemplate::default().try_into().unwrap(),
@@ -1093,3 +1111,225 @@ fn replacing_existing_wide_characters() {
     );
     assert_snapshot!(snapshot);
 }
+
+#[test]
+fn unchanged_boundaries_are_not_rendered_again() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    tab.set_pane_frames(false);
+    tab.vertical_split(PaneId::Terminal(2), client_id);
+    let mut output = Output::default();
+    tab.render(&mut output, None);
+    assert!(
+        output.serialize().get(&client_id).unwrap().contains('│'),
+        "boundaries rendered after the split"
+    );
+    let mut output = Output::default();
+    tab.render(&mut output, None);
+    assert!(
+        !output.serialize().get(&client_id).unwrap().contains('│'),
+        "unchanged boundaries not rendered again"
+    );
+    tab.resize_whole_tab(Size {
+        cols: 100,
+        rows: 20,
+    });
+    let mut output = Output::default();
+    tab.render(&mut output, None);
+    assert!(
+        output.serialize().get(&client_id).unwrap().contains('│'),
+        "boundaries rendered again after a resize"
+    );
+}
+
+#[test]
+fn scroll_position_is_kept_when_resizing_narrower() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    let mut pty_bytes = String::new();
+    for line_number in 0..50 {
+        pty_bytes.push_str(&format!("line {:02} {}\r\n", line_number, "x".repeat(70)));
+    }
+    tab.handle_pty_bytes(1, Vec::from(pty_bytes.as_bytes()));
+    for _ in 0..10 {
+        tab.scroll_active_terminal_up(client_id);
+    }
+    let mut output = Output::default();
+    tab.render(&mut output, None);
+    let snapshot = take_snapshot(
+        output.serialize().get(&client_id).unwrap(),
+        size.rows,
+        size.cols,
+        Palette::default(),
+    );
+    assert!(
+        snapshot.contains("line 23 x"),
+        "scrolled up to line 23 before resizing"
+    );
+    let new_size = Size { cols: 60, rows: 20 };
+    tab.resize_whole_tab(new_size);
+    tab.set_force_render();
+    let mut output = Output::default();
+    tab.render(&mut output, None);
+    let snapshot = take_snapshot(
+        output.serialize().get(&client_id).unwrap(),
+        new_size.rows,
+        new_size.cols,
+        Palette::default(),
+    );
+    assert!(
+        snapshot.contains("line 23 x"),
+        "line 23 is still on screen after the lines were rewrapped"
+    );
+}
+
+#[test]
+fn pinned_pane_is_rendered_over_fullscreen_pane() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), client_id);
+    tab.handle_pty_bytes(1, "clock 12:00".as_bytes().to_vec());
+    tab.toggle_pane_pinned(PaneId::Terminal(1));
+    tab.toggle_active_pane_fullscreen(client_id);
+    assert!(
+        tab.is_fullscreen_active(),
+        "pane 2 is fullscreen although pane 1 is pinned"
+    );
+    let mut output = Output::default();
+    tab.render(&mut output, None);
+    assert!(
+        output
+            .serialize()
+            .get(&client_id)
+            .unwrap()
+            .contains("clock 12:00"),
+        "pinned pane is rendered"
+    );
+    let mut output = Output::default();
+    tab.render(&mut output, None);
+    assert!(
+        !output
+            .serialize()
+            .get(&client_id)
+            .unwrap()
+            .contains("clock 12:00"),
+        "pinned pane is not re-rendered while nothing below it changed"
+    );
+    assert_eq!(
+        tab.get_pane_id_at_position(&Position::new(5, 10)),
+        Some(PaneId::Terminal(1)),
+        "clicks on the pinned pane go to it rather than to the fullscreen pane"
+    );
+    assert_eq!(
+        tab.get_pane_id_at_position(&Position::new(5, 100)),
+        Some(PaneId::Terminal(2)),
+        "clicks elsewhere go to the fullscreen pane"
+    );
+    tab.toggle_pane_pinned(PaneId::Terminal(1));
+    let mut output = Output::default();
+    tab.render(&mut output, None);
+    assert!(
+        !output
+            .serialize()
+            .get(&client_id)
+            .unwrap()
+            .contains("clock 12:00"),
+        "unpinned pane is hidden again"
+    );
+}
+
+#[test]
+fn pty_output_marks_tab_for_render_until_rendered() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let mut output = Output::default();
+    assert!(!tab.needs_render(), "nothing to render before output");
+    tab.handle_pty_bytes(1, Vec::from("hi".as_bytes()));
+    tab.handle_pty_bytes(1, Vec::from(" there".as_bytes()));
+    assert!(tab.needs_render(), "output waits for the next render");
+    tab.render(&mut output, None);
+    assert!(!tab.needs_render(), "render catches up on all output");
+}
+
+#[test]
+fn cursor_hidden_by_the_application_stays_hidden_when_rendering() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    let mut output = Output::default();
+    tab.handle_pty_bytes(1, b"\x1b[?25l".to_vec());
+    tab.render(&mut output, None);
+    let rendered = output.serialize().remove(&client_id).unwrap();
+    assert!(rendered.ends_with("\u{1b}[?25l"), "cursor is hidden");
+    assert!(!rendered.contains("\u{1b}[?25h"), "cursor is not shown");
+}
+
+// renders a tab of 8 panes a number of times while one of them gets output, optionally forcing
+// a full render (every pane and the boundaries) each time, as was done before only the changed
+// panes were rendered
+fn render_with_one_busy_pane_of_eight(force_render: bool) -> (Duration, usize) {
+    let size = Size {
+        cols: 240,
+        rows: 60,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    for pid in 2..=8 {
+        if pid % 2 == 0 {
+            tab.vertical_split(PaneId::Terminal(pid), client_id);
+        } else {
+            tab.horizontal_split(PaneId::Terminal(pid), client_id);
+        }
+    }
+    let busy_pid = match tab.get_active_pane_id(client_id) {
+        Some(PaneId::Terminal(pid)) => pid,
+        _ => panic!("the active pane is a terminal"),
+    };
+    tab.render(&mut Output::default(), None);
+    let mut rendered_bytes = 0;
+    let start = Instant::now();
+    for line_number in 0..1000 {
+        tab.handle_pty_bytes(busy_pid, format!("line {}\r\n", line_number).into_bytes());
+        if force_render {
+            tab.set_force_render();
+        }
+        let mut output = Output::default();
+        tab.render(&mut output, None);
+        rendered_bytes += output.serialize().values().map(|o| o.len()).sum::<usize>();
+    }
+    (start.elapsed(), rendered_bytes)
+}
+
+// cargo test --release -p zellij-server bench_render -- --ignored --nocapture
+#[test]
+#[ignore]
+fn bench_render_with_one_busy_pane_of_eight() {
+    let (full_render_time, full_render_bytes) = render_with_one_busy_pane_of_eight(true);
+    let (dirty_render_time, dirty_render_bytes) = render_with_one_busy_pane_of_eight(false);
+    println!(
+        "1000 renders, every pane: {:?} ({} bytes), changed panes only: {:?} ({} bytes)",
+        full_render_time, full_render_bytes, dirty_render_time, dirty_render_bytes
+    );
+    assert!(
+        dirty_render_bytes < full_render_bytes,
+        "idle panes and unchanged boundaries are not rendered again"
+    );
+}
@@ -1,34 +1,55 @@
-use super::Tab;
+use super::{
+    normalize_pasted_newlines, CloseResult, Pane, PasteNewlines, Tab, MAX_LAYOUT_OP_HISTORY,
+    MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH,
+};
+use crate::panes::selection::SelectionMode;
 use crate::screen::CopyOptions;
-use crate::zellij_tile::data::{ModeInfo, Palette};
+use crate::ui::boundaries::BoundarySegment;
+use crate::zellij_tile::data::{Event, ModeInfo, Palette, PaletteColor, ScrollInfo};
 use crate::{
     os_input_output::{AsyncReader, Pid, ServerOsApi},
-    panes::PaneId,
+    panes::{PaneId, PluginPane},
+    pty::PtyInstruction,
     thread_bus::ThreadSenders,
-    ClientId,
+    wasm_vm::PluginInstruction,
+    ClientId, ServerInstruction,
 };
 use std::convert::TryInto;
 use std::path::PathBuf;
 use zellij_tile::prelude::Style;
-use zellij_utils::input::layout::LayoutTemplate;
+use zellij_utils::input::actions::Direction;
+use zellij_utils::input::layout::{
+    Direction as SplitDirection, Layout, LayoutPreset, LayoutTemplate, Run, RunPlugin,
+    RunPluginLocation, SplitSize,
+};
+use zellij_utils::input::options::CloseGravity;
+use zellij_utils::input::plugins::PluginTag;
 use zellij_utils::ipc::IpcReceiverWithContext;
-use zellij_utils::pane_size::{Size, SizeInPixels};
+use zellij_utils::pane_size::{Dimension, PaneGeom, Size, SizeInPixels};
+use zellij_utils::position::Position;
 
 use std::cell::RefCell;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::os::unix::io::RawFd;
 use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use zellij_utils::nix;
 
 use zellij_utils::{
-    input::command::TerminalAction,
+    channels::{self, SenderWithContext},
+    input::command::{RunCommand, TerminalAction},
     interprocess::local_socket::LocalSocketStream,
     ipc::{ClientToServerMsg, ServerToClientMsg},
 };
 
-#[derive(Clone)]
-struct FakeInputOutput {}
+#[derive(Clone, Default)]
+struct FakeInputOutput {
+    written_bytes: Arc<Mutex<HashMap<RawFd, Vec<u8>>>>,
+    running_processes: Arc<Mutex<HashSet<RawFd>>>, // terminals with a process other than the shell in the foreground
+    signalled_process_groups: Arc<Mutex<Vec<(Pid, i32)>>>,
+}
 
 impl ServerOsApi for FakeInputOutput {
     fn set_terminal_size_using_fd(&self, _fd: RawFd, _cols: u16, _rows: u16) {
@@ -37,7 +58,7 @@ impl ServerOsApi for FakeInputOutput {
     fn spawn_terminal(
         &self,
         _file_to_open: TerminalAction,
-        _quit_cb: Box<dyn Fn(PaneId) + Send>,
+        _quit_cb: Box<dyn Fn(PaneId, i32) + Send>,
     ) -> (RawFd, RawFd) {
         unimplemented!()
     }
@@ -47,11 +68,17 @@ impl ServerOsApi for FakeInputOutput {
     fn async_file_reader(&self, _fd: RawFd) -> Box<dyn AsyncReader> {
         unimplemented!()
     }
-    fn write_to_tty_stdin(&self, _fd: RawFd, _buf: &[u8]) -> Result<usize, nix::Error> {
-        unimplemented!()
+    fn write_to_tty_stdin(&self, fd: RawFd, buf: &[u8]) -> Result<usize, nix::Error> {
+        self.written_bytes
+            .lock()
+            .unwrap()
+            .entry(fd)
+            .or_default()
+            .extend_from_slice(buf);
+        Ok(buf.len())
     }
     fn tcdrain(&self, _fd: RawFd) -> Result<(), nix::Error> {
-        unimplemented!()
+        Ok(())
     }
     fn kill(&self, _pid: Pid) -> Result<(), nix::Error> {
         unimplemented!()
@@ -59,6 +86,9 @@ impl ServerOsApi for FakeInputOutput {
     fn force_kill(&self, _pid: Pid) -> Result<(), nix::Error> {
         unimplemented!()
     }
+    fn close_pty(&self, _fd: RawFd) -> Result<(), nix::Error> {
+        unimplemented!()
+    }
     fn box_clone(&self) -> Box<dyn ServerOsApi> {
         Box::new((*self).clone())
     }
@@ -81,18 +111,49 @@ impl ServerOsApi for FakeInputOutput {
     fn get_cwd(&self, _pid: Pid) -> Option<PathBuf> {
         unimplemented!()
     }
+    fn get_foreground_process_group(&self, fd: RawFd) -> Option<Pid> {
+        if self.running_processes.lock().unwrap().contains(&fd) {
+            Some(Pid::from_raw(fd + 1000))
+        } else {
+            Some(Pid::from_raw(fd))
+        }
+    }
+    fn get_session_id(&self, fd: RawFd) -> Option<Pid> {
+        Some(Pid::from_raw(fd))
+    }
+    fn signal_process_group(&self, pgid: Pid, signal: i32) -> Result<(), nix::Error> {
+        self.signalled_process_groups
+            .lock()
+            .unwrap()
+            .push((pgid, signal));
+        Ok(())
+    }
 }
 
 fn create_new_tab(size: Size) -> Tab {
+    create_new_tab_with_layout(size, LayoutTemplate::default().try_into().unwrap(), vec![1])
+}
+
+fn create_new_tab_with_layout(size: Size, layout: Layout, new_pids: Vec<RawFd>) -> Tab {
+    let senders = ThreadSenders::default().silently_fail_on_send();
+    create_new_tab_with_senders(size, layout, new_pids, senders)
+}
+
+fn create_new_tab_with_senders(
+    size: Size,
+    layout: Layout,
+    new_pids: Vec<RawFd>,
+    senders: ThreadSenders,
+) -> Tab {
     let index = 0;
     let position = 0;
     let name = String::new();
-    let os_api = Box::new(FakeInputOutput {});
-    let senders = ThreadSenders::default().silently_fail_on_send();
+    let os_api = Box::new(FakeInputOutput::default());
     let max_panes = None;
     let mode_info = ModeInfo::default();
     let style = Style::default();
     let draw_pane_frames = true;
+    let hide_single_pane_frame = false;
     let client_id = 1;
     let session_is_mirrored = true;
     let mut connected_clients = HashSet::new();
@@ -106,26 +167,47 @@ fn create_new_tab(size: Size) -> Tab {
         name,
         size,
         character_cell_info,
+        None,
         os_api,
         senders,
         max_panes,
         style,
         mode_info,
         draw_pane_frames,
+        hide_single_pane_frame,
         connected_clients,
         session_is_mirrored,
         client_id,
         copy_options,
+        MIN_TERMINAL_WIDTH,
+        MIN_TERMINAL_HEIGHT,
     );
-    tab.apply_layout(
-        LayoutTemplate::default().try_into().unwrap(),
-        vec![1],
-        index,
-        client_id,
-    );
+    tab.apply_layout(layout, new_pids, index, client_id);
     tab
 }
 
+fn assert_panes_cover_display_area(tab: &Tab, size: Size) {
+    let mut cells_covered = vec![vec![0; size.cols]; size.rows];
+    for (_, pane) in tab.get_tiled_panes() {
+        let geom = pane.position_and_size();
+        for row in cells_covered
+            .iter_mut()
+            .skip(geom.y)
+            .take(geom.rows.as_usize())
+        {
+            for cell in row.iter_mut().skip(geom.x).take(geom.cols.as_usize()) {
+                *cell += 1;
+            }
+        }
+    }
+    assert!(
+        cells_covered.iter().flatten().all(|cell| *cell == 1),
+        "panes cover the whole {}x{} display area exactly once",
+        size.cols,
+        size.rows
+    );
+}
+
 fn create_new_tab_with_cell_size(
     size: Size,
     character_cell_size: Rc<RefCell<Option<SizeInPixels>>>,
@@ -133,12 +215,13 @@ fn create_new_tab_with_cell_size(
     let index = 0;
     let position = 0;
     let name = String::new();
-    let os_api = Box::new(FakeInputOutput {});
+    let os_api = Box::new(FakeInputOutput::default());
     let senders = ThreadSenders::default().silently_fail_on_send();
     let max_panes = None;
     let mode_info = ModeInfo::default();
     let style = Style::default();
     let draw_pane_frames = true;
+    let hide_single_pane_frame = false;
     let client_id = 1;
     let session_is_mirrored = true;
     let mut connected_clients = HashSet::new();
@@ -151,16 +234,20 @@ fn create_new_tab_with_cell_size(
         name,
         size,
         character_cell_size,
+        None,
         os_api,
         senders,
         max_panes,
         style,
         mode_info,
         draw_pane_frames,
+        hide_single_pane_frame,
         connected_clients,
         session_is_mirrored,
         client_id,
         copy_options,
+        MIN_TERMINAL_WIDTH,
+        MIN_TERMINAL_HEIGHT,
     );
     tab.apply_layout(
         LayoutTemplate::default().try_into().unwrap(),
@@ -267,6 +354,101 @@ fn split_panes_vertically() {
     );
 }
 
+#[test]
+pub fn split_panes_horizontally_with_fixed_size_does_not_overlap() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    assert!(
+        !tab.horizontal_split_with_fixed_size(PaneId::Terminal(2), 1, Some(20)),
+        "fixed size that does not fit is refused"
+    );
+    assert_eq!(tab.tiled_panes.panes.len(), 1, "no pane was added");
+    tab.horizontal_split_with_fixed_size(PaneId::Terminal(2), 1, Some(5));
+    let top = tab.tiled_panes.panes[&PaneId::Terminal(1)].position_and_size();
+    let bottom = tab.tiled_panes.panes[&PaneId::Terminal(2)].position_and_size();
+    assert_eq!(bottom.rows.as_usize(), 5, "second pane gets the fixed size");
+    assert_eq!(
+        top.y + top.rows.as_usize(),
+        bottom.y,
+        "second pane starts below the first one"
+    );
+    assert_eq!(bottom.y + bottom.rows.as_usize(), 20);
+}
+
+#[test]
+fn split_panes_vertically_with_fixed_size() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let new_pane_id = PaneId::Terminal(2);
+    tab.vertical_split_with_fixed_size(new_pane_id, 1, Some(30));
+    assert_eq!(tab.tiled_panes.panes.len(), 2, "The tab has two panes");
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(1))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        91,
+        "first pane column count"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        30,
+        "second pane column count"
+    );
+
+    tab.resize_whole_tab(Size {
+        cols: 200,
+        rows: 20,
+    });
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(1))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        170,
+        "first pane absorbs the resize"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .x,
+        170,
+        "second pane x position after resize"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        30,
+        "second pane keeps its fixed size"
+    );
+}
+
 #[test]
 fn split_panes_horizontally() {
     let size = Size {
@@ -768,6 +950,40 @@ pub fn close_pane_with_another_pane_above_it() {
     );
 }
 
+#[test]
+pub fn extract_pane_with_another_pane_above_it() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let new_pane_id = PaneId::Terminal(2);
+    tab.horizontal_split(new_pane_id, 1);
+    let extracted_pane = tab.extract_pane(new_pane_id);
+    assert_eq!(
+        extracted_pane.map(|pane| pane.pid()),
+        Some(new_pane_id),
+        "Pane was extracted"
+    );
+    assert_eq!(tab.tiled_panes.panes.len(), 1, "One pane left in tab");
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(1))
+            .unwrap()
+            .position_and_size()
+            .rows
+            .as_usize(),
+        20,
+        "remaining pane row count"
+    );
+    assert!(
+        tab.extract_pane(PaneId::Terminal(1)).is_none(),
+        "Last pane cannot be extracted"
+    );
+    assert_eq!(tab.tiled_panes.panes.len(), 1, "Last pane left in tab");
+}
+
 #[test]
 pub fn close_pane_with_another_pane_below_it() {
     // ┌───────────┐            ┌───────────┐
@@ -2946,431 +3162,759 @@ pub fn move_active_pane_left() {
 }
 
 #[test]
-pub fn move_active_pane_left_to_the_most_recently_used_position() {
+pub fn move_focus_right_wraps_around_tab_edge() {
     let size = Size {
         cols: 121,
         rows: 20,
     };
     let mut tab = create_new_tab(size);
-    let new_pane_id_1 = PaneId::Terminal(2);
-    let new_pane_id_2 = PaneId::Terminal(3);
-    let new_pane_id_3 = PaneId::Terminal(4);
+    let new_pane_id = PaneId::Terminal(2);
 
-    tab.vertical_split(new_pane_id_1, 1);
-    tab.move_focus_left(1);
-    tab.horizontal_split(new_pane_id_2, 1);
-    tab.horizontal_split(new_pane_id_3, 1);
-    tab.move_focus_right(1);
-    tab.move_active_pane_left(1);
+    tab.vertical_split(new_pane_id, 1);
+    assert!(
+        !tab.move_focus_right(1),
+        "No pane to the right without wrapping"
+    );
+    assert!(tab.move_focus_right_wrapping(1), "Focus wrapped around");
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().pid(),
+        PaneId::Terminal(1),
+        "Focus moved to the leftmost pane"
+    );
+}
 
+#[test]
+pub fn focus_pane_by_index() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.horizontal_split(PaneId::Terminal(3), 1);
+    assert!(tab.focus_pane_by_index(0), "Focused first pane");
     assert_eq!(
-        tab.get_active_pane(1).unwrap().y(),
-        15,
-        "Active pane y position"
+        tab.get_active_pane(1).unwrap().pid(),
+        PaneId::Terminal(1),
+        "First pane is the top left one"
     );
+    assert!(tab.focus_pane_by_index(2), "Focused third pane");
     assert_eq!(
-        tab.get_active_pane(1).unwrap().x(),
-        0,
-        "Active pane x position"
+        tab.get_active_pane(1).unwrap().pid(),
+        PaneId::Terminal(3),
+        "Third pane is the bottom right one"
     );
-
+    assert!(!tab.focus_pane_by_index(3), "Index out of range");
     assert_eq!(
         tab.get_active_pane(1).unwrap().pid(),
-        PaneId::Terminal(2),
-        "Active pane PaneId"
+        PaneId::Terminal(3),
+        "Focus did not move"
     );
 }
 
 #[test]
-pub fn move_active_pane_right() {
+pub fn move_active_pane_to_front_of_focus_order() {
     let size = Size {
         cols: 121,
         rows: 20,
     };
     let mut tab = create_new_tab(size);
-    let new_pane_id = PaneId::Terminal(2);
-
-    tab.vertical_split(new_pane_id, 1);
-    tab.move_focus_left(1);
-    tab.move_active_pane_right(1);
-
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.vertical_split(PaneId::Terminal(3), 1);
+    tab.move_active_pane_to_front(1);
+    tab.focus_next_pane(1);
     assert_eq!(
-        tab.get_active_pane(1).unwrap().x(),
-        61,
-        "Active pane is the right one"
+        tab.get_active_pane(1).unwrap().pid(),
+        PaneId::Terminal(1),
+        "Pane 1 comes after the moved pane"
     );
+    tab.focus_previous_pane(1);
+    tab.focus_previous_pane(1);
     assert_eq!(
         tab.get_active_pane(1).unwrap().pid(),
-        PaneId::Terminal(1),
-        "Active pane is the right one"
+        PaneId::Terminal(2),
+        "Focus wrapped around to the last pane"
     );
 }
 
 #[test]
-pub fn move_active_pane_right_to_the_most_recently_used_position() {
+pub fn moving_a_pane_to_the_front_keeps_pane_indices() {
     let size = Size {
         cols: 121,
         rows: 20,
     };
     let mut tab = create_new_tab(size);
-    let new_pane_id_1 = PaneId::Terminal(2);
-    let new_pane_id_2 = PaneId::Terminal(3);
-    let new_pane_id_3 = PaneId::Terminal(4);
-
-    tab.vertical_split(new_pane_id_1, 1);
-    tab.horizontal_split(new_pane_id_2, 1);
-    tab.horizontal_split(new_pane_id_3, 1);
-    tab.move_focus_left(1);
-    tab.move_active_pane_right(1);
-
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.vertical_split(PaneId::Terminal(3), 1);
+    tab.move_active_pane_to_front(1);
+    assert!(tab.focus_pane_by_index(0), "Focused first pane");
     assert_eq!(
-        tab.get_active_pane(1).unwrap().y(),
-        15,
-        "Active pane y position"
+        tab.get_active_pane(1).unwrap().pid(),
+        PaneId::Terminal(1),
+        "The first pane is still the leftmost one"
     );
+    tab.focus_next_pane(1);
+    tab.focus_next_pane(1);
     assert_eq!(
-        tab.get_active_pane(1).unwrap().x(),
-        61,
-        "Active pane x position"
+        tab.get_active_pane(1).unwrap().pid(),
+        PaneId::Terminal(3),
+        "focus_next_pane wraps around to the moved pane"
     );
+}
+
+#[test]
+pub fn focus_pane_by_index_counts_panes_by_position_not_focus_order() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.vertical_split(PaneId::Terminal(3), 1);
+    // the focus order is now 1, 3, 2 while the panes are still 1, 2, 3 from the left
+    assert!(tab.focus_pane_by_index(1), "Focused second pane");
+    tab.move_active_pane_to_back(1);
+    for (index, pane_id) in [1, 2, 3].iter().enumerate() {
+        assert!(tab.focus_pane_by_index(index), "Focused pane {}", index);
+        assert_eq!(
+            tab.get_active_pane(1).unwrap().pid(),
+            PaneId::Terminal(*pane_id),
+            "Pane {} is counted from the left",
+            index
+        );
+    }
+    tab.focus_pane_by_index(0);
+    tab.focus_next_pane(1);
     assert_eq!(
         tab.get_active_pane(1).unwrap().pid(),
-        PaneId::Terminal(1),
-        "Active pane Paneid"
+        PaneId::Terminal(3),
+        "focus_next_pane follows the focus order instead"
     );
 }
 
 #[test]
-pub fn resize_down_with_pane_above() {
-    // ┌───────────┐                  ┌───────────┐
-    // │           │                  │           │
-    // │           │                  │           │
-    // ├───────────┤ ==resize=down==> │           │
-    // │███████████│                  ├───────────┤
-    // │███████████│                  │███████████│
-    // │███████████│                  │███████████│
-    // └───────────┘                  └───────────┘
-    // █ == focused pane
+pub fn hide_frame_of_single_pane() {
     let size = Size {
         cols: 121,
         rows: 20,
     };
     let mut tab = create_new_tab(size);
-    let new_pane_id = PaneId::Terminal(2);
-    tab.horizontal_split(new_pane_id, 1);
-    tab.resize_down(1);
-
-    assert_eq!(
+    tab.toggle_pane_frames_for_single_pane();
+    let content_columns_of_pane_1 = |tab: &Tab| {
         tab.tiled_panes
             .panes
-            .get(&new_pane_id)
+            .get(&PaneId::Terminal(1))
             .unwrap()
-            .position_and_size()
-            .x,
-        0,
-        "focused pane x position"
+            .get_content_columns()
+    };
+    assert_eq!(
+        content_columns_of_pane_1(&tab),
+        121,
+        "single pane has no frame"
     );
+    tab.vertical_split(PaneId::Terminal(2), 1);
     assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&new_pane_id)
-            .unwrap()
-            .position_and_size()
-            .y,
-        11,
-        "focused pane y position"
+        content_columns_of_pane_1(&tab),
+        59,
+        "frame is drawn once there are two panes"
     );
+    tab.close_pane(PaneId::Terminal(2));
     assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&new_pane_id)
-            .unwrap()
-            .position_and_size()
-            .cols
-            .as_usize(),
+        content_columns_of_pane_1(&tab),
         121,
-        "focused pane column count"
+        "frame is hidden again once the second pane is closed"
     );
+}
+
+#[test]
+pub fn resize_whole_tab_keeps_panes_covering_the_viewport() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.vertical_split(PaneId::Terminal(3), 1);
+    for cols in 30..=200 {
+        let rows = 10 + cols % 15;
+        tab.resize_whole_tab(Size { cols, rows });
+        assert_panes_cover_display_area(&tab, Size { cols, rows });
+    }
+}
+
+#[test]
+pub fn toggle_fullscreen_restores_pane_geometry() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.horizontal_split(PaneId::Terminal(3), 1);
+    tab.vertical_split(PaneId::Terminal(4), 1);
+    tab.resize_left(1);
+    let geoms_before_fullscreen: Vec<PaneGeom> = tab
+        .get_tiled_panes()
+        .map(|(_, pane)| pane.position_and_size())
+        .collect();
+    tab.toggle_active_pane_fullscreen(1);
+    tab.toggle_active_pane_fullscreen(1);
+    let geoms_after_fullscreen: Vec<PaneGeom> = tab
+        .get_tiled_panes()
+        .map(|(_, pane)| pane.position_and_size())
+        .collect();
     assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&new_pane_id)
-            .unwrap()
-            .position_and_size()
-            .rows
-            .as_usize(),
-        9,
-        "focused pane row count"
+        geoms_after_fullscreen, geoms_before_fullscreen,
+        "panes are back where they were"
     );
 
+    let new_size = Size {
+        cols: 150,
+        rows: 31,
+    };
+    tab.toggle_active_pane_fullscreen(1);
+    tab.resize_whole_tab(new_size);
+    tab.toggle_active_pane_fullscreen(1);
+    assert_panes_cover_display_area(&tab, new_size);
+}
+
+#[test]
+pub fn snapshot_layout_recreates_pane_geometry() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.horizontal_split(PaneId::Terminal(3), 1);
+    tab.vertical_split(PaneId::Terminal(4), 1);
+    tab.resize_left(1);
+    let pane_geoms = |tab: &Tab| {
+        let mut geoms: Vec<(usize, usize, usize, usize)> = tab
+            .get_tiled_panes()
+            .map(|(_, pane)| {
+                let geom = pane.position_and_size();
+                (geom.x, geom.y, geom.cols.as_usize(), geom.rows.as_usize())
+            })
+            .collect();
+        geoms.sort_unstable();
+        geoms
+    };
+    let layout = tab.snapshot_layout().unwrap();
+    let restored_tab = create_new_tab_with_layout(size, layout, vec![1, 2, 3, 4]);
     assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(1))
-            .unwrap()
-            .position_and_size()
-            .x,
-        0,
-        "pane above x position"
+        pane_geoms(&restored_tab),
+        pane_geoms(&tab),
+        "restored tab has the same pane geometry"
     );
+}
+
+#[test]
+pub fn sync_excluded_pane_does_not_receive_input() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let os_api = FakeInputOutput::default();
+    let mut tab = create_new_tab(size);
+    tab.os_api = Box::new(os_api.clone());
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.vertical_split(PaneId::Terminal(3), 1);
+    tab.toggle_sync_panes_is_active();
+    tab.set_pane_sync_excluded(PaneId::Terminal(2), true);
+    tab.write_to_terminals_on_current_tab(b"ls".to_vec());
+    let written_bytes = os_api.written_bytes.lock().unwrap();
     assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(1))
-            .unwrap()
-            .position_and_size()
-            .y,
-        0,
-        "pane above y position"
+        written_bytes.get(&1),
+        Some(&b"ls".to_vec()),
+        "pane 1 got input"
     );
+    assert_eq!(written_bytes.get(&2), None, "excluded pane 2 got no input");
     assert_eq!(
+        written_bytes.get(&3),
+        Some(&b"ls".to_vec()),
+        "pane 3 got input"
+    );
+}
+
+#[test]
+pub fn close_pane_without_aligned_neighbors() {
+    // a pinwheel, in which no neighbor of the middle pane lines up with it:
+    // ┌──────────┬────┐
+    // │    1     │    │
+    // ├────┬─────┤ 2  │
+    // │    │  5  │    │
+    // │ 4  ├─────┴────┤
+    // │    │    3     │
+    // └────┴──────────┘
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    for pid in 2..=5 {
+        tab.vertical_split(PaneId::Terminal(pid), 1);
+    }
+    let pane_geom = |x: usize, y: usize, cols: usize, rows: usize| {
+        let mut cols_dimension = Dimension::percent(cols as f64 / size.cols as f64 * 100.0);
+        cols_dimension.set_inner(cols);
+        let mut rows_dimension = Dimension::percent(rows as f64 / size.rows as f64 * 100.0);
+        rows_dimension.set_inner(rows);
+        PaneGeom {
+            x,
+            y,
+            cols: cols_dimension,
+            rows: rows_dimension,
+        }
+    };
+    let pinwheel = [
+        (1, pane_geom(0, 0, 80, 6)),
+        (2, pane_geom(80, 0, 41, 13)),
+        (3, pane_geom(40, 13, 81, 7)),
+        (4, pane_geom(0, 6, 40, 14)),
+        (5, pane_geom(40, 6, 40, 7)),
+    ];
+    for (pid, geom) in pinwheel {
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(1))
+            .get_mut(&PaneId::Terminal(pid))
             .unwrap()
-            .position_and_size()
-            .cols
-            .as_usize(),
+            .set_geom(geom);
+    }
+    assert_panes_cover_display_area(&tab, size);
+    tab.close_pane(PaneId::Terminal(5));
+    assert_eq!(tab.get_tiled_panes().count(), 4, "pane 5 was closed");
+    assert_panes_cover_display_area(&tab, size);
+}
+
+#[test]
+pub fn rebalance_corrects_percentage_drift() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.horizontal_split(PaneId::Terminal(3), 1);
+    tab.vertical_split(PaneId::Terminal(4), 1);
+    tab.close_pane(PaneId::Terminal(3));
+    tab.horizontal_split(PaneId::Terminal(5), 1);
+    tab.close_pane(PaneId::Terminal(4));
+    tab.close_pane(PaneId::Terminal(5));
+    // simulate the drift accumulated over a long session
+    tab.tiled_panes
+        .panes
+        .get_mut(&PaneId::Terminal(2))
+        .unwrap()
+        .reduce_width(0.3);
+    tab.rebalance();
+
+    let pane_1 = tab.tiled_panes.panes.get(&PaneId::Terminal(1)).unwrap();
+    let pane_2 = tab.tiled_panes.panes.get(&PaneId::Terminal(2)).unwrap();
+    let total_percent = pane_1.position_and_size().cols.as_percent().unwrap()
+        + pane_2.position_and_size().cols.as_percent().unwrap();
+    assert!(
+        (total_percent - 100.0).abs() < f64::EPSILON * 100.0,
+        "percentages sum up to 100 (got {})",
+        total_percent
+    );
+    assert_eq!(
+        pane_1.position_and_size().cols.as_usize() + pane_2.position_and_size().cols.as_usize(),
         121,
-        "pane above column count"
+        "panes fill the whole tab"
     );
     assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(1))
-            .unwrap()
-            .position_and_size()
-            .rows
-            .as_usize(),
-        11,
-        "pane above row count"
+        pane_2.position_and_size().x,
+        pane_1.position_and_size().cols.as_usize(),
+        "panes do not overlap"
     );
 }
 
 #[test]
-pub fn resize_down_with_pane_below() {
-    // ┌───────────┐                  ┌───────────┐
-    // │███████████│                  │███████████│
-    // │███████████│                  │███████████│
-    // ├───────────┤ ==resize=down==> │███████████│
-    // │           │                  ├───────────┤
-    // │           │                  │           │
-    // └───────────┘                  └───────────┘
-    // █ == focused pane
+pub fn resize_whole_tab_normalizes_percentages() {
     let size = Size {
         cols: 121,
         rows: 20,
     };
     let mut tab = create_new_tab(size);
-    let new_pane_id = PaneId::Terminal(2);
-    tab.horizontal_split(new_pane_id, 1);
-    tab.move_focus_up(1);
-    tab.resize_down(1);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.horizontal_split(PaneId::Terminal(3), 1);
+    for i in 0..50 {
+        if i % 2 == 0 {
+            tab.resize_left(1);
+        } else {
+            tab.resize_right(1);
+        }
+    }
+    tab.resize_whole_tab(size);
 
-    assert_eq!(
+    let cols_percent = |pid: RawFd| {
         tab.tiled_panes
             .panes
-            .get(&new_pane_id)
+            .get(&PaneId::Terminal(pid))
             .unwrap()
             .position_and_size()
-            .x,
-        0,
-        "pane below x position"
+            .cols
+            .as_percent()
+            .unwrap()
+    };
+    for (left_pane, right_pane) in [(1, 2), (1, 3)] {
+        let total_percent = cols_percent(left_pane) + cols_percent(right_pane);
+        assert!(
+            (total_percent - 100.0).abs() < f64::EPSILON * 100.0,
+            "percentages of panes {} and {} sum up to 100 (got {})",
+            left_pane,
+            right_pane,
+            total_percent
+        );
+    }
+}
+
+#[test]
+pub fn paste_to_active_terminal_with_bracketed_paste() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let os_api = FakeInputOutput::default();
+    let mut tab = create_new_tab(size);
+    tab.os_api = Box::new(os_api.clone());
+    tab.handle_pty_bytes(1, b"\x1b[?2004h".to_vec());
+    tab.paste_to_active_terminal("ls\nexit", 1);
+    assert_eq!(
+        os_api.written_bytes.lock().unwrap().remove(&1),
+        Some(b"\x1b[200~ls\rexit\x1b[201~".to_vec()),
+        "paste is bracketed"
     );
+    tab.handle_pty_bytes(1, b"\x1b[?2004l".to_vec());
+    tab.paste_to_active_terminal("ls\nexit", 1);
     assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&new_pane_id)
-            .unwrap()
-            .position_and_size()
-            .y,
-        11,
-        "pane below y position"
+        os_api.written_bytes.lock().unwrap().remove(&1),
+        Some(b"ls\rexit".to_vec()),
+        "paste is sent as is"
     );
+}
+
+#[test]
+pub fn bracketed_paste_strips_paste_markers_from_pasted_text() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let os_api = FakeInputOutput::default();
+    let mut tab = create_new_tab(size);
+    tab.os_api = Box::new(os_api.clone());
+    tab.handle_pty_bytes(1, b"\x1b[?2004h".to_vec());
+    tab.paste_to_active_terminal("ls\x1b[201~rm\x1b[200~", 1);
     assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&new_pane_id)
-            .unwrap()
-            .position_and_size()
-            .cols
-            .as_usize(),
-        121,
-        "pane below column count"
+        os_api.written_bytes.lock().unwrap().remove(&1),
+        Some(b"\x1b[200~lsrm\x1b[201~".to_vec()),
+        "paste markers inside the pasted text are removed"
     );
+    tab.paste_to_active_terminal("ls\x1b[20\x1b[201~1~", 1);
     assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&new_pane_id)
-            .unwrap()
-            .position_and_size()
-            .rows
-            .as_usize(),
-        9,
-        "pane below row count"
+        os_api.written_bytes.lock().unwrap().remove(&1),
+        Some(b"\x1b[200~ls\x1b[201~".to_vec()),
+        "paste markers joined by removing another marker are removed"
     );
+}
 
+#[test]
+pub fn paste_to_synchronized_terminals_brackets_per_pane() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let os_api = FakeInputOutput::default();
+    let mut tab = create_new_tab(size);
+    tab.os_api = Box::new(os_api.clone());
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.handle_pty_bytes(1, b"\x1b[?2004h".to_vec());
+    tab.toggle_sync_panes_is_active();
+    tab.paste_to_terminals_on_current_tab("ls\nexit");
     assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(1))
-            .unwrap()
-            .position_and_size()
-            .x,
-        0,
-        "focused pane x position"
+        os_api.written_bytes.lock().unwrap().remove(&1),
+        Some(b"\x1b[200~ls\rexit\x1b[201~".to_vec()),
+        "paste is bracketed for the pane that enabled bracketed paste"
     );
     assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(1))
-            .unwrap()
-            .position_and_size()
-            .y,
-        0,
-        "focused pane y position"
+        os_api.written_bytes.lock().unwrap().remove(&2),
+        Some(b"ls\rexit".to_vec()),
+        "paste is sent as is to the other pane"
     );
+}
+
+#[test]
+pub fn set_pane_name() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.set_pane_name(PaneId::Terminal(1), String::from("my pane"));
     assert_eq!(
         tab.tiled_panes
             .panes
             .get(&PaneId::Terminal(1))
             .unwrap()
-            .position_and_size()
-            .cols
-            .as_usize(),
-        121,
-        "focused pane column count"
+            .title(),
+        "my pane",
+        "Pane was renamed"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(1))
+            .get(&PaneId::Terminal(2))
             .unwrap()
-            .position_and_size()
-            .rows
-            .as_usize(),
-        11,
-        "focused pane row count"
+            .title(),
+        "Pane #2",
+        "Other pane kept its default title"
     );
 }
 
 #[test]
-pub fn resize_down_with_panes_above_and_below() {
-    // ┌───────────┐                  ┌───────────┐
-    // │           │                  │           │
-    // │           │                  │           │
-    // ├───────────┤                  ├───────────┤
-    // │███████████│ ==resize=down==> │███████████│
-    // │███████████│                  │███████████│
-    // │███████████│                  │███████████│
-    // ├───────────┤                  │███████████│
-    // │           │                  ├───────────┤
-    // │           │                  │           │
-    // └───────────┘                  └───────────┘
-    // █ == focused pane
+pub fn normalize_newlines_in_pasted_text() {
+    let pasted_text = b"echo foo\r\necho bar\recho baz\n";
+    assert_eq!(
+        normalize_pasted_newlines(pasted_text, PasteNewlines::CarriageReturn),
+        b"echo foo\recho bar\recho baz\r".to_vec()
+    );
+    assert_eq!(
+        normalize_pasted_newlines(pasted_text, PasteNewlines::LineFeed),
+        b"echo foo\necho bar\necho baz\n".to_vec()
+    );
+    assert_eq!(
+        normalize_pasted_newlines(pasted_text, PasteNewlines::Preserve),
+        pasted_text.to_vec()
+    );
+}
+
+#[test]
+pub fn drag_border_between_panes_with_mouse() {
     let size = Size {
         cols: 121,
-        rows: 30,
+        rows: 20,
     };
     let mut tab = create_new_tab(size);
-    let first_pane_id = PaneId::Terminal(1);
-    let new_pane_id_1 = PaneId::Terminal(2);
-    let new_pane_id_2 = PaneId::Terminal(3);
-    tab.horizontal_split(new_pane_id_1, 1);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+
+    // the right frame of the left pane
+    tab.handle_left_click(&Position::new(5, 60), 1);
+    tab.handle_mouse_hold(&Position::new(5, 65), 1);
+    tab.handle_mouse_release(&Position::new(5, 70), 1);
+
+    let pane_1 = tab.tiled_panes.panes.get(&PaneId::Terminal(1)).unwrap();
+    let pane_2 = tab.tiled_panes.panes.get(&PaneId::Terminal(2)).unwrap();
+    assert!(
+        (69..=72).contains(&pane_2.x()),
+        "border moved with the mouse (to {})",
+        pane_2.x()
+    );
+    assert_eq!(
+        pane_1.cols() + pane_2.cols(),
+        121,
+        "panes still fill the whole tab"
+    );
+    assert!(
+        !tab.tiled_panes.border_is_being_dragged(),
+        "dragging stopped on release"
+    );
+}
+
+#[test]
+pub fn swap_pane_left() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let new_pane_id = PaneId::Terminal(2);
+
+    tab.vertical_split(new_pane_id, 1);
+    tab.toggle_active_pane_fullscreen(1);
+    tab.swap_pane(1, Direction::Left);
+
+    assert!(!tab.is_fullscreen_active(), "Fullscreen was exited");
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().pid(),
+        PaneId::Terminal(2),
+        "Active pane did not change"
+    );
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().x(),
+        0,
+        "Active pane moved to the left"
+    );
+    assert_eq!(
+        tab.tiled_panes.panes.get(&PaneId::Terminal(1)).unwrap().x(),
+        61,
+        "Swapped pane moved to the right"
+    );
+}
+
+#[test]
+pub fn move_active_pane_left_to_the_most_recently_used_position() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let new_pane_id_1 = PaneId::Terminal(2);
+    let new_pane_id_2 = PaneId::Terminal(3);
+    let new_pane_id_3 = PaneId::Terminal(4);
+
+    tab.vertical_split(new_pane_id_1, 1);
+    tab.move_focus_left(1);
     tab.horizontal_split(new_pane_id_2, 1);
-    tab.move_focus_up(1);
-    tab.resize_down(1);
+    tab.horizontal_split(new_pane_id_3, 1);
+    tab.move_focus_right(1);
+    tab.move_active_pane_left(1);
 
     assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&new_pane_id_1)
-            .unwrap()
-            .position_and_size()
-            .x,
+        tab.get_active_pane(1).unwrap().y(),
+        15,
+        "Active pane y position"
+    );
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().x(),
         0,
-        "focused pane x position"
+        "Active pane x position"
     );
+
     assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&new_pane_id_1)
-            .unwrap()
-            .position_and_size()
-            .y,
+        tab.get_active_pane(1).unwrap().pid(),
+        PaneId::Terminal(2),
+        "Active pane PaneId"
+    );
+}
+
+#[test]
+pub fn move_active_pane_right() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let new_pane_id = PaneId::Terminal(2);
+
+    tab.vertical_split(new_pane_id, 1);
+    tab.move_focus_left(1);
+    tab.move_active_pane_right(1);
+
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().x(),
+        61,
+        "Active pane is the right one"
+    );
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().pid(),
+        PaneId::Terminal(1),
+        "Active pane is the right one"
+    );
+}
+
+#[test]
+pub fn move_active_pane_right_to_the_most_recently_used_position() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let new_pane_id_1 = PaneId::Terminal(2);
+    let new_pane_id_2 = PaneId::Terminal(3);
+    let new_pane_id_3 = PaneId::Terminal(4);
+
+    tab.vertical_split(new_pane_id_1, 1);
+    tab.horizontal_split(new_pane_id_2, 1);
+    tab.horizontal_split(new_pane_id_3, 1);
+    tab.move_focus_left(1);
+    tab.move_active_pane_right(1);
+
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().y(),
         15,
-        "focused pane y position"
+        "Active pane y position"
     );
     assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&new_pane_id_1)
-            .unwrap()
-            .position_and_size()
-            .cols
-            .as_usize(),
-        121,
-        "focused pane column count"
+        tab.get_active_pane(1).unwrap().x(),
+        61,
+        "Active pane x position"
     );
     assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&new_pane_id_1)
-            .unwrap()
-            .position_and_size()
-            .rows
-            .as_usize(),
-        9,
-        "focused pane row count"
+        tab.get_active_pane(1).unwrap().pid(),
+        PaneId::Terminal(1),
+        "Active pane Paneid"
     );
+}
+
+#[test]
+pub fn resize_down_with_pane_above() {
+    // ┌───────────┐                  ┌───────────┐
+    // │           │                  │           │
+    // │           │                  │           │
+    // ├───────────┤ ==resize=down==> │           │
+    // │███████████│                  ├───────────┤
+    // │███████████│                  │███████████│
+    // │███████████│                  │███████████│
+    // └───────────┘                  └───────────┘
+    // █ == focused pane
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let new_pane_id = PaneId::Terminal(2);
+    tab.horizontal_split(new_pane_id, 1);
+    tab.resize_down(1);
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&new_pane_id_2)
+            .get(&new_pane_id)
             .unwrap()
             .position_and_size()
             .x,
         0,
-        "pane below x position"
+        "focused pane x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&new_pane_id_2)
+            .get(&new_pane_id)
             .unwrap()
             .position_and_size()
             .y,
-        24,
-        "pane below y position"
+        11,
+        "focused pane y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&new_pane_id_2)
+            .get(&new_pane_id)
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
         121,
-        "pane below column count"
+        "focused pane column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&new_pane_id_2)
+            .get(&new_pane_id)
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        6,
-        "pane below row count"
+        9,
+        "focused pane row count"
     );
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&first_pane_id)
+            .get(&PaneId::Terminal(1))
             .unwrap()
             .position_and_size()
             .x,
@@ -3380,7 +3924,7 @@ pub fn resize_down_with_panes_above_and_below() {
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&first_pane_id)
+            .get(&PaneId::Terminal(1))
             .unwrap()
             .position_and_size()
             .y,
@@ -3390,7 +3934,7 @@ pub fn resize_down_with_panes_above_and_below() {
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&first_pane_id)
+            .get(&PaneId::Terminal(1))
             .unwrap()
             .position_and_size()
             .cols
@@ -3401,296 +3945,250 @@ pub fn resize_down_with_panes_above_and_below() {
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&first_pane_id)
+            .get(&PaneId::Terminal(1))
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        15,
+        11,
         "pane above row count"
     );
 }
 
 #[test]
-pub fn resize_down_with_multiple_panes_above() {
-    //
-    // ┌─────┬─────┐                    ┌─────┬─────┐
-    // │     │     │                    │     │     │
-    // ├─────┴─────┤  ==resize=down==>  │     │     │
-    // │███████████│                    ├─────┴─────┤
-    // │███████████│                    │███████████│
-    // └───────────┘                    └───────────┘
+pub fn resize_down_with_pane_below() {
+    // ┌───────────┐                  ┌───────────┐
+    // │███████████│                  │███████████│
+    // │███████████│                  │███████████│
+    // ├───────────┤ ==resize=down==> │███████████│
+    // │           │                  ├───────────┤
+    // │           │                  │           │
+    // └───────────┘                  └───────────┘
     // █ == focused pane
     let size = Size {
         cols: 121,
-        rows: 30,
+        rows: 20,
     };
     let mut tab = create_new_tab(size);
-    let first_pane_id = PaneId::Terminal(1);
-    let new_pane_id_1 = PaneId::Terminal(2);
-    let new_pane_id_2 = PaneId::Terminal(3);
-    tab.horizontal_split(new_pane_id_1, 1);
+    let new_pane_id = PaneId::Terminal(2);
+    tab.horizontal_split(new_pane_id, 1);
     tab.move_focus_up(1);
-    tab.vertical_split(new_pane_id_2, 1);
-    tab.move_focus_down(1);
     tab.resize_down(1);
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&new_pane_id_1)
+            .get(&new_pane_id)
             .unwrap()
             .position_and_size()
             .x,
         0,
-        "focused pane x position"
+        "pane below x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&new_pane_id_1)
+            .get(&new_pane_id)
             .unwrap()
             .position_and_size()
             .y,
-        16,
-        "focused pane y position"
+        11,
+        "pane below y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&new_pane_id_1)
+            .get(&new_pane_id)
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
         121,
-        "focused pane column count"
+        "pane below column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&new_pane_id_1)
+            .get(&new_pane_id)
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        14,
-        "focused pane row count"
+        9,
+        "pane below row count"
     );
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&new_pane_id_2)
+            .get(&PaneId::Terminal(1))
             .unwrap()
             .position_and_size()
             .x,
-        61,
-        "first pane above x position"
+        0,
+        "focused pane x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&new_pane_id_2)
+            .get(&PaneId::Terminal(1))
             .unwrap()
             .position_and_size()
             .y,
         0,
-        "first pane above y position"
+        "focused pane y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&new_pane_id_2)
-            .unwrap()
-            .position_and_size()
-            .cols
-            .as_usize(),
-        60,
-        "first pane above column count"
-    );
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&new_pane_id_2)
-            .unwrap()
-            .position_and_size()
-            .rows
-            .as_usize(),
-        16,
-        "first pane above row count"
-    );
-
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&first_pane_id)
-            .unwrap()
-            .position_and_size()
-            .x,
-        0,
-        "second pane above x position"
-    );
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&first_pane_id)
-            .unwrap()
-            .position_and_size()
-            .y,
-        0,
-        "second pane above y position"
-    );
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&first_pane_id)
+            .get(&PaneId::Terminal(1))
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
-        61,
-        "second pane above column count"
+        121,
+        "focused pane column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&first_pane_id)
+            .get(&PaneId::Terminal(1))
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        16,
-        "second pane above row count"
+        11,
+        "focused pane row count"
     );
 }
 
 #[test]
-pub fn resize_down_with_panes_above_aligned_left_with_current_pane() {
-    // ┌─────┬─────┐                    ┌─────┬─────┐
-    // │     │     │                    │     │     │
-    // │     │     │                    │     │     │
-    // ├─────┼─────┤  ==resize=down==>  ├─────┤     │
-    // │     │█████│                    │     ├─────┤
-    // │     │█████│                    │     │█████│
-    // └─────┴─────┘                    └─────┴─────┘
+pub fn resize_down_with_panes_above_and_below() {
+    // ┌───────────┐                  ┌───────────┐
+    // │           │                  │           │
+    // │           │                  │           │
+    // ├───────────┤                  ├───────────┤
+    // │███████████│ ==resize=down==> │███████████│
+    // │███████████│                  │███████████│
+    // │███████████│                  │███████████│
+    // ├───────────┤                  │███████████│
+    // │           │                  ├───────────┤
+    // │           │                  │           │
+    // └───────────┘                  └───────────┘
     // █ == focused pane
     let size = Size {
         cols: 121,
         rows: 30,
     };
     let mut tab = create_new_tab(size);
-    let pane_above_and_left = PaneId::Terminal(1);
-    let pane_to_the_left = PaneId::Terminal(2);
-    let focused_pane = PaneId::Terminal(3);
-    let pane_above = PaneId::Terminal(4);
-    tab.horizontal_split(pane_to_the_left, 1);
-    tab.vertical_split(focused_pane, 1);
+    let first_pane_id = PaneId::Terminal(1);
+    let new_pane_id_1 = PaneId::Terminal(2);
+    let new_pane_id_2 = PaneId::Terminal(3);
+    tab.horizontal_split(new_pane_id_1, 1);
+    tab.horizontal_split(new_pane_id_2, 1);
     tab.move_focus_up(1);
-    tab.vertical_split(pane_above, 1);
-    tab.move_focus_down(1);
     tab.resize_down(1);
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&focused_pane)
+            .get(&new_pane_id_1)
             .unwrap()
             .position_and_size()
             .x,
-        61,
+        0,
         "focused pane x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&focused_pane)
+            .get(&new_pane_id_1)
             .unwrap()
             .position_and_size()
             .y,
-        16,
+        15,
         "focused pane y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&focused_pane)
+            .get(&new_pane_id_1)
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
-        60,
+        121,
         "focused pane column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&focused_pane)
+            .get(&new_pane_id_1)
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        14,
+        9,
         "focused pane row count"
     );
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_above_and_left)
+            .get(&new_pane_id_2)
             .unwrap()
             .position_and_size()
             .x,
         0,
-        "pane above and to the left x position"
+        "pane below x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_above_and_left)
+            .get(&new_pane_id_2)
             .unwrap()
             .position_and_size()
             .y,
-        0,
-        "pane above and to the left y position"
+        24,
+        "pane below y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_above_and_left)
+            .get(&new_pane_id_2)
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
-        61,
-        "pane above and to the left column count"
+        121,
+        "pane below column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_above_and_left)
+            .get(&new_pane_id_2)
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        15,
-        "pane above and to the left row count"
+        6,
+        "pane below row count"
     );
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_above)
+            .get(&first_pane_id)
             .unwrap()
             .position_and_size()
             .x,
-        61,
+        0,
         "pane above x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_above)
+            .get(&first_pane_id)
             .unwrap()
             .position_and_size()
             .y,
@@ -3700,295 +4198,205 @@ pub fn resize_down_with_panes_above_aligned_left_with_current_pane() {
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_above)
+            .get(&first_pane_id)
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
-        60,
+        121,
         "pane above column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_above)
+            .get(&first_pane_id)
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        16,
+        15,
         "pane above row count"
     );
+}
+
+#[test]
+pub fn resize_down_with_multiple_panes_above() {
+    //
+    // ┌─────┬─────┐                    ┌─────┬─────┐
+    // │     │     │                    │     │     │
+    // ├─────┴─────┤  ==resize=down==>  │     │     │
+    // │███████████│                    ├─────┴─────┤
+    // │███████████│                    │███████████│
+    // └───────────┘                    └───────────┘
+    // █ == focused pane
+    let size = Size {
+        cols: 121,
+        rows: 30,
+    };
+    let mut tab = create_new_tab(size);
+    let first_pane_id = PaneId::Terminal(1);
+    let new_pane_id_1 = PaneId::Terminal(2);
+    let new_pane_id_2 = PaneId::Terminal(3);
+    tab.horizontal_split(new_pane_id_1, 1);
+    tab.move_focus_up(1);
+    tab.vertical_split(new_pane_id_2, 1);
+    tab.move_focus_down(1);
+    tab.resize_down(1);
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_to_the_left)
+            .get(&new_pane_id_1)
             .unwrap()
             .position_and_size()
             .x,
         0,
-        "pane to the left x position"
+        "focused pane x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_to_the_left)
+            .get(&new_pane_id_1)
             .unwrap()
             .position_and_size()
             .y,
-        15,
-        "pane to the left y position"
+        16,
+        "focused pane y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_to_the_left)
+            .get(&new_pane_id_1)
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
-        61,
-        "pane to the left column count"
+        121,
+        "focused pane column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_to_the_left)
+            .get(&new_pane_id_1)
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        15,
-        "pane to the left row count"
+        14,
+        "focused pane row count"
     );
-}
-
-#[test]
-pub fn resize_down_with_panes_below_aligned_left_with_current_pane() {
-    // ┌─────┬─────┐                    ┌─────┬─────┐
-    // │     │█████│                    │     │█████│
-    // │     │█████│                    │     │█████│
-    // ├─────┼─────┤  ==resize=down==>  ├─────┤█████│
-    // │     │     │                    │     ├─────┤
-    // │     │     │                    │     │     │
-    // └─────┴─────┘                    └─────┴─────┘
-    // █ == focused pane
-
-    let size = Size {
-        cols: 121,
-        rows: 30,
-    };
-    let mut tab = create_new_tab(size);
-    let pane_to_the_left = PaneId::Terminal(1);
-    let pane_below_and_left = PaneId::Terminal(2);
-    let pane_below = PaneId::Terminal(3);
-    let focused_pane = PaneId::Terminal(4);
-    tab.horizontal_split(pane_below_and_left, 1);
-    tab.vertical_split(pane_below, 1);
-    tab.move_focus_up(1);
-    tab.vertical_split(focused_pane, 1);
-    tab.resize_down(1);
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&focused_pane)
+            .get(&new_pane_id_2)
             .unwrap()
             .position_and_size()
             .x,
         61,
-        "focused pane x position"
+        "first pane above x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&focused_pane)
+            .get(&new_pane_id_2)
             .unwrap()
             .position_and_size()
             .y,
         0,
-        "focused pane y position"
+        "first pane above y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&focused_pane)
+            .get(&new_pane_id_2)
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
         60,
-        "focused pane column count"
+        "first pane above column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&focused_pane)
+            .get(&new_pane_id_2)
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
         16,
-        "focused pane row count"
+        "first pane above row count"
     );
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_to_the_left)
+            .get(&first_pane_id)
             .unwrap()
             .position_and_size()
             .x,
         0,
-        "pane above and to the left x position"
+        "second pane above x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_to_the_left)
+            .get(&first_pane_id)
             .unwrap()
             .position_and_size()
             .y,
         0,
-        "pane above and to the left y position"
+        "second pane above y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_to_the_left)
+            .get(&first_pane_id)
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
         61,
-        "pane above and to the left column count"
+        "second pane above column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_to_the_left)
+            .get(&first_pane_id)
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        15,
-        "pane above and to the left row count"
+        16,
+        "second pane above row count"
     );
-
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&pane_below)
-            .unwrap()
-            .position_and_size()
-            .x,
-        61,
-        "pane above x position"
-    );
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&pane_below)
-            .unwrap()
-            .position_and_size()
-            .y,
-        16,
-        "pane above y position"
-    );
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&pane_below)
-            .unwrap()
-            .position_and_size()
-            .cols
-            .as_usize(),
-        60,
-        "pane above column count"
-    );
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&pane_below)
-            .unwrap()
-            .position_and_size()
-            .rows
-            .as_usize(),
-        14,
-        "pane above row count"
-    );
-
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&pane_below_and_left)
-            .unwrap()
-            .position_and_size()
-            .x,
-        0,
-        "pane to the left x position"
-    );
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&pane_below_and_left)
-            .unwrap()
-            .position_and_size()
-            .y,
-        15,
-        "pane to the left y position"
-    );
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&pane_below_and_left)
-            .unwrap()
-            .position_and_size()
-            .cols
-            .as_usize(),
-        61,
-        "pane to the left column count"
-    );
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&pane_below_and_left)
-            .unwrap()
-            .position_and_size()
-            .rows
-            .as_usize(),
-        15,
-        "pane to the left row count"
-    );
-}
+}
 
 #[test]
-pub fn resize_down_with_panes_above_aligned_right_with_current_pane() {
+pub fn resize_down_with_panes_above_aligned_left_with_current_pane() {
     // ┌─────┬─────┐                    ┌─────┬─────┐
     // │     │     │                    │     │     │
     // │     │     │                    │     │     │
-    // ├─────┼─────┤  ==resize=down==>  │     ├─────┤
-    // │█████│     │                    ├─────┤     │
-    // │█████│     │                    │█████│     │
+    // ├─────┼─────┤  ==resize=down==>  ├─────┤     │
+    // │     │█████│                    │     ├─────┤
+    // │     │█████│                    │     │█████│
     // └─────┴─────┘                    └─────┴─────┘
     // █ == focused pane
-
     let size = Size {
         cols: 121,
         rows: 30,
     };
     let mut tab = create_new_tab(size);
-    let pane_above = PaneId::Terminal(1);
-    let focused_pane = PaneId::Terminal(2);
-    let pane_to_the_right = PaneId::Terminal(3);
-    let pane_above_and_right = PaneId::Terminal(4);
-    tab.horizontal_split(focused_pane, 1);
-    tab.vertical_split(pane_to_the_right, 1);
+    let pane_above_and_left = PaneId::Terminal(1);
+    let pane_to_the_left = PaneId::Terminal(2);
+    let focused_pane = PaneId::Terminal(3);
+    let pane_above = PaneId::Terminal(4);
+    tab.horizontal_split(pane_to_the_left, 1);
+    tab.vertical_split(focused_pane, 1);
     tab.move_focus_up(1);
-    tab.vertical_split(pane_above_and_right, 1);
+    tab.vertical_split(pane_above, 1);
     tab.move_focus_down(1);
-    tab.move_focus_left(1);
     tab.resize_down(1);
 
     assert_eq!(
@@ -3998,7 +4406,7 @@ pub fn resize_down_with_panes_above_aligned_right_with_current_pane() {
             .unwrap()
             .position_and_size()
             .x,
-        0,
+        61,
         "focused pane x position"
     );
     assert_eq!(
@@ -4019,7 +4427,7 @@ pub fn resize_down_with_panes_above_aligned_right_with_current_pane() {
             .position_and_size()
             .cols
             .as_usize(),
-        61,
+        60,
         "focused pane column count"
     );
     assert_eq!(
@@ -4037,140 +4445,140 @@ pub fn resize_down_with_panes_above_aligned_right_with_current_pane() {
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_above)
+            .get(&pane_above_and_left)
             .unwrap()
             .position_and_size()
             .x,
         0,
-        "pane above x position"
+        "pane above and to the left x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_above)
+            .get(&pane_above_and_left)
             .unwrap()
             .position_and_size()
             .y,
         0,
-        "pane above y position"
+        "pane above and to the left y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_above)
+            .get(&pane_above_and_left)
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
         61,
-        "pane above column count"
+        "pane above and to the left column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_above)
+            .get(&pane_above_and_left)
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        16,
-        "pane above row count"
+        15,
+        "pane above and to the left row count"
     );
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_to_the_right)
+            .get(&pane_above)
             .unwrap()
             .position_and_size()
             .x,
         61,
-        "pane to the right x position"
+        "pane above x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_to_the_right)
+            .get(&pane_above)
             .unwrap()
             .position_and_size()
             .y,
-        15,
-        "pane to the right y position"
+        0,
+        "pane above y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_to_the_right)
+            .get(&pane_above)
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
         60,
-        "pane to the right column count"
+        "pane above column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_to_the_right)
+            .get(&pane_above)
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        15,
-        "pane to the right row count"
+        16,
+        "pane above row count"
     );
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_above_and_right)
+            .get(&pane_to_the_left)
             .unwrap()
             .position_and_size()
             .x,
-        61,
-        "pane above and to the right x position"
+        0,
+        "pane to the left x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_above_and_right)
+            .get(&pane_to_the_left)
             .unwrap()
             .position_and_size()
             .y,
-        0,
-        "pane above and to the right y position"
+        15,
+        "pane to the left y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_above_and_right)
+            .get(&pane_to_the_left)
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
-        60,
-        "pane above and to the right column count"
+        61,
+        "pane to the left column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_above_and_right)
+            .get(&pane_to_the_left)
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
         15,
-        "pane above and to the right row count"
+        "pane to the left row count"
     );
 }
 
 #[test]
-pub fn resize_down_with_panes_below_aligned_right_with_current_pane() {
+pub fn resize_down_with_panes_below_aligned_left_with_current_pane() {
     // ┌─────┬─────┐                    ┌─────┬─────┐
-    // │█████│     │                    │█████│     │
-    // │█████│     │                    │█████│     │
-    // ├─────┼─────┤  ==resize=down==>  │█████├─────┤
-    // │     │     │                    ├─────┤     │
+    // │     │█████│                    │     │█████│
+    // │     │█████│                    │     │█████│
+    // ├─────┼─────┤  ==resize=down==>  ├─────┤█████│
+    // │     │     │                    │     ├─────┤
     // │     │     │                    │     │     │
     // └─────┴─────┘                    └─────┴─────┘
     // █ == focused pane
@@ -4180,15 +4588,14 @@ pub fn resize_down_with_panes_below_aligned_right_with_current_pane() {
         rows: 30,
     };
     let mut tab = create_new_tab(size);
-    let focused_pane = PaneId::Terminal(1);
-    let pane_below = PaneId::Terminal(2);
-    let pane_below_and_right = PaneId::Terminal(3);
-    let pane_to_the_right = PaneId::Terminal(4);
-    tab.horizontal_split(pane_below, 1);
-    tab.vertical_split(pane_below_and_right, 1);
+    let pane_to_the_left = PaneId::Terminal(1);
+    let pane_below_and_left = PaneId::Terminal(2);
+    let pane_below = PaneId::Terminal(3);
+    let focused_pane = PaneId::Terminal(4);
+    tab.horizontal_split(pane_below_and_left, 1);
+    tab.vertical_split(pane_below, 1);
     tab.move_focus_up(1);
-    tab.vertical_split(pane_to_the_right, 1);
-    tab.move_focus_left(1);
+    tab.vertical_split(focused_pane, 1);
     tab.resize_down(1);
 
     assert_eq!(
@@ -4198,7 +4605,7 @@ pub fn resize_down_with_panes_below_aligned_right_with_current_pane() {
             .unwrap()
             .position_and_size()
             .x,
-        0,
+        61,
         "focused pane x position"
     );
     assert_eq!(
@@ -4219,7 +4626,7 @@ pub fn resize_down_with_panes_below_aligned_right_with_current_pane() {
             .position_and_size()
             .cols
             .as_usize(),
-        61,
+        60,
         "focused pane column count"
     );
     assert_eq!(
@@ -4237,142 +4644,142 @@ pub fn resize_down_with_panes_below_aligned_right_with_current_pane() {
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_below)
+            .get(&pane_to_the_left)
             .unwrap()
             .position_and_size()
             .x,
         0,
-        "pane below x position"
+        "pane above and to the left x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_below)
+            .get(&pane_to_the_left)
             .unwrap()
             .position_and_size()
             .y,
-        16,
-        "pane below y position"
+        0,
+        "pane above and to the left y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_below)
+            .get(&pane_to_the_left)
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
         61,
-        "pane below column count"
+        "pane above and to the left column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_below)
+            .get(&pane_to_the_left)
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        14,
-        "pane below row count"
+        15,
+        "pane above and to the left row count"
     );
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_below_and_right)
+            .get(&pane_below)
             .unwrap()
             .position_and_size()
             .x,
         61,
-        "pane below and to the right x position"
+        "pane above x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_below_and_right)
+            .get(&pane_below)
             .unwrap()
             .position_and_size()
             .y,
-        15,
-        "pane below and to the right y position"
+        16,
+        "pane above y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_below_and_right)
+            .get(&pane_below)
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
         60,
-        "pane below and to the right column count"
+        "pane above column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_below_and_right)
+            .get(&pane_below)
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        15,
-        "pane below and to the right row count"
+        14,
+        "pane above row count"
     );
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_to_the_right)
+            .get(&pane_below_and_left)
             .unwrap()
             .position_and_size()
             .x,
-        61,
-        "pane to the right x position"
+        0,
+        "pane to the left x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_to_the_right)
+            .get(&pane_below_and_left)
             .unwrap()
             .position_and_size()
             .y,
-        0,
-        "pane to the right y position"
+        15,
+        "pane to the left y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_to_the_right)
+            .get(&pane_below_and_left)
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
-        60,
-        "pane to the right column count"
+        61,
+        "pane to the left column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&pane_to_the_right)
+            .get(&pane_below_and_left)
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
         15,
-        "pane to the right row count"
+        "pane to the left row count"
     );
 }
 
 #[test]
-pub fn resize_down_with_panes_above_aligned_left_and_right_with_current_pane() {
-    // ┌───┬───┬───┐                    ┌───┬───┬───┐
-    // │   │   │   │                    │   │   │   │
-    // │   │   │   │                    │   │   │   │
-    // ├───┼───┼───┤  ==resize=down==>  ├───┤   ├───┤
-    // │   │███│   │                    │   ├───┤   │
-    // │   │███│   │                    │   │███│   │
-    // └───┴───┴───┘                    └───┴───┴───┘
+pub fn resize_down_with_panes_above_aligned_right_with_current_pane() {
+    // ┌─────┬─────┐                    ┌─────┬─────┐
+    // │     │     │                    │     │     │
+    // │     │     │                    │     │     │
+    // ├─────┼─────┤  ==resize=down==>  │     ├─────┤
+    // │█████│     │                    ├─────┤     │
+    // │█████│     │                    │█████│     │
+    // └─────┴─────┘                    └─────┴─────┘
     // █ == focused pane
 
     let size = Size {
@@ -4380,378 +4787,495 @@ pub fn resize_down_with_panes_above_aligned_left_and_right_with_current_pane() {
         rows: 30,
     };
     let mut tab = create_new_tab(size);
-    tab.horizontal_split(PaneId::Terminal(2), 1);
-    tab.vertical_split(PaneId::Terminal(3), 1);
-    tab.vertical_split(PaneId::Terminal(4), 1);
+    let pane_above = PaneId::Terminal(1);
+    let focused_pane = PaneId::Terminal(2);
+    let pane_to_the_right = PaneId::Terminal(3);
+    let pane_above_and_right = PaneId::Terminal(4);
+    tab.horizontal_split(focused_pane, 1);
+    tab.vertical_split(pane_to_the_right, 1);
     tab.move_focus_up(1);
-    tab.vertical_split(PaneId::Terminal(5), 1);
-    tab.vertical_split(PaneId::Terminal(6), 1);
-    tab.move_focus_left(1);
+    tab.vertical_split(pane_above_and_right, 1);
     tab.move_focus_down(1);
+    tab.move_focus_left(1);
     tab.resize_down(1);
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(1))
+            .get(&focused_pane)
             .unwrap()
             .position_and_size()
             .x,
         0,
-        "pane 1 x position"
+        "focused pane x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(1))
+            .get(&focused_pane)
             .unwrap()
             .position_and_size()
             .y,
-        0,
-        "pane 1 y position"
+        16,
+        "focused pane y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(1))
+            .get(&focused_pane)
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
         61,
-        "pane 1 column count"
+        "focused pane column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(1))
+            .get(&focused_pane)
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        15,
-        "pane 1 row count"
+        14,
+        "focused pane row count"
     );
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(2))
+            .get(&pane_above)
             .unwrap()
             .position_and_size()
             .x,
         0,
-        "pane 2 x position"
+        "pane above x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(2))
+            .get(&pane_above)
             .unwrap()
             .position_and_size()
             .y,
-        15,
-        "pane 2 y position"
+        0,
+        "pane above y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(2))
+            .get(&pane_above)
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
         61,
-        "pane 2 column count"
+        "pane above column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(2))
+            .get(&pane_above)
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        15,
-        "pane 2 row count"
+        16,
+        "pane above row count"
     );
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(3))
+            .get(&pane_to_the_right)
             .unwrap()
             .position_and_size()
             .x,
         61,
-        "pane 3 x position"
+        "pane to the right x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(3))
+            .get(&pane_to_the_right)
             .unwrap()
             .position_and_size()
             .y,
-        16,
-        "pane 3 y position"
+        15,
+        "pane to the right y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(3))
+            .get(&pane_to_the_right)
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
-        30,
-        "pane 3 column count"
+        60,
+        "pane to the right column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(3))
+            .get(&pane_to_the_right)
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        14,
-        "pane 3 row count"
+        15,
+        "pane to the right row count"
     );
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(4))
+            .get(&pane_above_and_right)
             .unwrap()
             .position_and_size()
             .x,
-        91,
-        "pane 4 x position"
+        61,
+        "pane above and to the right x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(4))
+            .get(&pane_above_and_right)
             .unwrap()
             .position_and_size()
             .y,
-        15,
-        "pane 4 y position"
+        0,
+        "pane above and to the right y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(4))
+            .get(&pane_above_and_right)
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
-        30,
-        "pane 4 column count"
+        60,
+        "pane above and to the right column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(4))
+            .get(&pane_above_and_right)
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
         15,
-        "pane 4 row count"
+        "pane above and to the right row count"
     );
+}
+
+#[test]
+pub fn resize_down_with_panes_below_aligned_right_with_current_pane() {
+    // ┌─────┬─────┐                    ┌─────┬─────┐
+    // │█████│     │                    │█████│     │
+    // │█████│     │                    │█████│     │
+    // ├─────┼─────┤  ==resize=down==>  │█████├─────┤
+    // │     │     │                    ├─────┤     │
+    // │     │     │                    │     │     │
+    // └─────┴─────┘                    └─────┴─────┘
+    // █ == focused pane
+
+    let size = Size {
+        cols: 121,
+        rows: 30,
+    };
+    let mut tab = create_new_tab(size);
+    let focused_pane = PaneId::Terminal(1);
+    let pane_below = PaneId::Terminal(2);
+    let pane_below_and_right = PaneId::Terminal(3);
+    let pane_to_the_right = PaneId::Terminal(4);
+    tab.horizontal_split(pane_below, 1);
+    tab.vertical_split(pane_below_and_right, 1);
+    tab.move_focus_up(1);
+    tab.vertical_split(pane_to_the_right, 1);
+    tab.move_focus_left(1);
+    tab.resize_down(1);
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(5))
+            .get(&focused_pane)
             .unwrap()
             .position_and_size()
             .x,
-        61,
-        "pane 5 x position"
+        0,
+        "focused pane x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(5))
+            .get(&focused_pane)
             .unwrap()
             .position_and_size()
             .y,
         0,
-        "pane 5 y position"
+        "focused pane y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(5))
+            .get(&focused_pane)
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
-        30,
-        "pane 5 column count"
+        61,
+        "focused pane column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(5))
+            .get(&focused_pane)
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
         16,
-        "pane 5 row count"
+        "focused pane row count"
     );
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(6))
+            .get(&pane_below)
             .unwrap()
             .position_and_size()
             .x,
-        91,
-        "pane 6 x position"
+        0,
+        "pane below x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(6))
+            .get(&pane_below)
             .unwrap()
             .position_and_size()
             .y,
-        0,
-        "pane 6 y position"
+        16,
+        "pane below y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(6))
+            .get(&pane_below)
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
-        30,
-        "pane 6 column count"
+        61,
+        "pane below column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(6))
+            .get(&pane_below)
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        15,
-        "pane 6 row count"
+        14,
+        "pane below row count"
     );
-}
-
-#[test]
-pub fn resize_down_with_panes_below_aligned_left_and_right_with_current_pane() {
-    // ┌───┬───┬───┐                    ┌───┬───┬───┐
-    // │   │███│   │                    │   │███│   │
-    // │   │███│   │                    │   │███│   │
-    // ├───┼───┼───┤  ==resize=down==>  ├───┤███├───┤
-    // │   │   │   │                    │   ├───┤   │
-    // │   │   │   │                    │   │   │   │
-    // └───┴───┴───┘                    └───┴───┴───┘
-    // █ == focused pane
-
-    let size = Size {
-        cols: 121,
-        rows: 30,
-    };
-    let mut tab = create_new_tab(size);
-    tab.horizontal_split(PaneId::Terminal(2), 1);
-    tab.vertical_split(PaneId::Terminal(3), 1);
-    tab.vertical_split(PaneId::Terminal(4), 1);
-    tab.move_focus_up(1);
-    tab.vertical_split(PaneId::Terminal(5), 1);
-    tab.vertical_split(PaneId::Terminal(6), 1);
-    tab.move_focus_left(1);
-    tab.resize_down(1);
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(1))
+            .get(&pane_below_and_right)
             .unwrap()
             .position_and_size()
             .x,
-        0,
-        "pane 1 x position"
+        61,
+        "pane below and to the right x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(1))
+            .get(&pane_below_and_right)
             .unwrap()
             .position_and_size()
             .y,
-        0,
-        "pane 1 y position"
+        15,
+        "pane below and to the right y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(1))
+            .get(&pane_below_and_right)
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
-        61,
-        "pane 1 column count"
+        60,
+        "pane below and to the right column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(1))
+            .get(&pane_below_and_right)
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
         15,
-        "pane 1 row count"
+        "pane below and to the right row count"
     );
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(2))
+            .get(&pane_to_the_right)
             .unwrap()
             .position_and_size()
             .x,
-        0,
-        "pane 2 x position"
+        61,
+        "pane to the right x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(2))
+            .get(&pane_to_the_right)
             .unwrap()
             .position_and_size()
             .y,
-        15,
-        "pane 2 y position"
+        0,
+        "pane to the right y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(2))
+            .get(&pane_to_the_right)
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
-        61,
-        "pane 2 column count"
+        60,
+        "pane to the right column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(2))
+            .get(&pane_to_the_right)
+            .unwrap()
+            .position_and_size()
+            .rows
+            .as_usize(),
+        15,
+        "pane to the right row count"
+    );
+}
+
+#[test]
+pub fn resize_down_with_panes_above_aligned_left_and_right_with_current_pane() {
+    // ┌───┬───┬───┐                    ┌───┬───┬───┐
+    // │   │   │   │                    │   │   │   │
+    // │   │   │   │                    │   │   │   │
+    // ├───┼───┼───┤  ==resize=down==>  ├───┤   ├───┤
+    // │   │███│   │                    │   ├───┤   │
+    // │   │███│   │                    │   │███│   │
+    // └───┴───┴───┘                    └───┴───┴───┘
+    // █ == focused pane
+
+    let size = Size {
+        cols: 121,
+        rows: 30,
+    };
+    let mut tab = create_new_tab(size);
+    tab.horizontal_split(PaneId::Terminal(2), 1);
+    tab.vertical_split(PaneId::Terminal(3), 1);
+    tab.vertical_split(PaneId::Terminal(4), 1);
+    tab.move_focus_up(1);
+    tab.vertical_split(PaneId::Terminal(5), 1);
+    tab.vertical_split(PaneId::Terminal(6), 1);
+    tab.move_focus_left(1);
+    tab.move_focus_down(1);
+    tab.resize_down(1);
+
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(1))
+            .unwrap()
+            .position_and_size()
+            .x,
+        0,
+        "pane 1 x position"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(1))
+            .unwrap()
+            .position_and_size()
+            .y,
+        0,
+        "pane 1 y position"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(1))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        61,
+        "pane 1 column count"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(1))
+            .unwrap()
+            .position_and_size()
+            .rows
+            .as_usize(),
+        15,
+        "pane 1 row count"
+    );
+
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .x,
+        0,
+        "pane 2 x position"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .y,
+        15,
+        "pane 2 y position"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        61,
+        "pane 2 column count"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
             .unwrap()
             .position_and_size()
             .rows
@@ -4934,32 +5458,28 @@ pub fn resize_down_with_panes_below_aligned_left_and_right_with_current_pane() {
 }
 
 #[test]
-pub fn resize_down_with_panes_above_aligned_left_and_right_with_panes_to_the_left_and_right() {
-    // ┌─┬───────┬─┐                    ┌─┬───────┬─┐
-    // │ │       │ │                    │ │       │ │
-    // │ │       │ │                    │ │       │ │
-    // ├─┼─┬───┬─┼─┤  ==resize=down==>  ├─┤       ├─┤
-    // │ │ │███│ │ │                    │ ├─┬───┬─┤ │
-    // │ │ │███│ │ │                    │ │ │███│ │ │
-    // └─┴─┴───┴─┴─┘                    └─┴─┴───┴─┴─┘
+pub fn resize_down_with_panes_below_aligned_left_and_right_with_current_pane() {
+    // ┌───┬───┬───┐                    ┌───┬───┬───┐
+    // │   │███│   │                    │   │███│   │
+    // │   │███│   │                    │   │███│   │
+    // ├───┼───┼───┤  ==resize=down==>  ├───┤███├───┤
+    // │   │   │   │                    │   ├───┤   │
+    // │   │   │   │                    │   │   │   │
+    // └───┴───┴───┘                    └───┴───┴───┘
     // █ == focused pane
 
     let size = Size {
-        cols: 122,
+        cols: 121,
         rows: 30,
     };
     let mut tab = create_new_tab(size);
     tab.horizontal_split(PaneId::Terminal(2), 1);
-    tab.move_focus_up(1);
     tab.vertical_split(PaneId::Terminal(3), 1);
     tab.vertical_split(PaneId::Terminal(4), 1);
-    tab.move_focus_down(1);
+    tab.move_focus_up(1);
     tab.vertical_split(PaneId::Terminal(5), 1);
     tab.vertical_split(PaneId::Terminal(6), 1);
     tab.move_focus_left(1);
-    tab.vertical_split(PaneId::Terminal(7), 1);
-    tab.vertical_split(PaneId::Terminal(8), 1);
-    tab.move_focus_left(1);
     tab.resize_down(1);
 
     assert_eq!(
@@ -4990,7 +5510,7 @@ pub fn resize_down_with_panes_above_aligned_left_and_right_with_panes_to_the_lef
             .position_and_size()
             .cols
             .as_usize(),
-        60,
+        61,
         "pane 1 column count"
     );
     assert_eq!(
@@ -5033,7 +5553,7 @@ pub fn resize_down_with_panes_above_aligned_left_and_right_with_panes_to_the_lef
             .position_and_size()
             .cols
             .as_usize(),
-        60,
+        61,
         "pane 2 column count"
     );
     assert_eq!(
@@ -5055,7 +5575,7 @@ pub fn resize_down_with_panes_above_aligned_left_and_right_with_panes_to_the_lef
             .unwrap()
             .position_and_size()
             .x,
-        60,
+        61,
         "pane 3 x position"
     );
     assert_eq!(
@@ -5065,7 +5585,7 @@ pub fn resize_down_with_panes_above_aligned_left_and_right_with_panes_to_the_lef
             .unwrap()
             .position_and_size()
             .y,
-        0,
+        16,
         "pane 3 y position"
     );
     assert_eq!(
@@ -5076,7 +5596,7 @@ pub fn resize_down_with_panes_above_aligned_left_and_right_with_panes_to_the_lef
             .position_and_size()
             .cols
             .as_usize(),
-        31,
+        30,
         "pane 3 column count"
     );
     assert_eq!(
@@ -5087,7 +5607,7 @@ pub fn resize_down_with_panes_above_aligned_left_and_right_with_panes_to_the_lef
             .position_and_size()
             .rows
             .as_usize(),
-        16,
+        14,
         "pane 3 row count"
     );
 
@@ -5108,7 +5628,7 @@ pub fn resize_down_with_panes_above_aligned_left_and_right_with_panes_to_the_lef
             .unwrap()
             .position_and_size()
             .y,
-        0,
+        15,
         "pane 4 y position"
     );
     assert_eq!(
@@ -5119,7 +5639,7 @@ pub fn resize_down_with_panes_above_aligned_left_and_right_with_panes_to_the_lef
             .position_and_size()
             .cols
             .as_usize(),
-        31,
+        30,
         "pane 4 column count"
     );
     assert_eq!(
@@ -5141,7 +5661,7 @@ pub fn resize_down_with_panes_above_aligned_left_and_right_with_panes_to_the_lef
             .unwrap()
             .position_and_size()
             .x,
-        60,
+        61,
         "pane 5 x position"
     );
     assert_eq!(
@@ -5151,7 +5671,7 @@ pub fn resize_down_with_panes_above_aligned_left_and_right_with_panes_to_the_lef
             .unwrap()
             .position_and_size()
             .y,
-        16,
+        0,
         "pane 5 y position"
     );
     assert_eq!(
@@ -5162,7 +5682,7 @@ pub fn resize_down_with_panes_above_aligned_left_and_right_with_panes_to_the_lef
             .position_and_size()
             .cols
             .as_usize(),
-        15,
+        30,
         "pane 5 column count"
     );
     assert_eq!(
@@ -5173,7 +5693,7 @@ pub fn resize_down_with_panes_above_aligned_left_and_right_with_panes_to_the_lef
             .position_and_size()
             .rows
             .as_usize(),
-        14,
+        16,
         "pane 5 row count"
     );
 
@@ -5194,7 +5714,7 @@ pub fn resize_down_with_panes_above_aligned_left_and_right_with_panes_to_the_lef
             .unwrap()
             .position_and_size()
             .y,
-        15,
+        0,
         "pane 6 y position"
     );
     assert_eq!(
@@ -5205,7 +5725,7 @@ pub fn resize_down_with_panes_above_aligned_left_and_right_with_panes_to_the_lef
             .position_and_size()
             .cols
             .as_usize(),
-        31,
+        30,
         "pane 6 column count"
     );
     assert_eq!(
@@ -5219,209 +5739,121 @@ pub fn resize_down_with_panes_above_aligned_left_and_right_with_panes_to_the_lef
         15,
         "pane 6 row count"
     );
+}
+
+#[test]
+pub fn resize_down_with_panes_above_aligned_left_and_right_with_panes_to_the_left_and_right() {
+    // ┌─┬───────┬─┐                    ┌─┬───────┬─┐
+    // │ │       │ │                    │ │       │ │
+    // │ │       │ │                    │ │       │ │
+    // ├─┼─┬───┬─┼─┤  ==resize=down==>  ├─┤       ├─┤
+    // │ │ │███│ │ │                    │ ├─┬───┬─┤ │
+    // │ │ │███│ │ │                    │ │ │███│ │ │
+    // └─┴─┴───┴─┴─┘                    └─┴─┴───┴─┴─┘
+    // █ == focused pane
+
+    let size = Size {
+        cols: 122,
+        rows: 30,
+    };
+    let mut tab = create_new_tab(size);
+    tab.horizontal_split(PaneId::Terminal(2), 1);
+    tab.move_focus_up(1);
+    tab.vertical_split(PaneId::Terminal(3), 1);
+    tab.vertical_split(PaneId::Terminal(4), 1);
+    tab.move_focus_down(1);
+    tab.vertical_split(PaneId::Terminal(5), 1);
+    tab.vertical_split(PaneId::Terminal(6), 1);
+    tab.move_focus_left(1);
+    tab.vertical_split(PaneId::Terminal(7), 1);
+    tab.vertical_split(PaneId::Terminal(8), 1);
+    tab.move_focus_left(1);
+    tab.resize_down(1);
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(7))
+            .get(&PaneId::Terminal(1))
             .unwrap()
             .position_and_size()
             .x,
-        75,
-        "pane 7 x position"
+        0,
+        "pane 1 x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(7))
+            .get(&PaneId::Terminal(1))
             .unwrap()
             .position_and_size()
             .y,
-        16,
-        "pane 7 y position"
+        0,
+        "pane 1 y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(7))
+            .get(&PaneId::Terminal(1))
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
-        8,
-        "pane 7 column count"
+        60,
+        "pane 1 column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(7))
+            .get(&PaneId::Terminal(1))
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        14,
-        "pane 7 row count"
+        15,
+        "pane 1 row count"
     );
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(8))
+            .get(&PaneId::Terminal(2))
             .unwrap()
             .position_and_size()
             .x,
-        83,
-        "pane 8 x position"
+        0,
+        "pane 2 x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(8))
+            .get(&PaneId::Terminal(2))
             .unwrap()
             .position_and_size()
             .y,
-        16,
-        "pane 8 y position"
+        15,
+        "pane 2 y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(8))
+            .get(&PaneId::Terminal(2))
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
-        8,
-        "pane 8 column count"
+        60,
+        "pane 2 column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(8))
+            .get(&PaneId::Terminal(2))
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        14,
-        "pane 8 row count"
-    );
-}
-
-#[test]
-pub fn resize_down_with_panes_below_aligned_left_and_right_with_to_the_left_and_right() {
-    // ┌─┬─┬───┬─┬─┐                    ┌─┬─┬───┬─┬─┐
-    // │ │ │███│ │ │                    │ │ │███│ │ │
-    // │ │ │███│ │ │                    │ │ │███│ │ │
-    // ├─┼─┴───┴─┼─┤  ==resize=down==>  ├─┤ │███│ ├─┤
-    // │ │       │ │                    │ ├─┴───┴─┤ │
-    // │ │       │ │                    │ │       │ │
-    // └─┴───────┴─┘                    └─┴───────┴─┘
-    // █ == focused pane
-
-    let size = Size {
-        cols: 122,
-        rows: 30,
-    };
-    let mut tab = create_new_tab(size);
-    tab.horizontal_split(PaneId::Terminal(2), 1);
-    tab.move_focus_up(1);
-    tab.vertical_split(PaneId::Terminal(3), 1);
-    tab.vertical_split(PaneId::Terminal(4), 1);
-    tab.move_focus_left(1);
-    tab.vertical_split(PaneId::Terminal(5), 1);
-    tab.vertical_split(PaneId::Terminal(6), 1);
-    tab.move_focus_down(1);
-    tab.vertical_split(PaneId::Terminal(7), 1);
-    tab.vertical_split(PaneId::Terminal(8), 1);
-    tab.move_focus_left(1);
-    tab.move_focus_up(1);
-    tab.move_focus_left(1);
-    tab.resize_down(1);
-
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(1))
-            .unwrap()
-            .position_and_size()
-            .x,
-        0,
-        "pane 1 x position"
-    );
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(1))
-            .unwrap()
-            .position_and_size()
-            .y,
-        0,
-        "pane 1 y position"
-    );
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(1))
-            .unwrap()
-            .position_and_size()
-            .cols
-            .as_usize(),
-        60,
-        "pane 1 column count"
-    );
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(1))
-            .unwrap()
-            .position_and_size()
-            .rows
-            .as_usize(),
-        15,
-        "pane 1 row count"
-    );
-
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(2))
-            .unwrap()
-            .position_and_size()
-            .x,
-        0,
-        "pane 2 x position"
-    );
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(2))
-            .unwrap()
-            .position_and_size()
-            .y,
-        15,
-        "pane 2 y position"
-    );
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(2))
-            .unwrap()
-            .position_and_size()
-            .cols
-            .as_usize(),
-        60,
-        "pane 2 column count"
-    );
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(2))
-            .unwrap()
-            .position_and_size()
-            .rows
-            .as_usize(),
-        15,
-        "pane 2 row count"
+        15,
+        "pane 2 row count"
     );
 
     assert_eq!(
@@ -5452,7 +5884,7 @@ pub fn resize_down_with_panes_below_aligned_left_and_right_with_to_the_left_and_
             .position_and_size()
             .cols
             .as_usize(),
-        15,
+        31,
         "pane 3 column count"
     );
     assert_eq!(
@@ -5517,7 +5949,7 @@ pub fn resize_down_with_panes_below_aligned_left_and_right_with_to_the_left_and_
             .unwrap()
             .position_and_size()
             .x,
-        75,
+        60,
         "pane 5 x position"
     );
     assert_eq!(
@@ -5527,7 +5959,7 @@ pub fn resize_down_with_panes_below_aligned_left_and_right_with_to_the_left_and_
             .unwrap()
             .position_and_size()
             .y,
-        0,
+        16,
         "pane 5 y position"
     );
     assert_eq!(
@@ -5538,7 +5970,7 @@ pub fn resize_down_with_panes_below_aligned_left_and_right_with_to_the_left_and_
             .position_and_size()
             .cols
             .as_usize(),
-        8,
+        15,
         "pane 5 column count"
     );
     assert_eq!(
@@ -5549,7 +5981,7 @@ pub fn resize_down_with_panes_below_aligned_left_and_right_with_to_the_left_and_
             .position_and_size()
             .rows
             .as_usize(),
-        16,
+        14,
         "pane 5 row count"
     );
 
@@ -5560,7 +5992,7 @@ pub fn resize_down_with_panes_below_aligned_left_and_right_with_to_the_left_and_
             .unwrap()
             .position_and_size()
             .x,
-        83,
+        91,
         "pane 6 x position"
     );
     assert_eq!(
@@ -5570,7 +6002,7 @@ pub fn resize_down_with_panes_below_aligned_left_and_right_with_to_the_left_and_
             .unwrap()
             .position_and_size()
             .y,
-        0,
+        15,
         "pane 6 y position"
     );
     assert_eq!(
@@ -5581,7 +6013,7 @@ pub fn resize_down_with_panes_below_aligned_left_and_right_with_to_the_left_and_
             .position_and_size()
             .cols
             .as_usize(),
-        8,
+        31,
         "pane 6 column count"
     );
     assert_eq!(
@@ -5592,7 +6024,7 @@ pub fn resize_down_with_panes_below_aligned_left_and_right_with_to_the_left_and_
             .position_and_size()
             .rows
             .as_usize(),
-        16,
+        15,
         "pane 6 row count"
     );
 
@@ -5603,7 +6035,7 @@ pub fn resize_down_with_panes_below_aligned_left_and_right_with_to_the_left_and_
             .unwrap()
             .position_and_size()
             .x,
-        60,
+        75,
         "pane 7 x position"
     );
     assert_eq!(
@@ -5624,7 +6056,7 @@ pub fn resize_down_with_panes_below_aligned_left_and_right_with_to_the_left_and_
             .position_and_size()
             .cols
             .as_usize(),
-        31,
+        8,
         "pane 7 column count"
     );
     assert_eq!(
@@ -5646,7 +6078,7 @@ pub fn resize_down_with_panes_below_aligned_left_and_right_with_to_the_left_and_
             .unwrap()
             .position_and_size()
             .x,
-        91,
+        83,
         "pane 8 x position"
     );
     assert_eq!(
@@ -5656,7 +6088,7 @@ pub fn resize_down_with_panes_below_aligned_left_and_right_with_to_the_left_and_
             .unwrap()
             .position_and_size()
             .y,
-        15,
+        16,
         "pane 8 y position"
     );
     assert_eq!(
@@ -5667,7 +6099,7 @@ pub fn resize_down_with_panes_below_aligned_left_and_right_with_to_the_left_and_
             .position_and_size()
             .cols
             .as_usize(),
-        31,
+        8,
         "pane 8 column count"
     );
     assert_eq!(
@@ -5678,70 +6110,42 @@ pub fn resize_down_with_panes_below_aligned_left_and_right_with_to_the_left_and_
             .position_and_size()
             .rows
             .as_usize(),
-        15,
+        14,
         "pane 8 row count"
     );
 }
 
 #[test]
-pub fn cannot_resize_down_when_pane_below_is_at_minimum_height() {
-    // ┌───────────┐                  ┌───────────┐
-    // │███████████│                  │███████████│
-    // ├───────────┤ ==resize=down==> ├───────────┤
-    // │           │                  │           │
-    // └───────────┘                  └───────────┘
+pub fn resize_down_with_panes_below_aligned_left_and_right_with_to_the_left_and_right() {
+    // ┌─┬─┬───┬─┬─┐                    ┌─┬─┬───┬─┬─┐
+    // │ │ │███│ │ │                    │ │ │███│ │ │
+    // │ │ │███│ │ │                    │ │ │███│ │ │
+    // ├─┼─┴───┴─┼─┤  ==resize=down==>  ├─┤ │███│ ├─┤
+    // │ │       │ │                    │ ├─┴───┴─┤ │
+    // │ │       │ │                    │ │       │ │
+    // └─┴───────┴─┘                    └─┴───────┴─┘
     // █ == focused pane
 
     let size = Size {
-        cols: 121,
-        rows: 10,
+        cols: 122,
+        rows: 30,
     };
     let mut tab = create_new_tab(size);
     tab.horizontal_split(PaneId::Terminal(2), 1);
     tab.move_focus_up(1);
+    tab.vertical_split(PaneId::Terminal(3), 1);
+    tab.vertical_split(PaneId::Terminal(4), 1);
+    tab.move_focus_left(1);
+    tab.vertical_split(PaneId::Terminal(5), 1);
+    tab.vertical_split(PaneId::Terminal(6), 1);
+    tab.move_focus_down(1);
+    tab.vertical_split(PaneId::Terminal(7), 1);
+    tab.vertical_split(PaneId::Terminal(8), 1);
+    tab.move_focus_left(1);
+    tab.move_focus_up(1);
+    tab.move_focus_left(1);
     tab.resize_down(1);
 
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(1))
-            .unwrap()
-            .position_and_size()
-            .rows
-            .as_usize(),
-        5,
-        "pane 1 height stayed the same"
-    );
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(2))
-            .unwrap()
-            .position_and_size()
-            .rows
-            .as_usize(),
-        5,
-        "pane 2 height stayed the same"
-    );
-}
-
-#[test]
-pub fn resize_left_with_pane_to_the_left() {
-    // ┌─────┬─────┐                    ┌───┬───────┐
-    // │     │█████│                    │   │███████│
-    // │     │█████│  ==resize=left==>  │   │███████│
-    // │     │█████│                    │   │███████│
-    // └─────┴─────┘                    └───┴───────┘
-    // █ == focused pane
-
-    let size = Size {
-        cols: 121,
-        rows: 20,
-    };
-    let mut tab = create_new_tab(size);
-    tab.vertical_split(PaneId::Terminal(2), 1);
-    tab.resize_left(1);
-
     assert_eq!(
         tab.tiled_panes
             .panes
@@ -5770,7 +6174,7 @@ pub fn resize_left_with_pane_to_the_left() {
             .position_and_size()
             .cols
             .as_usize(),
-        54,
+        60,
         "pane 1 column count"
     );
     assert_eq!(
@@ -5781,7 +6185,7 @@ pub fn resize_left_with_pane_to_the_left() {
             .position_and_size()
             .rows
             .as_usize(),
-        20,
+        15,
         "pane 1 row count"
     );
 
@@ -5792,7 +6196,7 @@ pub fn resize_left_with_pane_to_the_left() {
             .unwrap()
             .position_and_size()
             .x,
-        54,
+        0,
         "pane 2 x position"
     );
     assert_eq!(
@@ -5802,7 +6206,7 @@ pub fn resize_left_with_pane_to_the_left() {
             .unwrap()
             .position_and_size()
             .y,
-        0,
+        15,
         "pane 2 y position"
     );
     assert_eq!(
@@ -5813,7 +6217,7 @@ pub fn resize_left_with_pane_to_the_left() {
             .position_and_size()
             .cols
             .as_usize(),
-        67,
+        60,
         "pane 2 column count"
     );
     assert_eq!(
@@ -5824,281 +6228,366 @@ pub fn resize_left_with_pane_to_the_left() {
             .position_and_size()
             .rows
             .as_usize(),
-        20,
+        15,
         "pane 2 row count"
     );
-}
-
-#[test]
-pub fn resize_left_with_pane_to_the_right() {
-    // ┌─────┬─────┐                    ┌───┬───────┐
-    // │█████│     │                    │███│       │
-    // │█████│     │  ==resize=left==>  │███│       │
-    // │█████│     │                    │███│       │
-    // └─────┴─────┘                    └───┴───────┘
-    // █ == focused pane
-    let size = Size {
-        cols: 121,
-        rows: 20,
-    };
-    let mut tab = create_new_tab(size);
-    tab.vertical_split(PaneId::Terminal(2), 1);
-    tab.move_focus_left(1);
-    tab.resize_left(1);
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(1))
+            .get(&PaneId::Terminal(3))
             .unwrap()
             .position_and_size()
             .x,
-        0,
-        "pane 1 x position"
+        60,
+        "pane 3 x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(1))
+            .get(&PaneId::Terminal(3))
             .unwrap()
             .position_and_size()
             .y,
         0,
-        "pane 1 y position"
+        "pane 3 y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(1))
+            .get(&PaneId::Terminal(3))
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
-        54,
-        "pane 1 column count"
+        15,
+        "pane 3 column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(1))
+            .get(&PaneId::Terminal(3))
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        20,
-        "pane 1 row count"
+        16,
+        "pane 3 row count"
     );
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(2))
+            .get(&PaneId::Terminal(4))
             .unwrap()
             .position_and_size()
             .x,
-        54,
-        "pane 2 x position"
+        91,
+        "pane 4 x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(2))
+            .get(&PaneId::Terminal(4))
             .unwrap()
             .position_and_size()
             .y,
         0,
-        "pane 2 y position"
+        "pane 4 y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(2))
+            .get(&PaneId::Terminal(4))
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
-        67,
-        "pane 2 column count"
+        31,
+        "pane 4 column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(2))
+            .get(&PaneId::Terminal(4))
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        20,
-        "pane 2 row count"
+        15,
+        "pane 4 row count"
     );
-}
-
-#[test]
-pub fn resize_left_with_panes_to_the_left_and_right() {
-    // ┌─────┬─────┬─────┐                    ┌─────┬───┬───────┐
-    // │     │█████│     │                    │     │███│       │
-    // │     │█████│     │  ==resize=left==>  │     │███│       │
-    // │     │█████│     │                    │     │███│       │
-    // └─────┴─────┴─────┘                    └─────┴───┴───────┘
-    // █ == focused pane
-
-    let size = Size {
-        cols: 121,
-        rows: 20,
-    };
-    let mut tab = create_new_tab(size);
-    tab.vertical_split(PaneId::Terminal(2), 1);
-    tab.vertical_split(PaneId::Terminal(3), 1);
-    tab.move_focus_left(1);
-    tab.resize_left(1);
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(1))
+            .get(&PaneId::Terminal(5))
             .unwrap()
             .position_and_size()
             .x,
-        0,
-        "pane 1 x position"
+        75,
+        "pane 5 x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(1))
+            .get(&PaneId::Terminal(5))
             .unwrap()
             .position_and_size()
             .y,
         0,
-        "pane 1 y position"
+        "pane 5 y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(1))
+            .get(&PaneId::Terminal(5))
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
-        54,
-        "pane 1 column count"
+        8,
+        "pane 5 column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(1))
+            .get(&PaneId::Terminal(5))
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        20,
-        "pane 1 row count"
+        16,
+        "pane 5 row count"
     );
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(2))
+            .get(&PaneId::Terminal(6))
             .unwrap()
             .position_and_size()
             .x,
-        54,
-        "pane 2 x position"
+        83,
+        "pane 6 x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(2))
+            .get(&PaneId::Terminal(6))
             .unwrap()
             .position_and_size()
             .y,
         0,
-        "pane 2 y position"
+        "pane 6 y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(2))
+            .get(&PaneId::Terminal(6))
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
-        36,
-        "pane 2 column count"
+        8,
+        "pane 6 column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(2))
+            .get(&PaneId::Terminal(6))
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        20,
-        "pane 2 row count"
+        16,
+        "pane 6 row count"
     );
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(3))
+            .get(&PaneId::Terminal(7))
             .unwrap()
             .position_and_size()
             .x,
-        90,
-        "pane 2 x position"
+        60,
+        "pane 7 x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(3))
+            .get(&PaneId::Terminal(7))
             .unwrap()
             .position_and_size()
             .y,
-        0,
-        "pane 2 y position"
+        16,
+        "pane 7 y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(3))
+            .get(&PaneId::Terminal(7))
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
         31,
-        "pane 2 column count"
+        "pane 7 column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(3))
+            .get(&PaneId::Terminal(7))
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        20,
-        "pane 2 row count"
+        14,
+        "pane 7 row count"
+    );
+
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(8))
+            .unwrap()
+            .position_and_size()
+            .x,
+        91,
+        "pane 8 x position"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(8))
+            .unwrap()
+            .position_and_size()
+            .y,
+        15,
+        "pane 8 y position"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(8))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        31,
+        "pane 8 column count"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(8))
+            .unwrap()
+            .position_and_size()
+            .rows
+            .as_usize(),
+        15,
+        "pane 8 row count"
     );
 }
 
 #[test]
-pub fn resize_left_with_multiple_panes_to_the_left() {
+pub fn cannot_resize_down_when_pane_below_is_at_minimum_height() {
+    // ┌───────────┐                  ┌───────────┐
+    // │███████████│                  │███████████│
+    // ├───────────┤ ==resize=down==> ├───────────┤
+    // │           │                  │           │
+    // └───────────┘                  └───────────┘
+    // █ == focused pane
+
+    let size = Size {
+        cols: 121,
+        rows: 10,
+    };
+    let mut tab = create_new_tab(size);
+    tab.horizontal_split(PaneId::Terminal(2), 1);
+    tab.move_focus_up(1);
+    tab.resize_down(1);
+
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(1))
+            .unwrap()
+            .position_and_size()
+            .rows
+            .as_usize(),
+        5,
+        "pane 1 height stayed the same"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .rows
+            .as_usize(),
+        5,
+        "pane 2 height stayed the same"
+    );
+}
+
+#[test]
+pub fn resize_active_pane_by_exact_amount() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    let resized_by = tab.resize_active_pane_by(1, Direction::Left, 1.0);
+    assert_eq!(resized_by, 1.0, "resized by the requested amount");
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(1))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_percent(),
+        Some(49.0),
+        "pane 1 width percent"
+    );
+
+    let resized_by = tab.resize_active_pane_by(1, Direction::Left, 100.0);
+    assert!(
+        resized_by > 0.0 && resized_by < 100.0,
+        "resize was clamped to the available space"
+    );
+    assert!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(1))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize()
+            >= MIN_TERMINAL_WIDTH,
+        "pane 1 was not reduced below its minimum width"
+    );
+}
+
+#[test]
+pub fn resize_left_with_pane_to_the_left() {
     // ┌─────┬─────┐                    ┌───┬───────┐
     // │     │█████│                    │   │███████│
-    // ├─────┤█████│  ==resize=left==>  ├───┤███████│
+    // │     │█████│  ==resize=left==>  │   │███████│
     // │     │█████│                    │   │███████│
     // └─────┴─────┘                    └───┴───────┘
     // █ == focused pane
+
     let size = Size {
         cols: 121,
         rows: 20,
     };
     let mut tab = create_new_tab(size);
     tab.vertical_split(PaneId::Terminal(2), 1);
-    tab.move_focus_left(1);
-    tab.horizontal_split(PaneId::Terminal(3), 1);
-    tab.move_focus_right(1);
     tab.resize_left(1);
 
     assert_eq!(
@@ -6140,7 +6629,7 @@ pub fn resize_left_with_multiple_panes_to_the_left() {
             .position_and_size()
             .rows
             .as_usize(),
-        10,
+        20,
         "pane 1 row count"
     );
 
@@ -6186,70 +6675,129 @@ pub fn resize_left_with_multiple_panes_to_the_left() {
         20,
         "pane 2 row count"
     );
+}
+
+#[test]
+pub fn resize_left_with_pane_to_the_right() {
+    // ┌─────┬─────┐                    ┌───┬───────┐
+    // │█████│     │                    │███│       │
+    // │█████│     │  ==resize=left==>  │███│       │
+    // │█████│     │                    │███│       │
+    // └─────┴─────┘                    └───┴───────┘
+    // █ == focused pane
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.move_focus_left(1);
+    tab.resize_left(1);
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(3))
+            .get(&PaneId::Terminal(1))
             .unwrap()
             .position_and_size()
             .x,
         0,
-        "pane 2 x position"
+        "pane 1 x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(3))
+            .get(&PaneId::Terminal(1))
             .unwrap()
             .position_and_size()
             .y,
-        10,
-        "pane 2 y position"
+        0,
+        "pane 1 y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(3))
+            .get(&PaneId::Terminal(1))
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
         54,
+        "pane 1 column count"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(1))
+            .unwrap()
+            .position_and_size()
+            .rows
+            .as_usize(),
+        20,
+        "pane 1 row count"
+    );
+
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .x,
+        54,
+        "pane 2 x position"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .y,
+        0,
+        "pane 2 y position"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        67,
         "pane 2 column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(3))
+            .get(&PaneId::Terminal(2))
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        10,
+        20,
         "pane 2 row count"
     );
 }
 
 #[test]
-pub fn resize_left_with_panes_to_the_left_aligned_top_with_current_pane() {
-    // ┌─────┬─────┐                    ┌─────┬─────┐
-    // │     │     │                    │     │     │
-    // ├─────┼─────┤  ==resize=left==>  ├───┬─┴─────┤
-    // │     │█████│                    │   │███████│
-    // └─────┴─────┘                    └───┴───────┘
+pub fn resize_left_with_panes_to_the_left_and_right() {
+    // ┌─────┬─────┬─────┐                    ┌─────┬───┬───────┐
+    // │     │█████│     │                    │     │███│       │
+    // │     │█████│     │  ==resize=left==>  │     │███│       │
+    // │     │█████│     │                    │     │███│       │
+    // └─────┴─────┴─────┘                    └─────┴───┴───────┘
     // █ == focused pane
 
     let size = Size {
         cols: 121,
-        rows: 30,
+        rows: 20,
     };
     let mut tab = create_new_tab(size);
-    tab.horizontal_split(PaneId::Terminal(2), 1);
+    tab.vertical_split(PaneId::Terminal(2), 1);
     tab.vertical_split(PaneId::Terminal(3), 1);
-    tab.move_focus_up(1);
-    tab.vertical_split(PaneId::Terminal(4), 1);
-    tab.move_focus_down(1);
+    tab.move_focus_left(1);
     tab.resize_left(1);
 
     assert_eq!(
@@ -6280,7 +6828,7 @@ pub fn resize_left_with_panes_to_the_left_aligned_top_with_current_pane() {
             .position_and_size()
             .cols
             .as_usize(),
-        61,
+        54,
         "pane 1 column count"
     );
     assert_eq!(
@@ -6291,7 +6839,7 @@ pub fn resize_left_with_panes_to_the_left_aligned_top_with_current_pane() {
             .position_and_size()
             .rows
             .as_usize(),
-        15,
+        20,
         "pane 1 row count"
     );
 
@@ -6302,7 +6850,7 @@ pub fn resize_left_with_panes_to_the_left_aligned_top_with_current_pane() {
             .unwrap()
             .position_and_size()
             .x,
-        0,
+        54,
         "pane 2 x position"
     );
     assert_eq!(
@@ -6312,7 +6860,7 @@ pub fn resize_left_with_panes_to_the_left_aligned_top_with_current_pane() {
             .unwrap()
             .position_and_size()
             .y,
-        15,
+        0,
         "pane 2 y position"
     );
     assert_eq!(
@@ -6323,7 +6871,7 @@ pub fn resize_left_with_panes_to_the_left_aligned_top_with_current_pane() {
             .position_and_size()
             .cols
             .as_usize(),
-        54,
+        36,
         "pane 2 column count"
     );
     assert_eq!(
@@ -6334,7 +6882,7 @@ pub fn resize_left_with_panes_to_the_left_aligned_top_with_current_pane() {
             .position_and_size()
             .rows
             .as_usize(),
-        15,
+        20,
         "pane 2 row count"
     );
 
@@ -6345,8 +6893,8 @@ pub fn resize_left_with_panes_to_the_left_aligned_top_with_current_pane() {
             .unwrap()
             .position_and_size()
             .x,
-        54,
-        "pane 3 x position"
+        90,
+        "pane 2 x position"
     );
     assert_eq!(
         tab.tiled_panes
@@ -6355,8 +6903,8 @@ pub fn resize_left_with_panes_to_the_left_aligned_top_with_current_pane() {
             .unwrap()
             .position_and_size()
             .y,
-        15,
-        "pane 3 y position"
+        0,
+        "pane 2 y position"
     );
     assert_eq!(
         tab.tiled_panes
@@ -6366,8 +6914,8 @@ pub fn resize_left_with_panes_to_the_left_aligned_top_with_current_pane() {
             .position_and_size()
             .cols
             .as_usize(),
-        67,
-        "pane 3 column count"
+        31,
+        "pane 2 column count"
     );
     assert_eq!(
         tab.tiled_panes
@@ -6377,60 +6925,166 @@ pub fn resize_left_with_panes_to_the_left_aligned_top_with_current_pane() {
             .position_and_size()
             .rows
             .as_usize(),
-        15,
-        "pane 3 row count"
+        20,
+        "pane 2 row count"
     );
+}
+
+#[test]
+pub fn resize_left_with_multiple_panes_to_the_left() {
+    // ┌─────┬─────┐                    ┌───┬───────┐
+    // │     │█████│                    │   │███████│
+    // ├─────┤█████│  ==resize=left==>  ├───┤███████│
+    // │     │█████│                    │   │███████│
+    // └─────┴─────┘                    └───┴───────┘
+    // █ == focused pane
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.move_focus_left(1);
+    tab.horizontal_split(PaneId::Terminal(3), 1);
+    tab.move_focus_right(1);
+    tab.resize_left(1);
 
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(4))
+            .get(&PaneId::Terminal(1))
             .unwrap()
             .position_and_size()
             .x,
-        61,
-        "pane 4 x position"
+        0,
+        "pane 1 x position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(4))
+            .get(&PaneId::Terminal(1))
             .unwrap()
             .position_and_size()
             .y,
         0,
-        "pane 4 y position"
+        "pane 1 y position"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(4))
+            .get(&PaneId::Terminal(1))
             .unwrap()
             .position_and_size()
             .cols
             .as_usize(),
-        60,
-        "pane 4 column count"
+        54,
+        "pane 1 column count"
     );
     assert_eq!(
         tab.tiled_panes
             .panes
-            .get(&PaneId::Terminal(4))
+            .get(&PaneId::Terminal(1))
             .unwrap()
             .position_and_size()
             .rows
             .as_usize(),
-        15,
-        "pane 4 row count"
+        10,
+        "pane 1 row count"
+    );
+
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .x,
+        54,
+        "pane 2 x position"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .y,
+        0,
+        "pane 2 y position"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        67,
+        "pane 2 column count"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .rows
+            .as_usize(),
+        20,
+        "pane 2 row count"
+    );
+
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(3))
+            .unwrap()
+            .position_and_size()
+            .x,
+        0,
+        "pane 2 x position"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(3))
+            .unwrap()
+            .position_and_size()
+            .y,
+        10,
+        "pane 2 y position"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(3))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        54,
+        "pane 2 column count"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(3))
+            .unwrap()
+            .position_and_size()
+            .rows
+            .as_usize(),
+        10,
+        "pane 2 row count"
     );
 }
 
 #[test]
-pub fn resize_left_with_panes_to_the_right_aligned_top_with_current_pane() {
+pub fn resize_left_with_panes_to_the_left_aligned_top_with_current_pane() {
     // ┌─────┬─────┐                    ┌─────┬─────┐
     // │     │     │                    │     │     │
     // ├─────┼─────┤  ==resize=left==>  ├───┬─┴─────┤
-    // │█████│     │                    │███│       │
+    // │     │█████│                    │   │███████│
     // └─────┴─────┘                    └───┴───────┘
     // █ == focused pane
 
@@ -6444,7 +7098,6 @@ pub fn resize_left_with_panes_to_the_right_aligned_top_with_current_pane() {
     tab.move_focus_up(1);
     tab.vertical_split(PaneId::Terminal(4), 1);
     tab.move_focus_down(1);
-    tab.move_focus_left(1);
     tab.resize_left(1);
 
     assert_eq!(
@@ -6621,12 +7274,12 @@ pub fn resize_left_with_panes_to_the_right_aligned_top_with_current_pane() {
 }
 
 #[test]
-pub fn resize_left_with_panes_to_the_left_aligned_bottom_with_current_pane() {
-    // ┌─────┬─────┐                    ┌───┬───────┐
-    // │     │█████│                    │   │███████│
-    // ├─────┼─────┤  ==resize=left==>  ├───┴─┬─────┤
+pub fn resize_left_with_panes_to_the_right_aligned_top_with_current_pane() {
+    // ┌─────┬─────┐                    ┌─────┬─────┐
     // │     │     │                    │     │     │
-    // └─────┴─────┘                    └─────┴─────┘
+    // ├─────┼─────┤  ==resize=left==>  ├───┬─┴─────┤
+    // │█████│     │                    │███│       │
+    // └─────┴─────┘                    └───┴───────┘
     // █ == focused pane
 
     let size = Size {
@@ -6638,6 +7291,8 @@ pub fn resize_left_with_panes_to_the_left_aligned_bottom_with_current_pane() {
     tab.vertical_split(PaneId::Terminal(3), 1);
     tab.move_focus_up(1);
     tab.vertical_split(PaneId::Terminal(4), 1);
+    tab.move_focus_down(1);
+    tab.move_focus_left(1);
     tab.resize_left(1);
 
     assert_eq!(
@@ -6668,7 +7323,7 @@ pub fn resize_left_with_panes_to_the_left_aligned_bottom_with_current_pane() {
             .position_and_size()
             .cols
             .as_usize(),
-        54,
+        61,
         "pane 1 column count"
     );
     assert_eq!(
@@ -6711,7 +7366,7 @@ pub fn resize_left_with_panes_to_the_left_aligned_bottom_with_current_pane() {
             .position_and_size()
             .cols
             .as_usize(),
-        61,
+        54,
         "pane 2 column count"
     );
     assert_eq!(
@@ -6733,7 +7388,7 @@ pub fn resize_left_with_panes_to_the_left_aligned_bottom_with_current_pane() {
             .unwrap()
             .position_and_size()
             .x,
-        61,
+        54,
         "pane 3 x position"
     );
     assert_eq!(
@@ -6754,7 +7409,7 @@ pub fn resize_left_with_panes_to_the_left_aligned_bottom_with_current_pane() {
             .position_and_size()
             .cols
             .as_usize(),
-        60,
+        67,
         "pane 3 column count"
     );
     assert_eq!(
@@ -6776,7 +7431,7 @@ pub fn resize_left_with_panes_to_the_left_aligned_bottom_with_current_pane() {
             .unwrap()
             .position_and_size()
             .x,
-        54,
+        61,
         "pane 4 x position"
     );
     assert_eq!(
@@ -6797,7 +7452,200 @@ pub fn resize_left_with_panes_to_the_left_aligned_bottom_with_current_pane() {
             .position_and_size()
             .cols
             .as_usize(),
-        67,
+        60,
+        "pane 4 column count"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(4))
+            .unwrap()
+            .position_and_size()
+            .rows
+            .as_usize(),
+        15,
+        "pane 4 row count"
+    );
+}
+
+#[test]
+pub fn resize_left_with_panes_to_the_left_aligned_bottom_with_current_pane() {
+    // ┌─────┬─────┐                    ┌───┬───────┐
+    // │     │█████│                    │   │███████│
+    // ├─────┼─────┤  ==resize=left==>  ├───┴─┬─────┤
+    // │     │     │                    │     │     │
+    // └─────┴─────┘                    └─────┴─────┘
+    // █ == focused pane
+
+    let size = Size {
+        cols: 121,
+        rows: 30,
+    };
+    let mut tab = create_new_tab(size);
+    tab.horizontal_split(PaneId::Terminal(2), 1);
+    tab.vertical_split(PaneId::Terminal(3), 1);
+    tab.move_focus_up(1);
+    tab.vertical_split(PaneId::Terminal(4), 1);
+    tab.resize_left(1);
+
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(1))
+            .unwrap()
+            .position_and_size()
+            .x,
+        0,
+        "pane 1 x position"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(1))
+            .unwrap()
+            .position_and_size()
+            .y,
+        0,
+        "pane 1 y position"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(1))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        54,
+        "pane 1 column count"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(1))
+            .unwrap()
+            .position_and_size()
+            .rows
+            .as_usize(),
+        15,
+        "pane 1 row count"
+    );
+
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .x,
+        0,
+        "pane 2 x position"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .y,
+        15,
+        "pane 2 y position"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        61,
+        "pane 2 column count"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .rows
+            .as_usize(),
+        15,
+        "pane 2 row count"
+    );
+
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(3))
+            .unwrap()
+            .position_and_size()
+            .x,
+        61,
+        "pane 3 x position"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(3))
+            .unwrap()
+            .position_and_size()
+            .y,
+        15,
+        "pane 3 y position"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(3))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        60,
+        "pane 3 column count"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(3))
+            .unwrap()
+            .position_and_size()
+            .rows
+            .as_usize(),
+        15,
+        "pane 3 row count"
+    );
+
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(4))
+            .unwrap()
+            .position_and_size()
+            .x,
+        54,
+        "pane 4 x position"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(4))
+            .unwrap()
+            .position_and_size()
+            .y,
+        0,
+        "pane 4 y position"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(4))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        67,
         "pane 4 column count"
     );
     assert_eq!(
@@ -13718,252 +14566,2381 @@ pub fn nondirectional_resize_increase_with_1_pane_to_left() {
 }
 
 #[test]
-pub fn nondirectional_resize_increase_with_2_panes_to_left() {
+pub fn nondirectional_resize_increase_with_2_panes_to_left() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.move_focus_left(1);
+    tab.horizontal_split(PaneId::Terminal(3), 1);
+    tab.move_focus_right(1);
+    tab.resize_increase(1);
+
+    // should behave like `resize_left_with_multiple_panes_to_the_left`
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .x,
+        54,
+        "pane 2 x position"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .y,
+        0,
+        "pane 2 y position"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        67,
+        "pane 2 column count"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .rows
+            .as_usize(),
+        20,
+        "pane 2 row count"
+    );
+}
+
+#[test]
+pub fn nondirectional_resize_increase_with_1_pane_to_right_1_pane_above() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.move_focus_left(1);
+    tab.horizontal_split(PaneId::Terminal(3), 1);
+    tab.resize_increase(1);
+
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(3))
+            .unwrap()
+            .position_and_size()
+            .y,
+        9,
+        "Pane 3 y coordinate"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(3))
+            .unwrap()
+            .position_and_size()
+            .x,
+        0,
+        "Pane 3 x coordinate"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(3))
+            .unwrap()
+            .position_and_size()
+            .rows
+            .as_usize(),
+        11,
+        "Pane 3 row count"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(3))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        67,
+        "Pane 3 col count"
+    );
+}
+
+#[test]
+pub fn nondirectional_resize_increase_with_1_pane_to_right_1_pane_to_left() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.vertical_split(PaneId::Terminal(3), 1);
+    tab.move_focus_left(1);
+    tab.resize_increase(1);
+
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .y,
+        0,
+        "Pane 3 y coordinate"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .x,
+        61,
+        "Pane 3 x coordinate"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .rows
+            .as_usize(),
+        20,
+        "Pane 3 row count"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        36,
+        "Pane 3 col count"
+    );
+}
+
+#[test]
+pub fn nondirectional_resize_increase_with_pane_above_aligned_right_with_current_pane() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.vertical_split(PaneId::Terminal(3), 1);
+    tab.move_focus_left(1);
+    tab.resize_increase(1);
+
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .y,
+        0,
+        "Pane 3 y coordinate"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .x,
+        61,
+        "Pane 3 x coordinate"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .rows
+            .as_usize(),
+        20,
+        "Pane 3 row count"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        36,
+        "Pane 3 col count"
+    );
+}
+
+#[test]
+pub fn custom_cursor_height_width_ratio() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let character_cell_size = Rc::new(RefCell::new(None));
+    let tab = create_new_tab_with_cell_size(size, character_cell_size.clone());
+    let initial_cursor_height_width_ratio = tab.tiled_panes.cursor_height_width_ratio();
+    *character_cell_size.borrow_mut() = Some(SizeInPixels {
+        height: 10,
+        width: 4,
+    });
+    let cursor_height_width_ratio_after_update = tab.tiled_panes.cursor_height_width_ratio();
+    assert_eq!(
+        initial_cursor_height_width_ratio, None,
+        "initially no ratio "
+    );
+    assert_eq!(
+        cursor_height_width_ratio_after_update,
+        Some(3),
+        "ratio updated successfully"
+    ); // 10 / 4 == 2.5, rounded: 3
+}
+
+#[test]
+pub fn diff_lines_between_two_panes() {
+    let first_pane_lines: Vec<String> = vec!["foo", "bar", "baz", "qux"]
+        .into_iter()
+        .map(String::from)
+        .collect();
+    let second_pane_lines: Vec<String> = vec!["foo", "baz", "quux", "qux", "corge"]
+        .into_iter()
+        .map(String::from)
+        .collect();
+    let (first_pane_differing_lines, second_pane_differing_lines) =
+        super::line_diff(&first_pane_lines, &second_pane_lines);
+    assert_eq!(
+        first_pane_differing_lines,
+        vec![1].into_iter().collect::<HashSet<usize>>(),
+        "removed line highlighted in first pane"
+    );
+    assert_eq!(
+        second_pane_differing_lines,
+        vec![2, 4].into_iter().collect::<HashSet<usize>>(),
+        "added lines highlighted in second pane"
+    );
+}
+
+#[test]
+pub fn toggle_diff_with_the_neighbouring_pane() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), client_id);
+    tab.move_focus_left(client_id);
+    tab.toggle_diff_pane(client_id, Direction::Right);
+    assert_eq!(
+        tab.diff_panes,
+        Some((PaneId::Terminal(1), PaneId::Terminal(2))),
+        "focused pane compared with its neighbour"
+    );
+    tab.toggle_diff_pane(client_id, Direction::Right);
+    assert_eq!(tab.diff_panes, None, "comparison toggled off");
+    tab.toggle_diff_pane(client_id, Direction::Left);
+    assert_eq!(tab.diff_panes, None, "no pane to compare with");
+}
+
+#[test]
+pub fn exited_pane_ignores_input_until_closed() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let os_api = FakeInputOutput::default();
+    let mut tab = create_new_tab(size);
+    tab.os_api = Box::new(os_api.clone());
+    tab.vertical_split(PaneId::Terminal(2), client_id);
+    tab.mark_pane_exited(PaneId::Terminal(2), 1);
+    tab.write_to_active_terminal(b"ls".to_vec(), client_id);
+    assert_eq!(
+        os_api.written_bytes.lock().unwrap().get(&2),
+        None,
+        "exited pane got no input"
+    );
+    assert!(
+        tab.get_all_pane_ids().contains(&PaneId::Terminal(2)),
+        "exited pane is held open"
+    );
+    tab.write_to_active_terminal(b"\r".to_vec(), client_id);
+    assert!(
+        !tab.get_all_pane_ids().contains(&PaneId::Terminal(2)),
+        "exited pane closed with the close key"
+    );
+}
+
+#[test]
+pub fn focus_last_pane_toggles_between_two_panes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.horizontal_split(PaneId::Terminal(3), 1);
+    tab.focus_last_pane(1);
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().pid(),
+        PaneId::Terminal(2),
+        "Focus moved to the previously active pane"
+    );
+    tab.focus_last_pane(1);
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().pid(),
+        PaneId::Terminal(3),
+        "Focus toggled back"
+    );
+    tab.close_pane(PaneId::Terminal(2));
+    tab.focus_last_pane(1);
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().pid(),
+        PaneId::Terminal(3),
+        "Focus did not move to a closed pane"
+    );
+}
+
+#[test]
+pub fn set_active_pane_size_to_percent() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.set_active_pane_size(Some(70.0), None, 1);
+    let cols_percent = tab
+        .get_active_pane(1)
+        .unwrap()
+        .position_and_size()
+        .cols
+        .as_percent()
+        .unwrap();
+    assert!(
+        (cols_percent - 70.0).abs() < 0.5,
+        "active pane takes up 70 percent of the width, got {}",
+        cols_percent
+    );
+    assert_panes_cover_display_area(&tab, size);
+    tab.set_active_pane_size(Some(100.0), None, 1);
+    let neighbor_cols = tab
+        .tiled_panes
+        .get_pane(PaneId::Terminal(1))
+        .unwrap()
+        .position_and_size()
+        .cols
+        .as_usize();
+    assert!(neighbor_cols > 0, "neighbor was not pushed out of the tab");
+    assert_panes_cover_display_area(&tab, size);
+}
+
+#[test]
+pub fn write_to_pane_group() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let os_api = FakeInputOutput::default();
+    let mut tab = create_new_tab(size);
+    tab.os_api = Box::new(os_api.clone());
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.vertical_split(PaneId::Terminal(3), 1);
+    tab.add_pane_to_group("servers", PaneId::Terminal(1));
+    tab.add_pane_to_group("servers", PaneId::Terminal(3));
+    tab.write_to_group("servers", b"ls".to_vec());
+    {
+        let written_bytes = os_api.written_bytes.lock().unwrap();
+        assert_eq!(
+            written_bytes.get(&1),
+            Some(&b"ls".to_vec()),
+            "pane 1 got input"
+        );
+        assert_eq!(written_bytes.get(&2), None, "pane 2 is not in the group");
+        assert_eq!(
+            written_bytes.get(&3),
+            Some(&b"ls".to_vec()),
+            "pane 3 got input"
+        );
+    }
+    tab.close_pane(PaneId::Terminal(3));
+    tab.remove_pane_from_group("servers", PaneId::Terminal(1));
+    tab.write_to_group("servers", b"pwd".to_vec());
+    let written_bytes = os_api.written_bytes.lock().unwrap();
+    assert_eq!(
+        written_bytes.get(&1),
+        Some(&b"ls".to_vec()),
+        "pane 1 was removed from the group"
+    );
+}
+
+#[test]
+pub fn close_pane_with_running_process_needs_confirmation() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let os_api = FakeInputOutput::default();
+    let mut tab = create_new_tab(size);
+    tab.os_api = Box::new(os_api.clone());
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    os_api.running_processes.lock().unwrap().insert(2);
+    assert_eq!(
+        tab.close_focused_pane(1),
+        CloseResult::NeedsConfirmation,
+        "pane with a running process was not closed"
+    );
+    assert!(tab.get_all_pane_ids().contains(&PaneId::Terminal(2)));
+    assert_eq!(
+        tab.close_focused_pane(1),
+        CloseResult::Closed,
+        "pane closed when trying again"
+    );
+    assert!(!tab.get_all_pane_ids().contains(&PaneId::Terminal(2)));
+    assert_eq!(
+        tab.close_focused_pane(1),
+        CloseResult::Closed,
+        "idle shell closed right away"
+    );
+}
+
+#[test]
+pub fn active_pane_scroll_info() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let mut pty_bytes = String::new();
+    for line_number in 0..50 {
+        pty_bytes.push_str(&format!("line {}\r\n", line_number));
+    }
+    tab.handle_pty_bytes(1, Vec::from(pty_bytes.as_bytes()));
+    let ScrollInfo {
+        offset,
+        scrollback_len,
+    } = tab.active_pane_scroll_info(1).unwrap();
+    assert_eq!(offset, 0, "pane is not scrolled");
+    assert!(scrollback_len > 0, "pane has scrollback");
+    for _ in 0..3 {
+        tab.scroll_active_terminal_up(1);
+    }
+    tab.scroll_active_terminal_down(1);
+    assert_eq!(
+        tab.active_pane_scroll_info(1),
+        Some(ScrollInfo {
+            offset: 2,
+            scrollback_len
+        }),
+        "scroll offset follows scrolling while the length stays the same"
+    );
+}
+
+#[test]
+pub fn smart_split_active_pane_alternates_direction_by_aspect_ratio() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.smart_split_active(PaneId::Terminal(2), 1);
+    tab.smart_split_active(PaneId::Terminal(3), 1);
+    assert_eq!(tab.tiled_panes.panes.len(), 3, "The tab has three panes");
+    let first_pane = tab
+        .tiled_panes
+        .panes
+        .get(&PaneId::Terminal(1))
+        .unwrap()
+        .position_and_size();
+    let second_pane = tab
+        .tiled_panes
+        .panes
+        .get(&PaneId::Terminal(2))
+        .unwrap()
+        .position_and_size();
+    let third_pane = tab
+        .tiled_panes
+        .panes
+        .get(&PaneId::Terminal(3))
+        .unwrap()
+        .position_and_size();
+    assert_eq!(
+        (
+            first_pane.x,
+            first_pane.y,
+            first_pane.cols.as_usize(),
+            first_pane.rows.as_usize()
+        ),
+        (0, 0, 61, 20),
+        "wide pane was split vertically"
+    );
+    assert_eq!(
+        (
+            second_pane.x,
+            second_pane.y,
+            second_pane.cols.as_usize(),
+            second_pane.rows.as_usize()
+        ),
+        (61, 0, 60, 10),
+        "tall pane was split horizontally"
+    );
+    assert_eq!(
+        (
+            third_pane.x,
+            third_pane.y,
+            third_pane.cols.as_usize(),
+            third_pane.rows.as_usize()
+        ),
+        (61, 10, 60, 10),
+        "new pane below the second one"
+    );
+}
+
+#[test]
+pub fn equalize_panes_in_a_grid() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.horizontal_split(PaneId::Terminal(3), 1);
+    tab.move_focus_left(1);
+    tab.horizontal_split(PaneId::Terminal(4), 1);
+    for _ in 0..3 {
+        tab.resize_right(1);
+        tab.resize_down(1);
+    }
+    tab.equalize_panes();
+
+    for pane_id in 1..=4 {
+        let geom = tab
+            .tiled_panes
+            .panes
+            .get(&PaneId::Terminal(pane_id))
+            .unwrap()
+            .position_and_size();
+        let cols_percent = geom.cols.as_percent().unwrap();
+        let rows_percent = geom.rows.as_percent().unwrap();
+        assert!(
+            (cols_percent - 50.0).abs() < 0.01 && (rows_percent - 50.0).abs() < 0.01,
+            "pane {} is 50%/50% (got {}%/{}%)",
+            pane_id,
+            cols_percent,
+            rows_percent
+        );
+    }
+    assert_panes_cover_display_area(&tab, size);
+}
+
+#[test]
+pub fn boundary_segments_follow_pane_frames_mode() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    assert_eq!(
+        tab.boundary_segments().len(),
+        8,
+        "every pane has its own frame"
+    );
+    tab.set_pane_frames(false);
+    assert_eq!(
+        tab.boundary_segments(),
+        vec![BoundarySegment::vertical(60, 0, 19)],
+        "a single boundary between the two panes"
+    );
+}
+
+#[test]
+pub fn focus_next_pane_in_direction_visits_every_pane_of_a_t_shaped_layout() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    // pane 1 spans the top, panes 2 and 3 share the bottom
+    tab.horizontal_split(PaneId::Terminal(2), 1);
+    tab.vertical_split(PaneId::Terminal(3), 1);
+    for (direction, expected_order) in [
+        (Direction::Down, [1, 2, 3]),
+        (Direction::Right, [1, 2, 3]),
+        (Direction::Up, [2, 1, 3]),
+    ] {
+        let start = tab.get_active_pane_id(1).unwrap();
+        let mut visited = vec![];
+        for _ in 0..3 {
+            tab.focus_next_pane_in_direction(1, direction.clone());
+            visited.push(tab.get_active_pane_id(1).unwrap());
+        }
+        let expected_order: Vec<PaneId> = expected_order
+            .iter()
+            .map(|id| PaneId::Terminal(*id))
+            .collect();
+        assert_eq!(
+            visited, expected_order,
+            "panes visited moving {:?}",
+            direction
+        );
+        assert_eq!(
+            tab.get_active_pane_id(1).unwrap(),
+            start,
+            "back at the starting pane"
+        );
+    }
+}
+
+#[test]
+pub fn pane_counts() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.toggle_floating_panes(1, None);
+    tab.new_pane(PaneId::Terminal(3), Some(1));
+    tab.set_pane_selectable(PaneId::Terminal(2), false);
+    assert_eq!(tab.pane_count(), 3, "tiled and floating panes are counted");
+    assert_eq!(
+        tab.selectable_pane_count(),
+        2,
+        "unselectable panes are not counted"
+    );
+}
+
+#[test]
+pub fn resize_and_focus_pane_with_id() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    assert!(tab.focus_pane(PaneId::Terminal(1), 1), "pane 1 focused");
+    assert_eq!(tab.get_active_pane_id(1), Some(PaneId::Terminal(1)));
+    assert!(
+        !tab.focus_pane(PaneId::Terminal(3), 1),
+        "a pane that does not exist is not focused"
+    );
+
+    tab.resize_pane_with_id(PaneId::Terminal(2), Direction::Left, 10.0);
+    let pane_2 = tab.tiled_panes.panes.get(&PaneId::Terminal(2)).unwrap();
+    assert!(
+        pane_2.position_and_size().cols.as_usize() > 60,
+        "pane 2 grew although it is not focused"
+    );
+    tab.resize_pane_with_id(PaneId::Terminal(2), Direction::Left, 100.0);
+    let pane_1 = tab.tiled_panes.panes.get(&PaneId::Terminal(1)).unwrap();
+    assert!(
+        pane_1.position_and_size().cols.as_usize() >= pane_1.min_width(),
+        "pane 1 was not made smaller than its minimum width"
+    );
+    assert_panes_cover_display_area(&tab, size);
+}
+
+#[test]
+pub fn resize_pane_with_id_rejects_invalid_percent() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    let pane_2_cols = |tab: &Tab| {
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize()
+    };
+    let cols_before = pane_2_cols(&tab);
+    for percent in [f64::INFINITY, f64::NEG_INFINITY, f64::NAN, -10.0, 0.0] {
+        assert_eq!(
+            tab.resize_pane_with_id(PaneId::Terminal(2), Direction::Left, percent),
+            0.0,
+            "pane was not resized by {}",
+            percent
+        );
+    }
+    assert_eq!(pane_2_cols(&tab), cols_before, "pane 2 kept its size");
+    let resized_by = tab.resize_pane_with_id(PaneId::Terminal(2), Direction::Left, 1000.0);
+    assert!(
+        resized_by > 0.0 && resized_by <= 100.0,
+        "percent is clamped to 100"
+    );
+    assert_panes_cover_display_area(&tab, size);
+}
+
+#[test]
+pub fn move_active_pane_to_direction_relocates_pane() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.vertical_split(PaneId::Terminal(3), 1);
+    tab.focus_pane(PaneId::Terminal(1), 1);
+    tab.move_active_pane_to_direction(1, Direction::Right);
+    tab.move_active_pane_to_direction(1, Direction::Right);
+    assert_panes_cover_display_area(&tab, size);
+    assert_eq!(
+        tab.get_active_pane_id(1),
+        Some(PaneId::Terminal(1)),
+        "the moved pane stays focused"
+    );
+    let pane_1 = tab.tiled_panes.panes.get(&PaneId::Terminal(1)).unwrap();
+    let pane_1_geom = pane_1.position_and_size();
+    assert_eq!(
+        pane_1_geom.x + pane_1_geom.cols.as_usize(),
+        size.cols,
+        "pane 1 moved to the far right"
+    );
+    assert_eq!(
+        pane_1_geom.rows.as_usize(),
+        size.rows,
+        "pane 1 is full height"
+    );
+    tab.move_active_pane_to_direction(1, Direction::Right);
+    let pane_1 = tab.tiled_panes.panes.get(&PaneId::Terminal(1)).unwrap();
+    assert_eq!(
+        pane_1.position_and_size(),
+        pane_1_geom,
+        "nothing happens without a pane in that direction"
+    );
+}
+
+#[test]
+pub fn silent_panes_are_reported_once_per_silence() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let (to_plugin, plugin_receiver) = channels::unbounded();
+    let mut senders = ThreadSenders::default().silently_fail_on_send();
+    senders.to_plugin = Some(SenderWithContext::new(to_plugin));
+    let mut tab = create_new_tab_with_senders(
+        size,
+        LayoutTemplate::default().try_into().unwrap(),
+        vec![1],
+        senders,
+    );
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    let reported_silent_panes = |plugin_receiver: &channels::Receiver<(PluginInstruction, _)>| {
+        plugin_receiver
+            .try_iter()
+            .filter_map(|(instruction, _)| match instruction {
+                PluginInstruction::Update(_, _, Event::PaneSilence(pane_info)) => {
+                    Some(pane_info.id)
+                }
+                _ => None,
+            })
+            .collect::<Vec<u32>>()
+    };
+    tab.set_pane_silence_threshold(PaneId::Terminal(2), Some(Duration::from_secs(0)));
+    assert_eq!(
+        reported_silent_panes(&plugin_receiver),
+        vec![2],
+        "only the monitored pane is reported"
+    );
+    tab.report_silent_panes();
+    assert_eq!(
+        reported_silent_panes(&plugin_receiver),
+        Vec::<u32>::new(),
+        "the same silence is not reported again"
+    );
+    tab.handle_pty_bytes(2, b"done".to_vec());
+    tab.report_silent_panes();
+    assert_eq!(
+        reported_silent_panes(&plugin_receiver),
+        vec![2],
+        "a new silence after more output is reported"
+    );
+}
+
+#[test]
+pub fn layout_focus_sets_the_initially_focused_pane() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    assert_eq!(tab.get_active_pane_id(1), Some(PaneId::Terminal(2)));
+    let layout = tab.snapshot_layout().unwrap();
+    assert_eq!(
+        layout
+            .parts
+            .iter()
+            .map(|part| part.focus.unwrap_or(false))
+            .collect::<Vec<bool>>(),
+        vec![false, true],
+        "the second pane is marked as focused"
+    );
+    let restored_tab = create_new_tab_with_layout(size, layout, vec![1, 2]);
+    assert_eq!(
+        restored_tab.get_active_pane_id(1),
+        Some(PaneId::Terminal(2)),
+        "the pane marked in the layout is focused rather than the first one"
+    );
+}
+
+#[test]
+pub fn scrolling_in_alternate_screen_is_forwarded_to_the_program() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let os_api = FakeInputOutput::default();
+    let mut tab = create_new_tab(size);
+    tab.os_api = Box::new(os_api.clone());
+    for i in 0..30 {
+        tab.handle_pty_bytes(1, format!("line {}\r\n", i).into_bytes());
+    }
+    // enter the alternate screen, like vim or less do
+    tab.handle_pty_bytes(1, "\u{1b}[?1049h".as_bytes().to_vec());
+    tab.scroll_active_terminal_up(1);
+    tab.scroll_terminal_up(&Position::new(5, 5), 2, 1);
+    let active_pane = tab.get_active_pane(1).unwrap();
+    assert!(active_pane.is_alternate_screen_active());
+    assert!(
+        !active_pane.is_scrolled(),
+        "the pane was not scrolled into the scrollback of the main screen"
+    );
+    assert_eq!(
+        os_api.written_bytes.lock().unwrap().get(&1),
+        Some(&"\u{1b}[A\u{1b}[A".as_bytes().to_vec()),
+        "the mouse wheel was sent to the program as arrow keys"
+    );
+}
+
+#[test]
+pub fn pane_remembers_its_run_command() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let command = RunCommand {
+        command: PathBuf::from("htop"),
+        args: vec![],
+        cwd: None,
+    };
+    tab.open_pane(PaneId::Terminal(2), Some(1), None);
+    tab.set_pane_run_command(PaneId::Terminal(2), Some(command.clone()));
+    assert_eq!(
+        tab.tiled_panes
+            .get_pane(PaneId::Terminal(2))
+            .unwrap()
+            .invoked_with(),
+        Some(Run::Command(command)),
+        "the pane remembers the command it was opened with"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .get_pane(PaneId::Terminal(1))
+            .unwrap()
+            .invoked_with(),
+        None,
+        "panes running the default shell have no command"
+    );
+}
+
+#[test]
+pub fn reserve_viewport_keeps_panes_out_of_the_reserved_space() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.reserve_viewport(2, 0, 0, 0);
+    // called again with the same values, this should change nothing
+    tab.reserve_viewport(2, 0, 0, 0);
+    tab.resize_whole_tab(size);
+    for (pane_id, pane) in tab.get_tiled_panes() {
+        let geom = pane.position_and_size();
+        assert_eq!(geom.y, 2, "{:?} starts below the reserved rows", pane_id);
+        assert_eq!(
+            geom.rows.as_usize(),
+            18,
+            "{:?} fills the rest of the screen",
+            pane_id
+        );
+    }
+    let cols: usize = tab
+        .get_tiled_panes()
+        .map(|(_, pane)| pane.position_and_size().cols.as_usize())
+        .sum();
+    assert_eq!(cols, 121, "panes still take up the whole width");
+    assert_eq!(
+        tab.get_pane_id_at(&Position::new(0, 5), false),
+        None,
+        "the reserved space has no panes"
+    );
+    assert!(tab.get_pane_id_at(&Position::new(2, 5), false).is_some());
+    tab.reserve_viewport(0, 0, 0, 0);
+    assert_panes_cover_display_area(&tab, size);
+}
+
+#[test]
+pub fn offsetting_panes_past_the_edge_stops_at_the_edge() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.tiled_panes.offset_panes(-2, -2);
+    let geom = tab
+        .tiled_panes
+        .get_pane(PaneId::Terminal(1))
+        .unwrap()
+        .position_and_size();
+    assert_eq!((geom.x, geom.y), (0, 0), "pane did not wrap around");
+    let viewport = *tab.viewport.borrow();
+    assert_eq!(
+        (viewport.x, viewport.y),
+        (0, 0),
+        "viewport did not wrap around"
+    );
+}
+
+#[test]
+pub fn reserve_viewport_moves_floating_panes_out_of_the_reserved_space() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.toggle_floating_panes(1, None);
+    tab.new_pane(PaneId::Terminal(2), Some(1));
+    tab.reserve_viewport(2, 0, 3, 0);
+    let geom = tab
+        .floating_panes
+        .get_pane(PaneId::Terminal(2))
+        .unwrap()
+        .position_and_size();
+    assert!(
+        geom.y >= 2 && geom.x >= 3,
+        "floating pane starts after the reserved space, {:?}",
+        geom
+    );
+    assert!(
+        geom.y + geom.rows.as_usize() <= size.rows && geom.x + geom.cols.as_usize() <= size.cols,
+        "floating pane is still on the screen, {:?}",
+        geom
+    );
+    assert_eq!(
+        tab.get_pane_id_at_position(&Position::new(0, geom.x as u16 + 1)),
+        None,
+        "the reserved space has no panes"
+    );
+    assert_eq!(
+        tab.get_pane_id_at_position(&Position::new(geom.y as i32 + 1, geom.x as u16 + 1)),
+        Some(PaneId::Terminal(2))
+    );
+}
+
+#[test]
+pub fn apply_preset_layout() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.horizontal_split(PaneId::Terminal(3), 1);
+    tab.focus_pane(PaneId::Terminal(3), 1);
+    tab.apply_preset_layout(LayoutPreset::MainVertical, 1);
+    assert_panes_cover_display_area(&tab, size);
+    let main_pane = tab
+        .tiled_panes
+        .get_pane(PaneId::Terminal(3))
+        .unwrap()
+        .position_and_size();
+    assert_eq!(
+        (main_pane.x, main_pane.y, main_pane.rows.as_usize()),
+        (0, 0, 20),
+        "the active pane is the main pane on the left"
+    );
+    tab.apply_preset_layout(LayoutPreset::EvenVertical, 1);
+    assert_panes_cover_display_area(&tab, size);
+    for (pane_id, pane) in tab.get_tiled_panes() {
+        assert_eq!(
+            pane.position_and_size().cols.as_usize(),
+            121,
+            "{:?} spans the whole width",
+            pane_id
+        );
+    }
+    tab.swap_layout(1);
+    assert_panes_cover_display_area(&tab, size);
+    assert_eq!(
+        tab.preset_layout,
+        Some(LayoutPreset::MainVertical),
+        "swapping moves on to the next preset"
+    );
+}
+
+#[test]
+pub fn click_on_boundary_between_panes_selects_the_left_pane() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.set_pane_frames(false);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    // without frames, the boundary is drawn in the last column of the left pane
+    let boundary_column = tab
+        .tiled_panes
+        .get_pane(PaneId::Terminal(1))
+        .unwrap()
+        .position_and_size()
+        .cols
+        .as_usize()
+        - 1;
+    let boundary = Position::new(5, boundary_column as u16);
+    assert_eq!(
+        tab.get_pane_id_at(&boundary, false),
+        Some(PaneId::Terminal(1)),
+        "the boundary belongs to the pane to its left"
+    );
+    tab.handle_left_click(&boundary, 1);
+    tab.handle_mouse_release(&boundary, 1);
+    assert_eq!(tab.get_active_pane_id(1), Some(PaneId::Terminal(1)));
+    tab.handle_left_click(&Position::new(5, boundary_column as u16 + 1), 1);
+    assert_eq!(
+        tab.get_active_pane_id(1),
+        Some(PaneId::Terminal(2)),
+        "the column right of the boundary belongs to the right pane"
+    );
+}
+
+#[test]
+pub fn new_panes_are_rejected_when_the_tab_is_full() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.max_panes = Some(2);
+    tab.reject_new_panes_over_max(true);
+    assert!(tab.vertical_split(PaneId::Terminal(2), 1));
+    assert!(!tab.horizontal_split(PaneId::Terminal(3), 1));
+    assert!(!tab.new_pane(PaneId::Terminal(4), Some(1)));
+    assert_eq!(tab.get_tiled_pane_ids().len(), 2, "existing panes are kept");
+    assert_eq!(
+        tab.get_active_pane_id(1),
+        Some(PaneId::Terminal(2)),
+        "focus stays on the last pane that was created"
+    );
+}
+
+#[test]
+pub fn duplicated_plugin_panes_are_rejected_when_the_tab_is_full() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let (to_plugin, plugin_receiver) = channels::unbounded();
+    let to_plugin = SenderWithContext::new(to_plugin);
+    tab.senders.to_plugin = Some(to_plugin.clone());
+    let (to_pty, pty_receiver) = channels::unbounded();
+    tab.senders.to_pty = Some(SenderWithContext::new(to_pty));
+    let mut plugin_pane = PluginPane::new(
+        1,
+        PaneGeom::default(),
+        to_plugin,
+        String::from("plugin"),
+        String::new(),
+    );
+    plugin_pane.set_invoked_with(Some(Run::Plugin(RunPlugin {
+        _allow_exec_host_cmd: false,
+        location: RunPluginLocation::Zellij(PluginTag::new("strider")),
+    })));
+    tab.tiled_panes
+        .insert_pane(PaneId::Plugin(1), Box::new(plugin_pane));
+    tab.focus_pane(PaneId::Plugin(1), 1);
+    tab.max_panes = Some(2);
+    tab.reject_new_panes_over_max(true);
+    let plugin_thread = std::thread::spawn(move || {
+        for (instruction, _) in plugin_receiver.iter() {
+            if let PluginInstruction::Load(pid_tx, ..) = instruction {
+                pid_tx.send(2).unwrap();
+                break;
+            }
+        }
+    });
+    tab.duplicate_active_pane(1);
+    plugin_thread.join().unwrap();
+    assert!(
+        !tab.get_all_pane_ids().contains(&PaneId::Plugin(2)),
+        "duplicate was not added"
+    );
+    let duplicate_was_closed = pty_receiver.try_iter().any(|(instruction, _)| {
+        matches!(instruction, PtyInstruction::ClosePane(PaneId::Plugin(2)))
+    });
+    assert!(duplicate_was_closed, "loaded plugin is unloaded again");
+}
+
+#[test]
+pub fn get_pane_title_by_id() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.set_pane_name(PaneId::Terminal(2), String::from("my pane"));
+    assert_eq!(
+        tab.get_pane_title(PaneId::Terminal(2)),
+        Some(String::from("my pane"))
+    );
+    assert_eq!(
+        tab.get_pane_title(PaneId::Terminal(3)),
+        None,
+        "no title for a pane that does not exist"
+    );
+}
+
+#[test]
+pub fn layout_op_history_is_capped() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    for i in 0..MAX_LAYOUT_OP_HISTORY + 10 {
+        tab.record_split(PaneId::Terminal(i as RawFd + 2), HashMap::new());
+    }
+    assert_eq!(
+        tab.layout_op_history.len(),
+        MAX_LAYOUT_OP_HISTORY,
+        "only the most recent ops are kept"
+    );
+}
+
+#[test]
+pub fn undo_split_restores_the_original_pane() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let geom_before_split = tab
+        .get_active_pane(1)
+        .map(|pane| pane.position_and_size())
+        .unwrap();
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.horizontal_split(PaneId::Terminal(3), 1);
+    assert!(tab.undo_last_layout_op(), "horizontal split undone");
+    assert!(tab.undo_last_layout_op(), "vertical split undone");
+    assert!(!tab.undo_last_layout_op(), "nothing left to undo");
+    assert_eq!(tab.get_tiled_pane_ids(), vec![PaneId::Terminal(1)]);
+    assert_eq!(
+        tab.get_active_pane(1).map(|pane| pane.position_and_size()),
+        Some(geom_before_split),
+        "pane has its original size back"
+    );
+}
+
+#[test]
+pub fn panes_that_do_not_fit_are_hidden_until_there_is_room() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.vertical_split(PaneId::Terminal(3), 1);
+    tab.vertical_split(PaneId::Terminal(4), 1);
+    // from least to most recently active: 4, 3, 1, 2
+    tab.move_focus_left(1);
+    tab.move_focus_left(1);
+    tab.move_focus_left(1);
+    tab.focus_last_pane(1);
+    assert_eq!(tab.get_active_pane_id(1), Some(PaneId::Terminal(2)));
+    tab.resize_whole_tab(Size { cols: 12, rows: 10 });
+    assert_eq!(
+        tab.panes_to_hide_count(),
+        2,
+        "panes are hidden until the rest fit"
+    );
+    assert!(
+        tab.is_pane_hidden(PaneId::Terminal(4)) && tab.is_pane_hidden(PaneId::Terminal(3)),
+        "the least recently active panes are hidden first"
+    );
+    assert!(
+        !tab.is_pane_hidden(PaneId::Terminal(1)) && !tab.is_pane_hidden(PaneId::Terminal(2)),
+        "the most recently active panes are still shown"
+    );
+    assert_eq!(tab.get_active_pane_id(1), Some(PaneId::Terminal(2)));
+    tab.resize_whole_tab(size);
+    assert_eq!(
+        tab.panes_to_hide_count(),
+        0,
+        "all panes are shown again once there is room"
+    );
+    assert_eq!(tab.get_active_pane_id(1), Some(PaneId::Terminal(2)));
+    assert_panes_cover_display_area(&tab, size);
+}
+
+#[test]
+pub fn panes_still_shown_while_others_do_not_fit_can_be_clicked_and_focused() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.vertical_split(PaneId::Terminal(3), 1);
+    tab.vertical_split(PaneId::Terminal(4), 1);
+    // from least to most recently active: 4, 3, 1, 2
+    tab.move_focus_left(1);
+    tab.move_focus_left(1);
+    tab.move_focus_left(1);
+    tab.focus_last_pane(1);
+    tab.resize_whole_tab(Size { cols: 12, rows: 10 });
+    assert_eq!(tab.panes_to_hide_count(), 2, "panes 3 and 4 are hidden");
+    let first_pane_geom = tab.pane_geoms()[&PaneId::Terminal(1)];
+    let inside_first_pane = Position::new(
+        (first_pane_geom.y + first_pane_geom.rows.as_usize() / 2) as i32,
+        (first_pane_geom.x + first_pane_geom.cols.as_usize() / 2) as u16,
+    );
+    tab.handle_left_click(&inside_first_pane, 1);
+    tab.handle_mouse_release(&inside_first_pane, 1);
+    assert_eq!(
+        tab.get_active_pane_id(1),
+        Some(PaneId::Terminal(1)),
+        "the clicked pane is focused"
+    );
+    assert!(
+        tab.move_focus_right(1),
+        "focus moves to the pane to the right"
+    );
+    assert_eq!(tab.get_active_pane_id(1), Some(PaneId::Terminal(2)));
+    assert!(
+        !tab.move_focus_right(1),
+        "focus does not move to the hidden panes"
+    );
+    assert_eq!(tab.panes_to_hide_count(), 2, "the panes are still hidden");
+}
+
+#[test]
+pub fn panes_hidden_for_lack_of_space_are_not_fullscreen() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.vertical_split(PaneId::Terminal(3), 1);
+    tab.vertical_split(PaneId::Terminal(4), 1);
+    // from least to most recently active: 4, 3, 1, 2
+    tab.move_focus_left(1);
+    tab.move_focus_left(1);
+    tab.move_focus_left(1);
+    tab.focus_last_pane(1);
+    tab.resize_whole_tab(Size { cols: 12, rows: 10 });
+    assert_eq!(tab.panes_to_hide_count(), 2, "panes 3 and 4 are hidden");
+    assert!(
+        !tab.is_fullscreen_active(),
+        "hiding panes that do not fit is not fullscreen"
+    );
+    tab.toggle_active_pane_fullscreen(1);
+    assert!(tab.is_fullscreen_active(), "fullscreen toggled on");
+    assert!(tab.is_pane_hidden(PaneId::Terminal(1)));
+    tab.toggle_active_pane_fullscreen(1);
+    assert!(!tab.is_fullscreen_active(), "fullscreen toggled off");
+    assert!(
+        tab.is_pane_hidden(PaneId::Terminal(3)) && tab.is_pane_hidden(PaneId::Terminal(4)),
+        "the panes that do not fit stay hidden"
+    );
+    assert!(!tab.is_pane_hidden(PaneId::Terminal(1)));
+    tab.resize_whole_tab(size);
+    assert_eq!(tab.panes_to_hide_count(), 0, "all panes are shown again");
+    assert_panes_cover_display_area(&tab, size);
+}
+
+#[test]
+pub fn set_and_clear_pane_border_color() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let red = PaletteColor::Rgb((255, 0, 0));
+    tab.set_pane_border_color(PaneId::Terminal(1), Some(red));
+    assert_eq!(
+        tab.tiled_panes
+            .get_pane(PaneId::Terminal(1))
+            .and_then(|pane| pane.border_color()),
+        Some(red)
+    );
+    tab.set_pane_border_color(PaneId::Terminal(1), None);
+    assert_eq!(
+        tab.tiled_panes
+            .get_pane(PaneId::Terminal(1))
+            .and_then(|pane| pane.border_color()),
+        None,
+        "border color cleared"
+    );
+}
+
+#[test]
+pub fn selection_keeps_scrolling_while_held_past_the_pane_edge() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let mut pty_bytes = String::new();
+    for i in 0..50 {
+        pty_bytes.push_str(&format!("line {}\r\n", i));
+    }
+    tab.handle_pty_bytes(1, Vec::from(pty_bytes.as_bytes()));
+    tab.handle_left_click(&Position::new(5, 10), 1);
+    // the top row is the frame of the pane
+    tab.handle_mouse_hold(&Position::new(0, 10), 1);
+    assert_eq!(tab.selection_edge_scroll, Some(Position::new(0, 10)));
+    std::thread::sleep(Duration::from_millis(20));
+    tab.scroll_selection_at_edge(1);
+    assert!(
+        tab.get_active_pane(1).unwrap().is_scrolled(),
+        "selection scrolled up the pane"
+    );
+    tab.handle_mouse_release(&Position::new(0, 10), 1);
+    assert_eq!(
+        tab.selection_edge_scroll, None,
+        "scrolling stops when the mouse is released"
+    );
+}
+
+#[test]
+pub fn pressing_a_pane_number_focuses_that_pane() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let os_api = FakeInputOutput::default();
+    tab.os_api = Box::new(os_api.clone());
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.show_pane_numbers();
+    let pane_numbers = tab.render_pane_numbers().unwrap();
+    assert!(pane_numbers.contains(" 1 ") && pane_numbers.contains(" 2 "));
+    tab.write_to_active_terminal(b"1".to_vec(), 1);
+    assert_eq!(tab.get_active_pane_id(1), Some(PaneId::Terminal(1)));
+    assert!(
+        tab.render_pane_numbers().is_none(),
+        "numbers hidden after a key press"
+    );
+    assert!(
+        os_api.written_bytes.lock().unwrap().is_empty(),
+        "the key press is not sent to the pane"
+    );
+}
+
+#[test]
+pub fn synchronized_input_is_not_sent_to_plugin_panes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let os_api = FakeInputOutput::default();
+    tab.os_api = Box::new(os_api.clone());
+    let (to_plugin, plugin_receiver) = channels::unbounded();
+    let to_plugin = SenderWithContext::new(to_plugin);
+    tab.senders.to_plugin = Some(to_plugin.clone());
+    let plugin_pane = PluginPane::new(
+        1,
+        PaneGeom::default(),
+        to_plugin,
+        String::from("plugin"),
+        String::new(),
+    );
+    tab.tiled_panes
+        .insert_pane(PaneId::Plugin(1), Box::new(plugin_pane));
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.toggle_sync_panes_is_active();
+    tab.write_to_terminals_on_current_tab(b"a".to_vec());
+    let plugin_received_keys = plugin_receiver
+        .try_iter()
+        .any(|(instruction, _)| matches!(instruction, PluginInstruction::Update(Some(1), ..)));
+    assert!(!plugin_received_keys, "plugin received nothing");
+    let written_bytes = os_api.written_bytes.lock().unwrap();
+    assert_eq!(written_bytes.get(&1), Some(&b"a".to_vec()));
+    assert_eq!(written_bytes.get(&2), Some(&b"a".to_vec()));
+}
+
+#[test]
+pub fn pane_geoms_and_hidden_panes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    let pane_geoms = tab.pane_geoms();
+    assert_eq!(
+        pane_geoms.keys().copied().collect::<Vec<_>>(),
+        vec![PaneId::Terminal(1), PaneId::Terminal(2)]
+    );
+    let left = pane_geoms[&PaneId::Terminal(1)];
+    let right = pane_geoms[&PaneId::Terminal(2)];
+    assert_eq!(
+        left.x + left.cols.as_usize(),
+        right.x,
+        "panes are side by side"
+    );
+    assert_eq!(right.x + right.cols.as_usize(), size.cols);
+    assert!(!tab.is_pane_hidden(PaneId::Terminal(1)));
+    tab.toggle_active_pane_fullscreen(1);
+    assert!(tab.is_pane_hidden(PaneId::Terminal(1)));
+    assert!(!tab.is_pane_hidden(PaneId::Terminal(2)));
+    assert_eq!(
+        tab.pane_geoms()[&PaneId::Terminal(2)].cols.as_usize(),
+        size.cols,
+        "the fullscreen pane takes up the whole width"
+    );
+}
+
+#[test]
+pub fn close_gravity_previously_active_gives_space_to_last_focused_pane() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.set_close_gravity(CloseGravity::PreviouslyActive);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.vertical_split(PaneId::Terminal(3), 1);
+    tab.move_focus_left(1);
+    let geoms_before_close = tab.pane_geoms();
+    tab.close_pane(PaneId::Terminal(2));
+    let geoms_after_close = tab.pane_geoms();
+    assert_eq!(
+        geoms_after_close.get(&PaneId::Terminal(1)),
+        geoms_before_close.get(&PaneId::Terminal(1)),
+        "pane to the left did not change"
+    );
+    assert_eq!(
+        geoms_after_close
+            .get(&PaneId::Terminal(3))
+            .map(|geom| geom.x),
+        geoms_before_close
+            .get(&PaneId::Terminal(2))
+            .map(|geom| geom.x),
+        "previously focused pane took over the space of the closed pane"
+    );
+    assert_panes_cover_display_area(&tab, size);
+}
+
+#[test]
+pub fn close_other_panes_leaves_active_pane_filling_the_tab() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.horizontal_split(PaneId::Terminal(3), 1);
+    tab.toggle_active_pane_fullscreen(1);
+    tab.close_other_panes(1, false);
+    assert_eq!(tab.get_tiled_pane_ids(), vec![PaneId::Terminal(3)]);
+    assert!(!tab.is_fullscreen_active(), "fullscreen was exited");
+    let geom = tab
+        .get_active_pane(1)
+        .map(|pane| pane.position_and_size())
+        .unwrap();
+    assert_eq!((geom.x, geom.y), (0, 0));
+    assert_eq!(
+        (geom.cols.as_usize(), geom.rows.as_usize()),
+        (size.cols, size.rows)
+    );
+}
+
+#[test]
+pub fn open_pane_splits_the_active_pane_in_the_given_direction() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.move_focus_left(1);
+    let geoms_before = tab.pane_geoms();
+    assert!(tab.open_pane(
+        PaneId::Terminal(3),
+        Some(1),
+        Some(SplitDirection::Horizontal)
+    ));
+    let geoms_after = tab.pane_geoms();
+    assert_eq!(
+        geoms_after.get(&PaneId::Terminal(2)),
+        geoms_before.get(&PaneId::Terminal(2)),
+        "the pane that was not active was not split"
+    );
+    let first_pane = geoms_after.get(&PaneId::Terminal(1)).unwrap();
+    let new_pane = geoms_after.get(&PaneId::Terminal(3)).unwrap();
+    assert_eq!(
+        new_pane.x, first_pane.x,
+        "new pane is below the active pane"
+    );
+    assert!(
+        new_pane.y > first_pane.y,
+        "new pane is below the active pane"
+    );
+    assert_eq!(tab.get_active_pane_id(1), Some(PaneId::Terminal(3)));
+}
+
+#[test]
+pub fn read_only_pane_ignores_typed_and_synchronized_input() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let os_api = FakeInputOutput::default();
+    tab.os_api = Box::new(os_api.clone());
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.set_pane_read_only(PaneId::Terminal(2), true);
+    tab.write_to_active_terminal(b"a".to_vec(), 1);
+    tab.toggle_sync_panes_is_active();
+    tab.write_to_terminals_on_current_tab(b"b".to_vec());
+    {
+        let written_bytes = os_api.written_bytes.lock().unwrap();
+        assert_eq!(written_bytes.get(&1), Some(&b"b".to_vec()));
+        assert_eq!(written_bytes.get(&2), None, "read-only pane got no input");
+    }
+    tab.set_pane_read_only(PaneId::Terminal(2), false);
+    tab.toggle_sync_panes_is_active();
+    tab.write_to_active_terminal(b"c".to_vec(), 1);
+    assert_eq!(
+        os_api.written_bytes.lock().unwrap().get(&2),
+        Some(&b"c".to_vec())
+    );
+}
+
+#[test]
+pub fn rotate_panes_moves_every_pane_one_slot_over() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.vertical_split(PaneId::Terminal(3), 1);
+    let geoms_before = tab.pane_geoms();
+    tab.rotate_panes(false);
+    let geoms_after = tab.pane_geoms();
+    assert_eq!(
+        geoms_after.get(&PaneId::Terminal(1)),
+        geoms_before.get(&PaneId::Terminal(2)),
+        "first pane moved to the second slot"
+    );
+    assert_eq!(
+        geoms_after.get(&PaneId::Terminal(2)),
+        geoms_before.get(&PaneId::Terminal(3)),
+        "second pane moved to the third slot"
+    );
+    assert_eq!(
+        geoms_after.get(&PaneId::Terminal(3)),
+        geoms_before.get(&PaneId::Terminal(1)),
+        "last pane wrapped around to the first slot"
+    );
+    tab.rotate_panes(true);
+    assert_eq!(tab.pane_geoms(), geoms_before, "reverse rotation undoes it");
+    assert_panes_cover_display_area(&tab, size);
+}
+
+#[test]
+pub fn rotate_panes_keeps_the_geom_override_of_each_pane() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    let geom_override = tab
+        .tiled_panes
+        .get_pane(PaneId::Terminal(1))
+        .unwrap()
+        .position_and_size();
+    tab.tiled_panes
+        .get_pane_mut(PaneId::Terminal(1))
+        .unwrap()
+        .get_geom_override(geom_override);
+    let second_slot = tab
+        .tiled_panes
+        .get_pane(PaneId::Terminal(2))
+        .unwrap()
+        .position_and_size();
+    tab.rotate_panes(false);
+    let pane = tab.tiled_panes.get_pane(PaneId::Terminal(1)).unwrap();
+    assert_eq!(
+        pane.position_and_size(),
+        second_slot,
+        "pane moved to the next slot"
+    );
+    assert_eq!(
+        pane.geom_override(),
+        Some(geom_override),
+        "pane kept its own geom override"
+    );
+}
+
+#[test]
+pub fn pane_logging_appends_raw_pty_bytes_until_stopped() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let log_path =
+        std::env::temp_dir().join(format!("zellij-pane-log-test-{}.log", std::process::id()));
+    let _ = std::fs::remove_file(&log_path);
+    tab.handle_pty_bytes(1, b"before ".to_vec());
+    tab.start_pane_logging(PaneId::Terminal(1), log_path.clone());
+    tab.handle_pty_bytes(1, b"\x1b[31mred\x1b[m\r\n".to_vec());
+    tab.stop_pane_logging(PaneId::Terminal(1));
+    tab.handle_pty_bytes(1, b"after".to_vec());
+    let logged = std::fs::read(&log_path).unwrap();
+    let _ = std::fs::remove_file(&log_path);
+    assert_eq!(logged, b"\x1b[31mred\x1b[m\r\n".to_vec());
+}
+
+#[test]
+pub fn dump_screen_writes_the_visible_rows_of_the_active_pane() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let dump_path = std::env::temp_dir().join(format!(
+        "zellij-dump-screen-test-{}.txt",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&dump_path);
+    tab.handle_pty_bytes(1, b"first line   \r\nsecond line".to_vec());
+    tab.dump_active_pane_screen_to_file(&dump_path, 1);
+    let dumped = std::fs::read_to_string(&dump_path).unwrap();
+    let _ = std::fs::remove_file(&dump_path);
+    assert_eq!(dumped, tab.dump_active_pane_screen(1).unwrap() + "\n");
+    assert!(
+        dumped.starts_with("first line\nsecond line\n"),
+        "trailing spaces are trimmed"
+    );
+}
+
+#[test]
+pub fn move_focus_up_and_down_report_whether_focus_moved() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.horizontal_split(PaneId::Terminal(2), 1);
+    assert!(!tab.move_focus_down(1), "already at the bottom");
+    assert!(tab.move_focus_up(1), "moved to the pane above");
+    assert!(!tab.move_focus_up(1), "already at the top");
+    assert!(tab.move_focus_down(1), "moved to the pane below");
+    assert_eq!(tab.get_active_pane_id(1), Some(PaneId::Terminal(2)));
+}
+
+#[test]
+pub fn active_pane_is_at_edge_in_directions_without_panes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    assert!(!tab.active_pane_is_at_edge(1, Direction::Left));
+    assert!(tab.active_pane_is_at_edge(1, Direction::Right));
+    assert!(tab.active_pane_is_at_edge(1, Direction::Up));
+    assert!(tab.active_pane_is_at_edge(1, Direction::Down));
+    tab.toggle_active_pane_fullscreen(1);
+    assert!(
+        tab.active_pane_is_at_edge(1, Direction::Left),
+        "a fullscreen pane is always at the edge"
+    );
+}
+
+#[test]
+pub fn smaller_min_pane_size_allows_splitting_short_panes() {
+    let size = Size { cols: 121, rows: 8 };
+    let mut tab = create_new_tab(size);
+    assert!(
+        !tab.horizontal_split(PaneId::Terminal(2), 1),
+        "two panes of the default minimum height do not fit"
+    );
+    tab.tiled_panes.set_min_pane_size(MIN_TERMINAL_WIDTH, 3);
+    assert!(tab.horizontal_split(PaneId::Terminal(2), 1));
+    for (_, pane) in tab.get_tiled_panes() {
+        assert_eq!(pane.min_height(), 3, "panes use the minimum of their tab");
+        assert!(pane.rows() >= 3);
+    }
+    assert_panes_cover_display_area(&tab, size);
+}
+
+#[test]
+pub fn request_pane_content_size_sizes_the_area_inside_the_frame() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    let achieved = tab
+        .request_pane_content_size(PaneId::Terminal(1), 30, 18)
+        .unwrap();
+    let pane = tab.tiled_panes.get_pane(PaneId::Terminal(1)).unwrap();
+    assert_eq!(
+        achieved,
+        (pane.get_content_columns(), pane.get_content_rows()),
+        "the achieved size is reported"
+    );
+    assert!(
+        (achieved.0 as isize - 30).abs() <= 1,
+        "content width is as requested: {:?}",
+        achieved
+    );
+    assert_eq!(achieved.1, 18, "full height content stays as it is");
+    assert_eq!(
+        tab.request_pane_content_size(PaneId::Terminal(3), 30, 18),
+        None
+    );
+    assert_panes_cover_display_area(&tab, size);
+}
+
+#[test]
+pub fn toggle_max_width_spans_the_active_pane_across_the_tab_and_back() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.move_focus_left(1);
+    tab.horizontal_split(PaneId::Terminal(3), 1);
+    let geoms_before = tab.pane_geoms();
+    tab.toggle_active_pane_max_width(1);
+    let maximized_geom = tab.pane_geoms()[&PaneId::Terminal(3)];
+    let geom_before = geoms_before[&PaneId::Terminal(3)];
+    assert_eq!(
+        (maximized_geom.x, maximized_geom.cols.as_usize()),
+        (0, size.cols)
+    );
+    assert_eq!(
+        (maximized_geom.y, maximized_geom.rows.as_usize()),
+        (geom_before.y, geom_before.rows.as_usize()),
+        "the height of the pane did not change"
+    );
+    assert!(
+        tab.is_pane_hidden(PaneId::Terminal(2)),
+        "overlapping pane is hidden"
+    );
+    assert!(!tab.is_pane_hidden(PaneId::Terminal(1)), "pane above stays");
+    assert_eq!(
+        tab.pane_geoms()[&PaneId::Terminal(1)],
+        geoms_before[&PaneId::Terminal(1)]
+    );
+    tab.toggle_active_pane_max_width(1);
+    assert_eq!(tab.pane_geoms(), geoms_before, "geometry is restored");
+    assert!(!tab.is_pane_hidden(PaneId::Terminal(2)));
+}
+
+#[test]
+pub fn panes_beside_a_pane_with_max_width_can_be_clicked_and_focused() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    tab.move_focus_left(1);
+    tab.horizontal_split(PaneId::Terminal(3), 1);
+    let geoms_before = tab.pane_geoms();
+    tab.toggle_active_pane_max_width(1);
+    let first_pane_geom = tab.pane_geoms()[&PaneId::Terminal(1)];
+    let inside_first_pane = Position::new(
+        (first_pane_geom.y + first_pane_geom.rows.as_usize() / 2) as i32,
+        (first_pane_geom.x + first_pane_geom.cols.as_usize() / 2) as u16,
+    );
+    tab.handle_left_click(&inside_first_pane, 1);
+    tab.handle_mouse_release(&inside_first_pane, 1);
+    assert_eq!(
+        tab.get_active_pane_id(1),
+        Some(PaneId::Terminal(1)),
+        "the clicked pane is focused"
+    );
+    assert!(
+        tab.is_pane_hidden(PaneId::Terminal(2)),
+        "the overlapped pane is still hidden"
+    );
+    assert!(tab.move_focus_down(1), "focus moves to the maximized pane");
+    assert_eq!(tab.get_active_pane_id(1), Some(PaneId::Terminal(3)));
+    assert!(
+        !tab.move_focus_right(1),
+        "focus does not move to the hidden pane"
+    );
+    tab.move_focus_up(1);
+    tab.toggle_active_pane_max_width(1);
+    assert_eq!(
+        tab.pane_geoms(),
+        geoms_before,
+        "geometry is restored even though another pane is focused"
+    );
+    assert!(!tab.is_pane_hidden(PaneId::Terminal(2)));
+}
+
+#[test]
+pub fn clicking_the_top_border_of_a_framed_pane_focuses_it() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), client_id);
+    assert_eq!(tab.get_active_pane_id(client_id), Some(PaneId::Terminal(2)));
+    let top_border_of_first_pane = Position::new(0, 10);
+    tab.handle_left_click(&top_border_of_first_pane, client_id);
+    assert_eq!(
+        tab.get_active_pane_id(client_id),
+        Some(PaneId::Terminal(1)),
+        "clicking the frame focused the framed pane"
+    );
+}
+
+#[test]
+pub fn active_pane_cursor_visibility_follows_dectcem() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    assert!(tab.active_pane_has_cursor(client_id));
+    assert!(!tab.active_pane_cursor_is_hidden(client_id));
+    tab.handle_pty_bytes(1, b"\x1b[?25l".to_vec());
+    assert!(!tab.active_pane_has_cursor(client_id));
+    assert!(tab.active_pane_cursor_is_hidden(client_id));
+    tab.handle_pty_bytes(1, b"\x1b[?25h".to_vec());
+    assert!(tab.active_pane_has_cursor(client_id));
+    assert!(!tab.active_pane_cursor_is_hidden(client_id));
+}
+
+#[test]
+pub fn maximize_active_pane_grows_it_until_its_neighbour_is_at_its_minimum() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), client_id);
+    tab.move_focus_left(client_id);
+    tab.maximize_active_pane(client_id, Direction::Right);
+    let geoms = tab.pane_geoms();
+    let (first_pane, second_pane) = (geoms[&PaneId::Terminal(1)], geoms[&PaneId::Terminal(2)]);
+    assert_eq!(first_pane.x, 0);
+    assert_eq!(
+        first_pane.cols.as_usize() + second_pane.cols.as_usize(),
+        size.cols,
+        "panes still cover the whole width"
+    );
+    assert!(
+        second_pane.cols.as_usize() < size.cols / 10,
+        "neighbour was shrunk as much as possible: {}",
+        second_pane.cols.as_usize()
+    );
+    tab.maximize_active_pane(client_id, Direction::Right);
+    assert_eq!(tab.pane_geoms(), geoms, "cannot grow any further");
+    assert_panes_cover_display_area(&tab, size);
+}
+
+#[test]
+pub fn layout_panes_with_a_fixed_size_keep_it_through_resizes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let pane_layout = |split_size: Option<SplitSize>| Layout {
+        direction: SplitDirection::Horizontal,
+        pane_name: None,
+        parts: vec![],
+        split_size,
+        run: None,
+        borderless: false,
+        focus: None,
+    };
+    let layout = Layout {
+        direction: SplitDirection::Vertical,
+        pane_name: None,
+        parts: vec![pane_layout(Some(SplitSize::Fixed(20))), pane_layout(None)],
+        split_size: None,
+        run: None,
+        borderless: false,
+        focus: None,
+    };
+    let mut tab = create_new_tab_with_layout(size, layout, vec![1, 2]);
+    let sidebar_cols = |tab: &Tab| tab.pane_geoms()[&PaneId::Terminal(1)].cols.as_usize();
+    assert_eq!(
+        sidebar_cols(&tab),
+        20,
+        "sidebar is laid out at its exact size"
+    );
+    for cols in [80, 200, 121] {
+        let new_size = Size { cols, rows: 20 };
+        tab.resize_whole_tab(new_size);
+        assert_eq!(
+            sidebar_cols(&tab),
+            20,
+            "sidebar keeps its size at {} cols",
+            cols
+        );
+        assert_panes_cover_display_area(&tab, new_size);
+    }
+}
+
+#[test]
+pub fn selections_are_cleared_when_the_last_client_detaches() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let mut pty_bytes = String::new();
+    for i in 0..10 {
+        pty_bytes.push_str(&format!("line {}\r\n", i));
+    }
+    tab.handle_pty_bytes(1, Vec::from(pty_bytes.as_bytes()));
+    tab.handle_left_click(&Position::new(2, 2), 1);
+    tab.handle_mouse_hold(&Position::new(4, 5), 1);
+    let selected_text = |tab: &Tab| {
+        tab.tiled_panes
+            .get_pane(PaneId::Terminal(1))
+            .unwrap()
+            .get_selected_text()
+    };
+    assert!(selected_text(&tab).is_some(), "text was selected");
+    tab.remove_client(1);
+    assert_eq!(selected_text(&tab), None, "selection was cleared");
+    assert!(!tab.selecting_with_mouse);
+}
+
+#[test]
+pub fn toggle_active_pane_line_wrap_survives_resizing_the_tab() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    assert!(
+        tab.get_active_pane(1).unwrap().line_wrap(),
+        "wrap is on by default"
+    );
+    tab.toggle_active_pane_line_wrap(1);
+    tab.resize_whole_tab(Size {
+        cols: 100,
+        rows: 20,
+    });
+    assert!(!tab.get_active_pane(1).unwrap().line_wrap());
+    tab.toggle_active_pane_line_wrap(1);
+    assert!(tab.get_active_pane(1).unwrap().line_wrap());
+}
+
+#[test]
+pub fn send_signal_to_active_pane_signals_its_foreground_process_group() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let os_api = FakeInputOutput::default();
+    tab.os_api = Box::new(os_api.clone());
+    os_api.running_processes.lock().unwrap().insert(1);
+    let sigkill = 9;
+    tab.send_signal_to_active_pane(1, sigkill);
+    assert_eq!(
+        *os_api.signalled_process_groups.lock().unwrap(),
+        vec![(Pid::from_raw(1001), sigkill)],
+        "the process running in the foreground was signalled, not the shell"
+    );
+    assert!(
+        tab.get_active_pane(1).is_some(),
+        "the pane is kept open so its output can still be read"
+    );
+}
+
+#[test]
+pub fn insert_synthetic_line_keeps_the_scroll_position() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let mut output = String::new();
+    for i in 0..50 {
+        output.push_str(&format!("line {}\r\n", i));
+    }
+    tab.handle_pty_bytes(1, output.into_bytes());
+    for _ in 0..3 {
+        tab.scroll_active_terminal_up(1);
+    }
+    let screen_before = tab.dump_active_pane_screen(1).unwrap();
+    tab.insert_synthetic_line(PaneId::Terminal(1), String::from("--- done ---"), 1);
+    assert_eq!(
+        tab.dump_active_pane_screen(1).unwrap(),
+        screen_before,
+        "the user still sees the lines they scrolled to"
+    );
+    tab.scroll_active_terminal_to_bottom(1);
+    assert!(
+        tab.dump_active_pane_screen(1)
+            .unwrap()
+            .contains("--- done ---"),
+        "the line was inserted at the bottom"
+    );
+}
+
+#[test]
+pub fn overlay_message_is_centered_by_its_display_width() {
     let size = Size {
         cols: 121,
         rows: 20,
     };
     let mut tab = create_new_tab(size);
-    tab.vertical_split(PaneId::Terminal(2), 1);
-    tab.move_focus_left(1);
-    tab.horizontal_split(PaneId::Terminal(3), 1);
-    tab.move_focus_right(1);
-    tab.resize_increase(1);
-
-    // should behave like `resize_left_with_multiple_panes_to_the_left`
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(2))
-            .unwrap()
-            .position_and_size()
-            .x,
-        54,
-        "pane 2 x position"
-    );
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(2))
-            .unwrap()
-            .position_and_size()
-            .y,
-        0,
-        "pane 2 y position"
-    );
+    tab.show_overlay_message(String::from("日本語"), Duration::from_secs(60));
+    // 6 columns of text and 2 of padding
     assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(2))
-            .unwrap()
-            .position_and_size()
-            .cols
-            .as_usize(),
-        67,
-        "pane 2 column count"
+        tab.render_overlay_message(),
+        Some(String::from(
+            "\u{1b}[11;57H\u{1b}[m\u{1b}[7m 日本語 \u{1b}[m"
+        )),
+        "wide characters are measured by the columns they take up"
     );
+    tab.show_overlay_message(String::from("expired"), Duration::from_secs(0));
+    tab.clear_expired_overlay_message();
     assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(2))
-            .unwrap()
-            .position_and_size()
-            .rows
-            .as_usize(),
-        20,
-        "pane 2 row count"
+        tab.render_overlay_message(),
+        None,
+        "expired messages are cleared"
     );
 }
 
 #[test]
-pub fn nondirectional_resize_increase_with_1_pane_to_right_1_pane_above() {
+pub fn active_pane_scrolled_is_only_sent_when_the_offset_changes() {
     let size = Size {
         cols: 121,
         rows: 20,
     };
     let mut tab = create_new_tab(size);
-    tab.vertical_split(PaneId::Terminal(2), 1);
-    tab.move_focus_left(1);
-    tab.horizontal_split(PaneId::Terminal(3), 1);
-    tab.resize_increase(1);
-
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(3))
-            .unwrap()
-            .position_and_size()
-            .y,
-        9,
-        "Pane 3 y coordinate"
-    );
+    let (to_plugin, plugin_receiver) = channels::unbounded();
+    tab.senders.to_plugin = Some(SenderWithContext::new(to_plugin));
+    let mut output = String::new();
+    for i in 0..50 {
+        output.push_str(&format!("line {}\r\n", i));
+    }
+    let count_scroll_events = |plugin_receiver: &channels::Receiver<(PluginInstruction, _)>| {
+        plugin_receiver
+            .try_iter()
+            .filter(|(instruction, _)| {
+                matches!(
+                    instruction,
+                    PluginInstruction::Update(_, _, Event::ActivePaneScrolled(..))
+                )
+            })
+            .count()
+    };
+    tab.update_scroll_info_in_plugins();
+    assert_eq!(count_scroll_events(&plugin_receiver), 1, "initial offset");
+    tab.handle_pty_bytes(1, output.into_bytes());
+    tab.update_scroll_info_in_plugins();
     assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(3))
-            .unwrap()
-            .position_and_size()
-            .x,
+        count_scroll_events(&plugin_receiver),
         0,
-        "Pane 3 x coordinate"
-    );
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(3))
-            .unwrap()
-            .position_and_size()
-            .rows
-            .as_usize(),
-        11,
-        "Pane 3 row count"
+        "output growing the scrollback is not a scroll"
     );
+    tab.scroll_active_terminal_up(1);
+    tab.update_scroll_info_in_plugins();
     assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(3))
-            .unwrap()
-            .position_and_size()
-            .cols
-            .as_usize(),
-        67,
-        "Pane 3 col count"
+        count_scroll_events(&plugin_receiver),
+        1,
+        "scrolling up is reported"
     );
 }
 
 #[test]
-pub fn nondirectional_resize_increase_with_1_pane_to_right_1_pane_to_left() {
+pub fn active_pane_title_change_is_reported_once() {
     let size = Size {
         cols: 121,
         rows: 20,
     };
     let mut tab = create_new_tab(size);
-    tab.vertical_split(PaneId::Terminal(2), 1);
-    tab.vertical_split(PaneId::Terminal(3), 1);
-    tab.move_focus_left(1);
-    tab.resize_increase(1);
+    let (to_plugin, plugin_receiver) = channels::unbounded();
+    tab.senders.to_plugin = Some(SenderWithContext::new(to_plugin));
+    tab.handle_pty_bytes(1, "\u{1b}]0;my title\u{7}".as_bytes().to_vec());
+    let reported_titles: Vec<String> = plugin_receiver
+        .try_iter()
+        .filter_map(|(instruction, _)| match instruction {
+            PluginInstruction::Update(_, _, Event::ActivePaneChanged(pane_info)) => {
+                Some(pane_info.title)
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        reported_titles,
+        vec![String::from("my title")],
+        "the new title was reported exactly once"
+    );
+    tab.handle_pty_bytes(1, "\u{1b}]0;my title\u{7}more".as_bytes().to_vec());
+    assert!(
+        !plugin_receiver.try_iter().any(|(instruction, _)| matches!(
+            instruction,
+            PluginInstruction::Update(_, _, Event::ActivePaneChanged(_))
+        )),
+        "setting the same title again is not reported"
+    );
+}
 
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(2))
-            .unwrap()
-            .position_and_size()
-            .y,
-        0,
-        "Pane 3 y coordinate"
+#[test]
+pub fn active_pane_change_is_reported_once() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let (to_plugin, plugin_receiver) = channels::unbounded();
+    let mut senders = ThreadSenders::default().silently_fail_on_send();
+    senders.to_plugin = Some(SenderWithContext::new(to_plugin));
+    let mut tab = create_new_tab_with_senders(
+        size,
+        LayoutTemplate::default().try_into().unwrap(),
+        vec![1],
+        senders,
     );
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(2))
-            .unwrap()
-            .position_and_size()
-            .x,
-        61,
-        "Pane 3 x coordinate"
+    tab.vertical_split(PaneId::Terminal(2), 1);
+    let _ = plugin_receiver.try_iter().count();
+    tab.move_focus_left(1);
+    tab.move_focus_left(1);
+    tab.focus_pane(PaneId::Terminal(1), 1);
+    let reported_panes: Vec<(u32, bool)> = plugin_receiver
+        .try_iter()
+        .filter_map(|(instruction, _)| match instruction {
+            PluginInstruction::Update(_, _, Event::ActivePaneChanged(pane_info)) => {
+                Some((pane_info.id, pane_info.is_plugin))
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        reported_panes,
+        vec![(1, false)],
+        "moving focus to the left pane was reported once, staying on it was not reported"
     );
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(2))
-            .unwrap()
-            .position_and_size()
-            .rows
-            .as_usize(),
-        20,
-        "Pane 3 row count"
+}
+
+#[test]
+pub fn copy_active_pane_scrollback_copies_only_the_last_lines() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let (to_server, server_receiver) = channels::unbounded();
+    tab.senders.to_server = Some(SenderWithContext::new(to_server));
+    let output: Vec<String> = (0..50).map(|i| format!("line {}", i)).collect();
+    tab.handle_pty_bytes(1, output.join("\r\n").into_bytes());
+    tab.scrollback_copy_line_limit = 10;
+    tab.copy_active_pane_scrollback(1);
+    let serialized_output = match server_receiver.try_recv() {
+        Ok((ServerInstruction::Render(Some(serialized_output)), _)) => serialized_output,
+        _ => panic!("scrollback was not sent to the client clipboard"),
+    };
+    let expected_clipboard_contents = output[40..].join("\n");
+    assert!(
+        serialized_output.get(&1).unwrap().contains(&format!(
+            "\u{1b}]52;c;{}\u{1b}\\",
+            base64::encode(&expected_clipboard_contents)
+        )),
+        "only the last 10 lines are copied"
     );
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(2))
-            .unwrap()
-            .position_and_size()
-            .cols
-            .as_usize(),
-        36,
-        "Pane 3 col count"
+}
+
+#[test]
+pub fn selection_in_a_horizontally_scrolled_pane_starts_at_the_clicked_column() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.toggle_active_pane_line_wrap(1);
+    let line = "abcdefghijklmnopqrstuvwxyz".repeat(8);
+    tab.handle_pty_bytes(1, line.into_bytes());
+    for _ in 0..10 {
+        tab.scroll_active_terminal_right(1);
+    }
+    tab.handle_left_click(&Position::new(1, 1), 1);
+    tab.handle_mouse_release(&Position::new(1, 5), 1);
+    let selected_text = tab
+        .get_active_pane(1)
+        .and_then(|pane| pane.get_selected_text())
+        .unwrap();
+    assert!(
+        selected_text.starts_with("klmn"),
+        "the columns scrolled out of view are accounted for, selected: {:?}",
+        selected_text
     );
 }
 
 #[test]
-pub fn nondirectional_resize_increase_with_pane_above_aligned_right_with_current_pane() {
+pub fn open_url_at_sends_the_url_under_the_mouse_to_the_server() {
     let size = Size {
         cols: 121,
         rows: 20,
     };
     let mut tab = create_new_tab(size);
-    tab.vertical_split(PaneId::Terminal(2), 1);
-    tab.vertical_split(PaneId::Terminal(3), 1);
-    tab.move_focus_left(1);
-    tab.resize_increase(1);
+    let (to_server, server_receiver) = channels::unbounded();
+    tab.senders.to_server = Some(SenderWithContext::new(to_server));
+    tab.handle_pty_bytes(1, "see https://zellij.dev/docs now".as_bytes().to_vec());
+    // the pane content starts at 1, 1 because of its frame
+    assert!(
+        !tab.open_url_at(&Position::new(1, 2)),
+        "no url under the mouse"
+    );
+    assert!(
+        tab.open_url_at(&Position::new(1, 10)),
+        "url under the mouse"
+    );
+    match server_receiver.try_recv() {
+        Ok((ServerInstruction::OpenUrl(url), _)) => assert_eq!(url, "https://zellij.dev/docs"),
+        _ => panic!("url was not sent to the server"),
+    }
+}
 
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(2))
-            .unwrap()
-            .position_and_size()
-            .y,
-        0,
-        "Pane 3 y coordinate"
+#[test]
+pub fn open_url_at_finds_mail_links_but_not_other_schemes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let (to_server, server_receiver) = channels::unbounded();
+    tab.senders.to_server = Some(SenderWithContext::new(to_server));
+    tab.handle_pty_bytes(
+        1,
+        "mailto:team@zellij.dev ftp://zellij.dev/files"
+            .as_bytes()
+            .to_vec(),
     );
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(2))
-            .unwrap()
-            .position_and_size()
-            .x,
-        61,
-        "Pane 3 x coordinate"
+    assert!(
+        tab.open_url_at(&Position::new(1, 5)),
+        "mail link under the mouse"
     );
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(2))
-            .unwrap()
-            .position_and_size()
-            .rows
-            .as_usize(),
-        20,
-        "Pane 3 row count"
+    match server_receiver.try_recv() {
+        Ok((ServerInstruction::OpenUrl(url), _)) => assert_eq!(url, "mailto:team@zellij.dev"),
+        _ => panic!("mail link was not sent to the server"),
+    }
+    assert!(
+        !tab.open_url_at(&Position::new(1, 30)),
+        "ftp url is not opened"
     );
-    assert_eq!(
-        tab.tiled_panes
-            .panes
-            .get(&PaneId::Terminal(2))
-            .unwrap()
-            .position_and_size()
-            .cols
-            .as_usize(),
-        36,
-        "Pane 3 col count"
+}
+
+#[test]
+pub fn open_url_at_prefers_the_hyperlink_target() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let (to_server, server_receiver) = channels::unbounded();
+    tab.senders.to_server = Some(SenderWithContext::new(to_server));
+    let content = "see \u{1b}]8;;https://zellij.dev/docs\u{1b}\\the docs\u{1b}]8;;\u{1b}\\ now";
+    tab.handle_pty_bytes(1, content.as_bytes().to_vec());
+    assert!(
+        tab.open_url_at(&Position::new(1, 6)),
+        "hyperlink under the mouse"
+    );
+    match server_receiver.try_recv() {
+        Ok((ServerInstruction::OpenUrl(url), _)) => assert_eq!(url, "https://zellij.dev/docs"),
+        _ => panic!("hyperlink target was not sent to the server"),
+    }
+}
+
+#[test]
+pub fn open_url_at_ignores_hyperlinks_that_are_not_web_or_mail_links() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let (to_server, server_receiver) = channels::unbounded();
+    tab.senders.to_server = Some(SenderWithContext::new(to_server));
+    let content = "see \u{1b}]8;;file:///etc/passwd\u{1b}\\the file\u{1b}]8;;\u{1b}\\ now";
+    tab.handle_pty_bytes(1, content.as_bytes().to_vec());
+    assert!(
+        !tab.open_url_at(&Position::new(1, 6)),
+        "file hyperlink is not opened"
+    );
+    assert!(
+        server_receiver.try_recv().is_err(),
+        "nothing was sent to the server"
     );
 }
 
 #[test]
-pub fn custom_cursor_height_width_ratio() {
+pub fn dragging_a_block_selection_copies_only_the_selected_columns() {
     let size = Size {
         cols: 121,
         rows: 20,
     };
-    let character_cell_size = Rc::new(RefCell::new(None));
-    let tab = create_new_tab_with_cell_size(size, character_cell_size.clone());
-    let initial_cursor_height_width_ratio = tab.tiled_panes.cursor_height_width_ratio();
-    *character_cell_size.borrow_mut() = Some(SizeInPixels {
-        height: 10,
-        width: 4,
-    });
-    let cursor_height_width_ratio_after_update = tab.tiled_panes.cursor_height_width_ratio();
-    assert_eq!(
-        initial_cursor_height_width_ratio, None,
-        "initially no ratio "
+    let mut tab = create_new_tab(size);
+    tab.handle_pty_bytes(
+        1,
+        "name   size  owner\r\nfoo    12    root\r\nbar    345   user"
+            .as_bytes()
+            .to_vec(),
     );
+    // the pane content starts at 1, 1 because of its frame
+    tab.handle_left_click_with_selection_mode(&Position::new(3, 14), SelectionMode::Block, 1);
+    tab.handle_mouse_hold(&Position::new(2, 10), 1);
+    tab.handle_mouse_release(&Position::new(1, 8), 1);
     assert_eq!(
-        cursor_height_width_ratio_after_update,
-        Some(3),
-        "ratio updated successfully"
-    ); // 10 / 4 == 2.5, rounded: 3
+        tab.get_active_pane(1)
+            .and_then(|pane| pane.get_selected_text()),
+        Some(String::from("size\n12\n345")),
+        "the mouse hold kept extending the block selection"
+    );
 }
@@ -4,7 +4,54 @@ use crate::{
     os_input_output::ServerOsApi, pty::PtyInstruction, screen::ScreenInstruction,
     wasm_vm::PluginInstruction, ServerInstruction,
 };
-use zellij_utils::{channels, channels::SenderWithContext, errors::ErrorContext};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use zellij_utils::{
+    channels, channels::SenderWithContext, errors::ErrorContext, lazy_static::lazy_static,
+};
+
+type DelayedScreenInstruction = (
+    Instant,
+    SenderWithContext<ScreenInstruction>,
+    ScreenInstruction,
+);
+
+lazy_static! {
+    // a single thread sends all the delayed instructions, rather than a sleeping thread for each
+    static ref DELAYED_SCREEN_INSTRUCTIONS: Mutex<mpsc::Sender<DelayedScreenInstruction>> = {
+        let (sender, receiver) = mpsc::channel();
+        thread::Builder::new()
+            .name("screen_timer".to_string())
+            .spawn(move || send_delayed_screen_instructions(receiver))
+            .unwrap();
+        Mutex::new(sender)
+    };
+}
+
+fn send_delayed_screen_instructions(receiver: mpsc::Receiver<DelayedScreenInstruction>) {
+    let mut pending: Vec<DelayedScreenInstruction> = vec![];
+    loop {
+        let now = Instant::now();
+        let (due, not_due): (Vec<_>, Vec<_>) = pending.into_iter().partition(|(at, ..)| *at <= now);
+        pending = not_due;
+        for (_, to_screen, instruction) in due {
+            let _ = to_screen.send(instruction);
+        }
+        let received = match pending.iter().map(|(at, ..)| *at).min() {
+            Some(next_at) => match receiver.recv_timeout(next_at.saturating_duration_since(now)) {
+                Ok(delayed_instruction) => Some(delayed_instruction),
+                Err(mpsc::RecvTimeoutError::Timeout) => None,
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            },
+            None => match receiver.recv() {
+                Ok(delayed_instruction) => Some(delayed_instruction),
+                Err(_) => break,
+            },
+        };
+        pending.extend(received);
+    }
+}
 
 /// A container for senders to the different threads in zellij on the server side
 #[derive(Default, Clone)]
@@ -35,6 +82,18 @@ impl ThreadSenders {
         }
     }
 
+    /// Sends `instruction` to the screen thread once `delay` has passed, eg. to render again when
+    /// something shown on screen expires
+    pub fn send_to_screen_after(&self, instruction: ScreenInstruction, delay: Duration) {
+        if let Some(to_screen) = &self.to_screen {
+            let _ = DELAYED_SCREEN_INSTRUCTIONS.lock().unwrap().send((
+                Instant::now() + delay,
+                to_screen.clone(),
+                instruction,
+            ));
+        }
+    }
+
     pub fn send_to_pty(
         &self,
         instruction: PtyInstruction,
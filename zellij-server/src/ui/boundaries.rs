@@ -6,6 +6,7 @@ use crate::tab::Pane;
 use ansi_term::Colour::{Fixed, RGB};
 use std::collections::HashMap;
 use zellij_tile::data::PaletteColor;
+pub use zellij_tile::data::{BoundaryOrientation, BoundarySegment};
 use zellij_utils::shared::colors;
 
 use std::fmt::{Display, Error, Formatter};
@@ -29,7 +30,7 @@ pub mod boundary_type {
 
 pub(crate) type BoundaryType = &'static str; // easy way to refer to boundary_type above
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub(crate) struct BoundarySymbol {
     boundary_type: BoundaryType,
     invisible: bool,
@@ -420,6 +421,15 @@ impl Coordinates {
     }
 }
 
+#[derive(Clone, Copy)]
+enum Edge {
+    Left,
+    Top,
+    Right,
+    Bottom,
+}
+
+#[derive(PartialEq)]
 pub struct Boundaries {
     viewport: Viewport,
     boundary_characters: HashMap<Coordinates, BoundarySymbol>,
@@ -433,25 +443,49 @@ impl Boundaries {
         }
     }
     pub fn add_rect(&mut self, rect: &dyn Pane, color: Option<PaletteColor>) {
-        if !self.is_fully_inside_screen(rect) {
-            return;
-        }
-        if rect.x() > self.viewport.x {
-            // left boundary
-            let boundary_x_coords = rect.x() - 1;
-            let first_row_coordinates = self.rect_right_boundary_row_start(rect);
-            let last_row_coordinates = self.rect_right_boundary_row_end(rect);
-            for row in first_row_coordinates..last_row_coordinates {
-                let coordinates = Coordinates::new(boundary_x_coords, row);
-                let symbol_to_add = if row == first_row_coordinates && row != self.viewport.y {
-                    BoundarySymbol::new(boundary_type::TOP_LEFT).color(color)
-                } else if row == last_row_coordinates - 1
-                    && row != self.viewport.y + self.viewport.rows - 1
-                {
-                    BoundarySymbol::new(boundary_type::BOTTOM_LEFT).color(color)
+        for (edge, segment) in self.rect_edges(rect) {
+            let (first_symbol, last_symbol) = match edge {
+                Edge::Left => (boundary_type::TOP_LEFT, boundary_type::BOTTOM_LEFT),
+                Edge::Top => (boundary_type::TOP_LEFT, boundary_type::TOP_RIGHT),
+                Edge::Right => (boundary_type::TOP_RIGHT, boundary_type::BOTTOM_RIGHT),
+                Edge::Bottom => (boundary_type::BOTTOM_LEFT, boundary_type::BOTTOM_RIGHT),
+            };
+            let cells: Vec<Coordinates> = match segment.orientation {
+                BoundaryOrientation::Vertical => (segment.start.1..=segment.end.1)
+                    .map(|row| Coordinates::new(segment.start.0, row))
+                    .collect(),
+                BoundaryOrientation::Horizontal => (segment.start.0..=segment.end.0)
+                    .map(|col| Coordinates::new(col, segment.start.1))
+                    .collect(),
+            };
+            if cells.is_empty() {
+                continue;
+            }
+            let last_index = cells.len() - 1;
+            for (index, coordinates) in cells.into_iter().enumerate() {
+                let (position, screen_start, screen_end) = match segment.orientation {
+                    BoundaryOrientation::Vertical => (
+                        coordinates.y,
+                        self.viewport.y,
+                        (self.viewport.y + self.viewport.rows).saturating_sub(1),
+                    ),
+                    BoundaryOrientation::Horizontal => (
+                        coordinates.x,
+                        self.viewport.x,
+                        self.viewport.cols.saturating_sub(1),
+                    ),
+                };
+                let boundary_type = if index == 0 && position != screen_start {
+                    first_symbol
+                } else if index == last_index && position != screen_end {
+                    last_symbol
                 } else {
-                    BoundarySymbol::new(boundary_type::VERTICAL).color(color)
+                    match segment.orientation {
+                        BoundaryOrientation::Vertical => boundary_type::VERTICAL,
+                        BoundaryOrientation::Horizontal => boundary_type::HORIZONTAL,
+                    }
                 };
+                let symbol_to_add = BoundarySymbol::new(boundary_type).color(color);
                 let next_symbol = self
                     .boundary_characters
                     .remove(&coordinates)
@@ -460,74 +494,52 @@ impl Boundaries {
                 self.boundary_characters.insert(coordinates, next_symbol);
             }
         }
+    }
+    pub fn segments(&self, rect: &dyn Pane) -> Vec<BoundarySegment> {
+        self.rect_edges(rect)
+            .into_iter()
+            .map(|(_edge, segment)| segment)
+            .collect()
+    }
+    fn rect_edges(&self, rect: &dyn Pane) -> Vec<(Edge, BoundarySegment)> {
+        let mut edges = vec![];
+        if !self.is_fully_inside_screen(rect) || rect.rows() == 0 || rect.cols() == 0 {
+            // a pane without rows or columns has no edges to draw
+            return edges;
+        }
+        let first_row = self.rect_right_boundary_row_start(rect);
+        let last_row = self.rect_right_boundary_row_end(rect) - 1;
+        let first_col = self.rect_bottom_boundary_col_start(rect);
+        let last_col = self.rect_bottom_boundary_col_end(rect) - 1;
+        if rect.x() > self.viewport.x {
+            let x = rect.x() - 1;
+            edges.push((
+                Edge::Left,
+                BoundarySegment::vertical(x, first_row, last_row),
+            ));
+        }
         if rect.y() > self.viewport.y {
-            // top boundary
-            let boundary_y_coords = rect.y() - 1;
-            let first_col_coordinates = self.rect_bottom_boundary_col_start(rect);
-            let last_col_coordinates = self.rect_bottom_boundary_col_end(rect);
-            for col in first_col_coordinates..last_col_coordinates {
-                let coordinates = Coordinates::new(col, boundary_y_coords);
-                let symbol_to_add = if col == first_col_coordinates && col != self.viewport.x {
-                    BoundarySymbol::new(boundary_type::TOP_LEFT).color(color)
-                } else if col == last_col_coordinates - 1 && col != self.viewport.cols - 1 {
-                    BoundarySymbol::new(boundary_type::TOP_RIGHT).color(color)
-                } else {
-                    BoundarySymbol::new(boundary_type::HORIZONTAL).color(color)
-                };
-                let next_symbol = self
-                    .boundary_characters
-                    .remove(&coordinates)
-                    .and_then(|current_symbol| combine_symbols(current_symbol, symbol_to_add))
-                    .unwrap_or(symbol_to_add);
-                self.boundary_characters.insert(coordinates, next_symbol);
-            }
+            let y = rect.y() - 1;
+            edges.push((
+                Edge::Top,
+                BoundarySegment::horizontal(y, first_col, last_col),
+            ));
         }
         if self.rect_right_boundary_is_before_screen_edge(rect) {
-            // right boundary
-            let boundary_x_coords = rect.right_boundary_x_coords() - 1;
-            let first_row_coordinates = self.rect_right_boundary_row_start(rect);
-            let last_row_coordinates = self.rect_right_boundary_row_end(rect);
-            for row in first_row_coordinates..last_row_coordinates {
-                let coordinates = Coordinates::new(boundary_x_coords, row);
-                let symbol_to_add = if row == first_row_coordinates && row != self.viewport.y {
-                    BoundarySymbol::new(boundary_type::TOP_RIGHT).color(color)
-                } else if row == last_row_coordinates - 1
-                    && row != self.viewport.y + self.viewport.rows - 1
-                {
-                    BoundarySymbol::new(boundary_type::BOTTOM_RIGHT).color(color)
-                } else {
-                    BoundarySymbol::new(boundary_type::VERTICAL).color(color)
-                };
-                let next_symbol = self
-                    .boundary_characters
-                    .remove(&coordinates)
-                    .and_then(|current_symbol| combine_symbols(current_symbol, symbol_to_add))
-                    .unwrap_or(symbol_to_add);
-                self.boundary_characters.insert(coordinates, next_symbol);
-            }
+            let x = rect.right_boundary_x_coords() - 1;
+            edges.push((
+                Edge::Right,
+                BoundarySegment::vertical(x, first_row, last_row),
+            ));
         }
         if self.rect_bottom_boundary_is_before_screen_edge(rect) {
-            // bottom boundary
-            let boundary_y_coords = rect.bottom_boundary_y_coords() - 1;
-            let first_col_coordinates = self.rect_bottom_boundary_col_start(rect);
-            let last_col_coordinates = self.rect_bottom_boundary_col_end(rect);
-            for col in first_col_coordinates..last_col_coordinates {
-                let coordinates = Coordinates::new(col, boundary_y_coords);
-                let symbol_to_add = if col == first_col_coordinates && col != self.viewport.x {
-                    BoundarySymbol::new(boundary_type::BOTTOM_LEFT).color(color)
-                } else if col == last_col_coordinates - 1 && col != self.viewport.cols - 1 {
-                    BoundarySymbol::new(boundary_type::BOTTOM_RIGHT).color(color)
-                } else {
-                    BoundarySymbol::new(boundary_type::HORIZONTAL).color(color)
-                };
-                let next_symbol = self
-                    .boundary_characters
-                    .remove(&coordinates)
-                    .and_then(|current_symbol| combine_symbols(current_symbol, symbol_to_add))
-                    .unwrap_or(symbol_to_add);
-                self.boundary_characters.insert(coordinates, next_symbol);
-            }
+            let y = rect.bottom_boundary_y_coords() - 1;
+            edges.push((
+                Edge::Bottom,
+                BoundarySegment::horizontal(y, first_col, last_col),
+            ));
         }
+        edges
     }
     pub fn render(&self) -> Vec<CharacterChunk> {
         let mut character_chunks = vec![];
@@ -125,7 +125,8 @@ impl PaneFrame {
         max_length: usize,
     ) -> Option<(Vec<TerminalCharacter>, usize)> {
         // string and length because of color
-        if self.scroll_position.0 > 0 || self.scroll_position.1 > 0 {
+        // only shown while scrolled up, so it disappears once back at the bottom
+        if self.scroll_position.0 > 0 {
             let prefix = " SCROLL: ";
             let full_indication =
                 format!(" {}/{} ", self.scroll_position.0, self.scroll_position.1);
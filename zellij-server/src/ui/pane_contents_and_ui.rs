@@ -32,6 +32,10 @@ impl<'a> PaneContentsAndUi<'a> {
             .filter(|(_c_id, p_id)| **p_id == pane.pid())
             .map(|(c_id, _p_id)| *c_id)
             .collect();
+        let style = match pane.palette_override() {
+            Some(colors) => Style { colors, ..style },
+            None => style,
+        };
         PaneContentsAndUi {
             pane,
             output,
@@ -206,7 +210,7 @@ impl<'a> PaneContentsAndUi<'a> {
                 _ => Some(self.style.colors.orange),
             }
         } else {
-            None
+            self.pane.border_color()
         }
     }
 }
@@ -0,0 +1,191 @@
+use super::{ClientOrTabIndex, Pty};
+use crate::panes::PaneId;
+use crate::zellij_tile::data::Palette;
+use crate::{
+    os_input_output::{async_trait, AsyncReader, Pid, ServerOsApi},
+    thread_bus::Bus,
+    ClientId,
+};
+use std::os::unix::io::RawFd;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use zellij_utils::input::command::{RunCommand, TerminalAction};
+use zellij_utils::ipc::IpcReceiverWithContext;
+use zellij_utils::nix;
+
+use zellij_utils::{
+    interprocess::local_socket::LocalSocketStream,
+    ipc::{ClientToServerMsg, ServerToClientMsg},
+};
+
+struct FakeAsyncReader {}
+
+#[async_trait]
+impl AsyncReader for FakeAsyncReader {
+    async fn read(&mut self, _buf: &mut [u8]) -> Result<usize, std::io::Error> {
+        Ok(0) // EOF
+    }
+}
+
+type QuitCb = Box<dyn Fn(PaneId, i32) + Send>;
+
+#[derive(Clone, Default)]
+struct FakeInputOutput {
+    quit_cbs: Arc<Mutex<Vec<(RawFd, QuitCb)>>>,
+    killed_pids: Arc<Mutex<Vec<Pid>>>,
+    closed_ptys: Arc<Mutex<Vec<RawFd>>>,
+}
+
+impl FakeInputOutput {
+    // calls the quit callback the way the waiter thread does once it reaped the child
+    fn exit_child(&self, pid_primary: RawFd, exit_status: i32) {
+        let quit_cbs = self.quit_cbs.lock().unwrap();
+        let (_, quit_cb) = quit_cbs
+            .iter()
+            .find(|(pid, _)| *pid == pid_primary)
+            .unwrap();
+        quit_cb(PaneId::Terminal(pid_primary), exit_status);
+    }
+}
+
+impl ServerOsApi for FakeInputOutput {
+    fn set_terminal_size_using_fd(&self, _fd: RawFd, _cols: u16, _rows: u16) {
+        // noop
+    }
+    fn spawn_terminal(
+        &self,
+        _file_to_open: TerminalAction,
+        quit_cb: Box<dyn Fn(PaneId, i32) + Send>,
+    ) -> (RawFd, RawFd) {
+        let mut quit_cbs = self.quit_cbs.lock().unwrap();
+        let pid_primary = quit_cbs.len() as RawFd + 10;
+        let child_pid = pid_primary + 1000;
+        quit_cbs.push((pid_primary, quit_cb));
+        (pid_primary, child_pid)
+    }
+    fn read_from_tty_stdout(&self, _fd: RawFd, _buf: &mut [u8]) -> Result<usize, nix::Error> {
+        unimplemented!()
+    }
+    fn async_file_reader(&self, _fd: RawFd) -> Box<dyn AsyncReader> {
+        Box::new(FakeAsyncReader {})
+    }
+    fn write_to_tty_stdin(&self, _fd: RawFd, _buf: &[u8]) -> Result<usize, nix::Error> {
+        unimplemented!()
+    }
+    fn tcdrain(&self, _fd: RawFd) -> Result<(), nix::Error> {
+        unimplemented!()
+    }
+    fn kill(&self, pid: Pid) -> Result<(), nix::Error> {
+        self.killed_pids.lock().unwrap().push(pid);
+        Ok(())
+    }
+    fn force_kill(&self, _pid: Pid) -> Result<(), nix::Error> {
+        unimplemented!()
+    }
+    fn close_pty(&self, fd: RawFd) -> Result<(), nix::Error> {
+        self.closed_ptys.lock().unwrap().push(fd);
+        Ok(())
+    }
+    fn box_clone(&self) -> Box<dyn ServerOsApi> {
+        Box::new((*self).clone())
+    }
+    fn send_to_client(&self, _client_id: ClientId, _msg: ServerToClientMsg) {
+        unimplemented!()
+    }
+    fn new_client(
+        &mut self,
+        _client_id: ClientId,
+        _stream: LocalSocketStream,
+    ) -> IpcReceiverWithContext<ClientToServerMsg> {
+        unimplemented!()
+    }
+    fn remove_client(&mut self, _client_id: ClientId) {
+        unimplemented!()
+    }
+    fn load_palette(&self) -> Palette {
+        unimplemented!()
+    }
+    fn get_cwd(&self, _pid: Pid) -> Option<PathBuf> {
+        unimplemented!()
+    }
+    fn get_foreground_process_group(&self, _fd: RawFd) -> Option<Pid> {
+        unimplemented!()
+    }
+    fn get_session_id(&self, _fd: RawFd) -> Option<Pid> {
+        unimplemented!()
+    }
+    fn signal_process_group(&self, _pgid: Pid, _signal: i32) -> Result<(), nix::Error> {
+        unimplemented!()
+    }
+}
+
+fn create_new_pty(fake_os_input: &FakeInputOutput) -> Pty {
+    let mut bus: Bus<_> = Bus::empty();
+    bus.os_input = Some(Box::new(fake_os_input.clone()));
+    Pty::new(bus, false)
+}
+
+fn spawn_terminal(pty: &mut Pty) -> RawFd {
+    let terminal_action = TerminalAction::RunCommand(RunCommand {
+        command: PathBuf::from("fake-shell"),
+        args: vec![],
+        cwd: None,
+    });
+    pty.spawn_terminal(Some(terminal_action), ClientOrTabIndex::TabIndex(0))
+}
+
+#[test]
+pub fn close_pane_kills_running_process() {
+    let fake_os_input = FakeInputOutput::default();
+    let mut pty = create_new_pty(&fake_os_input);
+    let pid = spawn_terminal(&mut pty);
+    let child_pid = *pty.id_to_child_pid.get(&pid).unwrap();
+    pty.close_pane(PaneId::Terminal(pid));
+    assert_eq!(
+        *fake_os_input.killed_pids.lock().unwrap(),
+        vec![Pid::from_raw(child_pid)],
+        "running process was sent SIGHUP"
+    );
+    assert_eq!(
+        *fake_os_input.closed_ptys.lock().unwrap(),
+        vec![pid],
+        "pty was closed"
+    );
+}
+
+#[test]
+pub fn close_pane_does_not_kill_exited_process() {
+    let fake_os_input = FakeInputOutput::default();
+    let mut pty = create_new_pty(&fake_os_input);
+    let pid = spawn_terminal(&mut pty);
+    fake_os_input.exit_child(pid, 0);
+    pty.close_pane(PaneId::Terminal(pid));
+    assert!(
+        fake_os_input.killed_pids.lock().unwrap().is_empty(),
+        "reaped pid was not signalled"
+    );
+    assert_eq!(
+        *fake_os_input.closed_ptys.lock().unwrap(),
+        vec![pid],
+        "pty was still closed"
+    );
+}
+
+#[test]
+pub fn close_pane_kills_only_running_processes_among_several() {
+    let fake_os_input = FakeInputOutput::default();
+    let mut pty = create_new_pty(&fake_os_input);
+    let exited_pid = spawn_terminal(&mut pty);
+    let running_pid = spawn_terminal(&mut pty);
+    let running_child_pid = *pty.id_to_child_pid.get(&running_pid).unwrap();
+    fake_os_input.exit_child(exited_pid, 1);
+    pty.close_tab(vec![
+        PaneId::Terminal(exited_pid),
+        PaneId::Terminal(running_pid),
+    ]);
+    assert_eq!(
+        *fake_os_input.killed_pids.lock().unwrap(),
+        vec![Pid::from_raw(running_child_pid)],
+        "only the running process was sent SIGHUP"
+    );
+}
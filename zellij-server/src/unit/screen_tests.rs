@@ -1,15 +1,20 @@
 use super::{CopyOptions, Screen, ScreenInstruction};
 use crate::panes::PaneId;
-use crate::zellij_tile::data::{ModeInfo, Palette};
+use crate::tab::{MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH};
+use crate::wasm_vm::PluginInstruction;
+use crate::zellij_tile::data::{Event, ModeInfo, Palette};
 use crate::{
     os_input_output::{AsyncReader, Pid, ServerOsApi},
     thread_bus::Bus,
-    ClientId,
+    ClientId, ServerInstruction,
 };
 use std::convert::TryInto;
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use zellij_utils::channels::{self, SenderWithContext};
 use zellij_utils::input::command::TerminalAction;
 use zellij_utils::input::layout::LayoutTemplate;
+use zellij_utils::input::options::{CloseGravity, PasteNewlines};
 use zellij_utils::ipc::IpcReceiverWithContext;
 use zellij_utils::pane_size::{Size, SizeInPixels};
 
@@ -33,7 +38,7 @@ impl ServerOsApi for FakeInputOutput {
     fn spawn_terminal(
         &self,
         _file_to_open: TerminalAction,
-        _quit_db: Box<dyn Fn(PaneId) + Send>,
+        _quit_db: Box<dyn Fn(PaneId, i32) + Send>,
     ) -> (RawFd, RawFd) {
         unimplemented!()
     }
@@ -55,6 +60,9 @@ impl ServerOsApi for FakeInputOutput {
     fn force_kill(&self, _pid: Pid) -> Result<(), nix::Error> {
         unimplemented!()
     }
+    fn close_pty(&self, _fd: RawFd) -> Result<(), nix::Error> {
+        unimplemented!()
+    }
     fn box_clone(&self) -> Box<dyn ServerOsApi> {
         Box::new((*self).clone())
     }
@@ -77,6 +85,15 @@ impl ServerOsApi for FakeInputOutput {
     fn get_cwd(&self, _pid: Pid) -> Option<PathBuf> {
         unimplemented!()
     }
+    fn get_foreground_process_group(&self, _fd: RawFd) -> Option<Pid> {
+        unimplemented!()
+    }
+    fn get_session_id(&self, _fd: RawFd) -> Option<Pid> {
+        unimplemented!()
+    }
+    fn signal_process_group(&self, _pgid: Pid, _signal: i32) -> Result<(), nix::Error> {
+        unimplemented!()
+    }
 }
 
 fn create_new_screen(size: Size) -> Screen {
@@ -90,6 +107,8 @@ fn create_new_screen(size: Size) -> Screen {
     let max_panes = None;
     let mode_info = ModeInfo::default();
     let draw_pane_frames = false;
+    let hide_single_pane_frame = false;
+    let hold_exited_panes = false;
     let session_is_mirrored = true;
     let copy_options = CopyOptions::default();
 
@@ -99,8 +118,16 @@ fn create_new_screen(size: Size) -> Screen {
         max_panes,
         mode_info,
         draw_pane_frames,
+        hide_single_pane_frame,
+        hold_exited_panes,
         session_is_mirrored,
         copy_options,
+        MIN_TERMINAL_WIDTH,
+        MIN_TERMINAL_HEIGHT,
+        PasteNewlines::default(),
+        false,
+        CloseGravity::default(),
+        None,
     )
 }
 
@@ -540,3 +567,212 @@ fn update_screen_pixel_dimensions() {
         "empty update does not delete existing data",
     );
 }
+
+#[test]
+fn pty_bytes_arriving_before_their_pane_are_buffered() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    screen.handle_pty_bytes(1, "early output".as_bytes().to_vec());
+    new_tab(&mut screen, 1);
+    screen.flush_pending_pty_bytes();
+
+    assert!(
+        screen.pending_pty_bytes.is_empty(),
+        "buffered bytes were handed to the new pane"
+    );
+    let dumped_screen = screen
+        .get_active_tab(1)
+        .unwrap()
+        .dump_active_pane_screen(1)
+        .unwrap();
+    assert!(
+        dumped_screen.starts_with("early output"),
+        "pane shows the output it produced before it existed"
+    );
+}
+
+#[test]
+fn pty_bytes_of_closed_ptys_are_not_handed_to_a_new_pane() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    screen.handle_pty_bytes(1, "stale output".as_bytes().to_vec());
+    screen.discard_pending_pty_bytes(1);
+    new_tab(&mut screen, 1);
+    screen.flush_pending_pty_bytes();
+
+    let dumped_screen = screen
+        .get_active_tab(1)
+        .unwrap()
+        .dump_active_pane_screen(1)
+        .unwrap();
+    assert!(
+        !dumped_screen.contains("stale output"),
+        "a pane reusing the fd does not get the output of the closed pty"
+    );
+}
+
+#[test]
+fn pane_activity_in_tabs_without_clients_is_reported_to_plugins() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    let (to_plugin, plugin_receiver) = channels::unbounded();
+    screen.bus.senders.to_plugin = Some(SenderWithContext::new(to_plugin));
+    new_tab(&mut screen, 1);
+    new_tab(&mut screen, 2);
+    screen.handle_pty_bytes(1, "output".as_bytes().to_vec());
+    screen.render();
+    let reported_activity = plugin_receiver.try_iter().any(|(instruction, _)| {
+        matches!(
+            instruction,
+            PluginInstruction::Update(None, None, Event::PaneActivity(pane_info))
+                if pane_info.id == 1
+        )
+    });
+    assert!(
+        reported_activity,
+        "activity in a tab no client is looking at was reported"
+    );
+}
+
+#[test]
+fn pane_progress_in_tabs_without_clients_is_reported_to_plugins() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    let (to_plugin, plugin_receiver) = channels::unbounded();
+    screen.bus.senders.to_plugin = Some(SenderWithContext::new(to_plugin));
+    new_tab(&mut screen, 1);
+    new_tab(&mut screen, 2);
+    screen.handle_pty_bytes(1, "\u{1b}]9;4;1;42\u{1b}\\".as_bytes().to_vec());
+    screen.render();
+    let reported_progress = plugin_receiver.try_iter().any(|(instruction, _)| {
+        matches!(
+            instruction,
+            PluginInstruction::Update(None, None, Event::PaneProgress(pane_info, Some(42)))
+                if pane_info.id == 1
+        )
+    });
+    assert!(
+        reported_progress,
+        "progress in a tab no client is looking at was reported"
+    );
+}
+
+#[test]
+fn break_focused_pane_to_new_tab() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1);
+    screen
+        .get_active_tab_mut(1)
+        .unwrap()
+        .vertical_split(PaneId::Terminal(2), 1);
+    screen.break_pane_to_new_tab(1);
+
+    assert_eq!(screen.tabs.len(), 2, "Screen now has two tabs");
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().get_all_pane_ids(),
+        vec![PaneId::Terminal(2)],
+        "focused pane moved to the new tab"
+    );
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().get_active_pane_id(1),
+        Some(PaneId::Terminal(2)),
+        "moved pane is focused"
+    );
+    assert_eq!(
+        screen.tabs.get(&0).unwrap().get_all_pane_ids(),
+        vec![PaneId::Terminal(1)],
+        "pane was removed from the original tab"
+    );
+
+    screen.break_pane_to_new_tab(1);
+    assert_eq!(screen.tabs.len(), 2, "the only pane of a tab is not moved");
+}
+
+// feeds the output of a command like `yes` to a pane in chunks, rendering after every chunk or
+// only when the throttle allows it
+fn render_busy_pane_output(coalesce_renders: bool) -> (Duration, usize) {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    let (to_server, server_receiver) = channels::unbounded();
+    screen.bus.senders.to_server = Some(SenderWithContext::new(to_server));
+    new_tab(&mut screen, 1);
+    let chunk = "y\r\n".repeat(1024).into_bytes();
+    let start = Instant::now();
+    for _ in 0..2000 {
+        screen.handle_pty_bytes(1, chunk.clone());
+        if coalesce_renders {
+            screen.maybe_render();
+        } else {
+            screen.render();
+        }
+    }
+    let elapsed = start.elapsed();
+    let renders = server_receiver
+        .try_iter()
+        .filter(|(instruction, _)| matches!(instruction, ServerInstruction::Render(..)))
+        .count();
+    (elapsed, renders)
+}
+
+// cargo test --release -p zellij-server bench_ -- --ignored --nocapture
+#[test]
+#[ignore]
+fn bench_coalesced_renders_of_busy_pane_output() {
+    let (render_every_chunk_time, render_every_chunk_count) = render_busy_pane_output(false);
+    let (coalesced_time, coalesced_count) = render_busy_pane_output(true);
+    println!(
+        "2000 chunks of 3KiB, rendering each: {:?} ({} renders), coalesced: {:?} ({} renders)",
+        render_every_chunk_time, render_every_chunk_count, coalesced_time, coalesced_count
+    );
+    assert!(
+        coalesced_count < render_every_chunk_count,
+        "output arriving within the throttle is rendered together"
+    );
+}
+
+#[test]
+#[ignore]
+fn bench_send_to_screen_after_throughput() {
+    let mut screen = create_new_screen(Size {
+        cols: 121,
+        rows: 20,
+    });
+    let (to_screen, screen_receiver) = channels::unbounded();
+    screen.bus.senders.to_screen = Some(SenderWithContext::new(to_screen));
+    let instruction_count = 10_000;
+    let start = Instant::now();
+    for _ in 0..instruction_count {
+        screen
+            .bus
+            .senders
+            .send_to_screen_after(ScreenInstruction::MaybeRender, Duration::from_millis(1));
+    }
+    for _ in 0..instruction_count {
+        screen_receiver.recv().unwrap();
+    }
+    println!(
+        "{} delayed screen instructions delivered in {:?}",
+        instruction_count,
+        start.elapsed()
+    );
+}
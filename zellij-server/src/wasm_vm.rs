@@ -4,6 +4,7 @@ use serde::{de::DeserializeOwned, Serialize};
 use std::{
     collections::{HashMap, HashSet},
     fs,
+    os::unix::io::RawFd,
     path::{Path, PathBuf},
     process,
     str::FromStr,
@@ -17,7 +18,7 @@ use wasmer::{
     WasmerEnv,
 };
 use wasmer_wasi::{Pipe, WasiEnv, WasiState};
-use zellij_tile::data::{Event, EventType, PluginIds};
+use zellij_tile::data::{Direction, Event, EventType, Palette, PaletteColor, PluginIds};
 
 use crate::{
     logging_pipe::LoggingPipe,
@@ -339,6 +340,22 @@ pub(crate) fn zellij_exports(store: &Store, plugin_env: &PluginEnv) -> ImportObj
         host_switch_tab_to,
         host_set_timeout,
         host_exec_cmd,
+        host_resize_pane,
+        host_focus_pane,
+        host_rename_pane,
+        host_set_pane_palette,
+        host_set_pane_sync_excluded,
+        host_add_pane_to_group,
+        host_remove_pane_from_group,
+        host_write_to_group,
+        host_set_pane_scrollback_limit,
+        host_set_pane_silence_threshold,
+        host_set_pane_border_color,
+        host_set_pane_read_only,
+        host_request_pane_content_size,
+        host_insert_pane_line,
+        host_request_pane_boundaries,
+        host_request_pane_geoms,
     }
 }
 
@@ -458,8 +475,200 @@ fn host_exec_cmd(plugin_env: &PluginEnv) {
         .unwrap();
 }
 
+fn host_resize_pane(plugin_env: &PluginEnv) {
+    let (pane_id, is_plugin, direction, percent): (u32, bool, Direction, f64) =
+        wasi_read_object(&plugin_env.wasi_env);
+    plugin_env
+        .senders
+        .send_to_screen(ScreenInstruction::ResizePane(
+            plugin_pane_id(pane_id, is_plugin),
+            direction.into(),
+            percent,
+        ))
+        .unwrap();
+}
+
+fn host_focus_pane(plugin_env: &PluginEnv) {
+    let (pane_id, is_plugin): (u32, bool) = wasi_read_object(&plugin_env.wasi_env);
+    plugin_env
+        .senders
+        .send_to_screen(ScreenInstruction::FocusPane(
+            plugin_pane_id(pane_id, is_plugin),
+            plugin_env.client_id,
+        ))
+        .unwrap();
+}
+
+fn host_rename_pane(plugin_env: &PluginEnv) {
+    let (pane_id, is_plugin, name): (u32, bool, String) = wasi_read_object(&plugin_env.wasi_env);
+    plugin_env
+        .senders
+        .send_to_screen(ScreenInstruction::RenamePane(
+            plugin_pane_id(pane_id, is_plugin),
+            name,
+        ))
+        .unwrap();
+}
+
+fn host_set_pane_palette(plugin_env: &PluginEnv) {
+    let (pane_id, is_plugin, palette): (u32, bool, Option<Palette>) =
+        wasi_read_object(&plugin_env.wasi_env);
+    plugin_env
+        .senders
+        .send_to_screen(ScreenInstruction::SetPanePalette(
+            plugin_pane_id(pane_id, is_plugin),
+            palette,
+        ))
+        .unwrap();
+}
+
+fn host_set_pane_sync_excluded(plugin_env: &PluginEnv) {
+    let (pane_id, is_plugin, excluded): (u32, bool, bool) = wasi_read_object(&plugin_env.wasi_env);
+    plugin_env
+        .senders
+        .send_to_screen(ScreenInstruction::SetPaneSyncExcluded(
+            plugin_pane_id(pane_id, is_plugin),
+            excluded,
+        ))
+        .unwrap();
+}
+
+fn host_add_pane_to_group(plugin_env: &PluginEnv) {
+    let (pane_id, is_plugin, group): (u32, bool, String) = wasi_read_object(&plugin_env.wasi_env);
+    plugin_env
+        .senders
+        .send_to_screen(ScreenInstruction::AddPaneToGroup(
+            plugin_pane_id(pane_id, is_plugin),
+            group,
+        ))
+        .unwrap();
+}
+
+fn host_remove_pane_from_group(plugin_env: &PluginEnv) {
+    let (pane_id, is_plugin, group): (u32, bool, String) = wasi_read_object(&plugin_env.wasi_env);
+    plugin_env
+        .senders
+        .send_to_screen(ScreenInstruction::RemovePaneFromGroup(
+            plugin_pane_id(pane_id, is_plugin),
+            group,
+        ))
+        .unwrap();
+}
+
+fn host_write_to_group(plugin_env: &PluginEnv) {
+    let (group, bytes): (String, Vec<u8>) = wasi_read_object(&plugin_env.wasi_env);
+    plugin_env
+        .senders
+        .send_to_screen(ScreenInstruction::WriteToGroup(
+            group,
+            bytes,
+            plugin_env.client_id,
+        ))
+        .unwrap();
+}
+
+fn host_set_pane_scrollback_limit(plugin_env: &PluginEnv) {
+    let (pane_id, is_plugin, lines): (u32, bool, usize) = wasi_read_object(&plugin_env.wasi_env);
+    plugin_env
+        .senders
+        .send_to_screen(ScreenInstruction::SetPaneScrollbackLimit(
+            plugin_pane_id(pane_id, is_plugin),
+            lines,
+        ))
+        .unwrap();
+}
+
+fn host_set_pane_silence_threshold(plugin_env: &PluginEnv) {
+    let (pane_id, is_plugin, threshold): (u32, bool, Option<Duration>) =
+        wasi_read_object(&plugin_env.wasi_env);
+    plugin_env
+        .senders
+        .send_to_screen(ScreenInstruction::SetPaneSilenceThreshold(
+            plugin_pane_id(pane_id, is_plugin),
+            threshold,
+        ))
+        .unwrap();
+}
+
+fn host_set_pane_border_color(plugin_env: &PluginEnv) {
+    let (pane_id, is_plugin, color): (u32, bool, Option<PaletteColor>) =
+        wasi_read_object(&plugin_env.wasi_env);
+    plugin_env
+        .senders
+        .send_to_screen(ScreenInstruction::SetPaneBorderColor(
+            plugin_pane_id(pane_id, is_plugin),
+            color,
+        ))
+        .unwrap();
+}
+
+fn host_set_pane_read_only(plugin_env: &PluginEnv) {
+    let (pane_id, is_plugin, read_only): (u32, bool, bool) = wasi_read_object(&plugin_env.wasi_env);
+    plugin_env
+        .senders
+        .send_to_screen(ScreenInstruction::SetPaneReadOnly(
+            plugin_pane_id(pane_id, is_plugin),
+            read_only,
+        ))
+        .unwrap();
+}
+
+fn host_request_pane_content_size(plugin_env: &PluginEnv) {
+    let (pane_id, is_plugin, cols, rows): (u32, bool, usize, usize) =
+        wasi_read_object(&plugin_env.wasi_env);
+    plugin_env
+        .senders
+        .send_to_screen(ScreenInstruction::RequestPaneContentSize(
+            plugin_pane_id(pane_id, is_plugin),
+            cols,
+            rows,
+        ))
+        .unwrap();
+}
+
+fn host_insert_pane_line(plugin_env: &PluginEnv) {
+    let (pane_id, is_plugin, text): (u32, bool, String) = wasi_read_object(&plugin_env.wasi_env);
+    plugin_env
+        .senders
+        .send_to_screen(ScreenInstruction::InsertSyntheticLine(
+            plugin_pane_id(pane_id, is_plugin),
+            text,
+            plugin_env.client_id,
+        ))
+        .unwrap();
+}
+
+fn host_request_pane_boundaries(plugin_env: &PluginEnv) {
+    plugin_env
+        .senders
+        .send_to_screen(ScreenInstruction::RequestPaneBoundaries(
+            plugin_env.plugin_id,
+            plugin_env.client_id,
+        ))
+        .unwrap();
+}
+
+fn host_request_pane_geoms(plugin_env: &PluginEnv) {
+    plugin_env
+        .senders
+        .send_to_screen(ScreenInstruction::RequestPaneGeoms(
+            plugin_env.plugin_id,
+            plugin_env.client_id,
+        ))
+        .unwrap();
+}
+
 // Helper Functions ---------------------------------------------------------------------------------------------------
 
+fn plugin_pane_id(pane_id: u32, is_plugin: bool) -> PaneId {
+    // the way panes are identified in PaneInfo
+    if is_plugin {
+        PaneId::Plugin(pane_id)
+    } else {
+        PaneId::Terminal(pane_id as RawFd)
+    }
+}
+
 pub fn wasi_read_string(wasi_env: &WasiEnv) -> String {
     let mut state = wasi_env.state();
     let wasi_file = state.fs.stdout_mut().unwrap().as_mut().unwrap();
@@ -95,6 +95,18 @@ pub enum Event {
     SystemClipboardFailure,
     InputReceived,
     Visible(bool),
+    ActivePaneChanged(PaneInfo),
+    ActivePaneScrolled(ScrollInfo),
+    PaneBell(PaneInfo),
+    PaneActivity(PaneInfo),
+    PaneSilence(PaneInfo),
+    // the percentage a pane reported with OSC 9;4, None once it is done
+    PaneProgress(PaneInfo, Option<u8>),
+    // the boundaries between the tiled panes of the plugin's tab, sent in reply to
+    // request_pane_boundaries
+    PaneBoundaries(Vec<BoundarySegment>),
+    // every pane of the plugin's tab, sent in reply to request_pane_geoms
+    PaneGeoms(Vec<PaneGeomInfo>),
 }
 
 /// Describes the different input modes, which change the way that keystrokes will be interpreted.
@@ -221,6 +233,7 @@ pub struct Palette {
     pub silver: PaletteColor,
     pub pink: PaletteColor,
     pub brown: PaletteColor,
+    pub diff_highlight: PaletteColor,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
@@ -242,6 +255,64 @@ pub struct ModeInfo {
     pub session_name: Option<String>,
 }
 
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct PaneInfo {
+    /* subset of fields to publish to plugins */
+    pub id: u32,
+    pub is_plugin: bool,
+    pub title: String,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct PaneGeomInfo {
+    /* the geometry a pane is drawn at, published to plugins */
+    pub id: u32,
+    pub is_plugin: bool,
+    pub x: usize,
+    pub y: usize,
+    pub cols: usize,
+    pub rows: usize,
+    pub is_hidden: bool, // behind a fullscreen pane or a hidden floating pane
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct ScrollInfo {
+    pub offset: usize, // how many lines the pane is scrolled up from the bottom
+    pub scrollback_len: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub enum BoundaryOrientation {
+    Horizontal,
+    Vertical,
+}
+
+// a straight line of boundary characters, start and end are the (x, y) coordinates of its first
+// and last cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub struct BoundarySegment {
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+    pub orientation: BoundaryOrientation,
+}
+
+impl BoundarySegment {
+    pub fn horizontal(y: usize, first_col: usize, last_col: usize) -> Self {
+        BoundarySegment {
+            start: (first_col, y),
+            end: (last_col, y),
+            orientation: BoundaryOrientation::Horizontal,
+        }
+    }
+    pub fn vertical(x: usize, first_row: usize, last_row: usize) -> Self {
+        BoundarySegment {
+            start: (x, first_row),
+            end: (x, last_row),
+            orientation: BoundaryOrientation::Vertical,
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct TabInfo {
     /* subset of fields to publish to plugins */
@@ -253,6 +324,8 @@ pub struct TabInfo {
     pub is_sync_panes_active: bool,
     pub are_floating_panes_visible: bool,
     pub other_focused_clients: Vec<ClientId>,
+    pub pane_count: usize,
+    pub selectable_pane_count: usize,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
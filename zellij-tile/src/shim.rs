@@ -1,5 +1,5 @@
 use serde::{de::DeserializeOwned, Serialize};
-use std::{io, path::Path};
+use std::{io, path::Path, time::Duration};
 
 use crate::data::*;
 
@@ -51,6 +51,96 @@ pub fn exec_cmd(cmd: &[&str]) {
     unsafe { host_exec_cmd() };
 }
 
+// pane_id and is_plugin identify the pane like the corresponding fields of PaneInfo
+pub fn resize_pane(pane_id: u32, is_plugin: bool, direction: Direction, percent: f64) {
+    object_to_stdout(&(pane_id, is_plugin, direction, percent));
+    unsafe { host_resize_pane() };
+}
+
+pub fn focus_pane(pane_id: u32, is_plugin: bool) {
+    object_to_stdout(&(pane_id, is_plugin));
+    unsafe { host_focus_pane() };
+}
+
+pub fn rename_pane(pane_id: u32, is_plugin: bool, name: &str) {
+    object_to_stdout(&(pane_id, is_plugin, name));
+    unsafe { host_rename_pane() };
+}
+
+// None reverts the pane to the colors of its tab
+pub fn set_pane_palette(pane_id: u32, is_plugin: bool, palette: Option<Palette>) {
+    object_to_stdout(&(pane_id, is_plugin, palette));
+    unsafe { host_set_pane_palette() };
+}
+
+// excluded panes do not receive input while the panes of their tab are synchronized
+pub fn set_pane_sync_excluded(pane_id: u32, is_plugin: bool, excluded: bool) {
+    object_to_stdout(&(pane_id, is_plugin, excluded));
+    unsafe { host_set_pane_sync_excluded() };
+}
+
+// groups belong to the tab of their panes, input written to a group is sent to all of its panes
+pub fn add_pane_to_group(pane_id: u32, is_plugin: bool, group: &str) {
+    object_to_stdout(&(pane_id, is_plugin, group));
+    unsafe { host_add_pane_to_group() };
+}
+
+pub fn remove_pane_from_group(pane_id: u32, is_plugin: bool, group: &str) {
+    object_to_stdout(&(pane_id, is_plugin, group));
+    unsafe { host_remove_pane_from_group() };
+}
+
+pub fn write_to_group(group: &str, bytes: &[u8]) {
+    object_to_stdout(&(group, bytes));
+    unsafe { host_write_to_group() };
+}
+
+// lowering the limit below the current scrollback length drops the oldest lines
+pub fn set_pane_scrollback_limit(pane_id: u32, is_plugin: bool, lines: usize) {
+    object_to_stdout(&(pane_id, is_plugin, lines));
+    unsafe { host_set_pane_scrollback_limit() };
+}
+
+// a PaneSilence event is sent once the pane has had no output for threshold, None stops monitoring it
+pub fn set_pane_silence_threshold(pane_id: u32, is_plugin: bool, threshold: Option<Duration>) {
+    object_to_stdout(&(pane_id, is_plugin, threshold));
+    unsafe { host_set_pane_silence_threshold() };
+}
+
+// the color is used while the pane is not focused, None reverts to the default border
+pub fn set_pane_border_color(pane_id: u32, is_plugin: bool, color: Option<PaletteColor>) {
+    object_to_stdout(&(pane_id, is_plugin, color));
+    unsafe { host_set_pane_border_color() };
+}
+
+// typed input to a read-only pane is dropped, mouse scrolling still goes through
+pub fn set_pane_read_only(pane_id: u32, is_plugin: bool, read_only: bool) {
+    object_to_stdout(&(pane_id, is_plugin, read_only));
+    unsafe { host_set_pane_read_only() };
+}
+
+// resizes the pane so that its content (inside the frame) is cols x rows, as far as the other panes allow
+pub fn request_pane_content_size(pane_id: u32, is_plugin: bool, cols: usize, rows: usize) {
+    object_to_stdout(&(pane_id, is_plugin, cols, rows));
+    unsafe { host_request_pane_content_size() };
+}
+
+// shows a line of text in a terminal pane without sending it to the program running in it
+pub fn insert_pane_line(pane_id: u32, is_plugin: bool, text: &str) {
+    object_to_stdout(&(pane_id, is_plugin, text));
+    unsafe { host_insert_pane_line() };
+}
+
+// the tab's boundaries arrive as an Event::PaneBoundaries
+pub fn request_pane_boundaries() {
+    unsafe { host_request_pane_boundaries() };
+}
+
+// the geometry of the tab's panes arrives as an Event::PaneGeoms
+pub fn request_pane_geoms() {
+    unsafe { host_request_pane_geoms() };
+}
+
 // Internal Functions
 
 #[doc(hidden)]
@@ -76,4 +166,20 @@ extern "C" {
     fn host_switch_tab_to(tab_idx: u32);
     fn host_set_timeout(secs: f64);
     fn host_exec_cmd();
+    fn host_resize_pane();
+    fn host_focus_pane();
+    fn host_rename_pane();
+    fn host_set_pane_palette();
+    fn host_set_pane_sync_excluded();
+    fn host_add_pane_to_group();
+    fn host_remove_pane_from_group();
+    fn host_write_to_group();
+    fn host_set_pane_scrollback_limit();
+    fn host_set_pane_silence_threshold();
+    fn host_set_pane_border_color();
+    fn host_set_pane_read_only();
+    fn host_request_pane_content_size();
+    fn host_insert_pane_line();
+    fn host_request_pane_boundaries();
+    fn host_request_pane_geoms();
 }
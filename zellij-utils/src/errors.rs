@@ -212,6 +212,9 @@ impl Display for ContextType {
 pub enum ScreenContext {
     HandlePtyBytes,
     Render,
+    MaybeRender,
+    DeferredRender,
+    PtyClosed,
     NewPane,
     ToggleFloatingPanes,
     TogglePaneEmbedOrFloating,
@@ -259,6 +262,9 @@ pub enum ScreenContext {
     SetFixedHeight,
     SetFixedWidth,
     ClosePane,
+    PaneExited,
+    ResizePane,
+    FocusPane,
     UpdatePaneName,
     NewTab,
     SwitchTabNext,
@@ -269,10 +275,68 @@ pub enum ScreenContext {
     TerminalResize,
     TerminalPixelDimensions,
     ChangeMode,
+    BreakPane,
+    SwapPane,
+    ToggleDiffPane,
+    ResizeActivePaneBy,
+    ToggleActivePaneRawView,
+    MoveFocusWrapping,
+    RebalancePanes,
+    RenamePane,
+    SetPanePalette,
+    DumpActivePaneToFile,
+    DumpActivePaneScreenToFile,
+    SearchActivePane,
+    ScrollToNextSearchMatch,
+    ScrollToPreviousSearchMatch,
+    CopyActivePaneScrollback,
+    SetPaneSyncExcluded,
+    DumpLayoutToFile,
+    Paste,
+    MoveActivePaneToFront,
+    MoveActivePaneToBack,
+    ToggleSinglePaneFrames,
+    FocusLastPane,
+    SetActivePaneSize,
+    AddPaneToGroup,
+    RemovePaneFromGroup,
+    WriteToGroup,
+    EqualizePanes,
+    FocusNextPaneInDirection,
+    ClearActivePane,
+    SetPaneScrollbackLimit,
+    ToggleActivePanePinned,
+    ScrollLeft,
+    ScrollRight,
+    MoveActivePaneToDirection,
+    SetPaneSilenceThreshold,
+    ReserveViewport,
+    SwapLayout,
+    UndoLastLayoutOp,
+    SetPaneBorderColor,
+    DuplicateActivePane,
+    ShowPaneNumbers,
+    CloseOtherPanes,
+    SetPaneReadOnly,
+    RotatePanes,
+    StartActivePaneLogging,
+    StopActivePaneLogging,
+    RequestPaneContentSize,
+    ToggleActivePaneMaxWidth,
+    ToggleActivePaneMaxHeight,
+    MaximizeActivePane,
+    ToggleActivePaneLineWrap,
+    SendSignalToActivePane,
+    InsertSyntheticLine,
+    RequestPaneBoundaries,
+    RequestPaneGeoms,
     LeftClick,
+    CtrlLeftClick,
+    AltLeftClick,
     RightClick,
     MouseRelease,
     MouseHold,
+    ScrollSelectionAtEdge,
     Copy,
     ToggleTab,
     AddClient,
@@ -334,4 +398,5 @@ pub enum ServerContext {
     DetachSession,
     AttachClient,
     ConnStatus,
+    OpenUrl,
 }
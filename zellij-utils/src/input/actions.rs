@@ -4,6 +4,7 @@ use super::command::RunCommandAction;
 use super::layout::TabLayout;
 use crate::input::options::OnForceClose;
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use zellij_tile::data::InputMode;
 
 use crate::position::Position;
@@ -17,6 +18,17 @@ pub enum Direction {
     Down,
 }
 
+impl From<zellij_tile::data::Direction> for Direction {
+    fn from(direction: zellij_tile::data::Direction) -> Self {
+        match direction {
+            zellij_tile::data::Direction::Left => Direction::Left,
+            zellij_tile::data::Direction::Right => Direction::Right,
+            zellij_tile::data::Direction::Up => Direction::Up,
+            zellij_tile::data::Direction::Down => Direction::Down,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub enum ResizeDirection {
     Left,
@@ -106,10 +118,97 @@ pub enum Action {
     /// Detach session and exit
     Detach,
     LeftClick(Position),
+    /// Open the url under the mouse, or click as usual if there is none
+    CtrlLeftClick(Position),
+    /// Start a block (rectangular) selection
+    AltLeftClick(Position),
     RightClick(Position),
     MouseRelease(Position),
     MouseHold(Position),
     Copy,
+    /// Move the focused pane to a new tab
+    BreakPane,
+    /// Swap the focused pane with its neighbour in the given direction
+    SwapPane(Direction),
+    /// Highlight the lines that differ between the focused pane and its neighbour in the given
+    /// direction, or stop highlighting them
+    ToggleDiffPane(Direction),
+    /// Resize the focused pane by the given percentage of the tab in the given direction
+    ResizeBy(Direction, f64),
+    /// Toggle showing the raw output of the focused pane, without interpreting escape sequences
+    ToggleRawView,
+    /// Move focus in the given direction, wrapping around to the opposite edge of the tab
+    MoveFocusWrapping(Direction),
+    /// Correct rounding drift in the sizes of the tiled panes
+    RebalancePanes,
+    /// Write the contents of the focused pane to a file (path, include scrollback, keep ANSI escapes)
+    DumpPane(PathBuf, bool, bool),
+    /// Write the visible screen of the focused pane to a file, row by row as it is rendered
+    DumpScreen(PathBuf),
+    /// Highlight the matches of the given text in the scrollback of the focused pane
+    Search(String),
+    /// Scroll the focused pane to the next search match
+    SearchNext,
+    /// Scroll the focused pane to the previous search match
+    SearchPrevious,
+    /// Copy the whole scrollback of the focused pane
+    CopyScrollback,
+    /// Write the layout of the panes in the current tab to a file
+    DumpLayout(PathBuf),
+    /// Paste text into the focused pane
+    Paste(String),
+    /// Make the focused pane the first in the pane order
+    MovePaneToFront,
+    /// Make the focused pane the last in the pane order
+    MovePaneToBack,
+    /// Toggle hiding the pane frame while a tab only has a single pane
+    ToggleSinglePaneFrames,
+    /// Focus the previously focused pane
+    FocusLastPane,
+    /// Set the width and/or height of the focused pane as a percentage of the tab
+    SetPaneSize(Option<f64>, Option<f64>),
+    /// Give every tiled pane in a row or column the same size
+    EqualizePanes,
+    /// Cycle the focus through every pane, following the neighbours in the given direction
+    FocusNextPaneInDirection(Direction),
+    /// Clear the screen and the scrollback of the focused pane
+    ClearScreen,
+    /// Keep the focused pane visible while another pane is fullscreen
+    TogglePanePinned,
+    /// Scroll the focused pane one column to the left, if its lines are not wrapped
+    ScrollLeft,
+    /// Scroll the focused pane one column to the right, if its lines are not wrapped
+    ScrollRight,
+    /// Take the focused pane out of its place and split the pane in the given direction with it
+    RelocatePane(Direction),
+    /// Keep the given number of rows/columns (top, bottom, left, right) of the tab free of panes
+    ReserveViewport(usize, usize, usize, usize),
+    /// Rearrange the panes of the tab in the next preset layout
+    SwapLayout,
+    /// Close the pane opened by the last split and give its space back, if it was split recently
+    UndoSplit,
+    /// Open a new pane running the same command or plugin as the focused pane, in its directory
+    DuplicatePane,
+    /// Show a number on every pane, the next key press focuses the pane with that number
+    ShowPaneNumbers,
+    /// Close every pane in the tab except the focused one, the plugin panes as well if true
+    CloseOtherPanes(bool),
+    /// Move every pane to the place of the next pane, or of the previous pane if true
+    RotatePanes(bool),
+    /// Append the output of the focused pane to a file
+    StartPaneLogging(PathBuf),
+    /// Stop appending the output of the focused pane to a file
+    StopPaneLogging,
+    /// Make the focused pane span the whole width of the tab, or restore it
+    ToggleMaxWidth,
+    /// Make the focused pane span the whole height of the tab, or restore it
+    ToggleMaxHeight,
+    /// Grow the focused pane in the given direction as far as the other panes allow
+    MaximizePane(Direction),
+    /// Toggle between wrapping the long lines of the focused pane and cutting them off
+    ToggleLineWrap,
+    /// Send a signal (eg. 9 for SIGKILL) to the process in the foreground of the focused pane
+    SendSignal(i32),
     /// Confirm a prompt
     Confirm,
     /// Deny a prompt
@@ -54,6 +54,39 @@ impl Not for Direction {
     }
 }
 
+// Arrangements of however many panes a tab has, rather than of a fixed set of panes
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(crate = "self::serde")]
+pub enum LayoutPreset {
+    // the panes side by side
+    #[serde(alias = "even_horizontal")]
+    EvenHorizontal,
+    // the panes on top of each other
+    #[serde(alias = "even_vertical")]
+    EvenVertical,
+    // the main pane on the left, the others on top of each other to its right
+    #[serde(alias = "main_vertical")]
+    MainVertical,
+    // the main pane at the top, the others side by side below it
+    #[serde(alias = "main_horizontal")]
+    MainHorizontal,
+    // the panes in as many rows as columns
+    #[serde(alias = "tiled")]
+    Tiled,
+}
+
+impl LayoutPreset {
+    pub fn next(self) -> Self {
+        match self {
+            LayoutPreset::EvenHorizontal => LayoutPreset::EvenVertical,
+            LayoutPreset::EvenVertical => LayoutPreset::MainVertical,
+            LayoutPreset::MainVertical => LayoutPreset::MainHorizontal,
+            LayoutPreset::MainHorizontal => LayoutPreset::Tiled,
+            LayoutPreset::Tiled => LayoutPreset::EvenHorizontal,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 #[serde(crate = "self::serde")]
 pub enum SplitSize {
@@ -26,8 +26,15 @@ impl From<termwiz::input::MouseEvent> for MouseEvent {
             .mouse_buttons
             .contains(termwiz::input::MouseButtons::LEFT)
         {
+            let button = if event.modifiers.contains(termwiz::input::Modifiers::CTRL) {
+                MouseButton::CtrlLeft
+            } else if event.modifiers.contains(termwiz::input::Modifiers::ALT) {
+                MouseButton::AltLeft
+            } else {
+                MouseButton::Left
+            };
             MouseEvent::Press(
-                MouseButton::Left,
+                button,
                 Position::new(event.y.saturating_sub(1) as i32, event.x.saturating_sub(1)),
             )
         } else if event
@@ -88,6 +95,10 @@ impl From<termwiz::input::MouseEvent> for MouseEvent {
 pub enum MouseButton {
     /// The left mouse button.
     Left,
+    /// The left mouse button while ctrl is held.
+    CtrlLeft,
+    /// The left mouse button while alt is held.
+    AltLeft,
     /// The right mouse button.
     Right,
     /// The middle mouse button.
@@ -67,6 +67,14 @@ pub struct Options {
     pub pane_frames: Option<bool>,
     #[clap(long)]
     #[serde(default)]
+    /// Hide the pane frame while a tab only has a single pane (true or false)
+    pub hide_single_pane_frame: Option<bool>,
+    #[clap(long)]
+    #[serde(default)]
+    /// Keep panes open after their command exits until they are closed explicitly (true or false)
+    pub hold_exited_panes: Option<bool>,
+    #[clap(long)]
+    #[serde(default)]
     /// Mirror session when multiple users are connected (true or false)
     pub mirror_session: Option<bool>,
     /// Set behaviour on force close (quit or detach)
@@ -89,6 +97,48 @@ pub struct Options {
     #[clap(long)]
     #[serde(default)]
     pub copy_on_select: Option<bool>,
+
+    /// Let programs running in panes copy to the clipboard with OSC52 (true or false)
+    #[clap(long)]
+    #[serde(default)]
+    pub copy_from_panes: Option<bool>,
+
+    /// The smallest width a pane can be split or resized to
+    #[clap(long)]
+    #[serde(default)]
+    pub min_pane_width: Option<usize>,
+
+    /// The smallest height a pane can be split or resized to
+    #[clap(long)]
+    #[serde(default)]
+    pub min_pane_height: Option<usize>,
+
+    /// Convert the newlines of pasted text to the given line ending (preserve, line-feed or carriage-return)
+    #[clap(long, arg_enum, hide_possible_values = true)]
+    #[serde(default)]
+    pub paste_newlines: Option<PasteNewlines>,
+
+    /// The most lines of a pane's scrollback that are copied at once
+    #[clap(long)]
+    #[serde(default)]
+    pub scrollback_copy_line_limit: Option<usize>,
+
+    /// Refuse to open new panes in a tab that already has the maximum number of panes, rather
+    /// than closing its oldest panes (true or false)
+    #[clap(long)]
+    #[serde(default)]
+    pub reject_panes_over_max: Option<bool>,
+
+    /// Which panes get the space of a closed pane (fixed, previously-active or largest)
+    #[clap(long, arg_enum, hide_possible_values = true)]
+    #[serde(default)]
+    pub close_gravity: Option<CloseGravity>,
+
+    /// How many times taller than wide a character cell is, used to pick the direction of
+    /// smart splits
+    #[clap(long)]
+    #[serde(default)]
+    pub cursor_height_width_ratio: Option<usize>,
 }
 
 #[derive(ArgEnum, Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
@@ -105,6 +155,42 @@ impl Default for Clipboard {
     }
 }
 
+#[derive(ArgEnum, Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasteNewlines {
+    #[serde(alias = "preserve")]
+    Preserve,
+    #[serde(alias = "line_feed")]
+    LineFeed,
+    #[serde(alias = "carriage_return")]
+    CarriageReturn,
+}
+
+impl Default for PasteNewlines {
+    fn default() -> Self {
+        Self::CarriageReturn
+    }
+}
+
+/// Which of the panes around a closed pane get the space it leaves behind
+#[derive(ArgEnum, Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloseGravity {
+    /// The panes to the left, then right, then above, then below
+    #[serde(alias = "fixed")]
+    Fixed,
+    /// The panes on the side of the pane that was focused before the closed one
+    #[serde(alias = "previously_active")]
+    PreviouslyActive,
+    /// The side with the most space
+    #[serde(alias = "largest")]
+    Largest,
+}
+
+impl Default for CloseGravity {
+    fn default() -> Self {
+        Self::Fixed
+    }
+}
+
 impl Options {
     pub fn from_yaml(from_yaml: Option<Options>) -> Options {
         if let Some(opts) = from_yaml {
@@ -120,6 +206,8 @@ impl Options {
     pub fn merge(&self, other: Options) -> Options {
         let mouse_mode = other.mouse_mode.or(self.mouse_mode);
         let pane_frames = other.pane_frames.or(self.pane_frames);
+        let hide_single_pane_frame = other.hide_single_pane_frame.or(self.hide_single_pane_frame);
+        let hold_exited_panes = other.hold_exited_panes.or(self.hold_exited_panes);
         let mirror_session = other.mirror_session.or(self.mirror_session);
         let simplified_ui = other.simplified_ui.or(self.simplified_ui);
         let default_mode = other.default_mode.or(self.default_mode);
@@ -131,6 +219,18 @@ impl Options {
         let copy_command = other.copy_command.or_else(|| self.copy_command.clone());
         let copy_clipboard = other.copy_clipboard.or(self.copy_clipboard);
         let copy_on_select = other.copy_on_select.or(self.copy_on_select);
+        let copy_from_panes = other.copy_from_panes.or(self.copy_from_panes);
+        let min_pane_width = other.min_pane_width.or(self.min_pane_width);
+        let min_pane_height = other.min_pane_height.or(self.min_pane_height);
+        let paste_newlines = other.paste_newlines.or(self.paste_newlines);
+        let scrollback_copy_line_limit = other
+            .scrollback_copy_line_limit
+            .or(self.scrollback_copy_line_limit);
+        let reject_panes_over_max = other.reject_panes_over_max.or(self.reject_panes_over_max);
+        let close_gravity = other.close_gravity.or(self.close_gravity);
+        let cursor_height_width_ratio = other
+            .cursor_height_width_ratio
+            .or(self.cursor_height_width_ratio);
 
         Options {
             simplified_ui,
@@ -140,12 +240,22 @@ impl Options {
             layout_dir,
             mouse_mode,
             pane_frames,
+            hide_single_pane_frame,
+            hold_exited_panes,
             mirror_session,
             on_force_close,
             scroll_buffer_size,
             copy_command,
             copy_clipboard,
             copy_on_select,
+            copy_from_panes,
+            min_pane_width,
+            min_pane_height,
+            paste_newlines,
+            scrollback_copy_line_limit,
+            reject_panes_over_max,
+            close_gravity,
+            cursor_height_width_ratio,
         }
     }
 
@@ -167,6 +277,9 @@ impl Options {
         let simplified_ui = merge_bool(other.simplified_ui, self.simplified_ui);
         let mouse_mode = merge_bool(other.mouse_mode, self.mouse_mode);
         let pane_frames = merge_bool(other.pane_frames, self.pane_frames);
+        let hide_single_pane_frame =
+            merge_bool(other.hide_single_pane_frame, self.hide_single_pane_frame);
+        let hold_exited_panes = merge_bool(other.hold_exited_panes, self.hold_exited_panes);
         let mirror_session = merge_bool(other.mirror_session, self.mirror_session);
 
         let default_mode = other.default_mode.or(self.default_mode);
@@ -178,6 +291,18 @@ impl Options {
         let copy_command = other.copy_command.or_else(|| self.copy_command.clone());
         let copy_clipboard = other.copy_clipboard.or(self.copy_clipboard);
         let copy_on_select = other.copy_on_select.or(self.copy_on_select);
+        let copy_from_panes = other.copy_from_panes.or(self.copy_from_panes);
+        let min_pane_width = other.min_pane_width.or(self.min_pane_width);
+        let min_pane_height = other.min_pane_height.or(self.min_pane_height);
+        let paste_newlines = other.paste_newlines.or(self.paste_newlines);
+        let scrollback_copy_line_limit = other
+            .scrollback_copy_line_limit
+            .or(self.scrollback_copy_line_limit);
+        let reject_panes_over_max = other.reject_panes_over_max.or(self.reject_panes_over_max);
+        let close_gravity = other.close_gravity.or(self.close_gravity);
+        let cursor_height_width_ratio = other
+            .cursor_height_width_ratio
+            .or(self.cursor_height_width_ratio);
 
         Options {
             simplified_ui,
@@ -187,12 +312,22 @@ impl Options {
             layout_dir,
             mouse_mode,
             pane_frames,
+            hide_single_pane_frame,
+            hold_exited_panes,
             mirror_session,
             on_force_close,
             scroll_buffer_size,
             copy_command,
             copy_clipboard,
             copy_on_select,
+            copy_from_panes,
+            min_pane_width,
+            min_pane_height,
+            paste_newlines,
+            scrollback_copy_line_limit,
+            reject_panes_over_max,
+            close_gravity,
+            cursor_height_width_ratio,
         }
     }
 
@@ -238,12 +373,22 @@ impl From<CliOptions> for Options {
             layout_dir: opts.layout_dir,
             mouse_mode: opts.mouse_mode,
             pane_frames: opts.pane_frames,
+            hide_single_pane_frame: opts.hide_single_pane_frame,
+            hold_exited_panes: opts.hold_exited_panes,
             mirror_session: opts.mirror_session,
             on_force_close: opts.on_force_close,
             scroll_buffer_size: opts.scroll_buffer_size,
             copy_command: opts.copy_command,
             copy_clipboard: opts.copy_clipboard,
             copy_on_select: opts.copy_on_select,
+            copy_from_panes: opts.copy_from_panes,
+            min_pane_width: opts.min_pane_width,
+            min_pane_height: opts.min_pane_height,
+            paste_newlines: opts.paste_newlines,
+            scrollback_copy_line_limit: opts.scrollback_copy_line_limit,
+            reject_panes_over_max: opts.reject_panes_over_max,
+            close_gravity: opts.close_gravity,
+            cursor_height_width_ratio: opts.cursor_height_width_ratio,
         }
     }
 }
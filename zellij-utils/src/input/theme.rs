@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use super::options::Options;
-use crate::shared::detect_theme_hue;
+use crate::shared::{default_palette, detect_theme_hue};
 use zellij_tile::data::{Palette, PaletteColor};
 
 /// Intermediate deserialization of themes
@@ -39,6 +39,8 @@ pub struct PaletteFromYaml {
     pub cyan: PaletteColorFromYaml,
     pub white: PaletteColorFromYaml,
     pub orange: PaletteColorFromYaml,
+    #[serde(default)]
+    pub diff_highlight: Option<PaletteColorFromYaml>,
 }
 
 /// Intermediate deserialization enum
@@ -98,6 +100,10 @@ impl From<PaletteFromYaml> for Palette {
             cyan: yaml.cyan.into(),
             white: yaml.white.into(),
             orange: yaml.orange.into(),
+            diff_highlight: yaml
+                .diff_highlight
+                .map(|color| color.into())
+                .unwrap_or_else(|| default_palette().diff_highlight),
             theme_hue: detect_theme_hue(yaml.bg.into()),
             ..Palette::default()
         }
@@ -67,6 +67,7 @@ pub mod colors {
     pub const SILVER: u8 = 245;
     pub const PINK: u8 = 207;
     pub const BROWN: u8 = 215;
+    pub const NAVY: u8 = 17;
 }
 
 pub fn _hex_to_rgb(hex: &str) -> (u8, u8, u8) {
@@ -96,6 +97,7 @@ pub fn default_palette() -> Palette {
         silver: PaletteColor::EightBit(colors::SILVER),
         pink: PaletteColor::EightBit(colors::PINK),
         brown: PaletteColor::EightBit(colors::BROWN),
+        diff_highlight: PaletteColor::EightBit(colors::NAVY),
     }
 }
 